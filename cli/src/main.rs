@@ -1,16 +1,76 @@
 use ::clap::Parser;
-use ark_serialize::Write;
-use settings::Cli;
+use ark_serialize::{Read, Write};
+use settings::{Cli, Protocol};
+use solidity_verifiers::RenderOptions;
 use std::path::Path;
 use std::{fs, io};
 
 mod settings;
 
+/// Path that stands for the standard input or output.
+const STDIO_PATH: &str = "-";
+
 fn create_or_open_then_write<T: AsRef<[u8]>>(path: &Path, content: &T) -> Result<(), io::Error> {
     let mut file = fs::OpenOptions::new().create(true).write(true).open(path)?;
     file.write_all(content.as_ref())
 }
 
+/// Reads all the bytes of the given file, or of the standard input if the path is `-`. The
+/// standard input is read as raw bytes on every platform (Rust does not translate the line
+/// endings on Windows), so the serialized data is not corrupted.
+fn read_input(path: &Path) -> Result<Vec<u8>, io::Error> {
+    if path == Path::new(STDIO_PATH) {
+        let mut data = Vec::new();
+        io::stdin().lock().read_to_end(&mut data)?;
+        return Ok(data);
+    }
+    fs::read(path)
+}
+
+/// Writes the content to the given file, or to the standard output if the path is `-`.
+fn write_output<T: AsRef<[u8]>>(path: &Path, content: &T) -> Result<(), io::Error> {
+    if path == Path::new(STDIO_PATH) {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(content.as_ref())?;
+        return stdout.flush();
+    }
+    create_or_open_then_write(path, content)
+}
+
+/// Renders one contract for each protocol verifier key in `in_dir`, writing it to `out_dir` with
+/// the file stem of the key and the `.sol` extension. The protocol of each key is inferred from
+/// its header, and the files that are not valid protocol verifier keys are logged and skipped.
+/// Returns the number of contracts written.
+fn render_batch(in_dir: &Path, out_dir: &Path, options: RenderOptions) -> Result<usize, io::Error> {
+    fs::create_dir_all(out_dir)?;
+    let mut paths = fs::read_dir(in_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, io::Error>>()?;
+    paths.sort();
+
+    let mut n_contracts = 0;
+    for path in paths.iter().filter(|p| p.is_file()) {
+        let protocol_vk = fs::read(path)?;
+        let contract = match Protocol::from_protocol_vk(&protocol_vk).and_then(|protocol| {
+            protocol
+                .render(&protocol_vk, options.clone())
+                .map_err(|e| e.to_string())
+        }) {
+            Ok(contract) => contract,
+            Err(e) => {
+                log::warn!("skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        let mut out_path = out_dir.join(path.file_stem().unwrap_or(path.as_os_str()));
+        out_path.set_extension("sol");
+        create_or_open_then_write(&out_path, &contract)?;
+        log::info!("{} -> {}", path.display(), out_path.display());
+        n_contracts += 1;
+    }
+    Ok(n_contracts)
+}
+
 fn main() {
     let cli = Cli::parse();
 
@@ -20,17 +80,101 @@ fn main() {
         .filter_level(cli.verbosity.log_level_filter())
         .init();
 
+    let options = cli.render_options();
     let out_path = cli.out;
 
+    if cli.batch {
+        let in_dir = cli
+            .protocol_vk
+            .expect("clap requires --protocol-vk in batch mode");
+        let n_contracts = render_batch(&in_dir, &out_path, options).unwrap();
+        log::info!("generated {} contracts", n_contracts);
+        return;
+    }
+
     // Fetch the exact protocol for which we need to generate the Decider verifier contract.
-    let protocol = cli.protocol;
-    // Fetch the protocol data passed by the user from the file.
-    let protocol_vk = std::fs::read(cli.protocol_vk).unwrap();
-
-    // Generate the Solidity Verifier contract for the selected protocol with the given data.
-    create_or_open_then_write(
-        &out_path,
-        &protocol.render(&protocol_vk, cli.pragma).unwrap(),
-    )
-    .unwrap();
+    let protocol = cli
+        .protocol
+        .expect("clap requires --protocol when not in batch mode");
+
+    // Generate the Solidity Verifier contract for the selected protocol with the given data,
+    // either from the protocol verifier key or from the verifier params passed by the user.
+    let contract = match (cli.protocol_vk, cli.from_verifier_params) {
+        (Some(protocol_vk_path), _) => {
+            let protocol_vk = read_input(&protocol_vk_path).unwrap();
+            protocol.render(&protocol_vk, options).unwrap()
+        }
+        (None, Some(verifier_params_path)) => {
+            let verifier_params = read_input(&verifier_params_path).unwrap();
+            protocol
+                .render_from_verifier_params(&verifier_params, cli.z_len, options)
+                .unwrap()
+        }
+        (None, None) => {
+            unreachable!("clap requires either --protocol-vk or --from-verifier-params")
+        }
+    };
+
+    write_output(&out_path, &contract).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Bn254, G1Projective, G2Projective};
+    use ark_ec::{pairing::Pairing, CurveGroup};
+    use ark_poly_commit::kzg10::VerifierKey;
+    use ark_std::{test_rng, UniformRand};
+    use solidity_verifiers::{KZG10VerifierKey, ProtocolVerifierKey};
+
+    #[test]
+    fn test_render_batch() {
+        let mut rng = test_rng();
+        let h = G2Projective::rand(&mut rng).into_affine();
+        let beta_h = G2Projective::rand(&mut rng).into_affine();
+        let kzg_vk = VerifierKey::<Bn254> {
+            g: G1Projective::rand(&mut rng).into_affine(),
+            gamma_g: G1Projective::rand(&mut rng).into_affine(),
+            h,
+            beta_h,
+            prepared_h: <Bn254 as Pairing>::G2Prepared::from(h),
+            prepared_beta_h: <Bn254 as Pairing>::G2Prepared::from(beta_h),
+        };
+        let mut protocol_vk = Vec::new();
+        KZG10VerifierKey::from((kzg_vk, Vec::new()))
+            .serialize_protocol_verifier_key(&mut protocol_vk)
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("sonobe-cli-batch-{}", std::process::id()));
+        let (in_dir, out_dir) = (dir.join("artifacts"), dir.join("contracts"));
+        fs::create_dir_all(&in_dir).unwrap();
+        fs::write(in_dir.join("circuit_a.ppot"), &protocol_vk).unwrap();
+        fs::write(in_dir.join("circuit_b.ppot"), &protocol_vk).unwrap();
+        // a file that is not a protocol verifier key is skipped without aborting the batch
+        fs::write(in_dir.join("notes.txt"), b"abc").unwrap();
+
+        let n_contracts = render_batch(&in_dir, &out_dir, RenderOptions::default()).unwrap();
+        assert_eq!(n_contracts, 2);
+        let expected = Protocol::Kzg
+            .render(&protocol_vk, RenderOptions::default())
+            .unwrap();
+        for name in ["circuit_a.sol", "circuit_b.sol"] {
+            assert_eq!(fs::read(out_dir.join(name)).unwrap(), expected);
+        }
+        assert!(!out_dir.join("notes.sol").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_write_files() {
+        let dir = std::env::temp_dir().join(format!("sonobe-cli-io-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("data");
+        // binary data, including line endings that must not be translated
+        let data = vec![0_u8, 13, 10, 255, 10, 13];
+        write_output(&path, &data).unwrap();
+        assert_eq!(read_input(&path).unwrap(), data);
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
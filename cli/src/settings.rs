@@ -1,9 +1,13 @@
-use ark_serialize::SerializationError;
+use ark_bn254::Bn254;
+use ark_groth16::VerifyingKey;
+use ark_poly_commit::kzg10::VerifierKey;
+use ark_serialize::{CanonicalDeserialize, SerializationError};
 use clap::{Parser, ValueEnum};
 use solidity_verifiers::{
-    Groth16VerifierKey, KZG10VerifierKey, NovaCycleFoldVerifierKey, ProtocolVerifierKey,
+    deserialize_protocol_name, Groth16VerifierKey, KZG10VerifierKey, NovaCycleFoldVerifierKey,
+    ProtocolVerifierKey, RenderOptions,
 };
-use std::{env, fmt::Display, path::PathBuf};
+use std::{env, fmt::Display, path::PathBuf, str::FromStr};
 
 fn get_default_out_path() -> PathBuf {
     let mut path = env::current_dir().unwrap();
@@ -24,24 +28,95 @@ impl Display for Protocol {
     }
 }
 
+impl FromStr for Protocol {
+    type Err = String;
+
+    /// Parses the protocol ignoring the case and the `-` and `_` separators, so that both
+    /// `nova-cyclefold` and `NovaCycleFold` are accepted.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalize = |s: &str| s.replace(['-', '_'], "").to_lowercase();
+        Self::value_variants()
+            .iter()
+            .find(|p| normalize(&p.to_string()) == normalize(s))
+            .copied()
+            .ok_or_else(|| {
+                let valid_values: Vec<String> = Self::value_variants()
+                    .iter()
+                    .filter_map(|p| p.to_possible_value())
+                    .map(|p| p.get_name().to_string())
+                    .collect();
+                format!(
+                    "invalid protocol '{}', valid values are: {}",
+                    s,
+                    valid_values.join(", ")
+                )
+            })
+    }
+}
+
+impl TryFrom<&str> for Protocol {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 // Would be nice to link this to the `Template` or `ProtocolVerifierKey` traits.
 // Sadly, this requires Boxing with `dyn` or similar which would complicate the code more than is actually required.
 impl Protocol {
+    /// Returns the protocol of the given serialized protocol verifier key, from the protocol name
+    /// written at its beginning by `ProtocolVerifierKey::serialize_protocol_verifier_key`.
+    pub(crate) fn from_protocol_vk(data: &[u8]) -> Result<Self, String> {
+        let name = deserialize_protocol_name(data).map_err(|e| e.to_string())?;
+        name.parse()
+    }
+
     pub(crate) fn render(
         &self,
         data: &[u8],
-        pragma: Option<String>,
+        options: RenderOptions,
     ) -> Result<Vec<u8>, SerializationError> {
         match self {
             Self::Groth16 => Ok(Groth16VerifierKey::deserialize_protocol_verifier_key(data)?
-                .render_as_template(pragma)),
+                .render_as_template(options)),
 
             Self::Kzg => Ok(KZG10VerifierKey::deserialize_protocol_verifier_key(data)?
-                .render_as_template(pragma)),
+                .render_as_template(options)),
             Self::NovaCycleFold => Ok(NovaCycleFoldVerifierKey::deserialize_protocol_verifier_key(
                 data,
             )?
-            .render_as_template(pragma)),
+            .render_as_template(options)),
+        }
+    }
+
+    /// Renders the contract from the serialized (compressed) verifier params of the protocol,
+    /// building the `ProtocolVerifierKey` from them. These are the Groth16 `VerifyingKey` for
+    /// Groth16, the KZG10 `VerifierKey` for KZG, and the Decider's `VerifierParam` (the tuple of
+    /// both) for Nova + CycleFold, which additionally requires the length of the IVC state.
+    pub(crate) fn render_from_verifier_params(
+        &self,
+        data: &[u8],
+        z_len: Option<usize>,
+        options: RenderOptions,
+    ) -> Result<Vec<u8>, SerializationError> {
+        match self {
+            Self::Groth16 => {
+                let vk = VerifyingKey::<Bn254>::deserialize_compressed(data)?;
+                Ok(Groth16VerifierKey::from(vk).render_as_template(options))
+            }
+            Self::Kzg => {
+                let vk = VerifierKey::<Bn254>::deserialize_compressed(data)?;
+                // pass `Vec::new()` since the verifier params do not contain the CRS points
+                Ok(KZG10VerifierKey::from((vk, Vec::new())).render_as_template(options))
+            }
+            Self::NovaCycleFold => {
+                let z_len = z_len.ok_or(SerializationError::InvalidData)?;
+                let (g16_vk, kzg_vk) =
+                    <(VerifyingKey<Bn254>, VerifierKey<Bn254>)>::deserialize_compressed(data)?;
+                Ok(NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, z_len))
+                    .render_as_template(options))
+            }
         }
     }
 }
@@ -96,18 +171,190 @@ pub(crate) struct Cli {
     pub verbosity: clap_verbosity_flag::Verbosity,
 
     /// Selects the protocol for which we want to generate the Solidity Verifier contract.
-    #[arg(short = 'p', long, value_enum, rename_all = "lower")]
-    pub protocol: Protocol,
+    #[arg(
+        short = 'p',
+        long,
+        value_enum,
+        rename_all = "lower",
+        required_unless_present = "batch"
+    )]
+    pub protocol: Option<Protocol>,
 
     #[arg(short = 'o', long, default_value=get_default_out_path().into_os_string())]
-    /// Sets the output path for all the artifacts generated by the command.
+    /// Sets the output path for all the artifacts generated by the command, or `-` to write the contract to the standard output. In batch mode, it is the directory where the contracts are written.
     pub out: PathBuf,
 
-    #[arg(short = 'k', long)]
-    /// Sets the input path for the file containing the verifier key required by the protocol chosen such that the verification contract can be generated.
-    pub protocol_vk: PathBuf,
+    #[arg(short = 'k', long, required_unless_present = "from_verifier_params")]
+    /// Sets the input path for the file containing the verifier key required by the protocol chosen such that the verification contract can be generated, or `-` to read it from the standard input. In batch mode, it is the directory containing the verifier keys.
+    pub protocol_vk: Option<PathBuf>,
+
+    #[arg(long, requires = "protocol_vk", conflicts_with = "protocol")]
+    /// Generates one contract for each protocol verifier key in the `--protocol-vk` directory, named after the file of the key, inferring the protocol of each key from its header.
+    pub batch: bool,
+
+    #[arg(long, conflicts_with_all = ["protocol_vk", "batch"])]
+    /// Sets the input path for the file containing the serialized verifier params of the protocol chosen, from which the verifier key is built, or `-` to read them from the standard input. Alternative to `--protocol-vk`.
+    pub from_verifier_params: Option<PathBuf>,
 
-    /// Selects the Solidity compiler version to be set in the Solidity Verifier contract artifact.
+    #[arg(long, requires = "from_verifier_params")]
+    /// Sets the length of the IVC state, required when rendering the Nova + CycleFold Decider contract from its verifier params.
+    pub z_len: Option<usize>,
+
+    /// Selects the Solidity compiler version to be set in the Solidity Verifier contract artifact, either as a version or range (eg. `^0.8.20`) or as a full `pragma` statement.
     #[arg(long, default_value=None)]
     pub pragma: Option<String>,
+
+    /// Sets the SPDX license identifier written in the first line of the Solidity Verifier contract artifact (eg. `MIT`). Defaults to the license of the protocol template, GPL-3.0 for the contracts that include the Groth16 verifier.
+    #[arg(long, default_value=None)]
+    pub spdx: Option<String>,
+}
+
+impl Cli {
+    /// returns the options of the header of the rendered contracts
+    pub(crate) fn render_options(&self) -> RenderOptions {
+        RenderOptions {
+            pragma: self.pragma.clone(),
+            spdx: self.spdx.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{G1Projective, G2Projective};
+    use ark_ec::{pairing::Pairing, CurveGroup};
+    use ark_serialize::CanonicalSerialize;
+    use ark_std::{test_rng, UniformRand};
+
+    #[test]
+    fn test_protocol_from_str() {
+        assert!(matches!(
+            Protocol::try_from("groth16"),
+            Ok(Protocol::Groth16)
+        ));
+        assert!(matches!(Protocol::try_from("kzg"), Ok(Protocol::Kzg)));
+        assert!(matches!(
+            Protocol::try_from("novacyclefold"),
+            Ok(Protocol::NovaCycleFold)
+        ));
+        assert!(matches!(
+            "nova-cyclefold".parse::<Protocol>(),
+            Ok(Protocol::NovaCycleFold)
+        ));
+
+        let err = Protocol::try_from("plonk").unwrap_err();
+        assert_eq!(
+            err,
+            "invalid protocol 'plonk', valid values are: groth16, kzg, nova-cyclefold"
+        );
+    }
+
+    #[test]
+    fn test_cli_render_options() {
+        let cli = Cli::try_parse_from([
+            "solidity-verifiers-cli",
+            "-p",
+            "groth16",
+            "-k",
+            "vk.bin",
+            "--pragma",
+            "^0.8.20",
+            "--spdx",
+            "MIT",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.render_options(),
+            RenderOptions {
+                pragma: Some("^0.8.20".to_string()),
+                spdx: Some("MIT".to_string()),
+            }
+        );
+
+        let cli =
+            Cli::try_parse_from(["solidity-verifiers-cli", "-p", "kzg", "-k", "vk.bin"]).unwrap();
+        assert_eq!(cli.render_options(), RenderOptions::default());
+    }
+
+    #[test]
+    fn test_protocol_from_protocol_vk() {
+        let mut rng = test_rng();
+        let h = G2Projective::rand(&mut rng).into_affine();
+        let beta_h = G2Projective::rand(&mut rng).into_affine();
+        let kzg_vk = VerifierKey::<Bn254> {
+            g: G1Projective::rand(&mut rng).into_affine(),
+            gamma_g: G1Projective::rand(&mut rng).into_affine(),
+            h,
+            beta_h,
+            prepared_h: <Bn254 as Pairing>::G2Prepared::from(h),
+            prepared_beta_h: <Bn254 as Pairing>::G2Prepared::from(beta_h),
+        };
+        let mut protocol_vk = Vec::new();
+        KZG10VerifierKey::from((kzg_vk, Vec::new()))
+            .serialize_protocol_verifier_key(&mut protocol_vk)
+            .unwrap();
+        assert!(matches!(
+            Protocol::from_protocol_vk(&protocol_vk),
+            Ok(Protocol::Kzg)
+        ));
+
+        // unknown protocol names are rejected
+        let mut unknown_vk = Vec::new();
+        "Plonk"
+            .to_string()
+            .serialize_uncompressed(&mut unknown_vk)
+            .unwrap();
+        assert!(Protocol::from_protocol_vk(&unknown_vk).is_err());
+        assert!(Protocol::from_protocol_vk(&[]).is_err());
+    }
+
+    #[test]
+    fn test_render_from_verifier_params() {
+        let mut rng = test_rng();
+        let g16_vk = VerifyingKey::<Bn254> {
+            alpha_g1: G1Projective::rand(&mut rng).into_affine(),
+            beta_g2: G2Projective::rand(&mut rng).into_affine(),
+            gamma_g2: G2Projective::rand(&mut rng).into_affine(),
+            delta_g2: G2Projective::rand(&mut rng).into_affine(),
+            gamma_abc_g1: (0..5)
+                .map(|_| G1Projective::rand(&mut rng).into_affine())
+                .collect(),
+        };
+        let h = G2Projective::rand(&mut rng).into_affine();
+        let beta_h = G2Projective::rand(&mut rng).into_affine();
+        let kzg_vk = VerifierKey::<Bn254> {
+            g: G1Projective::rand(&mut rng).into_affine(),
+            gamma_g: G1Projective::rand(&mut rng).into_affine(),
+            h,
+            beta_h,
+            prepared_h: <Bn254 as Pairing>::G2Prepared::from(h),
+            prepared_beta_h: <Bn254 as Pairing>::G2Prepared::from(beta_h),
+        };
+        let z_len = 1;
+
+        // serialized decider verifier params
+        let mut verifier_params = Vec::new();
+        (g16_vk.clone(), kzg_vk.clone())
+            .serialize_compressed(&mut verifier_params)
+            .unwrap();
+        let rendered = Protocol::NovaCycleFold
+            .render_from_verifier_params(&verifier_params, Some(z_len), RenderOptions::default())
+            .unwrap();
+
+        // the contract matches the one rendered from the protocol verifier key
+        let mut protocol_vk = Vec::new();
+        NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, z_len))
+            .serialize_protocol_verifier_key(&mut protocol_vk)
+            .unwrap();
+        let expected = Protocol::NovaCycleFold
+            .render(&protocol_vk, RenderOptions::default())
+            .unwrap();
+        assert_eq!(rendered, expected);
+
+        // the state length is required for Nova + CycleFold
+        assert!(Protocol::NovaCycleFold
+            .render_from_verifier_params(&verifier_params, None, RenderOptions::default())
+            .is_err());
+    }
 }
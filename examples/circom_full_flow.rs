@@ -101,11 +101,13 @@ fn main() {
     .unwrap();
     println!("generated Decider proof: {:?}", start.elapsed());
 
+    let bindings = nova.bindings().unwrap();
     let verified = DECIDERETH_FCircuit::verify(
         (g16_vk.clone(), kzg_vk.clone()),
         nova.i,
         nova.z_0.clone(),
         nova.z_i.clone(),
+        &bindings,
         &nova.U_i,
         &nova.u_i,
         &proof,
@@ -115,14 +117,16 @@ fn main() {
     println!("Decider proof verification: {}", verified);
 
     // Now, let's generate the Solidity code that verifies this Decider final proof
-    let function_selector =
-        get_function_selector_for_nova_cyclefold_verifier(nova.z_0.len() * 2 + 1);
+    let function_selector = get_function_selector_for_nova_cyclefold_verifier(
+        nova.z_0.len() * 2 + 1 + bindings.to_vec().len(),
+    );
 
     let calldata: Vec<u8> = prepare_calldata(
         function_selector,
         nova.i,
         nova.z_0,
         nova.z_i,
+        &bindings,
         &nova.U_i,
         &nova.u_i,
         proof,
@@ -22,10 +22,10 @@ use std::time::Instant;
 
 use folding_schemes::commitment::{kzg::KZG, pedersen::Pedersen};
 use folding_schemes::folding::nova::Nova;
-use folding_schemes::frontend::FCircuit;
+use folding_schemes::frontend::{ExternalInputs, FCircuit};
 use folding_schemes::{Error, FoldingScheme};
 mod utils;
-use folding_schemes::transcript::poseidon::poseidon_test_config;
+use folding_schemes::transcript::poseidon::poseidon_canonical_config;
 use utils::init_nova_ivc_params;
 
 /// This is the circuit that we want to fold, it implements the FCircuit trait. The parameter z_i
@@ -60,7 +60,7 @@ use utils::init_nova_ivc_params;
 ///           │                    │              
 ///           └────────────────────┘
 ///
-/// where each w_i value is set at the external_inputs array.
+/// where each w_i value is given as the typed external input of the step, `HashInput`.
 ///
 /// The last state z_i is used together with the external input w_i as inputs to compute the new
 /// state z_{i+1}.
@@ -72,11 +72,49 @@ where
     _f: PhantomData<F>,
     poseidon_config: PoseidonConfig<F>,
 }
+
+/// HashInput is the external input of each step, the w_i value that is hashed together with the
+/// state. It is not part of the state, so it is not carried to the next step.
+#[derive(Clone, Copy, Debug)]
+pub struct HashInput<F: PrimeField> {
+    pub w: F,
+}
+
+/// in-circuit counterpart of `HashInput`
+#[derive(Clone, Debug)]
+pub struct HashInputVar<F: PrimeField> {
+    pub w: FpVar<F>,
+}
+
+impl<F: PrimeField> ExternalInputs<F> for HashInput<F> {
+    type Var = HashInputVar<F>;
+
+    fn to_field_elements(&self) -> Vec<F> {
+        vec![self.w]
+    }
+    fn from_field_elements(elems: Vec<F>) -> Result<Self, Error> {
+        match elems[..] {
+            [w] => Ok(Self { w }),
+            _ => Err(Error::NotExpectedLength {
+                got: elems.len(),
+                expected: 1,
+            }),
+        }
+    }
+    fn var_from_field_elements(elems: Vec<FpVar<F>>) -> Result<Self::Var, SynthesisError> {
+        match &elems[..] {
+            [w] => Ok(HashInputVar { w: w.clone() }),
+            _ => Err(SynthesisError::Unsatisfiable),
+        }
+    }
+}
+
 impl<F: PrimeField> FCircuit<F> for ExternalInputsCircuits<F>
 where
     F: Absorb,
 {
     type Params = PoseidonConfig<F>;
+    type ExternalInputs = HashInput<F>;
 
     fn new(params: Self::Params) -> Result<Self, Error> {
         Ok(Self {
@@ -97,9 +135,9 @@ where
         &self,
         _i: usize,
         z_i: Vec<F>,
-        external_inputs: Vec<F>,
+        external_inputs: HashInput<F>,
     ) -> Result<Vec<F>, Error> {
-        let hash_input: [F; 2] = [z_i[0], external_inputs[0]];
+        let hash_input: [F; 2] = [z_i[0], external_inputs.w];
         let h = CRH::<F>::evaluate(&self.poseidon_config, hash_input).unwrap();
         Ok(vec![h])
     }
@@ -111,11 +149,11 @@ where
         cs: ConstraintSystemRef<F>,
         _i: usize,
         z_i: Vec<FpVar<F>>,
-        external_inputs: Vec<FpVar<F>>,
+        external_inputs: HashInputVar<F>,
     ) -> Result<Vec<FpVar<F>>, SynthesisError> {
         let crh_params =
             CRHParametersVar::<F>::new_constant(cs.clone(), self.poseidon_config.clone())?;
-        let hash_input: [FpVar<F>; 2] = [z_i[0].clone(), external_inputs[0].clone()];
+        let hash_input: [FpVar<F>; 2] = [z_i[0].clone(), external_inputs.w];
         let h = CRHGadget::<F>::evaluate(&crh_params, &hash_input)?;
         Ok(vec![h])
     }
@@ -124,9 +162,11 @@ where
 /// cargo test --example external_inputs
 #[cfg(test)]
 pub mod tests {
+    use super::utils::init_nova_ivc_params_with_seed;
     use super::*;
     use ark_r1cs_std::R1CSVar;
     use ark_relations::r1cs::ConstraintSystem;
+    use folding_schemes::transcript::poseidon::poseidon_test_config;
 
     // test to check that the ExternalInputsCircuits computes the same values inside and outside the circuit
     #[test]
@@ -137,21 +177,71 @@ pub mod tests {
 
         let circuit = ExternalInputsCircuits::<Fr>::new(poseidon_config).unwrap();
         let z_i = vec![Fr::from(1_u32)];
-        let external_inputs = vec![Fr::from(3_u32)];
+        let external_inputs = HashInput { w: Fr::from(3_u32) };
 
         let z_i1 = circuit
-            .step_native(0, z_i.clone(), external_inputs.clone())
+            .step_native(0, z_i.clone(), external_inputs)
             .unwrap();
 
         let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
-        let external_inputsVar =
-            Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(external_inputs)).unwrap();
+        let external_inputsVar = HashInputVar {
+            w: FpVar::<Fr>::new_witness(cs.clone(), || Ok(external_inputs.w)).unwrap(),
+        };
 
         let computed_z_i1Var = circuit
             .generate_step_constraints(cs.clone(), 0, z_iVar, external_inputsVar)
             .unwrap();
+        assert!(cs.is_satisfied().unwrap());
         assert_eq!(computed_z_i1Var.value().unwrap(), z_i1);
     }
+
+    // test to check that the HashInput is converted from and into the field elements that the
+    // folding scheme handles
+    #[test]
+    fn test_hash_input_field_elements() {
+        let external_inputs = HashInput { w: Fr::from(3_u32) };
+        let elems = external_inputs.to_field_elements();
+        assert_eq!(elems, vec![Fr::from(3_u32)]);
+        assert_eq!(
+            HashInput::from_field_elements(elems).unwrap().w,
+            external_inputs.w
+        );
+        assert!(HashInput::<Fr>::from_field_elements(vec![]).is_err());
+    }
+
+    // test to check that the size of the augmented circuit of ExternalInputsCircuits is the same
+    // across runs
+    #[test]
+    fn test_augmented_circuit_size() {
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            ExternalInputsCircuits<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+        >;
+        let F_circuit = ExternalInputsCircuits::<Fr>::new(poseidon_test_config::<Fr>()).unwrap();
+
+        let sizes: Vec<_> = (0..2)
+            .map(|seed| {
+                let (prover_params, _, _) = init_nova_ivc_params_with_seed(F_circuit.clone(), seed);
+                let nova =
+                    NOVA::init(&prover_params, F_circuit.clone(), vec![Fr::from(1_u32)]).unwrap();
+                (
+                    nova.num_constraints(),
+                    nova.num_witness_variables(),
+                    nova.circuit_size_breakdown().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(sizes[0], sizes[1]);
+
+        let (num_constraints, _, (fcircuit, overhead)) = sizes[0];
+        assert!(fcircuit.constraints > 0);
+        assert_eq!(fcircuit.constraints + overhead.constraints, num_constraints);
+    }
 }
 
 /// cargo run --release --example external_inputs
@@ -160,16 +250,13 @@ fn main() {
     let initial_state = vec![Fr::from(1_u32)];
 
     // prepare the external inputs to be used at each folding step
-    let external_inputs = vec![
-        vec![Fr::from(3_u32)],
-        vec![Fr::from(33_u32)],
-        vec![Fr::from(73_u32)],
-        vec![Fr::from(103_u32)],
-        vec![Fr::from(125_u32)],
-    ];
+    let external_inputs: Vec<HashInput<Fr>> = [3_u32, 33, 73, 103, 125]
+        .into_iter()
+        .map(|w| HashInput { w: Fr::from(w) })
+        .collect();
     assert_eq!(external_inputs.len(), num_steps);
 
-    let poseidon_config = poseidon_test_config::<Fr>();
+    let poseidon_config = poseidon_canonical_config::<Fr>().unwrap();
     let F_circuit = ExternalInputsCircuits::<Fr>::new(poseidon_config).unwrap();
 
     println!("Prepare Nova ProverParams & VerifierParams");
@@ -193,11 +280,9 @@ fn main() {
     let mut folding_scheme = NOVA::init(&prover_params, F_circuit, initial_state.clone()).unwrap();
 
     // compute a step of the IVC
-    for (i, external_inputs_at_step) in external_inputs.iter().enumerate() {
+    for (i, external_inputs_at_step) in external_inputs.into_iter().enumerate() {
         let start = Instant::now();
-        folding_scheme
-            .prove_step(external_inputs_at_step.clone())
-            .unwrap();
+        folding_scheme.prove_step(external_inputs_at_step).unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
     println!(
@@ -207,16 +292,18 @@ fn main() {
     );
 
     let (running_instance, incoming_instance, cyclefold_instance) = folding_scheme.instances();
+    let proof_data = folding_scheme.proof_data();
 
     println!("Run the Nova's IVC verifier");
     NOVA::verify(
         verifier_params,
         initial_state.clone(),
         folding_scheme.state(), // latest state
-        Fr::from(num_steps as u32),
+        Fr::from(folding_scheme.num_steps()),
         running_instance,
         incoming_instance,
         cyclefold_instance,
+        proof_data,
     )
     .unwrap();
 }
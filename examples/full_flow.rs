@@ -45,6 +45,7 @@ pub struct CubicFCircuit<F: PrimeField> {
 }
 impl<F: PrimeField> FCircuit<F> for CubicFCircuit<F> {
     type Params = ();
+    type ExternalInputs = ();
     fn new(_params: Self::Params) -> Result<Self, Error> {
         Ok(Self { _f: PhantomData })
     }
@@ -54,12 +55,7 @@ impl<F: PrimeField> FCircuit<F> for CubicFCircuit<F> {
     fn external_inputs_len(&self) -> usize {
         0
     }
-    fn step_native(
-        &self,
-        _i: usize,
-        z_i: Vec<F>,
-        _external_inputs: Vec<F>,
-    ) -> Result<Vec<F>, Error> {
+    fn step_native(&self, _i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
         Ok(vec![z_i[0] * z_i[0] * z_i[0] + z_i[0] + F::from(5_u32)])
     }
     fn generate_step_constraints(
@@ -67,7 +63,7 @@ impl<F: PrimeField> FCircuit<F> for CubicFCircuit<F> {
         cs: ConstraintSystemRef<F>,
         _i: usize,
         z_i: Vec<FpVar<F>>,
-        _external_inputs: Vec<FpVar<F>>,
+        _external_inputs: (),
     ) -> Result<Vec<FpVar<F>>, SynthesisError> {
         let five = FpVar::<F>::new_constant(cs.clone(), F::from(5u32))?;
         let z_i = z_i[0].clone();
@@ -103,7 +99,7 @@ fn main() {
     // run n steps of the folding iteration
     for i in 0..n_steps {
         let start = Instant::now();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(()).unwrap();
         println!("Nova::prove_step {}: {:?}", i, start.elapsed());
     }
 
@@ -117,11 +113,13 @@ fn main() {
     .unwrap();
     println!("generated Decider proof: {:?}", start.elapsed());
 
+    let bindings = nova.bindings().unwrap();
     let verified = DECIDERETH_FCircuit::verify(
         (g16_vk.clone(), kzg_vk.clone()),
         nova.i,
         nova.z_0.clone(),
         nova.z_i.clone(),
+        &bindings,
         &nova.U_i,
         &nova.u_i,
         &proof,
@@ -131,14 +129,16 @@ fn main() {
     println!("Decider proof verification: {}", verified);
 
     // Now, let's generate the Solidity code that verifies this Decider final proof
-    let function_selector =
-        get_function_selector_for_nova_cyclefold_verifier(nova.z_0.len() * 2 + 1);
+    let function_selector = get_function_selector_for_nova_cyclefold_verifier(
+        nova.z_0.len() * 2 + 1 + bindings.to_vec().len(),
+    );
 
     let calldata: Vec<u8> = prepare_calldata(
         function_selector,
         nova.i,
         nova.z_0,
         nova.z_i,
+        &bindings,
         &nova.U_i,
         &nova.u_i,
         proof,
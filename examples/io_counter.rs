@@ -0,0 +1,213 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
+
+use ark_crypto_primitives::{
+    crh::{
+        poseidon::constraints::{CRHGadget, CRHParametersVar},
+        poseidon::CRH,
+        CRHScheme, CRHSchemeGadget,
+    },
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::alloc::AllocVar;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::fields::FieldVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use std::time::Instant;
+
+use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
+use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+use folding_schemes::commitment::{kzg::KZG, pedersen::Pedersen};
+use folding_schemes::folding::nova::Nova;
+use folding_schemes::frontend::FCircuit;
+use folding_schemes::transcript::poseidon::poseidon_canonical_config;
+use folding_schemes::{Error, FoldingScheme};
+mod utils;
+use utils::init_nova_ivc_params;
+
+/// This is the circuit that we want to fold. Its state is a counter, which is incremented at
+/// each step, and each step exposes as public output (IO) the hash of the counter, `H(z_i)`. The
+/// verifier checks the sequence of hashes at the end, without knowing the counter.
+#[derive(Clone, Debug)]
+pub struct CounterHashFCircuit<F: PrimeField> {
+    poseidon_config: PoseidonConfig<F>,
+}
+impl<F: PrimeField + Absorb> FCircuit<F> for CounterHashFCircuit<F> {
+    type Params = PoseidonConfig<F>;
+    type ExternalInputs = ();
+
+    fn new(poseidon_config: Self::Params) -> Result<Self, Error> {
+        Ok(Self { poseidon_config })
+    }
+    fn state_len(&self) -> usize {
+        1
+    }
+    fn external_inputs_len(&self) -> usize {
+        0
+    }
+    fn io_len(&self) -> usize {
+        1
+    }
+
+    /// computes the hash of the counter, which is the public output of the step
+    fn step_io_native(
+        &self,
+        _i: usize,
+        z_i: Vec<F>,
+        _external_inputs: (),
+    ) -> Result<Vec<F>, Error> {
+        let h = CRH::<F>::evaluate(&self.poseidon_config, z_i)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Ok(vec![h])
+    }
+
+    /// generates the constraints computing the hash of the counter
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        Ok(vec![CRHGadget::<F>::evaluate(&crh_params, &z_i)?])
+    }
+
+    /// increments the counter
+    fn step_native(&self, _i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
+        Ok(vec![z_i[0] + F::one()])
+    }
+
+    /// generates the constraints incrementing the counter
+    fn generate_step_constraints(
+        &self,
+        _cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        Ok(vec![z_i[0].clone() + FpVar::<F>::one()])
+    }
+}
+
+/// returns the public outputs expected for the steps of the IVC that starts at the counter `z_0`
+fn expected_io(
+    poseidon_config: &PoseidonConfig<Fr>,
+    z_0: Fr,
+    num_steps: usize,
+) -> Result<Vec<Vec<Fr>>, Error> {
+    (0..num_steps)
+        .map(|i| {
+            let h = CRH::<Fr>::evaluate(poseidon_config, vec![z_0 + Fr::from(i as u64)])
+                .map_err(|e| Error::Other(e.to_string()))?;
+            Ok(vec![h])
+        })
+        .collect()
+}
+
+/// cargo test --example io_counter
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    // test to check that the CounterHashFCircuit computes the same values inside and outside the
+    // circuit
+    #[test]
+    fn test_f_circuit() {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+
+        let circuit = CounterHashFCircuit::<Fr>::new(poseidon_canonical_config().unwrap()).unwrap();
+        let z_i = vec![Fr::from(7_u32)];
+
+        let z_i1 = circuit.step_native(0, z_i.clone(), ()).unwrap();
+        let io = circuit.step_io_native(0, z_i.clone(), ()).unwrap();
+
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let computed_z_i1Var = circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar.clone(), ())
+            .unwrap();
+        let computed_ioVar = circuit
+            .generate_step_io_constraints(cs.clone(), 0, z_iVar, ())
+            .unwrap();
+        assert_eq!(computed_z_i1Var.value().unwrap(), z_i1);
+        assert_eq!(computed_ioVar.value().unwrap(), io);
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+/// cargo run --release --example io_counter
+fn main() {
+    let num_steps = 5;
+    let initial_state = vec![Fr::from(1_u32)];
+
+    let poseidon_config = poseidon_canonical_config::<Fr>().unwrap();
+    let F_circuit = CounterHashFCircuit::<Fr>::new(poseidon_config.clone()).unwrap();
+
+    println!("Prepare Nova ProverParams & VerifierParams");
+    let (prover_params, verifier_params, _) =
+        init_nova_ivc_params::<CounterHashFCircuit<Fr>>(F_circuit.clone());
+
+    type NOVA = Nova<
+        Projective,
+        GVar,
+        Projective2,
+        GVar2,
+        CounterHashFCircuit<Fr>,
+        KZG<'static, Bn254>,
+        Pedersen<Projective2>,
+    >;
+
+    println!("Initialize FoldingScheme");
+    let mut folding_scheme = NOVA::init(&prover_params, F_circuit, initial_state.clone()).unwrap();
+
+    // compute a step of the IVC
+    for i in 0..num_steps {
+        let start = Instant::now();
+        folding_scheme.prove_step(()).unwrap();
+        println!("Nova::prove_step {}: {:?}", i, start.elapsed());
+    }
+
+    let (running_instance, incoming_instance, cyclefold_instance) = folding_scheme.instances();
+    let proof_data = folding_scheme.proof_data();
+
+    // the verifier computes the expected hashes of the counter by itself, and checks them
+    // against the public outputs bound to the IVC
+    let io = expected_io(&poseidon_config, initial_state[0], num_steps).unwrap();
+    assert_eq!(io, folding_scheme.io);
+
+    println!("Run the Nova's IVC verifier checking the IO of the steps");
+    NOVA::verify_with_io(
+        verifier_params.clone(),
+        initial_state.clone(),
+        folding_scheme.state(), // latest state
+        Fr::from(folding_scheme.num_steps()),
+        running_instance.clone(),
+        incoming_instance.clone(),
+        cyclefold_instance.clone(),
+        proof_data.clone(),
+        io.clone(),
+    )
+    .unwrap();
+
+    // a different IO sequence is rejected
+    let mut wrong_io = io;
+    wrong_io[0][0] += Fr::from(1_u32);
+    assert!(NOVA::verify_with_io(
+        verifier_params,
+        initial_state,
+        folding_scheme.state(),
+        Fr::from(folding_scheme.num_steps()),
+        running_instance,
+        incoming_instance,
+        cyclefold_instance,
+        proof_data,
+        wrong_io,
+    )
+    .is_err());
+}
@@ -0,0 +1,80 @@
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
+
+use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
+use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+use folding_schemes::commitment::{kzg::KZG, pedersen::Pedersen};
+use folding_schemes::folding::nova::Nova;
+use folding_schemes::frontend::{examples::SyntheticFCircuit, FCircuit};
+use folding_schemes::FoldingScheme;
+mod utils;
+use utils::init_nova_ivc_params;
+
+/// cargo run --release --example step_stats --features stats
+///
+/// folds a few steps of a synthetic circuit and prints the time spent in each phase of each
+/// `prove_step`, recorded when the `stats` feature is enabled
+fn main() {
+    let num_steps = 5;
+    let n_constraints = 1 << 12;
+    let initial_state = vec![Fr::from(1_u32)];
+
+    let F_circuit = SyntheticFCircuit::<Fr>::new(n_constraints).unwrap();
+
+    println!("Prepare Nova ProverParams & VerifierParams");
+    let (prover_params, verifier_params, _) =
+        init_nova_ivc_params::<SyntheticFCircuit<Fr>>(F_circuit);
+
+    type NOVA = Nova<
+        Projective,
+        GVar,
+        Projective2,
+        GVar2,
+        SyntheticFCircuit<Fr>,
+        KZG<'static, Bn254>,
+        Pedersen<Projective2>,
+    >;
+
+    println!("Initialize FoldingScheme");
+    let mut folding_scheme = NOVA::init(&prover_params, F_circuit, initial_state.clone()).unwrap();
+    for _ in 0..num_steps {
+        folding_scheme.prove_step(()).unwrap();
+    }
+
+    println!(
+        "{:>4} {:>11} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "step", "constraints", "witness", "fold", "cyclefold", "commit", "other", "total"
+    );
+    for s in folding_scheme.stats() {
+        println!(
+            "{:>4} {:>11} {:>12?} {:>12?} {:>12?} {:>12?} {:>12?} {:>12?}",
+            s.step,
+            s.constraints,
+            s.witness_time,
+            s.fold_time,
+            s.cyclefold_time,
+            s.commit_time,
+            s.other_time(),
+            s.total_time
+        );
+    }
+
+    let (running_instance, incoming_instance, cyclefold_instance) = folding_scheme.instances();
+    let proof_data = folding_scheme.proof_data();
+
+    println!("Run the Nova's IVC verifier");
+    NOVA::verify(
+        verifier_params,
+        initial_state,
+        folding_scheme.state(),
+        Fr::from(folding_scheme.num_steps()),
+        running_instance,
+        incoming_instance,
+        cyclefold_instance,
+        proof_data,
+    )
+    .unwrap();
+}
@@ -9,6 +9,7 @@ use ark_groth16::{Groth16, ProvingKey, VerifyingKey as G16VerifierKey};
 use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
 use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
 use ark_std::Zero;
+use rand::{rngs::StdRng, SeedableRng};
 use std::time::Instant;
 
 use folding_schemes::{
@@ -21,11 +22,12 @@ use folding_schemes::{
         decider_eth_circuit::DeciderEthCircuit, get_r1cs, Nova, ProverParams, VerifierParams,
     },
     frontend::FCircuit,
-    transcript::poseidon::poseidon_test_config,
+    transcript::poseidon::poseidon_canonical_config,
     FoldingScheme,
 };
 
-// This method computes the Nova's Prover & Verifier parameters for the example.
+// This method computes the Nova's Prover & Verifier parameters for the example, from a random
+// seed which is printed so that the run can be reproduced with `init_nova_ivc_params_with_seed`.
 // Warning: this method is only for testing purposes. For a real world use case those parameters
 // should be generated carefully (both the PoseidonConfig and the PedersenParams).
 #[allow(clippy::type_complexity)]
@@ -36,10 +38,28 @@ pub(crate) fn init_nova_ivc_params<FC: FCircuit<Fr>>(
     VerifierParams<G1, G2>,
     KZGVerifierKey<Bn254>,
 ) {
-    let mut rng = ark_std::test_rng();
-    let poseidon_config = poseidon_test_config::<Fr>();
+    let seed = rand::random::<u64>();
+    println!("Nova params seed: {}", seed);
+    init_nova_ivc_params_with_seed(F_circuit, seed)
+}
+
+// Computes the same parameters as `init_nova_ivc_params`, drawing all the randomness (the KZG
+// and Pedersen params) from an rng seeded with the given seed, so that two calls with the same
+// seed return identical parameters.
+#[allow(clippy::type_complexity)]
+pub(crate) fn init_nova_ivc_params_with_seed<FC: FCircuit<Fr>>(
+    F_circuit: FC,
+    seed: u64,
+) -> (
+    ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
+    VerifierParams<G1, G2>,
+    KZGVerifierKey<Bn254>,
+) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let poseidon_config = poseidon_canonical_config::<Fr>().unwrap();
 
     // get the CM & CF_CM len
+    let state_len = F_circuit.state_len();
     let (r1cs, cf_r1cs) = get_r1cs::<G1, GVar, G2, GVar2, FC>(&poseidon_config, F_circuit).unwrap();
     let cs_len = r1cs.A.n_rows;
     let cf_cs_len = cf_r1cs.A.n_rows;
@@ -58,6 +78,7 @@ pub(crate) fn init_nova_ivc_params<FC: FCircuit<Fr>>(
         poseidon_config: poseidon_config.clone(),
         r1cs,
         cf_r1cs,
+        state_len,
     };
     (fs_prover_params, fs_verifier_params, kzg_vk)
 }
@@ -96,4 +117,34 @@ pub(crate) fn init_ivc_and_decider_params<FC: FCircuit<Fr>>(
     (fs_prover_params, kzg_vk, g16_pk, g16_vk)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_serialize::CanonicalSerialize;
+    use folding_schemes::frontend::examples::SyntheticFCircuit;
+
+    #[test]
+    fn test_init_nova_ivc_params_with_seed() {
+        let F_circuit = SyntheticFCircuit::<Fr>::new(10).unwrap();
+        let (pp_a, vp_a, kzg_vk_a) = init_nova_ivc_params_with_seed(F_circuit, 42);
+        let (pp_b, vp_b, kzg_vk_b) = init_nova_ivc_params_with_seed(F_circuit, 42);
+        let (pp_c, _, kzg_vk_c) = init_nova_ivc_params_with_seed(F_circuit, 43);
+
+        let to_bytes = |vk: &KZGVerifierKey<Bn254>| {
+            let mut bytes = vec![];
+            vk.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        assert_eq!(vp_a.to_bytes().unwrap(), vp_b.to_bytes().unwrap());
+        assert_eq!(pp_a.cs_params.powers_of_g, pp_b.cs_params.powers_of_g);
+        assert_eq!(pp_a.cf_cs_params, pp_b.cf_cs_params);
+        assert_eq!(to_bytes(&kzg_vk_a), to_bytes(&kzg_vk_b));
+
+        // a different seed gives different commitment params
+        assert_ne!(pp_a.cs_params.powers_of_g, pp_c.cs_params.powers_of_g);
+        assert_ne!(pp_a.cf_cs_params, pp_c.cf_cs_params);
+        assert_ne!(to_bytes(&kzg_vk_a), to_bytes(&kzg_vk_c));
+    }
+}
+
 fn main() {}
@@ -0,0 +1,50 @@
+// compares the KZG verification with and without the precomputed verifier data of
+// `PreparedVerifier`, verifying many proofs against the same evaluation points
+use ark_bn254::{Bn254, Fr};
+use ark_std::{test_rng, UniformRand, Zero};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use folding_schemes::commitment::{
+    kzg::{PreparedVerifier, KZG},
+    CommitmentScheme,
+};
+
+fn bench_kzg_verify(c: &mut Criterion) {
+    let mut rng = test_rng();
+    let n = 1 << 10;
+    let (pk, vk) = KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+    let points: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+        .take(4)
+        .collect();
+    let proofs: Vec<_> = points
+        .iter()
+        .map(|z| {
+            let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+                .take(n)
+                .collect();
+            let cm = KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+            let proof = KZG::<Bn254>::prove_with_challenge(&pk, *z, &v, &Fr::zero(), None).unwrap();
+            (*z, cm, proof)
+        })
+        .collect();
+    let prepared_vk = PreparedVerifier::<Bn254>::new(&vk, &points);
+
+    c.bench_function("kzg_verify", |b| {
+        b.iter(|| {
+            for (z, cm, proof) in proofs.iter() {
+                KZG::<Bn254>::verify_with_challenge(&vk, *z, cm, proof).unwrap();
+            }
+        })
+    });
+    c.bench_function("kzg_prepared_verify", |b| {
+        b.iter(|| {
+            for (z, cm, proof) in proofs.iter() {
+                prepared_vk.verify_with_challenge(*z, cm, proof).unwrap();
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_kzg_verify);
+criterion_main!(benches);
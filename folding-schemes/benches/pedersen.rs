@@ -0,0 +1,35 @@
+// compares the serial msm of the Pedersen commitments with the chunked `parallel_msm` used by
+// `Pedersen::commit` when the `parallel` feature is enabled
+use ark_bn254::{Fr, G1Projective as Projective};
+use ark_ec::VariableBaseMSM;
+use ark_std::{test_rng, UniformRand};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use folding_schemes::commitment::{
+    pedersen::{parallel_msm, Pedersen},
+    CommitmentScheme,
+};
+
+fn bench_pedersen_commit(c: &mut Criterion) {
+    let mut rng = test_rng();
+    let n = 1 << 16;
+    let (params, _) = Pedersen::<Projective>::setup(&mut rng, n).unwrap();
+    let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+        .take(n)
+        .collect();
+    let bases = &params.generators[..n];
+    assert_eq!(
+        Projective::msm_unchecked(bases, &v),
+        parallel_msm::<Projective>(bases, &v)
+    );
+
+    c.bench_function("pedersen_commit_serial_2^16", |b| {
+        b.iter(|| Projective::msm_unchecked(bases, &v))
+    });
+    c.bench_function("pedersen_commit_parallel_2^16", |b| {
+        b.iter(|| parallel_msm::<Projective>(bases, &v))
+    });
+}
+
+criterion_group!(benches, bench_pedersen_commit);
+criterion_main!(benches);
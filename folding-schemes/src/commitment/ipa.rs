@@ -26,7 +26,7 @@ use super::{pedersen::Params as PedersenParams, CommitmentScheme};
 use crate::transcript::Transcript;
 use crate::utils::{
     powers_of,
-    vec::{vec_add, vec_scalar_mul},
+    vec::{pad_to_next_power_of_two, vec_add, vec_scalar_mul},
 };
 use crate::Error;
 
@@ -68,13 +68,37 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         Ok((p.clone(), p))
     }
 
+    /// note that IPA proofs are computed over vectors of power of two length, so the params need
+    /// to fit `len` padded to the next power of two.
+    fn fits(params: &Self::ProverParams, len: usize) -> bool {
+        params.generators.len() >= len.next_power_of_two()
+    }
+
+    /// the largest power of two not greater than the number of generators
+    fn max_len(params: &Self::ProverParams) -> usize {
+        match params.generators.len() {
+            0 => 0,
+            n => 1 << n.ilog2(),
+        }
+    }
+
+    fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool {
+        a == b
+    }
+
+    /// commits to `a`. Since padding a vector with zeros does not change its commitment, the
+    /// commitment is also the one of `a` padded to the next power of two, which is the vector over
+    /// which the proofs are computed.
     fn commit(
         params: &PedersenParams<C>,
         a: &[C::ScalarField],
         r: &C::ScalarField, // blinding factor
     ) -> Result<C, Error> {
         if params.generators.len() < a.len() {
-            return Err(Error::PedersenParamsLen(params.generators.len(), a.len()));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: a.len(),
+            });
         }
         if !H && (!r.is_zero()) {
             return Err(Error::BlindingNotZero);
@@ -88,6 +112,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         Ok(params.h.mul(r) + C::msm_unchecked(&params.generators[..a.len()], a))
     }
 
+    /// proves the opening of the commitment to `a`. The IPA requires vectors of power of two
+    /// length, so `a` is padded with zeros up to the next power of two, which changes neither the
+    /// commitment nor the evaluation `v=p(x)`. The verifier takes the padded length from the
+    /// number of rounds of the proof.
     fn prove(
         params: &Self::ProverParams,
         transcript: &mut impl Transcript<C>,
@@ -96,9 +124,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         blind: &C::ScalarField,
         rng: Option<&mut dyn RngCore>,
     ) -> Result<Self::Proof, Error> {
-        if !a.len().is_power_of_two() {
-            return Err(Error::NotPowerOfTwo("a".to_string(), a.len()));
-        }
+        let mut a = pad_to_next_power_of_two(a.to_vec());
         if !H && (!blind.is_zero()) {
             return Err(Error::BlindingNotZero);
         }
@@ -106,7 +132,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         let k = (f64::from(d as u32).log2()) as usize;
 
         if params.generators.len() < a.len() {
-            return Err(Error::PedersenParamsLen(params.generators.len(), a.len()));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: a.len(),
+            });
         }
         // blinding factors
         let l: Vec<C::ScalarField>;
@@ -129,7 +158,6 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         let s = transcript.get_challenge();
         let U = C::generator().mul(s);
 
-        let mut a = a.to_owned();
         let mut b = powers_of(x, d);
         let v = inner_prod(&a, &b)?;
 
@@ -160,9 +188,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
             u[j] = transcript.get_challenge();
 
             let uj = u[j];
-            let uj_inv = u[j]
-                .inverse()
-                .ok_or(Error::Other("error on computing inverse".to_string()))?;
+            let uj_inv = u[j].inverse().ok_or(Error::ZeroInverse)?;
 
             // a_hi * uj^-1 + a_lo * uj
             a = vec_add(
@@ -183,13 +209,22 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         }
 
         if a.len() != 1 {
-            return Err(Error::NotExpectedLength(a.len(), 1));
+            return Err(Error::NotExpectedLength {
+                got: a.len(),
+                expected: 1,
+            });
         }
         if b.len() != 1 {
-            return Err(Error::NotExpectedLength(b.len(), 1));
+            return Err(Error::NotExpectedLength {
+                got: b.len(),
+                expected: 1,
+            });
         }
         if G.len() != 1 {
-            return Err(Error::NotExpectedLength(G.len(), 1));
+            return Err(Error::NotExpectedLength {
+                got: G.len(),
+                expected: 1,
+            });
         }
 
         Ok((
@@ -213,7 +248,9 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         _rng: Option<&mut dyn RngCore>,
     ) -> Result<Self::Proof, Error> {
         // not supported because the prover logic computes challenges as it advances on the logic
-        Err(Error::NotSupported("IPA::prove_with_challenge".to_string()))
+        Err(Error::NotSupported {
+            feature: "IPA::prove_with_challenge",
+        })
     }
 
     fn verify(
@@ -271,9 +308,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         // compute u[i]^-1 once
         let mut u_invs = vec![C::ScalarField::zero(); u.len()];
         for (j, u_j) in u.iter().enumerate() {
-            u_invs[j] = u_j
-                .inverse()
-                .ok_or(Error::Other("error on computing inverse".to_string()))?;
+            u_invs[j] = u_j.inverse().ok_or(Error::ZeroInverse)?;
         }
 
         // compute b & G from s
@@ -282,7 +317,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for IPA<C, H> {
         let b = s_b_inner(&u, &x)?;
         let d: usize = 2_u64.pow(k as u32) as usize;
         if params.generators.len() < d {
-            return Err(Error::PedersenParamsLen(params.generators.len(), d));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: d,
+            });
         }
         let G = C::msm_unchecked(&params.generators, &s);
 
@@ -383,12 +421,12 @@ fn build_s_gadget<F: PrimeField, CF: PrimeField>(
 
 fn inner_prod<F: PrimeField>(a: &[F], b: &[F]) -> Result<F, Error> {
     if a.len() != b.len() {
-        return Err(Error::NotSameLength(
-            "a".to_string(),
-            a.len(),
-            "b".to_string(),
-            b.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "a",
+            a_len: a.len(),
+            b: "b",
+            b_len: b.len(),
+        });
     }
     let c = cfg_iter!(a)
         .zip(cfg_iter!(b))
@@ -403,10 +441,7 @@ fn s_b_inner<F: PrimeField>(u: &[F], x: &F) -> Result<F, Error> {
     let mut c: F = F::one();
     let mut x_2_i = *x; // x_2_i is x^{2^i}, starting from x^{2^0}=x
     for u_i in u.iter() {
-        c *= (*u_i * x_2_i)
-            + u_i
-                .inverse()
-                .ok_or(Error::Other("error on computing inverse".to_string()))?;
+        c *= (*u_i * x_2_i) + u_i.inverse().ok_or(Error::ZeroInverse)?;
         x_2_i *= x_2_i;
     }
     Ok(c)
@@ -563,7 +598,7 @@ mod tests {
     use ark_pallas::{constraints::GVar, Fq, Fr, Projective};
     use ark_r1cs_std::{alloc::AllocVar, bits::boolean::Boolean, eq::EqGadget};
     use ark_relations::r1cs::ConstraintSystem;
-    use ark_std::UniformRand;
+    use ark_std::{One, UniformRand};
     use std::ops::Mul;
 
     use super::*;
@@ -613,6 +648,65 @@ mod tests {
         IPA::<Projective, hiding>::verify(&params, &mut transcript_v, &cm, &proof).unwrap();
     }
 
+    #[test]
+    fn test_ipa_tampered_proof() {
+        test_ipa_tampered_proof_opt::<false>();
+        test_ipa_tampered_proof_opt::<true>();
+    }
+    fn test_ipa_tampered_proof_opt<const hiding: bool>() {
+        let mut rng = ark_std::test_rng();
+        // not a power of two, so the vector is padded to 16 elements and the proof has 4 rounds
+        let d = 13;
+
+        let (params, _) = IPA::<Projective, hiding>::setup(&mut rng, d).unwrap();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let a: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(d)
+            .collect();
+        let r_blind: Fr = if hiding {
+            Fr::rand(&mut rng)
+        } else {
+            Fr::zero()
+        };
+        let cm = IPA::<Projective, hiding>::commit(&params, &a, &r_blind).unwrap();
+
+        let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let proof = IPA::<Projective, hiding>::prove(
+            &params,
+            &mut transcript_p,
+            &cm,
+            &a,
+            &r_blind,
+            Some(&mut rng),
+        )
+        .unwrap();
+        assert_eq!(proof.0.L.len(), 4);
+
+        let verify = |proof: &(Proof<Projective>, Fr, Fr)| {
+            let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+            IPA::<Projective, hiding>::verify(&params, &mut transcript_v, &cm, proof)
+        };
+        verify(&proof).unwrap();
+
+        // tampered evaluation
+        let mut tampered = proof.clone();
+        tampered.1 += Fr::one();
+        assert!(verify(&tampered).is_err());
+        // tampered final element of the folded vector
+        let mut tampered = proof.clone();
+        tampered.0.a += Fr::one();
+        assert!(verify(&tampered).is_err());
+        // tampered cross term of a round
+        let mut tampered = proof.clone();
+        tampered.0.L.swap(0, 1);
+        assert!(verify(&tampered).is_err());
+        // missing round
+        let mut tampered = proof;
+        tampered.0.R.pop();
+        assert!(verify(&tampered).is_err());
+    }
+
     #[test]
     fn test_ipa_gadget() {
         test_ipa_gadget_opt::<false>();
@@ -5,7 +5,7 @@
 /// vectors indistinctly, and the arkworks KZG10 implementation contains all the methods under the
 /// same trait, which requires the Pairing trait, where the prover does not need access to the
 /// Pairing but only to G1.
-use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
 use ark_ff::PrimeField;
 use ark_poly::{
     univariate::{DenseOrSparsePolynomial, DensePolynomial},
@@ -16,7 +16,7 @@ use ark_poly_commit::kzg10::{
 };
 use ark_std::rand::RngCore;
 use ark_std::{borrow::Cow, fmt::Debug};
-use ark_std::{One, Zero};
+use ark_std::{One, UniformRand, Zero};
 use core::marker::PhantomData;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
@@ -40,6 +40,14 @@ pub struct Proof<C: CurveGroup> {
 }
 
 /// KZG implements the CommitmentScheme trait for the KZG commitment scheme.
+///
+/// The SRS given by `setup` (or loaded from a trusted setup ceremony) must contain at least as many
+/// powers of `g` as the length of the longest committed vector, since the vectors are committed as
+/// the coefficients of a polynomial. For Nova these are the witness and the error term of the
+/// AugmentedFCircuit, so the SRS needs the number of constraints of the AugmentedFCircuit (the
+/// length returned by `get_cs_params_len`) rounded up to the next power of two. `Nova::init`
+/// checks it through `ProverParams::max_circuit_size`. The verifier key and the powers of `g` are
+/// the ones used by the Solidity `KZG10Verifier`.
 #[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct KZG<'a, E: Pairing, const H: bool = false> {
     _a: PhantomData<&'a ()>,
@@ -80,17 +88,37 @@ where
         Ok((powers, vk))
     }
 
+    fn fits(params: &Self::ProverParams, len: usize) -> bool {
+        params.powers_of_g.len() >= len
+    }
+
+    /// since the vectors are padded to the next power of two before being interpolated, this is
+    /// the largest power of two not greater than the number of powers of g
+    fn max_len(params: &Self::ProverParams) -> usize {
+        match params.powers_of_g.len() {
+            0 => 0,
+            n => 1 << n.ilog2(),
+        }
+    }
+
+    fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool {
+        a.powers_of_g == b.powers_of_g
+    }
+
     /// commit implements the CommitmentScheme commit interface, adapting the implementation from
     /// https://github.com/arkworks-rs/poly-commit/tree/c724fa666e935bbba8db5a1421603bab542e15ab/poly-commit/src/kzg10/mod.rs#L178
     /// with the main difference being the removal of the blinding factors and the no-dependency to
     /// the Pairing trait.
+    /// The vector `v` is interpolated over a power of two domain, padding it with zeros up to the
+    /// next power of two length, so that the committed polynomial (and the evaluations of the
+    /// opening proofs) are the ones of the padded vector.
     fn commit(
         params: &Self::ProverParams,
         v: &[E::ScalarField],
         _blind: &E::ScalarField,
     ) -> Result<E::G1, Error> {
         if !_blind.is_zero() || H {
-            return Err(Error::NotSupportedYet("hiding".to_string()));
+            return Err(Error::NotSupportedYet { feature: "hiding" });
         }
 
         let polynomial = poly_from_vec(v.to_vec())?;
@@ -130,7 +158,7 @@ where
         _rng: Option<&mut dyn RngCore>,
     ) -> Result<Self::Proof, Error> {
         if !_blind.is_zero() || H {
-            return Err(Error::NotSupportedYet("hiding".to_string()));
+            return Err(Error::NotSupportedYet { feature: "hiding" });
         }
 
         let polynomial = poly_from_vec(v.to_vec())?;
@@ -185,7 +213,7 @@ where
         proof: &Self::Proof,
     ) -> Result<(), Error> {
         if H {
-            return Err(Error::NotSupportedYet("hiding".to_string()));
+            return Err(Error::NotSupportedYet { feature: "hiding" });
         }
 
         // verify the KZG proof using arkworks method
@@ -206,6 +234,215 @@ where
     }
 }
 
+/// PairingAccumulator accumulates the pairing checks of multiple KZG proofs, so that all of them
+/// are verified with a single final pairing check, see `KZG::accumulate_verify` and
+/// `KZG::finalize`.
+///
+/// Each KZG check `e(cm - eval⋅g, h) == e(proof, β⋅h - z⋅h)` is rewritten as
+/// `e(cm - eval⋅g + z⋅proof, h) == e(proof, β⋅h)`, and the checks are combined through a random
+/// linear combination, so that the final check is
+/// `e(Σ r_i⋅(cm_i - eval_i⋅g + z_i⋅proof_i), h) == e(Σ r_i⋅proof_i, β⋅h)`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PairingAccumulator<E: Pairing> {
+    /// Σ r_i⋅(cm_i - eval_i⋅g + z_i⋅proof_i)
+    pub lhs: E::G1,
+    /// Σ r_i⋅proof_i
+    pub rhs: E::G1,
+    /// number of accumulated proofs
+    pub count: usize,
+}
+
+impl<E: Pairing> Default for PairingAccumulator<E> {
+    fn default() -> Self {
+        Self {
+            lhs: E::G1::zero(),
+            rhs: E::G1::zero(),
+            count: 0,
+        }
+    }
+}
+
+impl<E: Pairing> PairingAccumulator<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a, E: Pairing, const H: bool> KZG<'a, E, H> {
+    /// accumulates the pairing check of the given KZG proof into `acc` instead of performing it,
+    /// the check is done when calling `finalize` over the accumulator. The randomness for the
+    /// linear combination is sampled from the given rng, which should be a cryptographically
+    /// secure rng that is not controlled by the prover.
+    pub fn accumulate_verify(
+        params: &VerifierKey<E>,
+        challenge: E::ScalarField,
+        cm: &E::G1,
+        proof: &Proof<E::G1>,
+        acc: &mut PairingAccumulator<E>,
+        rng: &mut impl RngCore,
+    ) -> Result<(), Error> {
+        if H {
+            return Err(Error::NotSupportedYet { feature: "hiding" });
+        }
+        let r = E::ScalarField::rand(rng);
+        acc.lhs += (*cm - params.g * proof.eval + proof.proof * challenge) * r;
+        acc.rhs += proof.proof * r;
+        acc.count += 1;
+        Ok(())
+    }
+
+    /// performs the final pairing check of the accumulator, returns true if all the accumulated
+    /// proofs are valid
+    pub fn finalize(params: &VerifierKey<E>, acc: &PairingAccumulator<E>) -> bool {
+        E::pairing(acc.lhs, params.h) == E::pairing(acc.rhs, params.beta_h)
+    }
+}
+
+/// PreparedVerifier contains the verifier data of the KZG opening proofs at a fixed set of
+/// evaluation points, precomputed once and reused across the verifications: for each point `z`
+/// the prepared `β⋅h - z⋅h` is stored, so that each check `e(cm - eval⋅g, h) == e(proof, β⋅h -
+/// z⋅h)` only computes a multi-pairing with prepared G2 elements. This speeds up verifying many
+/// proofs against the same evaluation points.
+#[derive(Debug, Clone)]
+pub struct PreparedVerifier<E: Pairing> {
+    g: E::G1Affine,
+    h: E::G2Affine,
+    beta_h: E::G2Affine,
+    prepared_h: E::G2Prepared,
+    /// evaluation points together with the prepared `β⋅h - z⋅h`
+    points: Vec<(E::ScalarField, E::G2Prepared)>,
+}
+
+impl<E: Pairing> PreparedVerifier<E> {
+    /// precomputes the verifier data of the given verifier key for the given evaluation points
+    pub fn new(vk: &VerifierKey<E>, points: &[E::ScalarField]) -> Self {
+        let points = points
+            .iter()
+            .map(|z| {
+                (
+                    *z,
+                    (vk.beta_h.into_group() - vk.h * *z).into_affine().into(),
+                )
+            })
+            .collect();
+        Self {
+            g: vk.g,
+            h: vk.h,
+            beta_h: vk.beta_h,
+            prepared_h: vk.h.into(),
+            points,
+        }
+    }
+
+    /// verifies the KZG proof of `cm` at the evaluation point `challenge`, equivalent to
+    /// `KZG::verify_with_challenge`. Points that were not given at `new` are also supported, but
+    /// their verifier data is computed at each call.
+    pub fn verify_with_challenge(
+        &self,
+        challenge: E::ScalarField,
+        cm: &E::G1,
+        proof: &Proof<E::G1>,
+    ) -> Result<(), Error> {
+        let lhs = *cm - self.g * proof.eval;
+        let rhs = -proof.proof;
+        let v = match self.points.iter().find(|(z, _)| *z == challenge) {
+            Some((_, prepared_beta_z_h)) => E::multi_pairing(
+                [lhs, rhs],
+                [self.prepared_h.clone(), prepared_beta_z_h.clone()],
+            ),
+            None => E::multi_pairing(
+                [lhs, rhs],
+                [
+                    self.h,
+                    (self.beta_h.into_group() - self.h * challenge).into_affine(),
+                ],
+            ),
+        };
+        if !v.is_zero() {
+            return Err(Error::CommitmentVerificationFail);
+        }
+        Ok(())
+    }
+}
+
+/// PairingBackend computes the final pairing check of the KZG verification, so that it can be
+/// routed to an external backend (eg. an HSM or a remote service) instead of being computed in
+/// software, see `KZG::verify_with_backend`.
+pub trait PairingBackend<E: Pairing> {
+    /// returns whether the product of the pairings `Π e(a_i, b_i)` is the identity of the target
+    /// group
+    fn check_product_of_pairings(
+        &self,
+        a: &[E::G1Affine],
+        b: &[E::G2Affine],
+    ) -> Result<bool, Error>;
+}
+
+/// SoftwarePairing is the default PairingBackend, which computes the pairings locally.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SoftwarePairing;
+
+impl<E: Pairing> PairingBackend<E> for SoftwarePairing {
+    fn check_product_of_pairings(
+        &self,
+        a: &[E::G1Affine],
+        b: &[E::G2Affine],
+    ) -> Result<bool, Error> {
+        if a.len() != b.len() {
+            return Err(Error::NotSameLength {
+                a: "a.len()",
+                a_len: a.len(),
+                b: "b.len()",
+                b_len: b.len(),
+            });
+        }
+        Ok(E::multi_pairing(a.iter().copied(), b.iter().copied()).is_zero())
+    }
+}
+
+/// ExternalPairing is the hook to plug an external PairingBackend from a function, which receives
+/// the points of the pairing check and returns its result.
+#[derive(Debug, Clone, Copy)]
+pub struct ExternalPairing<F>(pub F);
+
+impl<E, F> PairingBackend<E> for ExternalPairing<F>
+where
+    E: Pairing,
+    F: Fn(&[E::G1Affine], &[E::G2Affine]) -> Result<bool, Error>,
+{
+    fn check_product_of_pairings(
+        &self,
+        a: &[E::G1Affine],
+        b: &[E::G2Affine],
+    ) -> Result<bool, Error> {
+        (self.0)(a, b)
+    }
+}
+
+impl<'a, E: Pairing, const H: bool> KZG<'a, E, H> {
+    /// verifies the KZG proof of `cm` at the evaluation point `challenge` as
+    /// `verify_with_challenge`, where the final pairing check `e(cm - eval⋅g, h) ⋅ e(-proof, β⋅h -
+    /// z⋅h) == 1` is computed by the given PairingBackend
+    pub fn verify_with_backend(
+        params: &VerifierKey<E>,
+        challenge: E::ScalarField,
+        cm: &E::G1,
+        proof: &Proof<E::G1>,
+        backend: &impl PairingBackend<E>,
+    ) -> Result<(), Error> {
+        if H {
+            return Err(Error::NotSupportedYet { feature: "hiding" });
+        }
+        let lhs = (*cm - params.g * proof.eval).into_affine();
+        let rhs = (-proof.proof).into_affine();
+        let beta_z_h = (params.beta_h.into_group() - params.h * challenge).into_affine();
+        if !backend.check_product_of_pairings(&[lhs, rhs], &[params.h, beta_z_h])? {
+            return Err(Error::CommitmentVerificationFail);
+        }
+        Ok(())
+    }
+}
+
 fn check_degree_is_too_large(
     degree: usize,
     num_powers: usize,
@@ -240,11 +477,12 @@ fn convert_to_bigints<F: PrimeField>(p: &[F]) -> Vec<F::BigInt> {
 
 #[cfg(test)]
 mod tests {
-    use ark_bn254::{Bn254, Fr, G1Projective as G1};
+    use ark_bn254::{Bn254, Fr, G1Affine, G1Projective as G1, G2Affine};
     use ark_std::{test_rng, UniformRand};
 
     use super::*;
     use crate::transcript::poseidon::{poseidon_test_config, PoseidonTranscript};
+    use crate::transcript::Transcript;
 
     #[test]
     fn test_kzg_commitment_scheme() {
@@ -265,4 +503,129 @@ mod tests {
         // verify the proof:
         KZG::<Bn254>::verify(&vk, transcript_v, &cm, &proof).unwrap();
     }
+
+    #[test]
+    fn test_kzg_pairing_accumulator() {
+        let mut rng = &mut test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let n = 10;
+        let (pk, vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+        // generate 5 proofs
+        let mut proofs = vec![];
+        for _ in 0..5 {
+            let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+            let cm = KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+            let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+            let proof = KZG::<Bn254>::prove(&pk, transcript_p, &cm, &v, &Fr::zero(), None).unwrap();
+            let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+            transcript_v.absorb_point(&cm).unwrap();
+            let challenge = transcript_v.get_challenge();
+            proofs.push((challenge, cm, proof));
+        }
+
+        let mut acc = PairingAccumulator::<Bn254>::new();
+        for (challenge, cm, proof) in proofs.iter() {
+            KZG::<Bn254>::accumulate_verify(&vk, *challenge, cm, proof, &mut acc, &mut rng)
+                .unwrap();
+        }
+        assert_eq!(acc.count, 5);
+        assert!(KZG::<Bn254>::finalize(&vk, &acc));
+
+        // one invalid proof makes the final check fail
+        proofs[2].2.eval += Fr::one();
+        let mut acc = PairingAccumulator::<Bn254>::new();
+        for (challenge, cm, proof) in proofs.iter() {
+            KZG::<Bn254>::accumulate_verify(&vk, *challenge, cm, proof, &mut acc, &mut rng)
+                .unwrap();
+        }
+        assert!(!KZG::<Bn254>::finalize(&vk, &acc));
+    }
+
+    #[test]
+    fn test_kzg_prepared_verifier() {
+        let mut rng = &mut test_rng();
+
+        let n = 10;
+        let (pk, vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+        let points: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(3).collect();
+        let prepared_vk = PreparedVerifier::<Bn254>::new(&vk, &points);
+
+        // proofs at the prepared points and at a point which was not prepared
+        for z in [points.clone(), vec![Fr::rand(rng)]].concat() {
+            let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+            let cm = KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+            let mut proof =
+                KZG::<Bn254>::prove_with_challenge(&pk, z, &v, &Fr::zero(), None).unwrap();
+            KZG::<Bn254>::verify_with_challenge(&vk, z, &cm, &proof).unwrap();
+            prepared_vk.verify_with_challenge(z, &cm, &proof).unwrap();
+
+            // both verifications reject an invalid proof
+            proof.eval += Fr::one();
+            assert!(KZG::<Bn254>::verify_with_challenge(&vk, z, &cm, &proof).is_err());
+            assert!(prepared_vk.verify_with_challenge(z, &cm, &proof).is_err());
+        }
+    }
+
+    #[test]
+    fn test_kzg_pairing_backend() {
+        let mut rng = &mut test_rng();
+
+        /// backend that counts its invocations and computes the pairings in software
+        struct MockPairing {
+            calls: std::cell::Cell<usize>,
+        }
+        impl PairingBackend<Bn254> for MockPairing {
+            fn check_product_of_pairings(
+                &self,
+                a: &[G1Affine],
+                b: &[G2Affine],
+            ) -> Result<bool, Error> {
+                self.calls.set(self.calls.get() + 1);
+                PairingBackend::<Bn254>::check_product_of_pairings(&SoftwarePairing, a, b)
+            }
+        }
+        let mock = MockPairing {
+            calls: std::cell::Cell::new(0),
+        };
+
+        let n = 10;
+        let (pk, vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+        let cm = KZG::<Bn254>::commit(&pk, &v, &Fr::zero()).unwrap();
+        let z = Fr::rand(rng);
+        let mut proof = KZG::<Bn254>::prove_with_challenge(&pk, z, &v, &Fr::zero(), None).unwrap();
+
+        // the result through the backend matches the one of the software verification, for a
+        // valid and an invalid proof
+        for valid in [true, false] {
+            let calls = mock.calls.get();
+            let expected = KZG::<Bn254>::verify_with_challenge(&vk, z, &cm, &proof);
+            assert_eq!(expected.is_ok(), valid);
+            assert_eq!(
+                KZG::<Bn254>::verify_with_backend(&vk, z, &cm, &proof, &mock).is_ok(),
+                valid
+            );
+            assert_eq!(mock.calls.get(), calls + 1);
+            assert_eq!(
+                KZG::<Bn254>::verify_with_backend(&vk, z, &cm, &proof, &SoftwarePairing).is_ok(),
+                valid
+            );
+            proof.eval += Fr::one();
+        }
+
+        // an external backend rejecting the pairing check makes the verification fail
+        proof.eval -= Fr::from(2_u32);
+        let rejecting =
+            ExternalPairing(|_: &[G1Affine], _: &[G2Affine]| -> Result<bool, Error> { Ok(false) });
+        assert!(matches!(
+            KZG::<Bn254>::verify_with_backend(&vk, z, &cm, &proof, &rejecting),
+            Err(Error::CommitmentVerificationFail)
+        ));
+        KZG::<Bn254>::verify_with_challenge(&vk, z, &cm, &proof).unwrap();
+    }
 }
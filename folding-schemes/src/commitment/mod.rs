@@ -8,6 +8,19 @@ use crate::Error;
 pub mod ipa;
 pub mod kzg;
 pub mod pedersen;
+pub mod split;
+pub mod testing;
+
+/// CommitmentHandle is returned by `CommitmentScheme::commit_deferred`, it contains the
+/// commitment together with the committed vector and blinding factor, so that the opening proof
+/// can be computed later through `CommitmentScheme::open`, once the challenge is known. This
+/// allows to use the commitment schemes inside larger interactive protocols.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CommitmentHandle<C: CurveGroup> {
+    pub cm: C,
+    v: Vec<C::ScalarField>,
+    blind: C::ScalarField,
+}
 
 /// CommitmentScheme defines the vector commitment scheme trait. Where `H` indicates if to use the
 /// commitment in hiding mode or not.
@@ -23,12 +36,49 @@ pub trait CommitmentScheme<C: CurveGroup, const H: bool = false>: Clone + Debug
         len: usize,
     ) -> Result<(Self::ProverParams, Self::VerifierParams), Error>;
 
+    /// returns true if the given params are large enough to commit to vectors of `len` elements
+    fn fits(params: &Self::ProverParams, len: usize) -> bool;
+
+    /// returns the maximum length of the vectors that can be committed with the given params
+    fn max_len(params: &Self::ProverParams) -> usize;
+
+    /// returns true if both params are the same commitment key, so that the commitments computed
+    /// with them can be folded together
+    fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool;
+
     fn commit(
         params: &Self::ProverParams,
         v: &[C::ScalarField],
         blind: &C::ScalarField,
     ) -> Result<C, Error>;
 
+    /// commit phase of the deferred opening: commits to `v` and returns a handle that can be
+    /// opened later with `open`
+    fn commit_deferred(
+        params: &Self::ProverParams,
+        v: &[C::ScalarField],
+        blind: &C::ScalarField,
+    ) -> Result<CommitmentHandle<C>, Error> {
+        let cm = Self::commit(params, v, blind)?;
+        Ok(CommitmentHandle {
+            cm,
+            v: v.to_vec(),
+            blind: *blind,
+        })
+    }
+
+    /// opening phase of the deferred opening: computes the opening proof of the commitment
+    /// contained in the given handle for the given challenge. The proof can be verified with
+    /// `verify_with_challenge`.
+    fn open(
+        params: &Self::ProverParams,
+        handle: &CommitmentHandle<C>,
+        challenge: Self::ProverChallenge,
+        rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        Self::prove_with_challenge(params, challenge, &handle.v, &handle.blind, rng)
+    }
+
     fn prove(
         params: &Self::ProverParams,
         transcript: &mut impl Transcript<C>,
@@ -81,6 +131,7 @@ mod tests {
         poseidon::{poseidon_test_config, PoseidonTranscript},
         Transcript,
     };
+    use crate::utils::vec::pad_to_next_power_of_two;
 
     #[test]
     fn test_homomorphic_property_using_Commitment_trait() {
@@ -165,4 +216,87 @@ mod tests {
         let transcript_v = &mut PoseidonTranscript::<C>::new(poseidon_config);
         CS::verify(verifier_params, transcript_v, &cm_3, &proof).unwrap();
     }
+
+    #[test]
+    fn test_deferred_opening() {
+        let mut rng = &mut test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let n: usize = 16;
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+        let (kzg_pk, kzg_vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+        // commit phase, the opening is deferred until the challenge is available
+        let handle = KZG::<Bn254>::commit_deferred(&kzg_pk, &v, &Fr::zero()).unwrap();
+        let cm = KZG::<Bn254>::commit(&kzg_pk, &v, &Fr::zero()).unwrap();
+        assert_eq!(handle.cm, cm);
+
+        // the challenge arrives later, here we derive it the same way as the non-interactive
+        // `prove` does, so that both proofs can be compared
+        let transcript = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        transcript.absorb_point(&handle.cm).unwrap();
+        let challenge = transcript.get_challenge();
+        let deferred_proof = KZG::<Bn254>::open(&kzg_pk, &handle, challenge, None).unwrap();
+
+        // non-interactive path
+        let transcript_p = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        let proof = KZG::<Bn254>::prove(&kzg_pk, transcript_p, &cm, &v, &Fr::zero(), None).unwrap();
+        assert_eq!(deferred_proof, proof);
+
+        KZG::<Bn254>::verify_with_challenge(&kzg_vk, challenge, &handle.cm, &deferred_proof)
+            .unwrap();
+        let transcript_v = &mut PoseidonTranscript::<G1>::new(&poseidon_config);
+        KZG::<Bn254>::verify(&kzg_vk, transcript_v, &cm, &deferred_proof).unwrap();
+    }
+
+    #[test]
+    fn test_non_power_of_two_length() {
+        let mut rng = &mut test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        // the vector is padded to 128 elements
+        let n: usize = 100;
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(rng)).take(n).collect();
+
+        let (pedersen_params, _) = Pedersen::<G1>::setup(&mut rng, n).unwrap();
+        let (kzg_pk, kzg_vk): (ProverKey<G1>, VerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, n).unwrap();
+
+        test_non_power_of_two_length_opt::<G1, IPA<G1>>(
+            &poseidon_config,
+            &pedersen_params,
+            &pedersen_params,
+            &v,
+        );
+        test_non_power_of_two_length_opt::<G1, KZG<Bn254>>(&poseidon_config, &kzg_pk, &kzg_vk, &v);
+    }
+
+    fn test_non_power_of_two_length_opt<C: CurveGroup, CS: CommitmentScheme<C>>(
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        prover_params: &CS::ProverParams,
+        verifier_params: &CS::VerifierParams,
+        v: &[C::ScalarField],
+    ) where
+        <C as ark_ec::Group>::ScalarField: Absorb,
+    {
+        let cm = CS::commit(prover_params, v, &C::ScalarField::zero()).unwrap();
+        // the padding is transparent, committing to the padded vector gives the same commitment
+        let padded_v = pad_to_next_power_of_two(v.to_vec());
+        assert_eq!(padded_v.len(), 128);
+        let padded_cm = CS::commit(prover_params, &padded_v, &C::ScalarField::zero()).unwrap();
+        assert_eq!(cm, padded_cm);
+
+        let transcript_p = &mut PoseidonTranscript::<C>::new(poseidon_config);
+        let proof = CS::prove(
+            prover_params,
+            transcript_p,
+            &cm,
+            v,
+            &C::ScalarField::zero(),
+            None,
+        )
+        .unwrap();
+
+        let transcript_v = &mut PoseidonTranscript::<C>::new(poseidon_config);
+        CS::verify(verifier_params, transcript_v, &cm, &proof).unwrap();
+    }
 }
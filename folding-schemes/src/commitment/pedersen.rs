@@ -2,9 +2,11 @@ use ark_ec::CurveGroup;
 use ark_ff::Field;
 use ark_r1cs_std::{boolean::Boolean, groups::GroupOpsBounds, prelude::CurveVar};
 use ark_relations::r1cs::SynthesisError;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::Zero;
 use ark_std::{rand::RngCore, UniformRand};
 use core::marker::PhantomData;
+use rayon::{iter::ParallelIterator, slice::ParallelSlice};
 
 use super::CommitmentScheme;
 use crate::transcript::Transcript;
@@ -18,17 +20,99 @@ pub struct Proof<C: CurveGroup> {
     pub r_u: C::ScalarField, // blind
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Params<C: CurveGroup> {
     pub h: C,
     pub generators: Vec<C::Affine>,
 }
 
+impl<C: CurveGroup> Params<C> {
+    /// returns the params made of the generators starting at `offset`, which are the ones used by
+    /// `Pedersen::commit_with_offset`. The opening proofs of the commitments with offset are
+    /// computed and verified with these params.
+    pub fn with_offset(&self, offset: usize) -> Result<Self, Error> {
+        if offset > self.generators.len() {
+            return Err(Error::PedersenParamsLen {
+                generators: self.generators.len(),
+                len: offset,
+            });
+        }
+        Ok(Self {
+            h: self.h,
+            generators: self.generators[offset..].to_vec(),
+        })
+    }
+}
+
+/// returns the size of the chunks in which `parallel_msm` splits `n` scalar/base pairs: one chunk
+/// per rayon thread, but never more chunks than pairs
+pub fn msm_chunk_size(n: usize) -> usize {
+    n.div_ceil(rayon::current_num_threads().min(n).max(1))
+        .max(1)
+}
+
+/// computes the multi-scalar multiplication `<bases, scalars>` splitting the pairs into chunks of
+/// `msm_chunk_size` pairs, which are computed in parallel and summed. The result is the same as
+/// the one of the serial `C::msm_unchecked`.
+pub fn parallel_msm<C: CurveGroup>(bases: &[C::Affine], scalars: &[C::ScalarField]) -> C {
+    let chunk_size = msm_chunk_size(scalars.len());
+    bases[..scalars.len()]
+        .par_chunks(chunk_size)
+        .zip(scalars.par_chunks(chunk_size))
+        .map(|(bases, scalars)| C::msm_unchecked(bases, scalars))
+        .sum()
+}
+
+/// computes `<bases, scalars>` with `parallel_msm` when the `parallel` feature is enabled
+#[cfg(feature = "parallel")]
+fn msm<C: CurveGroup>(bases: &[C::Affine], scalars: &[C::ScalarField]) -> C {
+    parallel_msm(bases, scalars)
+}
+
+/// computes `<bases, scalars>` with the serial `C::msm_unchecked`
+#[cfg(not(feature = "parallel"))]
+fn msm<C: CurveGroup>(bases: &[C::Affine], scalars: &[C::ScalarField]) -> C {
+    C::msm_unchecked(bases, scalars)
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Pedersen<C: CurveGroup, const H: bool = false> {
     _c: PhantomData<C>,
 }
 
+impl<C: CurveGroup, const H: bool> Pedersen<C, H> {
+    /// commits to `v` with the generators starting at `offset`, so that multiple vectors can be
+    /// committed with disjoint subsets of the generators of a single key.
+    ///
+    /// The commitments are binding only across disjoint subsets: the commitments to vectors
+    /// within non-overlapping generator ranges can not be confused, since together they are a
+    /// commitment to the concatenated vector under the whole key. But with overlapping ranges
+    /// they can, eg. `v=[0, x]` at offset 0 and `v=[x]` at offset 1 have the same commitment, so
+    /// the ranges of the different parts of the protocol must not overlap. The blinding base `h`
+    /// is shared by all the subsets.
+    pub fn commit_with_offset(
+        params: &Params<C>,
+        v: &[C::ScalarField],
+        r: &C::ScalarField, // blinding factor
+        offset: usize,
+    ) -> Result<C, Error> {
+        if params.generators.len() < offset + v.len() {
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: offset + v.len(),
+            });
+        }
+        if !H && (!r.is_zero()) {
+            return Err(Error::BlindingNotZero);
+        }
+        let generators = &params.generators[offset..offset + v.len()];
+        if !H {
+            return Ok(msm(generators, v));
+        }
+        Ok(params.h.mul(r) + msm::<C>(generators, v))
+    }
+}
+
 /// Implements the CommitmentScheme trait for Pedersen commitments
 impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
     type ProverParams = Params<C>;
@@ -51,24 +135,39 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         Ok((p.clone(), p))
     }
 
+    fn fits(params: &Self::ProverParams, len: usize) -> bool {
+        params.generators.len() >= len
+    }
+
+    fn max_len(params: &Self::ProverParams) -> usize {
+        params.generators.len()
+    }
+
+    fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool {
+        a == b
+    }
+
     fn commit(
         params: &Self::ProverParams,
         v: &[C::ScalarField],
         r: &C::ScalarField, // blinding factor
     ) -> Result<C, Error> {
         if params.generators.len() < v.len() {
-            return Err(Error::PedersenParamsLen(params.generators.len(), v.len()));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: v.len(),
+            });
         }
         if !H && (!r.is_zero()) {
             return Err(Error::BlindingNotZero);
         }
 
         // h⋅r + <g, v>
-        // use msm_unchecked because we already ensured at the if that lengths match
+        // use an unchecked msm because we already ensured at the if that lengths match
         if !H {
-            return Ok(C::msm_unchecked(&params.generators[..v.len()], v));
+            return Ok(msm(&params.generators[..v.len()], v));
         }
-        Ok(params.h.mul(r) + C::msm_unchecked(&params.generators[..v.len()], v))
+        Ok(params.h.mul(r) + msm::<C>(&params.generators[..v.len()], v))
     }
 
     fn prove(
@@ -81,7 +180,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
     ) -> Result<Self::Proof, Error> {
         transcript.absorb_point(cm)?;
         let r1 = transcript.get_challenge();
-        let d = transcript.get_challenges(v.len());
+        let d = transcript.get_challenges_batched(v.len());
 
         // R = h⋅r_1 + <g, d>
         // use msm_unchecked because we already ensured at the if that lengths match
@@ -105,7 +204,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         _rng: Option<&mut dyn RngCore>,
     ) -> Result<Self::Proof, Error> {
         if params.generators.len() < v.len() {
-            return Err(Error::PedersenParamsLen(params.generators.len(), v.len()));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: v.len(),
+            });
         }
         if !H && (!r.is_zero()) {
             return Err(Error::BlindingNotZero);
@@ -131,7 +233,7 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
     ) -> Result<(), Error> {
         transcript.absorb_point(cm)?;
         transcript.get_challenge(); // r_1
-        transcript.get_challenges(proof.u.len()); // d
+        transcript.get_challenges_batched(proof.u.len()); // d
         transcript.absorb_point(&proof.R)?;
         let e = transcript.get_challenge();
         Self::verify_with_challenge(params, e, cm, proof)
@@ -144,10 +246,10 @@ impl<C: CurveGroup, const H: bool> CommitmentScheme<C, H> for Pedersen<C, H> {
         proof: &Proof<C>,
     ) -> Result<(), Error> {
         if params.generators.len() < proof.u.len() {
-            return Err(Error::PedersenParamsLen(
-                params.generators.len(),
-                proof.u.len(),
-            ));
+            return Err(Error::PedersenParamsLen {
+                generators: params.generators.len(),
+                len: proof.u.len(),
+            });
         }
         if !H && (!proof.r_u.is_zero()) {
             return Err(Error::BlindingNotZero);
@@ -209,6 +311,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use ark_ec::VariableBaseMSM;
     use ark_ff::{BigInteger, PrimeField};
     use ark_pallas::{constraints::GVar, Fq, Fr, Projective};
     use ark_r1cs_std::{alloc::AllocVar, eq::EqGadget};
@@ -252,6 +355,80 @@ mod tests {
         Pedersen::<Projective, hiding>::verify(&params, &mut transcript_v, &cm, &proof).unwrap();
     }
 
+    #[test]
+    fn test_parallel_msm() {
+        let mut rng = ark_std::test_rng();
+        let (params, _) = Pedersen::<Projective>::setup(&mut rng, 1 << 10).unwrap();
+        // lengths shorter than, equal to and longer than the number of threads
+        let threads = rayon::current_num_threads();
+        for n in [0, 1, 3, threads, threads + 1, 1 << 10] {
+            let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+                .take(n)
+                .collect();
+            let bases = &params.generators[..n];
+            assert_eq!(
+                parallel_msm::<Projective>(bases, &v),
+                Projective::msm_unchecked(bases, &v)
+            );
+            assert!(n.div_ceil(msm_chunk_size(n)) <= n.max(1));
+        }
+    }
+
+    #[test]
+    fn test_pedersen_commit_with_offset() {
+        test_pedersen_commit_with_offset_opt::<false>();
+        test_pedersen_commit_with_offset_opt::<true>();
+    }
+    fn test_pedersen_commit_with_offset_opt<const hiding: bool>() {
+        let mut rng = ark_std::test_rng();
+
+        let n: usize = 4;
+        let (params, _) = Pedersen::<Projective>::setup(&mut rng, 2 * n).unwrap();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(n)
+            .collect();
+        let r: Fr = if hiding {
+            Fr::rand(&mut rng)
+        } else {
+            Fr::zero()
+        };
+        // the same data committed with two disjoint generator subsets
+        let cm_0 = Pedersen::<Projective, hiding>::commit_with_offset(&params, &v, &r, 0).unwrap();
+        let cm_n = Pedersen::<Projective, hiding>::commit_with_offset(&params, &v, &r, n).unwrap();
+        assert_ne!(cm_0, cm_n);
+        assert_eq!(
+            cm_0,
+            Pedersen::<Projective, hiding>::commit(&params, &v, &r).unwrap()
+        );
+
+        // both commitments open with the params of their offset
+        for (offset, cm) in [(0, cm_0), (n, cm_n)] {
+            let offset_params = params.with_offset(offset).unwrap();
+            let mut transcript_p = PoseidonTranscript::<Projective>::new(&poseidon_config);
+            let mut transcript_v = PoseidonTranscript::<Projective>::new(&poseidon_config);
+            let proof = Pedersen::<Projective, hiding>::prove(
+                &offset_params,
+                &mut transcript_p,
+                &cm,
+                &v,
+                &r,
+                None,
+            )
+            .unwrap();
+            Pedersen::<Projective, hiding>::verify(&offset_params, &mut transcript_v, &cm, &proof)
+                .unwrap();
+        }
+
+        // the vector must fit in the generators after the offset
+        assert!(matches!(
+            Pedersen::<Projective, hiding>::commit_with_offset(&params, &v, &r, n + 1),
+            Err(Error::PedersenParamsLen { .. })
+        ));
+        assert!(params.with_offset(2 * n + 1).is_err());
+    }
+
     #[test]
     fn test_pedersen_circuit() {
         test_pedersen_circuit_opt::<false>();
@@ -0,0 +1,410 @@
+/// Commitment scheme for proving in a two-party split, where the params party holds the
+/// commitment key and the input party holds the inputs and witnesses of the folding scheme.
+///
+/// The input party runs the prover (eg. `Nova::prove_step`) using `SplitCommitment` as its
+/// commitment scheme, whose params are only a handle to the params party (see `ParamsParty`)
+/// together with a pool of precomputed masks (see `Mask`). Each vector `v` to be committed is
+/// sent to the params party as `v + ρ`, where `ρ` is a fresh random mask, and the params party
+/// commits to it with its key. The input party then removes the precomputed commitment to the
+/// mask, `Commit(v + ρ) - Commit(ρ)`, which by the homomorphism of the commitments is the same
+/// commitment that would be obtained proving locally.
+///
+/// The input party never sees the commitment key, and since each mask is uniformly random and
+/// used only once, the params party only sees uniformly random vectors. The masks and their
+/// commitments have to be generated (see `Mask::precompute`) by the input party or by a dealer
+/// that does not collude with the params party.
+use ark_ec::CurveGroup;
+use ark_std::{fmt::Debug, marker::PhantomData, rand::RngCore, UniformRand, Zero};
+use std::sync::{Arc, Mutex};
+
+use super::CommitmentScheme;
+use crate::transcript::Transcript;
+use crate::Error;
+
+/// ParamsParty is the side of the split holding the commitment key, it commits to the masked
+/// vectors sent by the input party. It can be implemented on top of a communication channel to
+/// a remote party, or with `LocalParamsParty` when both parties run in the same process.
+pub trait ParamsParty<C: CurveGroup>: Debug + Send + Sync {
+    /// returns the maximum length of the vectors that can be committed with the key
+    fn max_len(&self) -> usize;
+
+    /// commits to the masked vector `v` with the blinding factor `blind`
+    fn commit_masked(&self, v: &[C::ScalarField], blind: &C::ScalarField) -> Result<C, Error>;
+}
+
+/// LocalParamsParty is a ParamsParty holding the params of the commitment scheme `CS`
+#[derive(Clone, Debug)]
+pub struct LocalParamsParty<C: CurveGroup, CS: CommitmentScheme<C>> {
+    params: CS::ProverParams,
+}
+
+impl<C: CurveGroup, CS: CommitmentScheme<C>> LocalParamsParty<C, CS> {
+    pub fn new(params: CS::ProverParams) -> Self {
+        Self { params }
+    }
+}
+
+impl<C: CurveGroup, CS: CommitmentScheme<C>> ParamsParty<C> for LocalParamsParty<C, CS>
+where
+    CS::ProverParams: Send + Sync,
+{
+    fn max_len(&self) -> usize {
+        CS::max_len(&self.params)
+    }
+    fn commit_masked(&self, v: &[C::ScalarField], blind: &C::ScalarField) -> Result<C, Error> {
+        CS::commit(&self.params, v, blind)
+    }
+}
+
+/// Mask is a random vector `ρ` together with its commitment `Commit(ρ)`, which is used once by
+/// the input party to hide a vector sent to the params party
+#[derive(Clone, Debug)]
+pub struct Mask<C: CurveGroup> {
+    pub rho: Vec<C::ScalarField>,
+    pub cm: C,
+}
+
+impl<C: CurveGroup> Mask<C> {
+    /// samples `n` masks of length `len` and commits to them with the params of `CS`. It has to
+    /// be run by the input party or by a dealer that does not collude with the params party, eg.
+    /// in a preprocessing phase before the key is handed to the params party.
+    pub fn precompute<CS: CommitmentScheme<C>>(
+        params: &CS::ProverParams,
+        len: usize,
+        n: usize,
+        mut rng: impl RngCore,
+    ) -> Result<Vec<Self>, Error> {
+        (0..n)
+            .map(|_| {
+                let rho: Vec<C::ScalarField> =
+                    std::iter::repeat_with(|| C::ScalarField::rand(&mut rng))
+                        .take(len)
+                        .collect();
+                let cm = CS::commit(params, &rho, &C::ScalarField::zero())?;
+                Ok(Self { rho, cm })
+            })
+            .collect()
+    }
+}
+
+/// SplitParams are the params of the input party, which only contain the handle to the params
+/// party and the pool of unused masks, one of which is consumed by each commitment
+#[derive(Clone, Debug)]
+pub struct SplitParams<C: CurveGroup> {
+    pub params_party: Arc<dyn ParamsParty<C>>,
+    masks: Arc<Mutex<Vec<Mask<C>>>>,
+}
+
+impl<C: CurveGroup> SplitParams<C> {
+    /// returns the SplitParams for the given params party, with the given precomputed masks
+    pub fn new(params_party: Arc<dyn ParamsParty<C>>, masks: Vec<Mask<C>>) -> Self {
+        Self {
+            params_party,
+            masks: Arc::new(Mutex::new(masks)),
+        }
+    }
+
+    /// returns the SplitParams of a params party running in the same process with the given
+    /// params of `CS`, with `n_masks` masks of the maximum length supported by the params
+    pub fn local<CS: CommitmentScheme<C>>(
+        params: CS::ProverParams,
+        n_masks: usize,
+        rng: impl RngCore,
+    ) -> Result<Self, Error>
+    where
+        CS: 'static,
+        CS::ProverParams: Send + Sync,
+    {
+        let masks = Mask::precompute::<CS>(&params, CS::max_len(&params), n_masks, rng)?;
+        Ok(Self::new(
+            Arc::new(LocalParamsParty::<C, CS>::new(params)),
+            masks,
+        ))
+    }
+
+    /// adds the given precomputed masks to the pool
+    pub fn add_masks(&self, masks: Vec<Mask<C>>) -> Result<(), Error> {
+        self.masks
+            .lock()
+            .map_err(|e| Error::Other(e.to_string()))?
+            .extend(masks);
+        Ok(())
+    }
+
+    /// returns the number of masks left, ie. the number of vectors that can still be committed
+    pub fn masks_left(&self) -> Result<usize, Error> {
+        Ok(self
+            .masks
+            .lock()
+            .map_err(|e| Error::Other(e.to_string()))?
+            .len())
+    }
+}
+
+/// SplitCommitment is the commitment scheme used by the input party, where the commitments are
+/// computed by the params party with the commitment scheme `CS`. The opening proofs need the
+/// commitment key, so they are not supported, while the verification is the one of `CS`.
+#[derive(Clone, Debug)]
+pub struct SplitCommitment<C: CurveGroup, CS: CommitmentScheme<C>> {
+    _c: PhantomData<C>,
+    _cs: PhantomData<CS>,
+}
+
+impl<C: CurveGroup, CS: CommitmentScheme<C>> CommitmentScheme<C> for SplitCommitment<C, CS>
+where
+    CS: 'static,
+    CS::ProverParams: Send + Sync,
+{
+    type ProverParams = SplitParams<C>;
+    type VerifierParams = CS::VerifierParams;
+    type Proof = CS::Proof;
+    type ProverChallenge = CS::ProverChallenge;
+    type Challenge = CS::Challenge;
+
+    /// runs the setup of `CS`, with the params party running in the same process. The returned
+    /// params have no masks, which are added with `SplitParams::add_masks`.
+    fn setup(
+        mut rng: impl RngCore,
+        len: usize,
+    ) -> Result<(Self::ProverParams, Self::VerifierParams), Error> {
+        let (params, vk) = CS::setup(&mut rng, len)?;
+        Ok((SplitParams::local::<CS>(params, 0, rng)?, vk))
+    }
+
+    fn fits(params: &Self::ProverParams, len: usize) -> bool {
+        len <= Self::max_len(params)
+    }
+
+    fn max_len(params: &Self::ProverParams) -> usize {
+        params.params_party.max_len()
+    }
+
+    /// the commitment key is held by the params party, so the params are only known to be the
+    /// same when they point to the same params party
+    fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool {
+        Arc::ptr_eq(&a.params_party, &b.params_party)
+    }
+
+    fn commit(
+        params: &Self::ProverParams,
+        v: &[C::ScalarField],
+        blind: &C::ScalarField,
+    ) -> Result<C, Error> {
+        let mask = params
+            .masks
+            .lock()
+            .map_err(|e| Error::Other(e.to_string()))?
+            .pop()
+            .ok_or(Error::SplitMasksExhausted)?;
+        if mask.rho.len() < v.len() {
+            return Err(Error::SplitMaskTooShort {
+                len: v.len(),
+                mask_len: mask.rho.len(),
+            });
+        }
+        // v is padded with zeros to the length of the mask, which does not change its commitment
+        let v_masked: Vec<C::ScalarField> = mask
+            .rho
+            .iter()
+            .enumerate()
+            .map(|(i, rho_i)| v.get(i).map_or(*rho_i, |v_i| *v_i + rho_i))
+            .collect();
+        let cm_masked = params.params_party.commit_masked(&v_masked, blind)?;
+        Ok(cm_masked - mask.cm)
+    }
+
+    fn prove(
+        _params: &Self::ProverParams,
+        _transcript: &mut impl Transcript<C>,
+        _cm: &C,
+        _v: &[C::ScalarField],
+        _blind: &C::ScalarField,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        Err(Error::NotSupported {
+            feature: "opening proofs of SplitCommitment",
+        })
+    }
+
+    fn prove_with_challenge(
+        _params: &Self::ProverParams,
+        _challenge: Self::ProverChallenge,
+        _v: &[C::ScalarField],
+        _blind: &C::ScalarField,
+        _rng: Option<&mut dyn RngCore>,
+    ) -> Result<Self::Proof, Error> {
+        Err(Error::NotSupported {
+            feature: "opening proofs of SplitCommitment",
+        })
+    }
+
+    fn verify(
+        params: &Self::VerifierParams,
+        transcript: &mut impl Transcript<C>,
+        cm: &C,
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        CS::verify(params, transcript, cm, proof)
+    }
+
+    fn verify_with_challenge(
+        params: &Self::VerifierParams,
+        challenge: Self::Challenge,
+        cm: &C,
+        proof: &Self::Proof,
+    ) -> Result<(), Error> {
+        CS::verify_with_challenge(params, challenge, cm, proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{constraints::GVar, Fr, G1Projective as Projective};
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+    use ark_std::One;
+
+    use crate::commitment::pedersen::{Params as PedersenParams, Pedersen};
+    use crate::folding::nova::{
+        tests::{test_nova_params, TestNova},
+        Nova, ProverParams,
+    };
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    /// params party that records the vectors it receives
+    #[derive(Debug)]
+    struct RecordingParamsParty {
+        params: PedersenParams<Projective>,
+        received: Mutex<Vec<Vec<Fr>>>,
+    }
+    impl ParamsParty<Projective> for RecordingParamsParty {
+        fn max_len(&self) -> usize {
+            Pedersen::<Projective>::max_len(&self.params)
+        }
+        fn commit_masked(&self, v: &[Fr], blind: &Fr) -> Result<Projective, Error> {
+            self.received.lock().unwrap().push(v.to_vec());
+            Pedersen::<Projective>::commit(&self.params, v, blind)
+        }
+    }
+
+    #[test]
+    fn test_split_commitment() {
+        let mut rng = ark_std::test_rng();
+        let n = 16;
+        let (params, _) = Pedersen::<Projective>::setup(&mut rng, n).unwrap();
+        let params_party = Arc::new(RecordingParamsParty {
+            params: params.clone(),
+            received: Mutex::new(vec![]),
+        });
+        let masks = Mask::precompute::<Pedersen<Projective>>(&params, n, 1, &mut rng).unwrap();
+        let split_params = SplitParams::<Projective>::new(params_party.clone(), masks);
+
+        // a vector of ones, as the boolean wires of a witness
+        let v = vec![Fr::one(); n];
+        let cm = Pedersen::<Projective>::commit(&params, &v, &Fr::zero()).unwrap();
+        let cm_split = SplitCommitment::<Projective, Pedersen<Projective>>::commit(
+            &split_params,
+            &v,
+            &Fr::zero(),
+        )
+        .unwrap();
+        assert_eq!(cm, cm_split);
+
+        // the params party did not receive any entry of the vector, nor a multiple of it
+        let received = params_party.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert!(received[0].iter().all(|r| !r.is_one()));
+        assert!(received[0].windows(2).all(|r| r[0] != r[1]));
+        drop(received);
+
+        // each mask is used only once
+        assert_eq!(split_params.masks_left().unwrap(), 0);
+        assert!(matches!(
+            SplitCommitment::<Projective, Pedersen<Projective>>::commit(
+                &split_params,
+                &v,
+                &Fr::zero(),
+            ),
+            Err(Error::SplitMasksExhausted)
+        ));
+
+        assert!(SplitCommitment::<Projective, Pedersen<Projective>>::fits(
+            &split_params,
+            n
+        ));
+    }
+
+    #[test]
+    fn test_split_prove_step() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        type SplitNOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            SplitCommitment<Projective, Pedersen<Projective>>,
+            SplitCommitment<Projective2, Pedersen<Projective2>>,
+        >;
+
+        let mut rng = ark_std::test_rng();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+        // the input party only holds the handles to the params party
+        let split_prover_params = ProverParams::<
+            Projective,
+            Projective2,
+            SplitCommitment<Projective, Pedersen<Projective>>,
+            SplitCommitment<Projective2, Pedersen<Projective2>>,
+        > {
+            poseidon_config: prover_params.poseidon_config.clone(),
+            cs_params: SplitParams::local::<Pedersen<Projective>>(
+                prover_params.cs_params.clone(),
+                32,
+                &mut rng,
+            )
+            .unwrap(),
+            cf_cs_params: SplitParams::local::<Pedersen<Projective2>>(
+                prover_params.cf_cs_params.clone(),
+                32,
+                &mut rng,
+            )
+            .unwrap(),
+        };
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        let mut split_nova = SplitNOVA::init(&split_prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+            split_nova.prove_step(()).unwrap();
+        }
+
+        // the split proving yields the same instances and witnesses as the local proving
+        assert_eq!(split_nova.z_i, nova.z_i);
+        assert_eq!(split_nova.U_i, nova.U_i);
+        assert_eq!(split_nova.W_i, nova.W_i);
+        assert_eq!(split_nova.u_i, nova.u_i);
+        assert_eq!(split_nova.w_i, nova.w_i);
+        assert_eq!(split_nova.cf_U_i, nova.cf_U_i);
+        assert_eq!(split_nova.cf_W_i, nova.cf_W_i);
+
+        assert!(split_prover_params.cs_params.masks_left().unwrap() < 32);
+        assert!(split_prover_params.cf_cs_params.masks_left().unwrap() < 32);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = split_nova.instances();
+        let proof_data = split_nova.proof_data();
+        SplitNOVA::verify(
+            verifier_params,
+            z_0,
+            split_nova.z_i,
+            split_nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
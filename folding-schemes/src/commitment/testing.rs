@@ -0,0 +1,62 @@
+/// Conformance checks for the implementations of the CommitmentScheme trait, which can be used
+/// to test new commitment schemes.
+use ark_ec::CurveGroup;
+use ark_std::{rand::RngCore, UniformRand, Zero};
+
+use super::CommitmentScheme;
+use crate::Error;
+
+/// length of the vectors used by the checks
+const LEN: usize = 16;
+
+/// checks that the commitment scheme is additively homomorphic, this is, that for random vectors
+/// `a`, `b` and a random scalar `r`, `commit(a) + r * commit(b) == commit(a + r * b)`. In hiding
+/// mode the blinding factors are combined in the same way. Returns `Error::NotEqual` if the
+/// property does not hold.
+pub fn check_homomorphism<C: CurveGroup, CS: CommitmentScheme<C, H>, const H: bool>(
+    mut rng: impl RngCore,
+) -> Result<(), Error> {
+    let (params, _) = CS::setup(&mut rng, LEN)?;
+
+    let a: Vec<C::ScalarField> = std::iter::repeat_with(|| C::ScalarField::rand(&mut rng))
+        .take(LEN)
+        .collect();
+    let b: Vec<C::ScalarField> = std::iter::repeat_with(|| C::ScalarField::rand(&mut rng))
+        .take(LEN)
+        .collect();
+    let r = C::ScalarField::rand(&mut rng);
+    let (blind_a, blind_b) = if H {
+        (
+            C::ScalarField::rand(&mut rng),
+            C::ScalarField::rand(&mut rng),
+        )
+    } else {
+        (C::ScalarField::zero(), C::ScalarField::zero())
+    };
+
+    let cm_a = CS::commit(&params, &a, &blind_a)?;
+    let cm_b = CS::commit(&params, &b, &blind_b)?;
+
+    let c: Vec<C::ScalarField> = a.iter().zip(b.iter()).map(|(a, b)| *a + r * b).collect();
+    let cm_c = CS::commit(&params, &c, &(blind_a + r * blind_b))?;
+
+    if cm_a + cm_b * r != cm_c {
+        return Err(Error::NotEqual);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_pallas::Projective;
+    use ark_std::test_rng;
+
+    use crate::commitment::pedersen::Pedersen;
+
+    #[test]
+    fn test_check_homomorphism() {
+        check_homomorphism::<Projective, Pedersen<Projective>, false>(test_rng()).unwrap();
+        check_homomorphism::<Projective, Pedersen<Projective, true>, true>(test_rng()).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    any::TypeId,
     borrow::Borrow,
     cmp::{max, min},
 };
@@ -17,6 +18,7 @@ use num_bigint::BigUint;
 use num_integer::Integer;
 
 use crate::utils::gadgets::{MatrixGadget, SparseMatrixVar, VectorGadget};
+use crate::Error;
 
 /// `LimbVar` represents a single limb of a non-native unsigned integer in the
 /// circuit.
@@ -170,8 +172,10 @@ impl<F: PrimeField> ToBitsGadget<F> for LimbVar<F> {
 /// in that the latter runs the expensive `reduce` (`align` + `modulo` in our
 /// terminology) after each arithmetic operation, while the former only reduces
 /// the integer when explicitly called.
+/// The second field is the number of bits of each limb (before any arithmetic
+/// operation), see `LimbConfig`.
 #[derive(Debug, Clone)]
-pub struct NonNativeUintVar<F: PrimeField>(pub Vec<LimbVar<F>>);
+pub struct NonNativeUintVar<F: PrimeField>(pub Vec<LimbVar<F>>, pub usize);
 
 impl<F: PrimeField> NonNativeUintVar<F> {
     pub const fn bits_per_limb() -> usize {
@@ -196,25 +200,28 @@ impl<F: PrimeField> NonNativeUintVar<F> {
         // optimal value based on the modulus size
         55
     }
-}
 
-struct BoundedBigUint(BigUint, usize);
+    /// Returns the number of bits per limb of the `NonNativeUintVar`s
+    /// allocated in `cs`, which is the one of the `LimbConfig` attached to
+    /// `cs`, or `bits_per_limb` if there is none.
+    pub fn bits_per_limb_in(cs: &ConstraintSystemRef<F>) -> usize {
+        LimbConfig::get(cs).map_or(Self::bits_per_limb(), |config| config.bits_per_limb)
+    }
 
-impl<F: PrimeField> AllocVar<BoundedBigUint, F> for NonNativeUintVar<F> {
-    fn new_variable<T: Borrow<BoundedBigUint>>(
-        cs: impl Into<Namespace<F>>,
-        f: impl FnOnce() -> Result<T, SynthesisError>,
+    /// Allocates the integer `x` of at most `l` bits, decomposed into limbs of
+    /// `bits_per_limb` bits.
+    fn new_bounded(
+        cs: ConstraintSystemRef<F>,
+        x: &BigUint,
+        l: usize,
+        bits_per_limb: usize,
         mode: AllocationMode,
     ) -> Result<Self, SynthesisError> {
-        let cs = cs.into().cs();
-        let v = f()?;
-        let BoundedBigUint(x, l) = v.borrow();
-
         let mut limbs = vec![];
-        for chunk in (0..*l)
+        for chunk in (0..l)
             .map(|i| x.bit(i as u64))
             .collect::<Vec<_>>()
-            .chunks(Self::bits_per_limb())
+            .chunks(bits_per_limb)
         {
             let limb = F::from_bigint(F::BigInt::from_bits_le(chunk)).unwrap();
             let limb = FpVar::new_variable(cs.clone(), || Ok(limb), mode)?;
@@ -225,7 +232,95 @@ impl<F: PrimeField> AllocVar<BoundedBigUint, F> for NonNativeUintVar<F> {
             });
         }
 
-        Ok(Self(limbs))
+        Ok(Self(limbs, bits_per_limb))
+    }
+}
+
+/// `LimbConfig` is the decomposition into limbs of the `NonNativeUintVar`s that
+/// represent the elements of a non-native field `T` in a circuit over the
+/// native field `F`: `num_limbs` limbs of `bits_per_limb` bits.
+/// The config is attached to a constraint system with `LimbConfig::set`, and the
+/// `NonNativeUintVar`s allocated afterwards in that constraint system use its
+/// `bits_per_limb` instead of the default `NonNativeUintVar::bits_per_limb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimbConfig {
+    pub bits_per_limb: usize,
+    pub num_limbs: usize,
+}
+
+impl LimbConfig {
+    /// Returns the default config for the non-native field `T` over `F`, with
+    /// `NonNativeUintVar::bits_per_limb` bits per limb.
+    pub fn default_for<F: PrimeField, T: PrimeField>() -> Self {
+        let bits_per_limb = NonNativeUintVar::<F>::bits_per_limb();
+        Self {
+            bits_per_limb,
+            num_limbs: (T::MODULUS_BIT_SIZE as usize).div_ceil(bits_per_limb),
+        }
+    }
+
+    /// Checks that `num_limbs` limbs of `bits_per_limb` bits are the ones needed
+    /// to represent the elements of `T`, and that the limbs of the product of
+    /// two of them, plus a third one, do not overflow `F`, so that the
+    /// non-native arithmetic of the folding does not need to align the limbs.
+    pub fn check<F: PrimeField, T: PrimeField>(&self) -> Result<(), Error> {
+        let target_bits = T::MODULUS_BIT_SIZE as usize;
+        if self.bits_per_limb == 0 || self.num_limbs != target_bits.div_ceil(self.bits_per_limb) {
+            return Err(Error::InvalidLimbConfig {
+                bits_per_limb: self.bits_per_limb,
+                num_limbs: self.num_limbs,
+                target_bits,
+            });
+        }
+        // each limb of the product is the sum of up to `num_limbs` products of
+        // two limbs, which together with the limb of the added integer has to
+        // be smaller than `F::MODULUS_MINUS_ONE_DIV_TWO`
+        let log_num_limbs = (usize::BITS - (self.num_limbs - 1).leading_zeros()) as usize;
+        if 2 * self.bits_per_limb + log_num_limbs + 1 >= F::MODULUS_BIT_SIZE as usize - 1 {
+            return Err(Error::LimbOverflow {
+                bits_per_limb: self.bits_per_limb,
+                native_bits: F::MODULUS_BIT_SIZE as usize,
+            });
+        }
+        Ok(())
+    }
+
+    /// Attaches the config to the constraint system `cs`.
+    pub fn set<F: PrimeField>(&self, cs: &ConstraintSystemRef<F>) {
+        if let Some(cs) = cs.borrow() {
+            cs.cache_map
+                .borrow_mut()
+                .insert(TypeId::of::<Self>(), Box::new(*self));
+        }
+    }
+
+    /// Returns the config attached to the constraint system `cs`, if any.
+    pub fn get<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> Option<Self> {
+        cs.borrow().and_then(|cs| {
+            let cache_map = cs.cache_map.borrow();
+            cache_map
+                .get(&TypeId::of::<Self>())
+                .and_then(|config| config.downcast_ref::<Self>())
+                .copied()
+        })
+    }
+}
+
+/// `BoundedBigUint` is an unsigned integer `x` together with the number of bits `l` that is
+/// allocated for it, ie. `x < 2^l`.
+pub struct BoundedBigUint(pub BigUint, pub usize);
+
+impl<F: PrimeField> AllocVar<BoundedBigUint, F> for NonNativeUintVar<F> {
+    fn new_variable<T: Borrow<BoundedBigUint>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> Result<T, SynthesisError>,
+        mode: AllocationMode,
+    ) -> Result<Self, SynthesisError> {
+        let cs = cs.into().cs();
+        let v = f()?;
+        let BoundedBigUint(x, l) = v.borrow();
+        let bits_per_limb = Self::bits_per_limb_in(&cs);
+        Self::new_bounded(cs, x, *l, bits_per_limb, mode)
     }
 }
 
@@ -238,10 +333,11 @@ impl<F: PrimeField, G: PrimeField> AllocVar<G, F> for NonNativeUintVar<F> {
         let cs = cs.into().cs();
         let v = f()?;
         let v = v.borrow();
+        let bits_per_limb = Self::bits_per_limb_in(&cs);
 
         let mut limbs = vec![];
 
-        for chunk in v.into_bigint().to_bits_le().chunks(Self::bits_per_limb()) {
+        for chunk in v.into_bigint().to_bits_le().chunks(bits_per_limb) {
             let limb = F::from_bigint(F::BigInt::from_bits_le(chunk)).unwrap();
             let limb = FpVar::new_variable(cs.clone(), || Ok(limb), mode)?;
             Self::enforce_bit_length(&limb, chunk.len())?;
@@ -251,7 +347,7 @@ impl<F: PrimeField, G: PrimeField> AllocVar<G, F> for NonNativeUintVar<F> {
             });
         }
 
-        Ok(Self(limbs))
+        Ok(Self(limbs, bits_per_limb))
     }
 }
 
@@ -276,7 +372,7 @@ impl<F: PrimeField> R1CSVar<F> for NonNativeUintVar<F> {
         let mut r = BigUint::zero();
 
         for limb in self.0.value()?.into_iter().rev() {
-            r <<= Self::bits_per_limb();
+            r <<= self.1;
             r += Into::<BigUint>::into(limb);
         }
 
@@ -289,6 +385,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
     /// be aligned.
     /// Adapted from https://github.com/akosba/jsnark/blob/0955389d0aae986ceb25affc72edf37a59109250/JsnarkCircuitBuilder/src/circuit/auxiliary/LongElement.java#L801-L872
     pub fn enforce_lt(&self, other: &Self) -> Result<(), SynthesisError> {
+        assert_eq!(self.1, other.1);
         let len = max(self.0.len(), other.0.len());
         let zero = LimbVar::zero();
 
@@ -360,7 +457,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
         // `0 <= p - 1 < 2^bits_per_limb < F::MODULUS_MINUS_ONE_DIV_TWO`.
         // This guarantees that the true value in `helper` corresponds to a
         // positive limb in `delta`.
-        Self::enforce_bit_length(&(p - FpVar::one()), Self::bits_per_limb())?;
+        Self::enforce_bit_length(&(p - FpVar::one()), self.1)?;
 
         Ok(())
     }
@@ -372,6 +469,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
     /// Similar implementations can also be found in https://github.com/alex-ozdemir/bellman-bignat/blob/0585b9d90154603a244cba0ac80b9aafe1d57470/src/mp/bignat.rs#L566-L661
     /// and https://github.com/arkworks-rs/r1cs-std/blob/4020fbc22625621baa8125ede87abaeac3c1ca26/src/fields/emulated_fp/reduce.rs#L201-L323
     pub fn enforce_equal_unaligned(&self, other: &Self) -> Result<(), SynthesisError> {
+        assert_eq!(self.1, other.1);
         let len = min(self.0.len(), other.0.len());
 
         // Group the limbs of `self` and `other` so that each group nearly
@@ -403,7 +501,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
                 let mut xx = LimbVar::zero();
                 let mut yy = LimbVar::zero();
                 while j < len {
-                    let shift = BigUint::one() << (Self::bits_per_limb() * (j - i));
+                    let shift = BigUint::one() << (self.1 * (j - i));
                     assert!(shift < F::MODULUS_MINUS_ONE_DIV_TWO.into());
                     let shift = LimbVar::constant(shift.into());
                     match (
@@ -420,7 +518,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
                     j += 1;
                 }
                 // Store the grouped limbs and their size.
-                steps.push((j - i) * Self::bits_per_limb());
+                steps.push((j - i) * self.1);
                 x_grouped.push(xx);
                 y_grouped.push(yy);
                 // Start the next group
@@ -519,11 +617,12 @@ impl<F: PrimeField> CondSelectGadget<F> for NonNativeUintVar<F> {
         false_value: &Self,
     ) -> Result<Self, SynthesisError> {
         assert_eq!(true_value.0.len(), false_value.0.len());
+        assert_eq!(true_value.1, false_value.1);
         let mut v = vec![];
         for i in 0..true_value.0.len() {
             v.push(cond.select(&true_value.0[i], &false_value.0[i])?);
         }
-        Ok(Self(v))
+        Ok(Self(v, true_value.1))
     }
 }
 
@@ -532,7 +631,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
         let mut r = BigUint::zero();
 
         for i in self.0.iter().rev() {
-            r <<= Self::bits_per_limb();
+            r <<= self.1;
             r += &i.ub;
         }
 
@@ -614,6 +713,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
 
     /// Compute `self + other`, without aligning the limbs.
     pub fn add_no_align(&self, other: &Self) -> Self {
+        assert_eq!(self.1, other.1);
         let mut z = vec![LimbVar::zero(); max(self.0.len(), other.0.len())];
         for (i, v) in self.0.iter().enumerate() {
             z[i] = z[i].add(v).unwrap();
@@ -621,12 +721,13 @@ impl<F: PrimeField> NonNativeUintVar<F> {
         for (i, v) in other.0.iter().enumerate() {
             z[i] = z[i].add(v).unwrap();
         }
-        Self(z)
+        Self(z, self.1)
     }
 
     /// Compute `self * other`, without aligning the limbs.
     /// Implements the O(n) approach described in xJsnark, Section IV.B.1)
     pub fn mul_no_align(&self, other: &Self) -> Result<Self, SynthesisError> {
+        assert_eq!(self.1, other.1);
         let len = self.0.len() + other.0.len() - 1;
         if self.is_constant() || other.is_constant() {
             // Use the naive approach for constant operands, which costs no
@@ -643,7 +744,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
                 })
                 .collect::<Option<Vec<_>>>()
                 .unwrap();
-            return Ok(Self(z));
+            return Ok(Self(z, self.1));
         }
         let cs = self.cs().or(other.cs());
         let mode = if cs.is_none() {
@@ -710,39 +811,41 @@ impl<F: PrimeField> NonNativeUintVar<F> {
             l.mul_equals(&r, &o)?;
         }
 
-        Ok(Self(z))
+        Ok(Self(z, self.1))
     }
 
     /// Convert `Self` to an element in `M`, i.e., compute `Self % M::MODULUS`.
     pub fn modulo<M: PrimeField>(&self) -> Result<Self, SynthesisError> {
+        self.modulo_by(&M::MODULUS.into())
+    }
+
+    /// Compute `Self % m` for an arbitrary modulus `m > 0`.
+    pub fn modulo_by(&self, m: &BigUint) -> Result<Self, SynthesisError> {
         let cs = self.cs();
         let mode = if cs.is_none() {
             AllocationMode::Constant
         } else {
             AllocationMode::Witness
         };
-        let m: BigUint = M::MODULUS.into();
         // Provide the quotient and remainder as hints
         let (q, r) = {
             let v = self.value().unwrap_or_default();
-            let (q, r) = v.div_rem(&m);
-            let q_ubound = self.ubound().div_ceil(&m);
-            let r_ubound = &m;
+            let (q, r) = v.div_rem(m);
+            let q_ubound = self.ubound().div_ceil(m);
+            let r_ubound = m;
             (
-                Self::new_variable(
-                    cs.clone(),
-                    || Ok(BoundedBigUint(q, q_ubound.bits() as usize)),
-                    mode,
-                )?,
-                Self::new_variable(
-                    cs.clone(),
-                    || Ok(BoundedBigUint(r, r_ubound.bits() as usize)),
-                    mode,
-                )?,
+                Self::new_bounded(cs.clone(), &q, q_ubound.bits() as usize, self.1, mode)?,
+                Self::new_bounded(cs.clone(), &r, r_ubound.bits() as usize, self.1, mode)?,
             )
         };
 
-        let m = Self::new_constant(cs.clone(), BoundedBigUint(m, M::MODULUS_BIT_SIZE as usize))?;
+        let m = Self::new_bounded(
+            cs.clone(),
+            m,
+            m.bits() as usize,
+            self.1,
+            AllocationMode::Constant,
+        )?;
         // Enforce `self = q * m + r`
         q.mul_no_align(&m)?
             .add_no_align(&r)
@@ -755,6 +858,7 @@ impl<F: PrimeField> NonNativeUintVar<F> {
 
     /// Enforce that `self` is congruent to `other` modulo `M::MODULUS`.
     pub fn enforce_congruent<M: PrimeField>(&self, other: &Self) -> Result<(), SynthesisError> {
+        assert_eq!(self.1, other.1);
         let cs = self.cs();
         let mode = if cs.is_none() {
             AllocationMode::Constant
@@ -774,13 +878,25 @@ impl<F: PrimeField> NonNativeUintVar<F> {
                 ((y - x) / &m, false)
             };
             (
-                Self::new_variable(cs.clone(), || Ok(BoundedBigUint(d, bits)), mode)?,
+                Self::new_bounded(cs.clone(), &d, bits, self.1, mode)?,
                 Boolean::new_variable(cs.clone(), || Ok(b), mode)?,
             )
         };
 
-        let zero = Self::new_constant(cs.clone(), BoundedBigUint(BigUint::zero(), bits))?;
-        let m = Self::new_constant(cs.clone(), BoundedBigUint(m, M::MODULUS_BIT_SIZE as usize))?;
+        let zero = Self::new_bounded(
+            cs.clone(),
+            &BigUint::zero(),
+            bits,
+            self.1,
+            AllocationMode::Constant,
+        )?;
+        let m = Self::new_bounded(
+            cs.clone(),
+            &m,
+            M::MODULUS_BIT_SIZE as usize,
+            self.1,
+            AllocationMode::Constant,
+        )?;
         let l = self.add_no_align(&is_ge.select(&zero, &q)?.mul_no_align(&m)?);
         let r = other.add_no_align(&is_ge.select(&q, &zero)?.mul_no_align(&m)?);
         // If `self >= other`, enforce `self = other + q * m`
@@ -793,11 +909,13 @@ impl<F: PrimeField> NonNativeUintVar<F> {
 
 impl<F: PrimeField, B: AsRef<[Boolean<F>]>> From<B> for NonNativeUintVar<F> {
     fn from(bits: B) -> Self {
+        let bits_per_limb = Self::bits_per_limb_in(&bits.as_ref().cs());
         Self(
             bits.as_ref()
-                .chunks(Self::bits_per_limb())
+                .chunks(bits_per_limb)
                 .map(LimbVar::from)
                 .collect::<Vec<_>>(),
+            bits_per_limb,
         )
     }
 }
@@ -859,6 +977,9 @@ impl<F: PrimeField, CF: PrimeField> MatrixGadget<NonNativeUintVar<CF>>
         &self,
         v: &[NonNativeUintVar<CF>],
     ) -> Result<Vec<NonNativeUintVar<CF>>, SynthesisError> {
+        let bits_per_limb = v
+            .first()
+            .map_or(NonNativeUintVar::<CF>::bits_per_limb(), |x| x.1);
         Ok(self
             .coeffs
             .iter()
@@ -886,7 +1007,7 @@ impl<F: PrimeField, CF: PrimeField> MatrixGadget<NonNativeUintVar<CF>>
                     })
                     .collect::<Option<Vec<_>>>()
                     .unwrap();
-                NonNativeUintVar(limbs)
+                NonNativeUintVar(limbs, bits_per_limb)
             })
             .collect())
     }
@@ -970,6 +1091,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_limb_config() -> Result<(), Box<dyn Error>> {
+        // 4 limbs of 64 bits
+        let config = LimbConfig {
+            bits_per_limb: 64,
+            num_limbs: 4,
+        };
+        config.check::<Fr, Fq>()?;
+        LimbConfig::default_for::<Fr, Fq>().check::<Fr, Fq>()?;
+        // too few and too many limbs
+        for num_limbs in [3, 5] {
+            let c = LimbConfig {
+                bits_per_limb: 64,
+                num_limbs,
+            };
+            assert!(c.check::<Fr, Fq>().is_err());
+        }
+        // the limbs of the products overflow Fr
+        let c = LimbConfig {
+            bits_per_limb: 127,
+            num_limbs: 2,
+        };
+        assert!(c.check::<Fr, Fq>().is_err());
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        assert_eq!(LimbConfig::get(&cs), None);
+        config.set(&cs);
+        assert_eq!(LimbConfig::get(&cs), Some(config));
+
+        let rng = &mut test_rng();
+        let a = Fq::rand(rng);
+        let b = Fq::rand(rng);
+        let a_var = NonNativeUintVar::new_witness(cs.clone(), || Ok(a))?;
+        let b_var = NonNativeUintVar::new_witness(cs.clone(), || Ok(b))?;
+        let ab_var = NonNativeUintVar::new_witness(cs.clone(), || Ok(a * b))?;
+        assert_eq!((a_var.0.len(), a_var.1), (4, 64));
+        assert_eq!(a_var.value()?, BigUint::from(a));
+
+        let ab = a_var.mul_no_align(&b_var)?.modulo::<Fq>()?;
+        assert_eq!(ab.1, 64);
+        ab.enforce_equal_unaligned(&ab_var)?;
+        assert!(cs.is_satisfied()?);
+        Ok(())
+    }
+
     #[test]
     fn test_pow() -> Result<(), Box<dyn Error>> {
         let cs = ConstraintSystem::<Fr>::new_ref();
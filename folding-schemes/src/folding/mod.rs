@@ -2,3 +2,5 @@ pub mod circuits;
 pub mod hypernova;
 pub mod nova;
 pub mod protogalaxy;
+pub mod verification_builder;
+pub mod verification_report;
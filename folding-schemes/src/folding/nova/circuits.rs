@@ -27,14 +27,14 @@ use super::{
     cyclefold::{
         CycleFoldChallengeGadget, CycleFoldCommittedInstanceVar, NIFSFullGadget, CF_IO_LEN,
     },
-    CommittedInstance,
+    CommittedInstance, IVCBindings,
 };
 use crate::constants::N_BITS_RO;
 use crate::folding::circuits::nonnative::{
     affine::{nonnative_affine_to_field_elements, NonNativeAffineVar},
     uint::NonNativeUintVar,
 };
-use crate::frontend::FCircuit;
+use crate::frontend::{ExternalInputs, FCircuit};
 
 /// CF1 represents the ConstraintField used for the main Nova circuit which is over E1::Fr, where
 /// E1 is the main curve where we do the folding.
@@ -93,17 +93,37 @@ where
 {
     /// hash implements the committed instance hash compatible with the native implementation from
     /// CommittedInstance.hash.
-    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U` is the
+    /// Returns `H(i, z_0, z_i, b, U_i)` for an IVC with the default IVCBindings (no metadata nor
+    /// IO), see `hash_with_bindings`.
+    #[allow(clippy::type_complexity)]
+    pub fn hash(
+        self,
+        crh_params: &CRHParametersVar<CF1<C>>,
+        i: FpVar<CF1<C>>,
+        z_0: Vec<FpVar<CF1<C>>>,
+        z_i: Vec<FpVar<CF1<C>>>,
+    ) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError> {
+        let bindings = IVCBindings::<CF1<C>>::default()
+            .to_vec()
+            .into_iter()
+            .map(FpVar::constant)
+            .collect();
+        self.hash_with_bindings(crh_params, i, z_0, z_i, bindings)
+    }
+
+    /// Returns `H(i, z_0, z_i, b, U_i)`, where `i` can be `i` but also `i+1`, `b` are the field
+    /// elements of the IVCBindings (see `IVCBindings::to_vec`), and `U` is the
     /// `CommittedInstance`.
     /// Additionally it returns the vector of the field elements from the self parameters, so they
     /// can be reused in other gadgets avoiding recalculating (reconstraining) them.
     #[allow(clippy::type_complexity)]
-    pub fn hash(
+    pub fn hash_with_bindings(
         self,
         crh_params: &CRHParametersVar<CF1<C>>,
         i: FpVar<CF1<C>>,
         z_0: Vec<FpVar<CF1<C>>>,
         z_i: Vec<FpVar<CF1<C>>>,
+        bindings: Vec<FpVar<CF1<C>>>,
     ) -> Result<(FpVar<CF1<C>>, Vec<FpVar<CF1<C>>>), SynthesisError> {
         let U_vec = [
             vec![self.u],
@@ -112,7 +132,7 @@ where
             self.cmW.to_constraint_field()?,
         ]
         .concat();
-        let input = [vec![i], z_0, z_i, U_vec.clone()].concat();
+        let input = [vec![i], z_0, z_i, bindings, U_vec.clone()].concat();
         Ok((
             CRHGadget::<C::ScalarField>::evaluate(crh_params, &input)?,
             U_vec,
@@ -185,6 +205,31 @@ where
         U_i: CommittedInstance<C>,
         u_i: CommittedInstance<C>,
         cmT: C,
+    ) -> Result<Vec<bool>, SynthesisError> {
+        Self::get_challenge_native_with_beacon(poseidon_config, U_i, u_i, cmT, None)
+    }
+
+    /// computes the challenge as `get_challenge_native`, absorbing the given beacon value (if
+    /// any) into the transcript after the instances and before squeezing the challenge
+    pub fn get_challenge_native_with_beacon(
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        U_i: CommittedInstance<C>,
+        u_i: CommittedInstance<C>,
+        cmT: C,
+        beacon: Option<C::ScalarField>,
+    ) -> Result<Vec<bool>, SynthesisError> {
+        Self::get_challenge_native_with_domain(poseidon_config, None, U_i, u_i, cmT, beacon)
+    }
+
+    /// computes the challenge as `get_challenge_native_with_beacon`, absorbing first the given
+    /// domain (if any) into the transcript, see `Nova::rotate_transcript_domain`
+    pub fn get_challenge_native_with_domain(
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        domain: Option<C::ScalarField>,
+        U_i: CommittedInstance<C>,
+        u_i: CommittedInstance<C>,
+        cmT: C,
+        beacon: Option<C::ScalarField>,
     ) -> Result<Vec<bool>, SynthesisError> {
         let (U_cmE_x, U_cmE_y) = nonnative_affine_to_field_elements::<C>(U_i.cmE)?;
         let (U_cmW_x, U_cmW_y) = nonnative_affine_to_field_elements::<C>(U_i.cmW)?;
@@ -194,6 +239,7 @@ where
 
         let mut sponge = PoseidonSponge::<C::ScalarField>::new(poseidon_config);
         let input = vec![
+            domain.into_iter().collect(),
             vec![U_i.u],
             U_i.x.clone(),
             U_cmE_x,
@@ -208,6 +254,7 @@ where
             u_cmW_y,
             cmT_x,
             cmT_y,
+            beacon.into_iter().collect(),
         ]
         .concat();
         sponge.absorb(&input);
@@ -222,16 +269,43 @@ where
         U_i_vec: Vec<FpVar<CF1<C>>>, // apready processed input, so we don't have to recompute these values
         u_i: CommittedInstanceVar<C>,
         cmT: NonNativeAffineVar<C>,
+    ) -> Result<Vec<Boolean<C::ScalarField>>, SynthesisError> {
+        Self::get_challenge_gadget_with_beacon(cs, poseidon_config, U_i_vec, u_i, cmT, None)
+    }
+
+    // compatible with the native get_challenge_native_with_beacon
+    pub fn get_challenge_gadget_with_beacon(
+        cs: ConstraintSystemRef<C::ScalarField>,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        U_i_vec: Vec<FpVar<CF1<C>>>,
+        u_i: CommittedInstanceVar<C>,
+        cmT: NonNativeAffineVar<C>,
+        beacon: Option<FpVar<C::ScalarField>>,
+    ) -> Result<Vec<Boolean<C::ScalarField>>, SynthesisError> {
+        Self::get_challenge_gadget_with_domain(cs, poseidon_config, None, U_i_vec, u_i, cmT, beacon)
+    }
+
+    // compatible with the native get_challenge_native_with_domain
+    pub fn get_challenge_gadget_with_domain(
+        cs: ConstraintSystemRef<C::ScalarField>,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        domain: Option<FpVar<C::ScalarField>>,
+        U_i_vec: Vec<FpVar<CF1<C>>>,
+        u_i: CommittedInstanceVar<C>,
+        cmT: NonNativeAffineVar<C>,
+        beacon: Option<FpVar<C::ScalarField>>,
     ) -> Result<Vec<Boolean<C::ScalarField>>, SynthesisError> {
         let mut sponge = PoseidonSpongeVar::<C::ScalarField>::new(cs, poseidon_config);
 
         let input: Vec<FpVar<C::ScalarField>> = [
+            domain.into_iter().collect(),
             U_i_vec,
             vec![u_i.u.clone()],
             u_i.x.clone(),
             u_i.cmE.to_constraint_field()?,
             u_i.cmW.to_constraint_field()?,
             cmT.to_constraint_field()?,
+            beacon.into_iter().collect(),
         ]
         .concat();
         sponge.absorb(&input)?;
@@ -258,6 +332,17 @@ pub struct AugmentedFCircuit<
     pub i_usize: Option<usize>,
     pub z_0: Option<Vec<C1::ScalarField>>,
     pub z_i: Option<Vec<C1::ScalarField>>,
+    /// digest of the metadata of the IVC
+    pub metadata: Option<C1::ScalarField>,
+    /// domain of the transcript of the folding challenge of the previous step, and the digest
+    /// of the label of the rotation to apply at this step (if any), see
+    /// `Nova::rotate_transcript_domain`
+    pub domain: Option<C1::ScalarField>,
+    pub domain_rotation: Option<C1::ScalarField>,
+    /// accumulator of the public outputs of the previous steps, for FCircuits that expose IO
+    /// (see `FCircuit::io_len`)
+    pub io_acc: Option<C1::ScalarField>,
+    /// field elements of the external inputs of the step, see `ExternalInputs`
     pub external_inputs: Option<Vec<C1::ScalarField>>,
     pub u_i_cmW: Option<C1>,
     pub U_i: Option<CommittedInstance<C1>>,
@@ -291,6 +376,10 @@ where
             i_usize: None,
             z_0: None,
             z_i: None,
+            metadata: None,
+            domain: None,
+            domain_rotation: None,
+            io_acc: None,
             external_inputs: None,
             u_i_cmW: None,
             U_i: None,
@@ -337,6 +426,23 @@ where
                 .z_i
                 .unwrap_or(vec![CF1::<C1>::zero(); self.F.state_len()]))
         })?;
+        let metadata = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.metadata.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let domain = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.domain.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let is_rotated = Boolean::new_witness(cs.clone(), || Ok(self.domain_rotation.is_some()))?;
+        let domain_rotation = FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+            Ok(self.domain_rotation.unwrap_or_else(CF1::<C1>::zero))
+        })?;
+        let io_acc = if self.F.io_len() > 0 {
+            Some(FpVar::<CF1<C1>>::new_witness(cs.clone(), || {
+                Ok(self.io_acc.unwrap_or_else(CF1::<C1>::zero))
+            })?)
+        } else {
+            None
+        };
         let external_inputs = Vec::<FpVar<CF1<C1>>>::new_witness(cs.clone(), || {
             Ok(self
                 .external_inputs
@@ -370,19 +476,68 @@ where
         )?;
 
         // get z_{i+1} from the F circuit
+        // in beacon mode, the first external input is the beacon value of the step, which is
+        // absorbed into the transcript of the folding challenge
+        let beacon = if self.F.beacon_mode() {
+            Some(
+                external_inputs
+                    .first()
+                    .cloned()
+                    .ok_or(SynthesisError::AssignmentMissing)?,
+            )
+        } else {
+            None
+        };
+
+        let external_inputs = FC::ExternalInputs::var_from_field_elements(external_inputs)?;
         let i_usize = self.i_usize.unwrap_or(0);
+        let is_basecase = i.is_zero()?;
+
+        // accumulate the public outputs of the step, io_{i+1} = H(io_i, IO), where io_0 = 0
+        let io_acc_i1 = match &io_acc {
+            Some(io_acc) => {
+                let io = self.F.generate_step_io_constraints(
+                    cs.clone(),
+                    i_usize,
+                    z_i.clone(),
+                    external_inputs.clone(),
+                )?;
+                if io.len() != self.F.io_len() {
+                    return Err(SynthesisError::Unsatisfiable);
+                }
+                io_acc.conditional_enforce_equal(&FpVar::zero(), &is_basecase)?;
+                Some(CRHGadget::<C1::ScalarField>::evaluate(
+                    &crh_params,
+                    &[vec![io_acc.clone()], io].concat(),
+                )?)
+            }
+            None => None,
+        };
+
         let z_i1 =
             self.F
                 .generate_step_constraints(cs.clone(), i_usize, z_i.clone(), external_inputs)?;
 
-        let is_basecase = i.is_zero()?;
+        // domain of the transcript of this step, d_{i+1} = H(d_i, label) if the domain is rotated
+        // at this step, and d_{i+1} = d_i otherwise, where d_0 = 0
+        domain.conditional_enforce_equal(&FpVar::zero(), &is_basecase)?;
+        let rotated_domain = CRHGadget::<C1::ScalarField>::evaluate(
+            &crh_params,
+            &[domain.clone(), domain_rotation],
+        )?;
+        let domain_i1 = is_rotated.select(&rotated_domain, &domain)?;
 
         // Primary Part
         // P.1. Compute u_i.x
-        // u_i.x[0] = H(i, z_0, z_i, U_i)
-        let (u_i_x, U_i_vec) =
-            U_i.clone()
-                .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        // u_i.x[0] = H(i, z_0, z_i, b_i, U_i)
+        let bindings = [vec![metadata.clone(), domain], io_acc.into_iter().collect()].concat();
+        let (u_i_x, U_i_vec) = U_i.clone().hash_with_bindings(
+            &crh_params,
+            i.clone(),
+            z_0.clone(),
+            z_i.clone(),
+            bindings,
+        )?;
         // u_i.x[1] = H(cf_U_i)
         let (cf_u_i_x, cf_U_i_vec) = cf_U_i.clone().hash(&crh_params)?;
 
@@ -402,13 +557,16 @@ where
 
         // P.3. nifs.verify, obtains U_{i+1} by folding u_i & U_i .
 
-        // compute r = H(u_i, U_i, cmT)
-        let r_bits = ChallengeGadget::<C1>::get_challenge_gadget(
+        // compute r = H(domain, u_i, U_i, cmT), or r = H(domain, u_i, U_i, cmT, beacon) in
+        // beacon mode
+        let r_bits = ChallengeGadget::<C1>::get_challenge_gadget_with_domain(
             cs.clone(),
             &self.poseidon_config,
+            Some(domain_i1.clone()),
             U_i_vec,
             u_i.clone(),
             cmT.clone(),
+            beacon,
         )?;
         let r = Boolean::le_bits_to_fp_var(&r_bits)?;
         // Also convert r_bits to a `NonNativeFieldVar`
@@ -427,20 +585,24 @@ where
         U_i1.cmW = U_i1_cmW;
 
         // P.4.a compute and check the first output of F'
-        // Base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, U_{\bot})
-        // Non-base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, U_{i+1})
-        let (u_i1_x, _) = U_i1.clone().hash(
+        // Base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, b_{i+1}, U_{\bot})
+        // Non-base case: u_{i+1}.x[0] == H((i+1, z_0, z_{i+1}, b_{i+1}, U_{i+1})
+        let bindings_i1 = [vec![metadata, domain_i1], io_acc_i1.into_iter().collect()].concat();
+        let (u_i1_x, _) = U_i1.clone().hash_with_bindings(
             &crh_params,
             i + FpVar::<CF1<C1>>::one(),
             z_0.clone(),
             z_i1.clone(),
+            bindings_i1.clone(),
         )?;
-        let (u_i1_x_base, _) = CommittedInstanceVar::new_constant(cs.clone(), u_dummy)?.hash(
-            &crh_params,
-            FpVar::<CF1<C1>>::one(),
-            z_0.clone(),
-            z_i1.clone(),
-        )?;
+        let (u_i1_x_base, _) = CommittedInstanceVar::new_constant(cs.clone(), u_dummy)?
+            .hash_with_bindings(
+                &crh_params,
+                FpVar::<CF1<C1>>::one(),
+                z_0.clone(),
+                z_i1.clone(),
+                bindings_i1,
+            )?;
         let x = FpVar::new_input(cs.clone(), || Ok(self.x.unwrap_or(u_i1_x_base.value()?)))?;
         x.enforce_equal(&is_basecase.select(&u_i1_x_base, &u_i1_x)?)?;
 
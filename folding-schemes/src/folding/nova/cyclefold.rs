@@ -35,6 +35,21 @@ use crate::Error;
 // public inputs length for the CycleFoldCircuit: |[r, p1.x,y, p2.x,y, p3.x,y]|
 pub const CF_IO_LEN: usize = 7;
 
+/// errors of the CycleFold part of a Nova step, returned by `prove_step` as `Error::CycleFold`
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum CycleFoldError {
+    #[error("The CycleFold running instance does not match its commitment in the public input of the incoming instance")]
+    CommitmentMismatch,
+    #[error("The public inputs of the CycleFold circuit do not match the expected ones")]
+    PublicInputMismatch,
+    #[error("CycleFold challenge derivation failed: {0}")]
+    ChallengeDerivationFailed(String),
+    #[error("CycleFold cross-term computation failed: {0}")]
+    CrossTermFailed(String),
+    #[error("CycleFoldCircuit synthesis failed: {0}")]
+    SynthesisError(SynthesisError),
+}
+
 /// CycleFoldCommittedInstanceVar is the CycleFold CommittedInstance representation in the Nova
 /// circuit.
 #[derive(Debug, Clone)]
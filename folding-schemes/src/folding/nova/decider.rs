@@ -0,0 +1,492 @@
+/// This file implements the Groth16 decider for Nova over BN254, which compresses the IVC proof
+/// into a single Groth16 proof over the `DeciderEthCircuit` (together with the two KZG openings
+/// of the final folded instance), that can be verified natively or by the `NovaCycleFold`
+/// Solidity verifier.
+use ark_bn254::{g1::Config as G1Config, Bn254, Fq, Fq2, Fr, G1Affine, G1Projective};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{
+    short_weierstrass::{Affine, SWCurveConfig},
+    AffineRepr, CurveGroup, Group,
+};
+use ark_ff::{PrimeField, Zero};
+use ark_groth16::{Groth16, Proof as Groth16Proof, ProvingKey, VerifyingKey};
+use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_serialize::{CanonicalSerialize, Write};
+use ark_snark::SNARK;
+use ark_std::rand::{CryptoRng, RngCore};
+use core::marker::PhantomData;
+use core::str::FromStr;
+use num_bigint::BigUint;
+use serde_json::{json, Value};
+
+use super::decider_eth::{Decider as DeciderEth, DeciderEthCircuit, Proof as DeciderEthProof};
+use super::{circuits::CF2, CommittedInstance, IVCBindings, Nova};
+use crate::commitment::{
+    kzg::{Proof as KZGProof, ProverKey as KZGProverKey, KZG},
+    pedersen::Params as PedersenParams,
+    CommitmentScheme,
+};
+use crate::frontend::FCircuit;
+use crate::Decider as DeciderTrait;
+use crate::Error;
+
+/// name under which the `NovaCycleFold` Solidity verifier identifies its verifier key, see
+/// `serialize_verifier_param`
+pub const NOVA_CYCLEFOLD_PROTOCOL_NAME: &str = "NovaCycleFold";
+
+pub type DeciderGroth16ProverParam = (ProvingKey<Bn254>, KZGProverKey<'static, G1Projective>);
+pub type DeciderGroth16VerifierParam = (VerifyingKey<Bn254>, KZGVerifierKey<Bn254>);
+
+/// DeciderGroth16Proof contains the decider proof together with the last running and incoming
+/// instances of the IVC, which are needed to verify it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DeciderGroth16Proof {
+    pub U_i: CommittedInstance<G1Projective>,
+    pub u_i: CommittedInstance<G1Projective>,
+    pub proof: DeciderEthProof<G1Projective, KZG<'static, Bn254>, Groth16<Bn254>>,
+}
+
+impl DeciderGroth16Proof {
+    /// returns the proof serialized as JSON, for the verifiers that do not use the arkworks
+    /// serialization (eg. the web ones). The field elements are encoded as decimal strings, the
+    /// G1 points as `{"x", "y"}` (the additive identity as `x = y = 0`, as in the EVM), and the
+    /// elements of Fq2 as `[c0, c1]`. The schema is:
+    /// ```text
+    /// {
+    ///   "U_i": { "cmE": G1, "u": Fr, "cmW": G1, "x": [Fr] },
+    ///   "u_i": { "cmE": G1, "u": Fr, "cmW": G1, "x": [Fr] },
+    ///   "snark_proof": { "a": G1, "b": { "x": Fq2, "y": Fq2 }, "c": G1 },
+    ///   "kzg_proofs": [{ "eval": Fr, "proof": G1 }, { "eval": Fr, "proof": G1 }],
+    ///   "cmT": G1,
+    ///   "r": Fr,
+    ///   "kzg_challenges": [Fr, Fr]
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        let proof = &self.proof;
+        json!({
+            "U_i": committed_instance_to_json(&self.U_i),
+            "u_i": committed_instance_to_json(&self.u_i),
+            "snark_proof": {
+                "a": point_to_json(&proof.snark_proof.a, fp_to_json),
+                "b": point_to_json(&proof.snark_proof.b, fq2_to_json),
+                "c": point_to_json(&proof.snark_proof.c, fp_to_json),
+            },
+            "kzg_proofs": proof.kzg_proofs.iter().map(|p| json!({
+                "eval": fp_to_json(&p.eval),
+                "proof": point_to_json(&p.proof.into_affine(), fp_to_json),
+            })).collect::<Vec<Value>>(),
+            "cmT": point_to_json(&proof.cmT.into_affine(), fp_to_json),
+            "r": fp_to_json(&proof.r),
+            "kzg_challenges": proof.kzg_challenges.iter().map(fp_to_json).collect::<Vec<Value>>(),
+        })
+        .to_string()
+    }
+
+    /// parses a proof serialized with `to_json`. The field elements must be canonical, and the
+    /// points must be on the curve and in the prime order subgroup.
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let v: Value = serde_json::from_str(s).map_err(|e| Error::JSONError(e.to_string()))?;
+
+        let snark_proof = get(&v, "snark_proof")?;
+        let snark_proof = Groth16Proof::<Bn254> {
+            a: point_from_json("snark_proof.a", get(snark_proof, "a")?, fp_from_json)?,
+            b: point_from_json("snark_proof.b", get(snark_proof, "b")?, fq2_from_json)?,
+            c: point_from_json("snark_proof.c", get(snark_proof, "c")?, fp_from_json)?,
+        };
+        let kzg_proofs = array::<2>(get(&v, "kzg_proofs")?, "kzg_proofs")?;
+        let kzg_challenges = array::<2>(get(&v, "kzg_challenges")?, "kzg_challenges")?;
+
+        Ok(Self {
+            U_i: committed_instance_from_json("U_i", get(&v, "U_i")?)?,
+            u_i: committed_instance_from_json("u_i", get(&v, "u_i")?)?,
+            proof: DeciderEthProof {
+                snark_proof,
+                kzg_proofs: [
+                    kzg_proof_from_json(kzg_proofs[0])?,
+                    kzg_proof_from_json(kzg_proofs[1])?,
+                ],
+                cmT: point_from_json::<G1Config>("cmT", get(&v, "cmT")?, fp_from_json)?.into(),
+                r: fp_from_json(get(&v, "r")?)?,
+                kzg_challenges: [
+                    fp_from_json(kzg_challenges[0])?,
+                    fp_from_json(kzg_challenges[1])?,
+                ],
+            },
+        })
+    }
+}
+
+fn committed_instance_to_json(ci: &CommittedInstance<G1Projective>) -> Value {
+    json!({
+        "cmE": point_to_json(&ci.cmE.into_affine(), fp_to_json),
+        "u": fp_to_json(&ci.u),
+        "cmW": point_to_json(&ci.cmW.into_affine(), fp_to_json),
+        "x": ci.x.iter().map(fp_to_json).collect::<Vec<Value>>(),
+    })
+}
+
+fn committed_instance_from_json(
+    name: &str,
+    v: &Value,
+) -> Result<CommittedInstance<G1Projective>, Error> {
+    let x = get(v, "x")?
+        .as_array()
+        .ok_or_else(|| Error::JSONError(format!("{}.x is not an array", name)))?;
+    Ok(CommittedInstance {
+        cmE: point_from_json::<G1Config>(&format!("{}.cmE", name), get(v, "cmE")?, fp_from_json)?
+            .into(),
+        u: fp_from_json(get(v, "u")?)?,
+        cmW: point_from_json::<G1Config>(&format!("{}.cmW", name), get(v, "cmW")?, fp_from_json)?
+            .into(),
+        x: x.iter()
+            .map(fp_from_json)
+            .collect::<Result<Vec<Fr>, Error>>()?,
+    })
+}
+
+fn kzg_proof_from_json(v: &Value) -> Result<KZGProof<G1Projective>, Error> {
+    Ok(KZGProof {
+        eval: fp_from_json(get(v, "eval")?)?,
+        proof: point_from_json::<G1Config>("kzg_proofs.proof", get(v, "proof")?, fp_from_json)?
+            .into(),
+    })
+}
+
+fn fp_to_json<F: PrimeField>(f: &F) -> Value {
+    let n: BigUint = f.into_bigint().into();
+    Value::String(n.to_string())
+}
+
+fn fp_from_json<F: PrimeField>(v: &Value) -> Result<F, Error> {
+    let s = v
+        .as_str()
+        .ok_or_else(|| Error::JSONError(format!("{} is not a string", v)))?;
+    let n = BigUint::from_str(s).map_err(|e| Error::JSONError(format!("{}: {}", s, e)))?;
+    F::BigInt::try_from(n)
+        .ok()
+        .and_then(F::from_bigint)
+        .ok_or_else(|| Error::JSONError(format!("{} is not a canonical field element", s)))
+}
+
+fn fq2_to_json(f: &Fq2) -> Value {
+    json!([fp_to_json(&f.c0), fp_to_json(&f.c1)])
+}
+
+fn fq2_from_json(v: &Value) -> Result<Fq2, Error> {
+    let c = array::<2>(v, "Fq2")?;
+    Ok(Fq2::new(fp_from_json(c[0])?, fp_from_json(c[1])?))
+}
+
+fn point_to_json<P: SWCurveConfig>(p: &Affine<P>, f: impl Fn(&P::BaseField) -> Value) -> Value {
+    // the additive identity is encoded as (0, 0), as in the EVM
+    let zero = P::BaseField::zero();
+    let (x, y) = p.xy().unwrap_or((&zero, &zero));
+    json!({ "x": f(x), "y": f(y) })
+}
+
+fn point_from_json<P: SWCurveConfig>(
+    name: &str,
+    v: &Value,
+    f: impl Fn(&Value) -> Result<P::BaseField, Error>,
+) -> Result<Affine<P>, Error> {
+    let (x, y) = (f(get(v, "x")?)?, f(get(v, "y")?)?);
+    if x.is_zero() && y.is_zero() {
+        return Ok(Affine::identity());
+    }
+    let p = Affine::<P>::new_unchecked(x, y);
+    if !p.is_on_curve() || !p.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Error::InvalidPoint {
+            name: name.to_string(),
+        });
+    }
+    Ok(p)
+}
+
+fn get<'a>(v: &'a Value, key: &str) -> Result<&'a Value, Error> {
+    v.get(key)
+        .ok_or_else(|| Error::JSONError(format!("missing field {}", key)))
+}
+
+fn array<'a, const N: usize>(v: &'a Value, name: &str) -> Result<[&'a Value; N], Error> {
+    let a = v
+        .as_array()
+        .filter(|a| a.len() == N)
+        .ok_or_else(|| Error::JSONError(format!("{} is not an array of length {}", name, N)))?;
+    Ok(core::array::from_fn(|i| &a[i]))
+}
+
+/// DeciderGroth16 is the onchain `Decider` instantiated with Groth16 over BN254 and KZG
+/// commitments, working directly with Nova instances.
+#[derive(Clone, Debug)]
+pub struct DeciderGroth16<GC1, C2, GC2, FC, CS2> {
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    _fc: PhantomData<FC>,
+    _cs2: PhantomData<CS2>,
+}
+
+type NovaBN254<GC1, C2, GC2, FC, CS2> =
+    Nova<G1Projective, GC1, C2, GC2, FC, KZG<'static, Bn254>, CS2>;
+
+impl<GC1, C2, GC2, FC, CS2> DeciderGroth16<GC1, C2, GC2, FC, CS2>
+where
+    C2: CurveGroup<BaseField = Fr, ScalarField = Fq>,
+    GC1: CurveVar<G1Projective, Fq> + ToConstraintFieldGadget<Fq>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<Fr>,
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    <C2 as Group>::ScalarField: Absorb,
+    for<'a> &'a GC1: GroupOpsBounds<'a, G1Projective, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// generates the Groth16 keys for the decider circuit of the given Nova instance, returning
+    /// them together with the KZG params used by Nova. The shape of the decider circuit only
+    /// depends on the circuits of Nova, so the keys can be reused for any number of steps.
+    pub fn setup(
+        nova: NovaBN254<GC1, C2, GC2, FC, CS2>,
+        kzg_vk: KZGVerifierKey<Bn254>,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Result<(DeciderGroth16ProverParam, DeciderGroth16VerifierParam), Error> {
+        let kzg_pk = nova.cs_params.clone();
+        let circuit =
+            DeciderEthCircuit::<G1Projective, GC1, C2, GC2, KZG<'static, Bn254>, CS2>::from_nova::<
+                FC,
+            >(nova)?;
+        let (g16_pk, g16_vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut rng)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        Ok(((g16_pk, kzg_pk), (g16_vk, kzg_vk)))
+    }
+
+    /// compresses the IVC proof of the given Nova instance into a decider proof
+    pub fn prove(
+        decider_pp: DeciderGroth16ProverParam,
+        rng: impl RngCore + CryptoRng,
+        nova: NovaBN254<GC1, C2, GC2, FC, CS2>,
+    ) -> Result<DeciderGroth16Proof, Error> {
+        let (U_i, u_i) = (nova.U_i.clone(), nova.u_i.clone());
+        let proof = DeciderEth::<
+            G1Projective,
+            GC1,
+            C2,
+            GC2,
+            FC,
+            KZG<'static, Bn254>,
+            CS2,
+            Groth16<Bn254>,
+            NovaBN254<GC1, C2, GC2, FC, CS2>,
+        >::prove(decider_pp, rng, nova)?;
+        Ok(DeciderGroth16Proof { U_i, u_i, proof })
+    }
+
+    /// verifies that `z_i` is the state obtained after `num_steps` steps of the IVC starting
+    /// at `z_0`, with the given IVCBindings (see `Nova::bindings`)
+    pub fn verify(
+        decider_vp: DeciderGroth16VerifierParam,
+        proof: &DeciderGroth16Proof,
+        z_0: Vec<Fr>,
+        z_i: Vec<Fr>,
+        num_steps: u64,
+        bindings: &IVCBindings<Fr>,
+    ) -> Result<bool, Error> {
+        DeciderEth::<
+            G1Projective,
+            GC1,
+            C2,
+            GC2,
+            FC,
+            KZG<'static, Bn254>,
+            CS2,
+            Groth16<Bn254>,
+            NovaBN254<GC1, C2, GC2, FC, CS2>,
+        >::verify(
+            decider_vp,
+            Fr::from(num_steps),
+            z_0,
+            z_i,
+            bindings,
+            &proof.U_i,
+            &proof.u_i,
+            &proof.proof,
+        )
+    }
+}
+
+/// serializes the decider verifier params in the format of the `NovaCycleFold` protocol of the
+/// `solidity-verifiers` crate (ie. `NovaCycleFoldVerifierKey::deserialize_protocol_verifier_key`),
+/// from which the Solidity verifier of the decider proofs is rendered. `z_len` is the length of
+/// the IVC state.
+pub fn serialize_verifier_param<W: Write>(
+    decider_vp: &DeciderGroth16VerifierParam,
+    z_len: usize,
+    mut writer: W,
+) -> Result<(), Error> {
+    let (g16_vk, kzg_vk) = decider_vp;
+    NOVA_CYCLEFOLD_PROTOCOL_NAME
+        .to_string()
+        .serialize_uncompressed(&mut writer)?;
+    g16_vk.serialize_compressed(&mut writer)?;
+    kzg_vk.serialize_compressed(&mut writer)?;
+    // the decider contract does not use the KZG batch check, so no CRS points are needed
+    Vec::<G1Affine>::new().serialize_compressed(&mut writer)?;
+    z_len.serialize_compressed(&mut writer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use ark_bn254::constraints::GVar;
+    use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
+
+    use crate::commitment::pedersen::Pedersen;
+    use crate::folding::nova::{get_cs_params_len, ProverParams};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_decider_groth16() {
+        type NOVA = NovaBN254<GVar, Projective2, GVar2, CubicFCircuit<Fr>, Pedersen<Projective2>>;
+        type DECIDER =
+            DeciderGroth16<GVar, Projective2, GVar2, CubicFCircuit<Fr>, Pedersen<Projective2>>;
+
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<G1Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (kzg_pk, kzg_vk) = KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params =
+            ProverParams::<G1Projective, Projective2, KZG<Bn254>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: kzg_pk,
+                cf_cs_params: cf_pedersen_params,
+            };
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        let num_steps = 3;
+        for _ in 0..num_steps {
+            nova.prove_step(()).unwrap();
+        }
+
+        let (decider_pp, decider_vp) = DECIDER::setup(nova.clone(), kzg_vk, &mut rng).unwrap();
+        let proof = DECIDER::prove(decider_pp, &mut rng, nova.clone()).unwrap();
+        let bindings = nova.bindings().unwrap();
+        assert!(DECIDER::verify(
+            decider_vp.clone(),
+            &proof,
+            z_0.clone(),
+            nova.z_i.clone(),
+            num_steps,
+            &bindings
+        )
+        .unwrap());
+
+        // the proof does not verify for a different number of steps nor a different state
+        assert!(DECIDER::verify(
+            decider_vp.clone(),
+            &proof,
+            z_0.clone(),
+            nova.z_i.clone(),
+            num_steps + 1,
+            &bindings
+        )
+        .is_err());
+        assert!(DECIDER::verify(
+            decider_vp.clone(),
+            &proof,
+            z_0.clone(),
+            z_0.clone(),
+            num_steps,
+            &bindings
+        )
+        .is_err());
+
+        // the proof parsed from its JSON encoding is the same, and it verifies
+        let proof_from_json = DeciderGroth16Proof::from_json(&proof.to_json()).unwrap();
+        assert_eq!(proof_from_json, proof);
+        assert!(DECIDER::verify(
+            decider_vp,
+            &proof_from_json,
+            z_0,
+            nova.z_i,
+            num_steps,
+            &bindings
+        )
+        .unwrap());
+    }
+
+    /// proof with the values of `testdata/decider_groth16_proof.json`
+    fn sample_proof() -> DeciderGroth16Proof {
+        let g1 = G1Projective::generator();
+        let zero = G1Projective::zero();
+        DeciderGroth16Proof {
+            U_i: CommittedInstance {
+                cmE: g1,
+                u: Fr::from(2_u32),
+                cmW: g1,
+                x: vec![Fr::from(3_u32), Fr::from(4_u32)],
+            },
+            u_i: CommittedInstance {
+                cmE: zero,
+                u: Fr::from(1_u32),
+                cmW: g1,
+                x: vec![Fr::from(5_u32), Fr::from(6_u32)],
+            },
+            proof: DeciderEthProof {
+                snark_proof: Groth16Proof {
+                    a: g1.into_affine(),
+                    b: ark_bn254::G2Affine::generator(),
+                    c: G1Affine::identity(),
+                },
+                kzg_proofs: [
+                    KZGProof {
+                        eval: Fr::from(7_u32),
+                        proof: g1,
+                    },
+                    KZGProof {
+                        eval: Fr::from(8_u32),
+                        proof: zero,
+                    },
+                ],
+                cmT: g1,
+                r: Fr::from(9_u32),
+                kzg_challenges: [Fr::from(10_u32), Fr::from(11_u32)],
+            },
+        }
+    }
+
+    #[test]
+    fn test_decider_groth16_proof_json_schema() {
+        let sample = include_str!("testdata/decider_groth16_proof.json");
+        let proof = sample_proof();
+
+        // the encoding matches the committed sample, regardless of the formatting
+        let expected: Value = serde_json::from_str(sample).unwrap();
+        let got: Value = serde_json::from_str(&proof.to_json()).unwrap();
+        assert_eq!(got, expected);
+        assert_eq!(DeciderGroth16Proof::from_json(sample).unwrap(), proof);
+
+        // points that are not on the curve and non-canonical field elements are rejected
+        let not_on_curve = sample.replacen("\"y\": \"2\"", "\"y\": \"3\"", 1);
+        assert!(matches!(
+            DeciderGroth16Proof::from_json(&not_on_curve),
+            Err(Error::InvalidPoint { name }) if name == "U_i.cmE"
+        ));
+        let non_canonical = sample.replace("\"r\": \"9\"", &format!("\"r\": \"{}\"", Fr::MODULUS));
+        assert!(matches!(
+            DeciderGroth16Proof::from_json(&non_canonical),
+            Err(Error::JSONError(_))
+        ));
+    }
+}
@@ -1,9 +1,10 @@
 /// This file implements the onchain (Ethereum's EVM) decider.
 use ark_bn254::Bn254;
 use ark_crypto_primitives::sponge::Absorb;
-use ark_ec::{AffineRepr, CurveGroup, Group};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, Group};
 use ark_ff::{BigInteger, PrimeField};
 use ark_groth16::Groth16;
+use ark_poly_commit::kzg10::VerifierKey;
 use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
 use ark_snark::SNARK;
 use ark_std::rand::{CryptoRng, RngCore};
@@ -11,9 +12,9 @@ use ark_std::{One, Zero};
 use core::marker::PhantomData;
 
 pub use super::decider_eth_circuit::{DeciderEthCircuit, KZGChallengesGadget};
-use super::{circuits::CF2, nifs::NIFS, CommittedInstance, Nova};
+use super::{circuits::CF2, nifs::NIFS, traits::NovaR1CS, CommittedInstance, IVCBindings, Nova};
 use crate::commitment::{
-    kzg::{Proof as KZGProof, KZG},
+    kzg::{PairingBackend, Proof as KZGProof, KZG},
     pedersen::Params as PedersenParams,
     CommitmentScheme,
 };
@@ -29,15 +30,15 @@ where
     CS1: CommitmentScheme<C1, ProverChallenge = C1::ScalarField, Challenge = C1::ScalarField>,
     S: SNARK<C1::ScalarField>,
 {
-    snark_proof: S::Proof,
-    kzg_proofs: [CS1::Proof; 2],
+    pub(crate) snark_proof: S::Proof,
+    pub(crate) kzg_proofs: [CS1::Proof; 2],
     // cmT and r are values for the last fold, U_{i+1}=NIFS.V(r, U_i, u_i, cmT), and they are
     // checked in-circuit
-    cmT: C1,
-    r: C1::ScalarField,
+    pub(crate) cmT: C1,
+    pub(crate) r: C1::ScalarField,
     // the KZG challenges are provided by the prover, but in-circuit they are checked to match
     // the in-circuit computed computed ones.
-    kzg_challenges: [C1::ScalarField; 2],
+    pub(crate) kzg_challenges: [C1::ScalarField; 2],
 }
 
 /// Onchain Decider, for ethereum use cases
@@ -54,6 +55,132 @@ pub struct Decider<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS> {
     _fs: PhantomData<FS>,
 }
 
+impl<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS> Decider<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<
+        C1,
+        ProverChallenge = C1::ScalarField,
+        Challenge = C1::ScalarField,
+        Proof = KZGProof<C1>,
+    >, // KZG commitment, where challenge is C1::Fr elem
+    // enforce that the CS2 is Pedersen commitment scheme, since we're at Ethereum's EVM decider
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    S: SNARK<C1::ScalarField>,
+    FS: FoldingScheme<C1, C2, FC>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'b> &'b GC2: GroupOpsBounds<'b, C2, GC2>,
+    // constrain FS into Nova, since this is a Decider specifically for Nova
+    Nova<C1, GC1, C2, GC2, FC, CS1, CS2>: From<FS>,
+{
+    /// verifies the SNARK proof of the Decider, returning the folded instance `U_{d+1}` whose
+    /// commitments are opened by the KZG proofs
+    #[allow(clippy::too_many_arguments)]
+    fn verify_snark(
+        snark_vk: &S::VerifyingKey,
+        i: C1::ScalarField,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        bindings: &IVCBindings<C1::ScalarField>,
+        running_instance: &CommittedInstance<C1>,
+        incoming_instance: &CommittedInstance<C1>,
+        proof: &Proof<C1, CS1, S>,
+    ) -> Result<CommittedInstance<C1>, Error> {
+        if i <= C1::ScalarField::one() {
+            return Err(Error::NotEnoughSteps);
+        }
+
+        // compute U = U_{d+1}= NIFS.V(U_d, u_d, cmT)
+        let U = NIFS::<C1, CS1>::verify(proof.r, running_instance, incoming_instance, &proof.cmT);
+
+        let (cmE_x, cmE_y) = NonNativeAffineVar::inputize(U.cmE)?;
+        let (cmW_x, cmW_y) = NonNativeAffineVar::inputize(U.cmW)?;
+        let (cmT_x, cmT_y) = NonNativeAffineVar::inputize(proof.cmT)?;
+
+        let public_input: Vec<C1::ScalarField> = vec![
+            vec![i],
+            z_0,
+            z_i,
+            bindings.to_vec(),
+            vec![U.u],
+            U.x.clone(),
+            cmE_x,
+            cmE_y,
+            cmW_x,
+            cmW_y,
+            proof.kzg_challenges.to_vec(),
+            vec![
+                proof.kzg_proofs[0].eval, // eval_W
+                proof.kzg_proofs[1].eval, // eval_E
+            ],
+            cmT_x,
+            cmT_y,
+            vec![proof.r],
+        ]
+        .concat();
+
+        let snark_v = S::verify(snark_vk, &public_input, &proof.snark_proof)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        if !snark_v {
+            return Err(Error::SNARKVerificationFail);
+        }
+
+        Ok(U)
+    }
+
+    /// verifies the Decider proof as `verify`, where the final pairing checks of the KZG openings
+    /// are computed by the given PairingBackend, eg. to run them inside an HSM
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_backend<E: Pairing<G1 = C1, ScalarField = C1::ScalarField>>(
+        vp: (S::VerifyingKey, VerifierKey<E>),
+        i: C1::ScalarField,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        bindings: &IVCBindings<C1::ScalarField>,
+        running_instance: &CommittedInstance<C1>,
+        incoming_instance: &CommittedInstance<C1>,
+        proof: &Proof<C1, CS1, S>,
+        backend: &impl PairingBackend<E>,
+    ) -> Result<bool, Error> {
+        let (snark_vk, kzg_vk) = vp;
+        let U = Self::verify_snark(
+            &snark_vk,
+            i,
+            z_0,
+            z_i,
+            bindings,
+            running_instance,
+            incoming_instance,
+            proof,
+        )?;
+
+        KZG::<E>::verify_with_backend(
+            &kzg_vk,
+            proof.kzg_challenges[0],
+            &U.cmW,
+            &proof.kzg_proofs[0],
+            backend,
+        )?;
+        KZG::<E>::verify_with_backend(
+            &kzg_vk,
+            proof.kzg_challenges[1],
+            &U.cmE,
+            &proof.kzg_proofs[1],
+            backend,
+        )?;
+
+        Ok(true)
+    }
+}
+
 impl<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS> DeciderTrait<C1, C2, FC, FS>
     for Decider<C1, GC1, C2, GC2, FC, CS1, CS2, S, FS>
 where
@@ -84,7 +211,7 @@ where
     type ProverParam = (S::ProvingKey, CS1::ProverParams);
     type Proof = Proof<C1, CS1, S>;
     type VerifierParam = (S::VerifyingKey, CS1::VerifierParams);
-    type PublicInput = Vec<C1::ScalarField>;
+    type PublicInput = IVCBindings<C1::ScalarField>;
     type CommittedInstanceWithWitness = ();
     type CommittedInstance = CommittedInstance<C1>;
 
@@ -110,10 +237,10 @@ where
         // the above `from_nova` call
         let challenge_W = circuit
             .kzg_c_W
-            .ok_or(Error::MissingValue("kzg_c_W".to_string()))?;
+            .ok_or(Error::MissingValue { name: "kzg_c_W" })?;
         let challenge_E = circuit
             .kzg_c_E
-            .ok_or(Error::MissingValue("kzg_c_E".to_string()))?;
+            .ok_or(Error::MissingValue { name: "kzg_c_E" })?;
 
         // generate KZG proofs
         let U_cmW_proof = CS1::prove_with_challenge(
@@ -145,49 +272,22 @@ where
         i: C1::ScalarField,
         z_0: Vec<C1::ScalarField>,
         z_i: Vec<C1::ScalarField>,
+        bindings: &Self::PublicInput,
         running_instance: &Self::CommittedInstance,
         incoming_instance: &Self::CommittedInstance,
         proof: &Self::Proof,
     ) -> Result<bool, Error> {
-        if i <= C1::ScalarField::one() {
-            return Err(Error::NotEnoughSteps);
-        }
-
         let (snark_vk, cs_vk): (S::VerifyingKey, CS1::VerifierParams) = vp;
-
-        // compute U = U_{d+1}= NIFS.V(U_d, u_d, cmT)
-        let U = NIFS::<C1, CS1>::verify(proof.r, running_instance, incoming_instance, &proof.cmT);
-
-        let (cmE_x, cmE_y) = NonNativeAffineVar::inputize(U.cmE)?;
-        let (cmW_x, cmW_y) = NonNativeAffineVar::inputize(U.cmW)?;
-        let (cmT_x, cmT_y) = NonNativeAffineVar::inputize(proof.cmT)?;
-
-        let public_input: Vec<C1::ScalarField> = vec![
-            vec![i],
+        let U = Self::verify_snark(
+            &snark_vk,
+            i,
             z_0,
             z_i,
-            vec![U.u],
-            U.x.clone(),
-            cmE_x,
-            cmE_y,
-            cmW_x,
-            cmW_y,
-            proof.kzg_challenges.to_vec(),
-            vec![
-                proof.kzg_proofs[0].eval, // eval_W
-                proof.kzg_proofs[1].eval, // eval_E
-            ],
-            cmT_x,
-            cmT_y,
-            vec![proof.r],
-        ]
-        .concat();
-
-        let snark_v = S::verify(&snark_vk, &public_input, &proof.snark_proof)
-            .map_err(|e| Error::Other(e.to_string()))?;
-        if !snark_v {
-            return Err(Error::SNARKVerificationFail);
-        }
+            bindings,
+            running_instance,
+            incoming_instance,
+            proof,
+        )?;
 
         // we're at the Ethereum EVM case, so the CS1 is KZG commitments
         CS1::verify_with_challenge(
@@ -207,12 +307,104 @@ where
     }
 }
 
-/// Prepares solidity calldata for calling the NovaDecider contract
+/// DeciderCycle contains the decider proof obtained by `Nova::fold_then_decide`, together with
+/// the public inputs needed to verify it.
+#[derive(Debug, Clone)]
+pub struct DeciderCycle<C1, CS1, S>
+where
+    C1: CurveGroup,
+    CS1: CommitmentScheme<C1, ProverChallenge = C1::ScalarField, Challenge = C1::ScalarField>,
+    S: SNARK<C1::ScalarField>,
+{
+    pub i: C1::ScalarField,
+    pub z_0: Vec<C1::ScalarField>,
+    pub z_i: Vec<C1::ScalarField>,
+    pub bindings: IVCBindings<C1::ScalarField>,
+    pub U_i: CommittedInstance<C1>,
+    pub u_i: CommittedInstance<C1>,
+    pub proof: Proof<C1, CS1, S>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<
+        C1,
+        ProverChallenge = C1::ScalarField,
+        Challenge = C1::ScalarField,
+        Proof = KZGProof<C1>,
+    >,
+    CS2: CommitmentScheme<C2, ProverParams = PedersenParams<C2>>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    /// folds `k` steps, using the i-th element of `external_inputs` at the i-th step, and then
+    /// compresses the resulting IVC proof with the onchain Decider. Afterwards the scheme starts a
+    /// fresh IVC from the last state (ie. the new `z_0` is the current `z_i`, see `Nova::reset`),
+    /// so that the depth of the recursion is bounded by `k`.
+    pub fn fold_then_decide<S: SNARK<C1::ScalarField>>(
+        &mut self,
+        decider_pp: (S::ProvingKey, CS1::ProverParams),
+        rng: impl RngCore + CryptoRng,
+        external_inputs: Vec<FC::ExternalInputs>,
+        k: usize,
+    ) -> Result<DeciderCycle<C1, CS1, S>, Error> {
+        // the Decider needs at least two folded steps
+        if k < 2 {
+            return Err(Error::NotEnoughSteps);
+        }
+        if external_inputs.len() != k {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "k",
+                b_len: k,
+            });
+        }
+        for inputs in external_inputs {
+            self.prove_step(inputs)?;
+        }
+
+        let proof = Decider::<C1, GC1, C2, GC2, FC, CS1, CS2, S, Self>::prove(
+            decider_pp,
+            rng,
+            self.clone(),
+        )?;
+        let cycle = DeciderCycle {
+            i: self.i,
+            z_0: self.z_0.clone(),
+            z_i: self.z_i.clone(),
+            bindings: self.bindings()?,
+            U_i: self.U_i.clone(),
+            u_i: self.u_i.clone(),
+            proof,
+        };
+
+        // start a new IVC from the last state
+        self.reset(self.z_i.clone())?;
+
+        Ok(cycle)
+    }
+}
+
+/// Prepares solidity calldata for calling the NovaDecider contract. The IVCBindings are appended
+/// to `[i, z_0, z_i]` in the first argument.
+#[allow(clippy::too_many_arguments)]
 pub fn prepare_calldata(
     function_signature_check: [u8; 4],
     i: ark_bn254::Fr,
     z_0: Vec<ark_bn254::Fr>,
     z_i: Vec<ark_bn254::Fr>,
+    bindings: &IVCBindings<ark_bn254::Fr>,
     running_instance: &CommittedInstance<ark_bn254::G1Projective>,
     incoming_instance: &CommittedInstance<ark_bn254::G1Projective>,
     proof: Proof<ark_bn254::G1Projective, KZG<'static, Bn254>, Groth16<Bn254>>,
@@ -226,6 +418,11 @@ pub fn prepare_calldata(
         z_i.iter()
             .flat_map(|v| v.into_bigint().to_bytes_be())
             .collect::<Vec<u8>>(), // z_i
+        bindings
+            .to_vec()
+            .iter()
+            .flat_map(|v| v.into_bigint().to_bytes_be())
+            .collect::<Vec<u8>>(), // bindings
         point_to_eth_format(running_instance.cmW.into_affine())?, // U_i_cmW
         point_to_eth_format(running_instance.cmE.into_affine())?, // U_i_cmE
         running_instance.u.into_bigint().to_bytes_be(), // U_i_u
@@ -288,12 +485,88 @@ pub mod tests {
     use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
     use std::time::Instant;
 
-    use crate::commitment::kzg::{ProverKey as KZGProverKey, KZG};
+    use crate::commitment::kzg::{ProverKey as KZGProverKey, SoftwarePairing, KZG};
     use crate::commitment::pedersen::Pedersen;
-    use crate::folding::nova::{get_cs_params_len, ProverParams};
+    use crate::folding::nova::{
+        get_cs_params_len, io_accumulator, tests::SquareIOFCircuit, ProverParams,
+    };
     use crate::frontend::tests::CubicFCircuit;
     use crate::transcript::poseidon::poseidon_test_config;
 
+    #[test]
+    fn test_decider_kzg_openings() {
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+        >;
+
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        // an SRS shorter than the AugmentedFCircuit can not be used
+        let (small_kzg_pk, _): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len.next_power_of_two() / 2).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, KZG<Bn254>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: small_kzg_pk,
+                cf_cs_params: cf_pedersen_params.clone(),
+            };
+        assert!(matches!(
+            NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]),
+            Err(Error::CircuitTooLarge { .. })
+        ));
+
+        let (kzg_pk, kzg_vk): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, KZG<Bn254>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: kzg_pk.clone(),
+                cf_cs_params: cf_pedersen_params,
+            };
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.prove_step(()).unwrap();
+        nova.prove_step(()).unwrap();
+
+        // open the commitments of the final folded instance at the challenges computed for the
+        // decider, as `Decider::prove` does, and check them as `Decider::verify` does
+        let circuit = DeciderEthCircuit::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            KZG<Bn254>,
+            Pedersen<Projective2>,
+        >::from_nova::<CubicFCircuit<Fr>>(nova)
+        .unwrap();
+        let (W_i1, U_i1) = (circuit.W_i1.unwrap(), circuit.U_i1.unwrap());
+        for (challenge, v, cm, eval) in [
+            (circuit.kzg_c_W.unwrap(), &W_i1.W, U_i1.cmW, circuit.eval_W),
+            (circuit.kzg_c_E.unwrap(), &W_i1.E, U_i1.cmE, circuit.eval_E),
+        ] {
+            let proof =
+                KZG::<Bn254>::prove_with_challenge(&kzg_pk, challenge, v, &Fr::zero(), None)
+                    .unwrap();
+            assert_eq!(Some(proof.eval), eval);
+            KZG::<Bn254>::verify_with_challenge(&kzg_vk, challenge, &cm, &proof).unwrap();
+        }
+    }
+
     #[test]
     fn test_decider() {
         // use Nova as FoldingScheme
@@ -347,9 +620,9 @@ pub mod tests {
         let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
         println!("Nova initialized, {:?}", start.elapsed());
         let start = Instant::now();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(()).unwrap();
         println!("prove_step, {:?}", start.elapsed());
-        nova.prove_step(vec![]).unwrap(); // do a 2nd step
+        nova.prove_step(()).unwrap(); // do a 2nd step
 
         // generate Groth16 setup
         let circuit = DeciderEthCircuit::<
@@ -377,11 +650,162 @@ pub mod tests {
         // decider proof verification
         let start = Instant::now();
         let decider_vp = (g16_vk, kzg_vk);
+        let bindings = nova.bindings().unwrap();
         let verified = DECIDER::verify(
-            decider_vp, nova.i, nova.z_0, nova.z_i, &nova.U_i, &nova.u_i, &proof,
+            decider_vp.clone(),
+            nova.i,
+            nova.z_0.clone(),
+            nova.z_i.clone(),
+            &bindings,
+            &nova.U_i,
+            &nova.u_i,
+            &proof,
         )
         .unwrap();
         assert!(verified);
         println!("Decider verify, {:?}", start.elapsed());
+
+        // the metadata digest is a public input, so the proof does not verify with another one
+        assert!(DECIDER::verify(
+            decider_vp.clone(),
+            nova.i,
+            nova.z_0.clone(),
+            nova.z_i.clone(),
+            &IVCBindings {
+                metadata: Fr::from(1_u32),
+                ..bindings
+            },
+            &nova.U_i,
+            &nova.u_i,
+            &proof,
+        )
+        .is_err());
+
+        // the final pairing checks can also be computed by a PairingBackend
+        let verified = DECIDER::verify_with_backend(
+            decider_vp,
+            nova.i,
+            nova.z_0,
+            nova.z_i,
+            &bindings,
+            &nova.U_i,
+            &nova.u_i,
+            &proof,
+            &SoftwarePairing,
+        )
+        .unwrap();
+        assert!(verified);
+    }
+
+    #[test]
+    fn test_fold_then_decide() {
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            SquareIOFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+        >;
+        type DECIDER = Decider<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            SquareIOFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+            Groth16<Bn254>,
+            NOVA,
+        >;
+
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = SquareIOFCircuit::<Fr>::new(()).unwrap();
+        let z_0 = vec![Fr::from(3_u32)];
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, SquareIOFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (kzg_pk, kzg_vk): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, KZG<Bn254>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: kzg_pk.clone(),
+                cf_cs_params: cf_pedersen_params,
+            };
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+
+        // the Decider circuit does not depend on the number of steps, so the Groth16 setup can be
+        // done once and reused for all the cycles
+        let mut nova_setup = nova.clone();
+        nova_setup.prove_step(()).unwrap();
+        nova_setup.prove_step(()).unwrap();
+        let circuit = DeciderEthCircuit::<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            KZG<Bn254>,
+            Pedersen<Projective2>,
+        >::from_nova::<SquareIOFCircuit<Fr>>(nova_setup)
+        .unwrap();
+        let mut rng = rand::rngs::OsRng;
+        let (g16_pk, g16_vk) = Groth16::<Bn254>::circuit_specific_setup(circuit, &mut rng).unwrap();
+
+        let k = 2;
+        let mut cycles = vec![];
+        for _ in 0..2 {
+            let cycle = nova
+                .fold_then_decide::<Groth16<Bn254>>(
+                    (g16_pk.clone(), kzg_pk.clone()),
+                    rng,
+                    vec![(); k],
+                    k,
+                )
+                .unwrap();
+            assert_eq!(cycle.i, Fr::from(k as u32));
+            // the IO accumulator of the cycle only covers its own steps
+            let mut io_acc = Fr::zero();
+            let mut z = cycle.z_0.clone();
+            for i in 0..k {
+                let io = F_circuit.step_io_native(i, z.clone(), ()).unwrap();
+                io_acc = io_accumulator(&prover_params.poseidon_config, io_acc, &io).unwrap();
+                z = F_circuit.step_native(i, z, ()).unwrap();
+            }
+            assert_eq!(cycle.bindings.io_acc, Some(io_acc));
+            // the IVC has been reset to the values of init
+            assert_eq!(nova.i, Fr::zero());
+            assert_eq!(nova.z_0, cycle.z_i);
+            assert!(nova.io.is_empty());
+            assert_eq!(nova.io_acc, Fr::zero());
+            assert!(nova.checkpoints.is_empty());
+            assert_eq!(nova.domain, Fr::zero());
+            cycles.push(cycle);
+        }
+        // the second cycle starts at the last state of the first one
+        assert_eq!(cycles[0].z_0, z_0);
+        assert_eq!(cycles[1].z_0, cycles[0].z_i);
+
+        for cycle in cycles {
+            let verified = DECIDER::verify(
+                (g16_vk.clone(), kzg_vk.clone()),
+                cycle.i,
+                cycle.z_0,
+                cycle.z_i,
+                &cycle.bindings,
+                &cycle.U_i,
+                &cycle.u_i,
+                &cycle.proof,
+            )
+            .unwrap();
+            assert!(verified);
+        }
     }
 }
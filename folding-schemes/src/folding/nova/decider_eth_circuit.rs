@@ -28,7 +28,7 @@ use crate::folding::circuits::nonnative::{
 };
 use crate::folding::nova::{
     circuits::{CommittedInstanceVar, CF1, CF2},
-    CommittedInstance, Nova, Witness,
+    metadata_digest, CommittedInstance, IVCBindings, Nova, Witness,
 };
 use crate::frontend::FCircuit;
 use crate::transcript::{
@@ -228,6 +228,9 @@ where
     pub z_0: Option<Vec<C1::ScalarField>>,
     /// current i-th state
     pub z_i: Option<Vec<C1::ScalarField>>,
+    /// IVCBindings of the last state (metadata digest, transcript domain, and IO accumulator for
+    /// FCircuits that expose IO), which are public inputs
+    pub bindings: Option<IVCBindings<C1::ScalarField>>,
     /// Nova instances
     pub u_i: Option<CommittedInstance<C1>>,
     pub w_i: Option<Witness<C1>>,
@@ -287,22 +290,19 @@ where
         let (kzg_challenge_W, kzg_challenge_E) =
             KZGChallengesGadget::<C1>::get_challenges_native(&nova.poseidon_config, U_i1.clone())?;
 
-        // get KZG evals
-        let mut W = W_i1.W.clone();
-        W.extend(
-            std::iter::repeat(C1::ScalarField::zero())
-                .take(W_i1.W.len().next_power_of_two() - W_i1.W.len()),
-        );
-        let mut E = W_i1.E.clone();
-        E.extend(
-            std::iter::repeat(C1::ScalarField::zero())
-                .take(W_i1.E.len().next_power_of_two() - W_i1.E.len()),
-        );
-        let p_W = poly_from_vec(W.to_vec())?;
+        // get KZG evals, poly_from_vec pads the vectors to the next power of two as KZG::commit
+        // does
+        let p_W = poly_from_vec(W_i1.W.clone())?;
         let eval_W = p_W.evaluate(&kzg_challenge_W);
-        let p_E = poly_from_vec(E.to_vec())?;
+        let p_E = poly_from_vec(W_i1.E.clone())?;
         let eval_E = p_E.evaluate(&kzg_challenge_E);
 
+        let bindings = IVCBindings {
+            metadata: metadata_digest(&nova.poseidon_config, &nova.metadata)?,
+            domain: nova.domain,
+            io_acc: (nova.F.io_len() > 0).then_some(nova.io_acc),
+        };
+
         Ok(Self {
             _c1: PhantomData,
             _gc1: PhantomData,
@@ -320,6 +320,7 @@ where
             i: Some(nova.i),
             z_0: Some(nova.z_0),
             z_i: Some(nova.z_i),
+            bindings: Some(bindings),
             u_i: Some(nova.u_i),
             w_i: Some(nova.w_i),
             U_i: Some(nova.U_i),
@@ -368,6 +369,9 @@ where
         let z_i = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
             Ok(self.z_i.unwrap_or(vec![CF1::<C1>::zero()]))
         })?;
+        let bindings = Vec::<FpVar<CF1<C1>>>::new_input(cs.clone(), || {
+            Ok(self.bindings.unwrap_or_default().to_vec())
+        })?;
 
         let u_dummy_native = CommittedInstance::<C1>::dummy(2);
         let w_dummy_native = Witness::<C1>::new(
@@ -420,10 +424,14 @@ where
         u_i.cmE.y.enforce_equal_unaligned(&zero)?;
         (u_i.u.is_one()?).enforce_equal(&Boolean::TRUE)?;
 
-        // 3.a u_i.x[0] == H(i, z_0, z_i, U_i)
-        let (u_i_x, U_i_vec) =
-            U_i.clone()
-                .hash(&crh_params, i.clone(), z_0.clone(), z_i.clone())?;
+        // 3.a u_i.x[0] == H(i, z_0, z_i, b, U_i)
+        let (u_i_x, U_i_vec) = U_i.clone().hash_with_bindings(
+            &crh_params,
+            i.clone(),
+            z_0.clone(),
+            z_i.clone(),
+            bindings,
+        )?;
         (u_i.x[0]).enforce_equal(&u_i_x)?;
 
         #[cfg(feature = "light-test")]
@@ -675,7 +683,7 @@ pub mod tests {
         let circuit = WrapperCircuit::<Fr, CubicFCircuit<Fr>> {
             FC: cubic_circuit,
             z_i: Some(z_i.clone()),
-            z_i1: Some(cubic_circuit.step_native(0, z_i, vec![]).unwrap()),
+            z_i1: Some(cubic_circuit.step_native(0, z_i, ()).unwrap()),
         };
 
         test_relaxed_r1cs_gadget(circuit);
@@ -718,7 +726,7 @@ pub mod tests {
         let circuit = WrapperCircuit::<Fr, CustomFCircuit<Fr>> {
             FC: custom_circuit,
             z_i: Some(z_i.clone()),
-            z_i1: Some(custom_circuit.step_native(0, z_i, vec![]).unwrap()),
+            z_i1: Some(custom_circuit.step_native(0, z_i, ()).unwrap()),
         };
         test_relaxed_r1cs_gadget(circuit);
     }
@@ -734,7 +742,7 @@ pub mod tests {
         let circuit = WrapperCircuit::<Fq, CustomFCircuit<Fq>> {
             FC: custom_circuit,
             z_i: Some(z_i.clone()),
-            z_i1: Some(custom_circuit.step_native(0, z_i, vec![]).unwrap()),
+            z_i1: Some(custom_circuit.step_native(0, z_i, ()).unwrap()),
         };
         circuit.generate_constraints(cs.clone()).unwrap();
         cs.finalize();
@@ -802,14 +810,16 @@ pub mod tests {
 
         // generate a Nova instance and do a step of it
         let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
-        nova.prove_step(vec![]).unwrap();
+        nova.prove_step(()).unwrap();
         let ivc_v = nova.clone();
         let verifier_params = VerifierParams::<Projective, Projective2> {
             poseidon_config: poseidon_config.clone(),
             r1cs: ivc_v.clone().r1cs,
             cf_r1cs: ivc_v.clone().cf_r1cs,
+            state_len: ivc_v.F.state_len(),
         };
         let (running_instance, incoming_instance, cyclefold_instance) = ivc_v.instances();
+        let proof_data = ivc_v.proof_data();
         NOVA::verify(
             verifier_params,
             z_0,
@@ -818,6 +828,7 @@ pub mod tests {
             running_instance,
             incoming_instance,
             cyclefold_instance,
+            proof_data,
         )
         .unwrap();
 
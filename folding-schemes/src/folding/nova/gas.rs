@@ -0,0 +1,108 @@
+/// Estimates of the gas of verifying on-chain the folding of a step of Nova, following the
+/// operations that the Nova+CycleFold solidity verifier (`verifyNovaProof`, of the
+/// solidity-verifiers crate) does over the data of the folded step. The elliptic curve ops are
+/// priced with the BN254 precompiles, so the estimates are only meaningful for C1 = BN254.
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+/// gas of a 32 bytes word of calldata, counting all its bytes as nonzero (EIP-2028), so that the
+/// estimate does not depend on the values of the step
+pub const GAS_CALLDATA_WORD: u64 = 32 * 16;
+/// gas of the ecAdd precompile (EIP-1108)
+pub const GAS_ECADD: u64 = 150;
+/// gas of the ecMul precompile (EIP-1108)
+pub const GAS_ECMUL: u64 = 6000;
+/// gas of a random linear combination of two field elements (a `mulmod` and an `addmod`)
+pub const GAS_RLC: u64 = 16;
+/// gas of extracting a limb of a coordinate (shift, mask and store into memory)
+pub const GAS_LIMB: u64 = 20;
+
+/// GasEstimate is the estimated gas of verifying on-chain the folding of a step, split by the
+/// kind of operation.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct GasEstimate {
+    /// calldata of the step: the incoming instance (u, x, cmW), cmT and the challenge r
+    pub calldata: u64,
+    /// elliptic curve ops folding cmE and cmW
+    pub ec_ops: u64,
+    /// field ops folding u and x, and decomposing into limbs the coordinates of the folded cmE
+    /// and cmW and of cmT
+    pub field_ops: u64,
+}
+
+impl GasEstimate {
+    pub fn total(&self) -> u64 {
+        self.calldata + self.ec_ops + self.field_ops
+    }
+}
+
+/// estimates the gas of verifying on-chain the folding of a step whose incoming instance has
+/// `io_len` public inputs. The estimate depends only on the sizes of the step data, so it is the
+/// same for all the steps of an IVC.
+pub fn estimate_step_gas<C: CurveGroup>(io_len: usize) -> GasEstimate
+where
+    C::BaseField: PrimeField,
+{
+    let io_len = io_len as u64;
+    // a coordinate is decomposed into limbs of the scalar field, as in
+    // `nonnative_field_to_field_elements`
+    let num_limbs = (C::BaseField::MODULUS_BIT_SIZE as u64)
+        .div_ceil(C::ScalarField::MODULUS_BIT_SIZE as u64 - 1);
+
+    // u, x, cmW, cmT and r, with the points as their affine coordinates
+    let calldata_words = 1 + io_len + 2 + 2 + 1;
+    GasEstimate {
+        calldata: calldata_words * GAS_CALLDATA_WORD,
+        // U_i.cmE + r * cmT and U_i.cmW + r * u_i.cmW
+        ec_ops: 2 * (GAS_ECMUL + GAS_ECADD),
+        // u, x, and the 2 coordinates of 3 points
+        field_ops: (1 + io_len) * GAS_RLC + 3 * 2 * num_limbs * GAS_LIMB,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_fold_gas_estimate() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        // the estimate is a static per-fold cost, which does not change with the proven steps
+        let estimate = nova.fold_gas_estimate();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+            assert_eq!(nova.fold_gas_estimate(), estimate);
+        }
+
+        assert!(estimate.total() > 0);
+        // 2 ecMul and 2 ecAdd, and 8 words of calldata for the 2 public inputs of the instance
+        assert_eq!(estimate.ec_ops, 12300);
+        assert_eq!(estimate.calldata, 8 * GAS_CALLDATA_WORD);
+        assert_eq!(estimate, estimate_step_gas::<ark_bn254::G1Projective>(2));
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -6,39 +6,69 @@ use ark_crypto_primitives::{
 };
 use ark_ec::{AffineRepr, CurveGroup, Group};
 use ark_ff::{BigInteger, Field, PrimeField, ToConstraintField};
-use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+use ark_r1cs_std::{
+    alloc::AllocVar, fields::fp::FpVar, groups::GroupOpsBounds, prelude::CurveVar,
+    ToConstraintFieldGadget,
+};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
 use ark_std::fmt::Debug;
 use ark_std::{One, Zero};
 use core::marker::PhantomData;
+use sha3::{Digest, Keccak256};
+#[cfg(feature = "fs")]
+use std::{fs, io::BufWriter, path::Path};
 
 use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
 
 use crate::ccs::r1cs::{extract_r1cs, extract_w_x, R1CS};
 use crate::commitment::CommitmentScheme;
+use crate::constants::N_BITS_RO;
 use crate::folding::circuits::nonnative::{
-    affine::nonnative_affine_to_field_elements, uint::nonnative_field_to_field_elements,
+    affine::nonnative_affine_to_field_elements,
+    uint::{nonnative_field_to_field_elements, LimbConfig},
+};
+use crate::folding::verification_report::VerificationReport;
+#[cfg(feature = "fs")]
+use crate::frontend::circom::export::{wires_layout_json, write_r1cs};
+use crate::frontend::{ExternalInputs, FCircuit};
+use crate::transcript::poseidon::{
+    deserialize_poseidon_config, poseidon_config_digest, poseidon_config_serialized_size,
+    serialize_poseidon_config,
 };
-use crate::frontend::FCircuit;
 use crate::utils::vec::is_zero_vec;
 use crate::Error;
 use crate::FoldingScheme;
 
 pub mod circuits;
 pub mod cyclefold;
+pub mod decider;
 pub mod decider_eth;
 pub mod decider_eth_circuit;
+pub mod gas;
 pub mod nifs;
+pub mod params_merkle;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod streaming;
 pub mod traits;
 
 use circuits::{AugmentedFCircuit, ChallengeGadget, CF2};
-use cyclefold::{CycleFoldChallengeGadget, CycleFoldCircuit};
+use cyclefold::{CycleFoldChallengeGadget, CycleFoldCircuit, CycleFoldError};
+use gas::{estimate_step_gas, GasEstimate};
 use nifs::NIFS;
 use traits::NovaR1CS;
 
+#[cfg(feature = "stats")]
+use stats::StepStats;
+use std::time::{Duration, Instant};
+
 #[cfg(test)]
 use cyclefold::CF_IO_LEN;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CommittedInstance<C: CurveGroup> {
     pub cmE: C,
     pub u: C::ScalarField,
@@ -64,14 +94,28 @@ where
 {
     /// hash implements the committed instance hash compatible with the gadget implemented in
     /// nova/circuits.rs::CommittedInstanceVar.hash.
-    /// Returns `H(i, z_0, z_i, U_i)`, where `i` can be `i` but also `i+1`, and `U_i` is the
-    /// `CommittedInstance`.
+    /// Returns `H(i, z_0, z_i, b, U_i)` for an IVC with the default IVCBindings (no metadata
+    /// nor IO), see `hash_with_bindings`.
     pub fn hash(
         &self,
         poseidon_config: &PoseidonConfig<C::ScalarField>,
         i: C::ScalarField,
         z_0: Vec<C::ScalarField>,
         z_i: Vec<C::ScalarField>,
+    ) -> Result<C::ScalarField, Error> {
+        self.hash_with_bindings(poseidon_config, i, z_0, z_i, &IVCBindings::default())
+    }
+
+    /// Returns `H(i, z_0, z_i, b, U_i)`, where `i` can be `i` but also `i+1`, `b` are the
+    /// IVCBindings (the metadata digest, the transcript domain, and the IO accumulator for
+    /// FCircuits that expose IO), and `U_i` is the `CommittedInstance`.
+    pub fn hash_with_bindings(
+        &self,
+        poseidon_config: &PoseidonConfig<C::ScalarField>,
+        i: C::ScalarField,
+        z_0: Vec<C::ScalarField>,
+        z_i: Vec<C::ScalarField>,
+        bindings: &IVCBindings<C::ScalarField>,
     ) -> Result<C::ScalarField, Error> {
         let (cmE_x, cmE_y) = nonnative_affine_to_field_elements::<C>(self.cmE)?;
         let (cmW_x, cmW_y) = nonnative_affine_to_field_elements::<C>(self.cmW)?;
@@ -82,6 +126,7 @@ where
                 vec![i],
                 z_0,
                 z_i,
+                bindings.to_vec(),
                 vec![self.u],
                 self.x.clone(),
                 cmE_x,
@@ -91,7 +136,9 @@ where
             ]
             .concat(),
         )
-        .map_err(|e| Error::Other(e.to_string()))
+        .map_err(|e| Error::CRHFail {
+            reason: e.to_string(),
+        })
     }
 }
 
@@ -140,12 +187,15 @@ where
         &self,
         poseidon_config: &PoseidonConfig<C::BaseField>,
     ) -> Result<C::BaseField, Error> {
-        CRH::<C::BaseField>::evaluate(poseidon_config, self.to_field_elements().unwrap())
-            .map_err(|e| Error::Other(e.to_string()))
+        CRH::<C::BaseField>::evaluate(poseidon_config, self.to_field_elements().unwrap()).map_err(
+            |e| Error::CRHFail {
+                reason: e.to_string(),
+            },
+        )
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Witness<C: CurveGroup> {
     pub E: Vec<C::ScalarField>,
     pub rE: C::ScalarField,
@@ -186,6 +236,12 @@ where
     }
 }
 
+/// Prover params of Nova+CycleFold. The commitment schemes of both curves are independent: CS1
+/// commits to the witnesses of the AugmentedFCircuit, and CS2 to the witnesses of the CycleFold
+/// circuit. The CycleFold circuit is small and does not depend on the FCircuit, so a cheap scheme
+/// with a small key suffices for CS2 (eg. Pedersen with the `cf_cs_len` returned by
+/// `get_cs_params_len`), even when CS1 is KZG. Notice that the onchain decider (`decider_eth`)
+/// requires CS2 to be Pedersen, since it opens the CycleFold commitments inside its circuit.
 #[derive(Debug, Clone)]
 pub struct ProverParams<C1, C2, CS1, CS2>
 where
@@ -199,40 +255,410 @@ where
     pub cf_cs_params: CS2::ProverParams,
 }
 
-#[derive(Debug, Clone)]
-pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
-    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
-    pub r1cs: R1CS<C1::ScalarField>,
-    pub cf_r1cs: R1CS<C2::ScalarField>,
+impl<C1, C2, CS1, CS2> ProverParams<C1, C2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    /// returns the maximum size of the AugmentedFCircuit that can be proven with the params, that
+    /// is the maximum number of constraints and of witness elements, given by the length of the
+    /// commitment key of the first curve. This is the counterpart of `get_cs_params_len`.
+    pub fn max_circuit_size(&self) -> usize {
+        CS1::max_len(&self.cs_params)
+    }
+
+    /// returns the maximum size of the CycleFold circuit that can be proven with the params,
+    /// given by the length of the commitment key of the second curve.
+    pub fn max_cf_circuit_size(&self) -> usize {
+        CS2::max_len(&self.cf_cs_params)
+    }
 }
 
-/// Implements Nova+CycleFold's IVC, described in [Nova](https://eprint.iacr.org/2021/370.pdf) and
-/// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), following the FoldingScheme trait
-#[derive(Clone, Debug)]
-pub struct Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+// the params are serialized field by field, writing the Poseidon config with
+// `serialize_poseidon_config` since it does not implement `CanonicalSerialize`
+impl<C1, C2, CS1, CS2> CanonicalSerialize for ProverParams<C1, C2, CS1, CS2>
 where
     C1: CurveGroup,
-    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     C2: CurveGroup,
-    GC2: CurveVar<C2, CF2<C2>>,
-    FC: FCircuit<C1::ScalarField>,
     CS1: CommitmentScheme<C1>,
     CS2: CommitmentScheme<C2>,
+    CS1::ProverParams: CanonicalSerialize,
+    CS2::ProverParams: CanonicalSerialize,
 {
-    _gc1: PhantomData<GC1>,
-    _c2: PhantomData<C2>,
-    _gc2: PhantomData<GC2>,
-    /// R1CS of the Augmented Function circuit
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_poseidon_config(&self.poseidon_config, &mut writer, compress)?;
+        self.cs_params.serialize_with_mode(&mut writer, compress)?;
+        self.cf_cs_params.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        poseidon_config_serialized_size(&self.poseidon_config, compress)
+            + self.cs_params.serialized_size(compress)
+            + self.cf_cs_params.serialized_size(compress)
+    }
+}
+
+impl<C1, C2, CS1, CS2> Valid for ProverParams<C1, C2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    CS1::ProverParams: CanonicalDeserialize,
+    CS2::ProverParams: CanonicalDeserialize,
+{
+    fn check(&self) -> Result<(), SerializationError> {
+        self.cs_params.check()?;
+        self.cf_cs_params.check()
+    }
+}
+
+impl<C1, C2, CS1, CS2> CanonicalDeserialize for ProverParams<C1, C2, CS1, CS2>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    CS1::ProverParams: CanonicalDeserialize,
+    CS2::ProverParams: CanonicalDeserialize,
+{
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let poseidon_config = deserialize_poseidon_config(&mut reader, compress, validate)?;
+        let cs_params = CS1::ProverParams::deserialize_with_mode(&mut reader, compress, validate)?;
+        let cf_cs_params =
+            CS2::ProverParams::deserialize_with_mode(&mut reader, compress, validate)?;
+        Ok(Self {
+            poseidon_config,
+            cs_params,
+            cf_cs_params,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifierParams<C1: CurveGroup, C2: CurveGroup> {
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
     pub r1cs: R1CS<C1::ScalarField>,
-    /// R1CS of the CycleFold circuit
     pub cf_r1cs: R1CS<C2::ScalarField>,
-    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
-    /// CommitmentScheme::ProverParams over C1
-    pub cs_params: CS1::ProverParams,
-    /// CycleFold CommitmentScheme::ProverParams, over C2
-    pub cf_cs_params: CS2::ProverParams,
-    /// F circuit, the circuit that is being folded
-    pub F: FC,
+    /// length of the state of the FCircuit
+    pub state_len: usize,
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> CanonicalSerialize for VerifierParams<C1, C2> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        serialize_poseidon_config(&self.poseidon_config, &mut writer, compress)?;
+        self.r1cs.serialize_with_mode(&mut writer, compress)?;
+        self.cf_r1cs.serialize_with_mode(&mut writer, compress)?;
+        self.state_len.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        poseidon_config_serialized_size(&self.poseidon_config, compress)
+            + self.r1cs.serialized_size(compress)
+            + self.cf_r1cs.serialized_size(compress)
+            + self.state_len.serialized_size(compress)
+    }
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> Valid for VerifierParams<C1, C2> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.r1cs.check()?;
+        self.cf_r1cs.check()
+    }
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> CanonicalDeserialize for VerifierParams<C1, C2> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(Self {
+            poseidon_config: deserialize_poseidon_config(&mut reader, compress, validate)?,
+            r1cs: R1CS::deserialize_with_mode(&mut reader, compress, validate)?,
+            cf_r1cs: R1CS::deserialize_with_mode(&mut reader, compress, validate)?,
+            state_len: usize::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> VerifierParams<C1, C2> {
+    /// returns the bits of security of the folding, given by its soundness error, which is
+    /// `≤ 2/|S|` for the space `S` of the folding challenges. The challenges are of `N_BITS_RO`
+    /// bits, and they are bounded by the scalar fields of both curves, since they are used in
+    /// both.
+    pub fn security_bits(&self) -> usize {
+        let field_bits =
+            C1::ScalarField::MODULUS_BIT_SIZE.min(C2::ScalarField::MODULUS_BIT_SIZE) as usize - 1;
+        N_BITS_RO.min(field_bits) - 1
+    }
+
+    /// checks that the number of public inputs of the given instances matches the one of the
+    /// R1CS structures in the params, returning `Error::PublicInputCountMismatch` otherwise, and
+    /// that the initial and last states have the length of the state of the FCircuit, returning
+    /// `Error::StateLengthMismatch` otherwise.
+    pub fn check_public_inputs_count(
+        &self,
+        z_0: &[C1::ScalarField],
+        z_i: &[C1::ScalarField],
+        U_i: &CommittedInstance<C1>,
+        u_i: &CommittedInstance<C1>,
+        cf_U_i: &CommittedInstance<C2>,
+    ) -> Result<(), Error> {
+        // the AugmentedFCircuit has two public inputs, H(i, z_0, z_i, U_i) and H(cf_U_i)
+        if self.r1cs.l != 2 {
+            return Err(Error::PublicInputCountMismatch {
+                expected: 2,
+                got: self.r1cs.l,
+            });
+        }
+        for x_len in [U_i.x.len(), u_i.x.len()] {
+            if x_len != self.r1cs.l {
+                return Err(Error::PublicInputCountMismatch {
+                    expected: self.r1cs.l,
+                    got: x_len,
+                });
+            }
+        }
+        if cf_U_i.x.len() != self.cf_r1cs.l {
+            return Err(Error::PublicInputCountMismatch {
+                expected: self.cf_r1cs.l,
+                got: cf_U_i.x.len(),
+            });
+        }
+        for z in [z_0, z_i] {
+            if z.len() != self.state_len {
+                return Err(Error::StateLengthMismatch {
+                    expected: self.state_len,
+                    got: z.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// returns the canonical serialization of the params: the Poseidon config followed by the
+    /// AugmentedFCircuit R1CS and the CycleFold R1CS, using the compressed arkworks serialization
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(self.to_leaves()?.concat())
+    }
+
+    /// returns the canonical serialization of each of the components of the params, the Poseidon
+    /// config, the AugmentedFCircuit R1CS and the CycleFold R1CS, whose concatenation is
+    /// `to_bytes`
+    pub fn to_leaves(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let mut poseidon_bytes = vec![];
+        serialize_poseidon_config(&self.poseidon_config, &mut poseidon_bytes, Compress::Yes)?;
+        let mut r1cs_bytes = vec![];
+        serialize_r1cs(&self.r1cs, &mut r1cs_bytes)?;
+        let mut cf_r1cs_bytes = vec![];
+        serialize_r1cs(&self.cf_r1cs, &mut cf_r1cs_bytes)?;
+        Ok(vec![poseidon_bytes, r1cs_bytes, cf_r1cs_bytes])
+    }
+
+    /// returns the keccak256 digest of the canonical serialization of the params (see
+    /// `to_bytes`), which matches the `keccak256` of the same bytes computed on the EVM, so it can
+    /// be stored on-chain to pin the verifier params
+    pub fn digest(&self) -> Result<[u8; 32], Error> {
+        Ok(Keccak256::digest(self.to_bytes()?).into())
+    }
+}
+
+fn serialize_r1cs<F: PrimeField>(r1cs: &R1CS<F>, bytes: &mut Vec<u8>) -> Result<(), Error> {
+    r1cs.l.serialize_compressed(&mut *bytes)?;
+    for m in [&r1cs.A, &r1cs.B, &r1cs.C] {
+        m.n_rows.serialize_compressed(&mut *bytes)?;
+        m.n_cols.serialize_compressed(&mut *bytes)?;
+        m.coeffs.serialize_compressed(&mut *bytes)?;
+    }
+    Ok(())
+}
+
+// checks that the lengths of the given witness and committed instance match the R1CS
+fn check_instance_len<C: CurveGroup>(
+    r1cs: &R1CS<C::ScalarField>,
+    w: &Witness<C>,
+    u: &CommittedInstance<C>,
+) -> Result<(), Error> {
+    for (len, expected) in [
+        (u.x.len(), r1cs.l),
+        (w.W.len(), r1cs.A.n_cols - 1 - r1cs.l),
+        (w.E.len(), r1cs.A.n_rows),
+    ] {
+        if len != expected {
+            return Err(Error::NotExpectedLength { got: len, expected });
+        }
+    }
+    Ok(())
+}
+
+/// returns the digest of the metadata attached to an IVC, which is hashed together with the
+/// state into the public input of each step, so that the metadata can not be changed without
+/// invalidating the IVC proof. The digest of empty metadata is zero.
+pub fn metadata_digest<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    metadata: &[u8],
+) -> Result<F, Error> {
+    if metadata.is_empty() {
+        return Ok(F::zero());
+    }
+    // pack the bytes into field elements, prefixed by the length to avoid trailing zero bytes
+    // resulting in the same digest
+    let chunk_len = ((F::MODULUS_BIT_SIZE - 1) / 8) as usize;
+    let input = [
+        vec![F::from(metadata.len() as u64)],
+        metadata
+            .chunks(chunk_len)
+            .map(F::from_le_bytes_mod_order)
+            .collect(),
+    ]
+    .concat();
+    CRH::<F>::evaluate(poseidon_config, input).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+/// IVCBindings are the values bound to the IVC besides the step counter and the states, which
+/// are hashed together with them into the public input of each step (see
+/// `CommittedInstance::hash_with_bindings`), and are public inputs of the Decider.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IVCBindings<F: PrimeField> {
+    /// digest of the metadata of the IVC, see `metadata_digest`
+    pub metadata: F,
+    /// domain of the transcript of the last folding challenge, see `rotated_domain`
+    pub domain: F,
+    /// accumulator of the public outputs of the steps, only for FCircuits that expose IO (see
+    /// `io_accumulator`)
+    pub io_acc: Option<F>,
+}
+
+impl<F: PrimeField> IVCBindings<F> {
+    /// returns the bindings as field elements, in the order in which they are hashed and
+    /// inputted to the Decider
+    pub fn to_vec(&self) -> Vec<F> {
+        [
+            vec![self.metadata, self.domain],
+            self.io_acc.into_iter().collect(),
+        ]
+        .concat()
+    }
+}
+
+/// IVCProofData is the data of the IVC proof that the verifier can not take from its own params,
+/// which is given to `Nova::verify` together with the instances, see `Nova::proof_data`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IVCProofData {
+    /// digest of the Poseidon config used by the prover (see `poseidon_config_digest`), checked
+    /// against the one of the VerifierParams
+    pub poseidon_config_digest: [u8; 32],
+    /// application metadata bound to the IVC, see `Nova::with_metadata`
+    pub metadata: Vec<u8>,
+    /// labels of the rotations of the transcript domain, see `Nova::rotate_transcript_domain`
+    pub domain_labels: Vec<Vec<u8>>,
+}
+
+impl IVCProofData {
+    /// returns the proof data of an IVC proven with the given Poseidon config, metadata and
+    /// rotations of the transcript domain
+    pub fn new<F: PrimeField>(
+        poseidon_config: &PoseidonConfig<F>,
+        metadata: Vec<u8>,
+        domain_labels: Vec<Vec<u8>>,
+    ) -> Self {
+        Self {
+            poseidon_config_digest: poseidon_config_digest(poseidon_config),
+            metadata,
+            domain_labels,
+        }
+    }
+}
+
+/// returns the domain of the transcript of the folding challenges after rotating the domain
+/// `prev` with the given label, which is `H(prev, d)`, where `d` is the digest of the label (see
+/// `metadata_digest`). The initial domain is zero.
+pub fn rotated_domain<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    prev: F,
+    label: &[u8],
+) -> Result<F, Error> {
+    let d = metadata_digest(poseidon_config, label)?;
+    CRH::<F>::evaluate(poseidon_config, [prev, d]).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+/// returns the accumulator of the public outputs (IO) of the steps after the step with the
+/// given IO, which is `H(prev, io)`, where `prev` is the accumulator of the previous steps (zero
+/// for the initial state), see `FCircuit::io_len`
+pub fn io_accumulator<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    prev: F,
+    io: &[F],
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(poseidon_config, [&[prev], io].concat()).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+/// returns the checkpoint of the state `z_i` at step `i`, which is `H(prev, i, z_i)`, where `prev`
+/// is the checkpoint of the previous step (zero for the initial state). Since the checkpoints are
+/// chained, two IVCs with the same checkpoint at step `k` went through the same states up to `k`.
+pub fn state_checkpoint<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    prev: F,
+    i: F,
+    z_i: &[F],
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(poseidon_config, [&[prev, i], z_i].concat()).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+/// returns the commitment `H(z_i, nonce)` to the state `z_i`, which can be published before
+/// revealing the state, see `Nova::verify_with_state_commitment`. The nonce has to be random for
+/// the commitment to hide the state. Notice that the IVC proof does not hide the state, since
+/// its verification takes `z_i` and the witnesses of the instances contain it.
+pub fn state_commitment<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    z_i: &[F],
+    nonce: F,
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(poseidon_config, [z_i, &[nonce]].concat()).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+fn check_point<C: CurveGroup>(name: &str, p: C) -> Result<(), Error> {
+    p.into_affine().check().map_err(|_| Error::InvalidPoint {
+        name: name.to_string(),
+    })
+}
+
+/// named check of the IVC verification, see `VerifierState::verify_self_report`
+pub(crate) type NamedCheck<'a> = (&'static str, Box<dyn Fn() -> Result<(), Error> + 'a>);
+
+/// VerifierState contains only the data needed to verify the last state of a Nova instance,
+/// obtained through `Nova::clone_for_verification`. It does not contain the prover params nor
+/// the FCircuit. Notice that Nova's IVC verification is not succinct, so the witnesses of the
+/// instances are still needed in order to check the R1CS relations.
+#[derive(Debug, Clone)]
+pub struct VerifierState<C1: CurveGroup, C2: CurveGroup> {
+    pub vp: VerifierParams<C1, C2>,
+    /// number of steps
     pub i: C1::ScalarField,
     /// initial state
     pub z_0: Vec<C1::ScalarField>,
@@ -243,120 +669,675 @@ where
     pub u_i: CommittedInstance<C1>,
     pub W_i: Witness<C1>,
     pub U_i: CommittedInstance<C1>,
-
     /// CycleFold running instance
     pub cf_W_i: Witness<C2>,
     pub cf_U_i: CommittedInstance<C2>,
+    /// checkpoints of the states of each step (see `state_checkpoint`), not needed by
+    /// `verify_self`. Empty unless enabled through `Nova::with_checkpoints`
+    pub checkpoints: Vec<C1::ScalarField>,
+    /// application metadata bound to the IVC, see `Nova::with_metadata`
+    pub metadata: Vec<u8>,
+    /// labels of the rotations of the transcript domain, see `Nova::rotate_transcript_domain`
+    pub domain_labels: Vec<Vec<u8>>,
+    /// accumulator of the public outputs of the steps, for FCircuits that expose IO (see
+    /// `io_accumulator`)
+    pub io_acc: Option<C1::ScalarField>,
+    /// digest of the Poseidon config used by the prover (see `poseidon_config_digest`), checked
+    /// against the one of `vp` by `verify_self`
+    pub poseidon_config_digest: [u8; 32],
 }
 
-impl<C1, GC1, C2, GC2, FC, CS1, CS2> FoldingScheme<C1, C2, FC>
-    for Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+impl<C1, C2> VerifierState<C1, C2>
 where
-    C1: CurveGroup,
-    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
     C2: CurveGroup,
-    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
-    FC: FCircuit<C1::ScalarField>,
-    CS1: CommitmentScheme<C1>,
-    CS2: CommitmentScheme<C2>,
     <C1 as CurveGroup>::BaseField: PrimeField,
-    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField + Absorb,
     <C1 as Group>::ScalarField: Absorb,
     <C2 as Group>::ScalarField: Absorb,
-    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
-    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
-    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
 {
-    type PreprocessorParam = (Self::ProverParam, FC);
-    type ProverParam = ProverParams<C1, C2, CS1, CS2>;
-    type VerifierParam = VerifierParams<C1, C2>;
-    type CommittedInstanceWithWitness = (CommittedInstance<C1>, Witness<C1>);
-    type CFCommittedInstanceWithWitness = (CommittedInstance<C2>, Witness<C2>);
-
-    fn preprocess(
-        prep_param: &Self::PreprocessorParam,
-    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
-        let (prover_params, F_circuit) = prep_param;
-
-        let (r1cs, cf_r1cs) =
-            get_r1cs::<C1, GC1, C2, GC2, FC>(&prover_params.poseidon_config, F_circuit.clone())?;
-
-        let verifier_params = VerifierParams::<C1, C2> {
-            poseidon_config: prover_params.poseidon_config.clone(),
-            r1cs,
-            cf_r1cs,
-        };
-        Ok((prover_params.clone(), verifier_params))
+    /// returns the state of the IVC proof given by the last state, instances and proof data, as
+    /// received by `Nova::verify`, to be verified against the given VerifierParams. `io_acc` is
+    /// the accumulator of the public outputs of the steps, for FCircuits that expose IO.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        vp: VerifierParams<C1, C2>,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        num_steps: C1::ScalarField,
+        running_instance: (CommittedInstance<C1>, Witness<C1>),
+        incoming_instance: (CommittedInstance<C1>, Witness<C1>),
+        cyclefold_instance: (CommittedInstance<C2>, Witness<C2>),
+        proof_data: IVCProofData,
+        io_acc: Option<C1::ScalarField>,
+    ) -> Self {
+        let (U_i, W_i) = running_instance;
+        let (u_i, w_i) = incoming_instance;
+        let (cf_U_i, cf_W_i) = cyclefold_instance;
+        Self {
+            i: num_steps,
+            z_0,
+            z_i,
+            u_i,
+            w_i,
+            U_i,
+            W_i,
+            cf_U_i,
+            cf_W_i,
+            checkpoints: vec![],
+            metadata: proof_data.metadata,
+            domain_labels: proof_data.domain_labels,
+            io_acc,
+            poseidon_config_digest: proof_data.poseidon_config_digest,
+            vp,
+        }
     }
 
-    /// Initializes the Nova+CycleFold's IVC for the given parameters and initial state `z_0`.
-    fn init(pp: &Self::ProverParam, F: FC, z_0: Vec<C1::ScalarField>) -> Result<Self, Error> {
-        // prepare the circuit to obtain its R1CS
-        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
-        let cs2 = ConstraintSystem::<C1::BaseField>::new_ref();
-
-        let augmented_F_circuit =
-            AugmentedFCircuit::<C1, C2, GC2, FC>::empty(&pp.poseidon_config, F.clone());
-        let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
+    /// returns the IVCBindings of the state, which are hashed into the public input of the
+    /// incoming instance
+    pub fn bindings(&self) -> Result<IVCBindings<C1::ScalarField>, Error> {
+        let domain = self
+            .domain_labels
+            .iter()
+            .try_fold(C1::ScalarField::zero(), |domain, label| {
+                rotated_domain(&self.vp.poseidon_config, domain, label)
+            })?;
+        Ok(IVCBindings {
+            metadata: metadata_digest(&self.vp.poseidon_config, &self.metadata)?,
+            domain,
+            io_acc: self.io_acc,
+        })
+    }
 
-        augmented_F_circuit.generate_constraints(cs.clone())?;
-        cs.finalize();
-        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
-        let r1cs = extract_r1cs::<C1::ScalarField>(&cs);
+    /// checks that the state is well formed, without performing the verification: the number of
+    /// public inputs and the lengths of the witnesses match the R1CS structures of the params, and
+    /// the commitments are valid curve points (on the curve and in the prime order subgroup).
+    /// This is much cheaper than `verify_self`, and can be used to discard malformed proofs.
+    pub fn validate_structure(&self) -> Result<(), Error> {
+        self.vp.check_public_inputs_count(
+            &self.z_0,
+            &self.z_i,
+            &self.U_i,
+            &self.u_i,
+            &self.cf_U_i,
+        )?;
 
-        cf_circuit.generate_constraints(cs2.clone())?;
-        cs2.finalize();
-        let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
-        let cf_r1cs = extract_r1cs::<C1::BaseField>(&cs2);
+        check_instance_len(&self.vp.r1cs, &self.w_i, &self.u_i)?;
+        check_instance_len(&self.vp.r1cs, &self.W_i, &self.U_i)?;
+        check_instance_len(&self.vp.cf_r1cs, &self.cf_W_i, &self.cf_U_i)?;
 
-        // setup the dummy instances
-        let (w_dummy, u_dummy) = r1cs.dummy_instance();
-        let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
+        for (name, p) in [
+            ("u_i.cmE", self.u_i.cmE),
+            ("u_i.cmW", self.u_i.cmW),
+            ("U_i.cmE", self.U_i.cmE),
+            ("U_i.cmW", self.U_i.cmW),
+        ] {
+            check_point(name, p)?;
+        }
+        check_point("cf_U_i.cmE", self.cf_U_i.cmE)?;
+        check_point("cf_U_i.cmW", self.cf_U_i.cmW)?;
+        Ok(())
+    }
 
-        // W_dummy=W_0 is a 'dummy witness', all zeroes, but with the size corresponding to the
-        // R1CS that we're working with.
-        Ok(Self {
-            _gc1: PhantomData,
-            _c2: PhantomData,
-            _gc2: PhantomData,
-            r1cs,
-            cf_r1cs,
-            poseidon_config: pp.poseidon_config.clone(),
-            cs_params: pp.cs_params.clone(),
-            cf_cs_params: pp.cf_cs_params.clone(),
-            F,
-            i: C1::ScalarField::zero(),
-            z_0: z_0.clone(),
-            z_i: z_0,
-            w_i: w_dummy.clone(),
-            u_i: u_dummy.clone(),
-            W_i: w_dummy,
-            U_i: u_dummy,
-            // cyclefold running instance
-            cf_W_i: cf_w_dummy.clone(),
-            cf_U_i: cf_u_dummy.clone(),
-        })
+    /// returns the checks of the committed instances against the public data of the IVC, which
+    /// do not need their witnesses: the Poseidon config and the number of public inputs, that
+    /// `u_i.x` points to the running instances, and that `u_i` is un-relaxed. These are the
+    /// first checks of `verify_self`, and the ones of `verify_streaming` before reading the
+    /// witnesses.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn instance_checks<'a>(
+        vp: &'a VerifierParams<C1, C2>,
+        prover_poseidon_digest: [u8; 32],
+        i: C1::ScalarField,
+        z_0: &'a [C1::ScalarField],
+        z_i: &'a [C1::ScalarField],
+        bindings: IVCBindings<C1::ScalarField>,
+        U_i: &'a CommittedInstance<C1>,
+        u_i: &'a CommittedInstance<C1>,
+        cf_U_i: &'a CommittedInstance<C2>,
+    ) -> Vec<NamedCheck<'a>> {
+        let checks: [NamedCheck<'a>; 5] = [
+            (
+                "poseidon_config",
+                Box::new(move || {
+                    if poseidon_config_digest(&vp.poseidon_config) != prover_poseidon_digest {
+                        return Err(Error::ParamsMismatch {
+                            params: "Poseidon config",
+                        });
+                    }
+                    Ok(())
+                }),
+            ),
+            (
+                "instances_io_len",
+                Box::new(move || vp.check_public_inputs_count(z_0, z_i, U_i, u_i, cf_U_i)),
+            ),
+            // u_i.X[0] == H(i, z_0, z_i, b, U_i)
+            (
+                "running_instance_hash",
+                Box::new(move || {
+                    let expected_u_i_x = U_i.hash_with_bindings(
+                        &vp.poseidon_config,
+                        i,
+                        z_0.to_vec(),
+                        z_i.to_vec(),
+                        &bindings,
+                    )?;
+                    if u_i.x.first() != Some(&expected_u_i_x) {
+                        return Err(Error::IVCVerificationFail);
+                    }
+                    Ok(())
+                }),
+            ),
+            // u_i.X[1] == H(cf_U_i)
+            (
+                "cyclefold_instance_hash",
+                Box::new(move || {
+                    let expected_cf_u_i_x = cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
+                    if u_i.x.get(1) != Some(&expected_cf_u_i_x) {
+                        return Err(Error::IVCVerificationFail);
+                    }
+                    Ok(())
+                }),
+            ),
+            // u_i.cmE==0, u_i.u==1 (=u_i is a un-relaxed instance)
+            (
+                "incoming_instance_unrelaxed",
+                Box::new(move || {
+                    if !u_i.cmE.is_zero() || !u_i.u.is_one() {
+                        return Err(Error::IVCVerificationFail);
+                    }
+                    Ok(())
+                }),
+            ),
+        ];
+        checks.into()
     }
 
-    /// Implements IVC.P of Nova+CycleFold
-    fn prove_step(&mut self, external_inputs: Vec<C1::ScalarField>) -> Result<(), Error> {
-        let augmented_F_circuit: AugmentedFCircuit<C1, C2, GC2, FC>;
+    /// returns the list of checks of IVC.V of Nova+CycleFold over the contained state, which is
+    /// run by both `verify_self` and `verify_self_report`
+    fn checks(&self) -> Result<Vec<NamedCheck<'_>>, Error> {
+        let mut checks = Self::instance_checks(
+            &self.vp,
+            self.poseidon_config_digest,
+            self.i,
+            &self.z_0,
+            &self.z_i,
+            self.bindings()?,
+            &self.U_i,
+            &self.u_i,
+            &self.cf_U_i,
+        );
+        checks.push((
+            "incoming_instance_r1cs",
+            Box::new(move || self.vp.r1cs.check_instance_relation(&self.w_i, &self.u_i)),
+        ));
+        checks.push((
+            "running_instance_relaxed_r1cs",
+            Box::new(move || {
+                self.vp
+                    .r1cs
+                    .check_relaxed_instance_relation(&self.W_i, &self.U_i)
+            }),
+        ));
+        checks.push((
+            "cyclefold_instance_relaxed_r1cs",
+            Box::new(move || {
+                self.vp
+                    .cf_r1cs
+                    .check_relaxed_instance_relation(&self.cf_W_i, &self.cf_U_i)
+            }),
+        ));
+        Ok(checks)
+    }
 
-        if self.z_i.len() != self.F.state_len() {
-            return Err(Error::NotSameLength(
-                "z_i.len()".to_string(),
-                self.z_i.len(),
-                "F.state_len()".to_string(),
-                self.F.state_len(),
-            ));
-        }
-        if external_inputs.len() != self.F.external_inputs_len() {
-            return Err(Error::NotSameLength(
-                "F.external_inputs_len()".to_string(),
-                self.F.external_inputs_len(),
-                "external_inputs.len()".to_string(),
-                external_inputs.len(),
-            ));
+    /// Implements IVC.V of Nova+CycleFold over the contained state, returning the error of the
+    /// first failing check. Returns `Error::ParamsMismatch` if the Poseidon config of `vp`
+    /// is not the one used by the prover.
+    pub fn verify_self(&self) -> Result<(), Error> {
+        for (_, check) in self.checks()? {
+            check()?;
+        }
+        Ok(())
+    }
+
+    /// runs the same checks as `verify_self`, returning a VerificationReport with the result of
+    /// each one of them instead of stopping at the first failing check
+    pub fn verify_self_report(&self) -> VerificationReport {
+        let mut report = VerificationReport::new();
+        match self.checks() {
+            Ok(checks) => {
+                for (name, check) in checks {
+                    report.check(name, check);
+                }
+            }
+            Err(e) => {
+                report.check("bindings", || Err(e));
+            }
+        }
+        report
+    }
+
+    /// returns the compressed serialization of the proof (step counter, states, instances,
+    /// witnesses, checkpoints, metadata, domain labels, IO accumulator and Poseidon config
+    /// digest), without the
+    /// params
+    pub fn proof_to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = vec![];
+        self.i.serialize_compressed(&mut bytes)?;
+        self.z_0.serialize_compressed(&mut bytes)?;
+        self.z_i.serialize_compressed(&mut bytes)?;
+        self.w_i.serialize_compressed(&mut bytes)?;
+        self.u_i.serialize_compressed(&mut bytes)?;
+        self.W_i.serialize_compressed(&mut bytes)?;
+        self.U_i.serialize_compressed(&mut bytes)?;
+        self.cf_W_i.serialize_compressed(&mut bytes)?;
+        self.cf_U_i.serialize_compressed(&mut bytes)?;
+        self.checkpoints.serialize_compressed(&mut bytes)?;
+        self.metadata.serialize_compressed(&mut bytes)?;
+        self.domain_labels.serialize_compressed(&mut bytes)?;
+        self.io_acc.serialize_compressed(&mut bytes)?;
+        self.poseidon_config_digest
+            .serialize_compressed(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// verifies the proof as `verify_self`, and if the verification fails writes a reproduction
+    /// bundle to `path` before returning the error. The bundle is a JSON object with the name
+    /// and error of the first failing check (`failing_check`, `error`), the digest of the params
+    /// (`params_digest`, see `VerifierParams::digest`), the report of all the checks (`report`,
+    /// see `verify_self_report`) and the proof (`proof`, see `proof_to_bytes`), with the bytes
+    /// hex encoded. Nothing is written if the verification succeeds.
+    #[cfg(feature = "fs")]
+    pub fn verify_or_dump(&self, path: &Path) -> Result<(), Error> {
+        let err = match self.verify_self() {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+        let report = self.verify_self_report();
+        let failing_check = report
+            .checks
+            .iter()
+            .find(|c| !c.passed)
+            .map(|c| c.name.clone());
+        let report_json: serde_json::Value =
+            serde_json::from_str(&report.to_json()).map_err(|e| Error::JSONError(e.to_string()))?;
+        let to_hex =
+            |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+
+        let bundle = serde_json::json!({
+            "failing_check": failing_check,
+            "error": err.to_string(),
+            "params_digest": to_hex(&self.vp.digest()?),
+            "report": report_json,
+            "proof": to_hex(&self.proof_to_bytes()?),
+        });
+        fs::write(path, bundle.to_string())?;
+        Err(err)
+    }
+
+    /// as `verify_self`, but first checks that the params provide at least `min_bits` bits of
+    /// security (see `VerifierParams::security_bits`), returning `Error::InsufficientSecurity`
+    /// otherwise
+    pub fn verify_with_min_security(&self, min_bits: usize) -> Result<(), Error> {
+        let security_bits = self.vp.security_bits();
+        if security_bits < min_bits {
+            return Err(Error::InsufficientSecurity {
+                bits: security_bits,
+                required: min_bits,
+            });
+        }
+        self.verify_self()
+    }
+
+    /// checks that there is a checkpoint for each step, that the first one matches the initial
+    /// state and that the last one is chained from the previous one over the last state. None of
+    /// the checkpoints are bound by the IVC proof, since the previous checkpoint (and every
+    /// intermediate one) is given by the prover, so they are only meaningful when the prover that
+    /// computed them is trusted.
+    pub fn verify_checkpoints(&self) -> Result<(), Error> {
+        let n = self.checkpoints.len();
+        if n == 0 || C1::ScalarField::from((n - 1) as u64) != self.i {
+            return Err(Error::CheckpointMismatch { index: n });
+        }
+        let zero = C1::ScalarField::zero();
+        if self.checkpoints[0] != state_checkpoint(&self.vp.poseidon_config, zero, zero, &self.z_0)?
+        {
+            return Err(Error::CheckpointMismatch { index: 0 });
+        }
+        if n > 1
+            && self.checkpoints[n - 1]
+                != state_checkpoint(
+                    &self.vp.poseidon_config,
+                    self.checkpoints[n - 2],
+                    self.i,
+                    &self.z_i,
+                )?
+        {
+            return Err(Error::CheckpointMismatch { index: n - 1 });
+        }
+        Ok(())
+    }
+
+    /// verifies a chain of IVC proofs spanning several generations of params (eg. after rotating
+    /// the commitment keys, which can not be mixed within a single IVC), where `proofs[k]` is
+    /// verified against `params[k]`. The transition point between two generations is the last
+    /// state of a generation, which must be the initial state of the next one, whose steps are
+    /// counted again from 0. The params of each proof must match the given ones, returning
+    /// `Error::ChainParamsMismatch` otherwise, and a discontinuity returns `Error::ProofChainBroken`
+    /// with the generation at which the chain breaks.
+    pub fn verify_chain(params: &[VerifierParams<C1, C2>], proofs: &[Self]) -> Result<(), Error> {
+        if proofs.is_empty() {
+            return Err(Error::Empty);
+        }
+        if params.len() != proofs.len() {
+            return Err(Error::NotSameLength {
+                a: "params.len()",
+                a_len: params.len(),
+                b: "proofs.len()",
+                b_len: proofs.len(),
+            });
+        }
+        for (k, (vp, proof)) in params.iter().zip(proofs.iter()).enumerate() {
+            if proof.vp.digest()? != vp.digest()? {
+                return Err(Error::ChainParamsMismatch { generation: k });
+            }
+            if k > 0 && proof.z_0 != proofs[k - 1].z_i {
+                return Err(Error::ProofChainBroken { generation: k });
+            }
+            proof.verify_self()?;
+        }
+        Ok(())
+    }
+}
+
+/// FinalizedNova is a Nova instance sealed through `Nova::finalize`, after its last step. It
+/// only exposes the IVC proof and its verification, so no further steps can be folded into a
+/// proof once it has been finalized.
+#[derive(Debug, Clone)]
+pub struct FinalizedNova<C1: CurveGroup, C2: CurveGroup> {
+    state: VerifierState<C1, C2>,
+}
+
+impl<C1, C2> FinalizedNova<C1, C2>
+where
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    C2: CurveGroup,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField + Absorb,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+{
+    /// returns the IVC proof, this is, the last state together with the instances and the
+    /// verifier params needed to verify it
+    pub fn ivc_proof(&self) -> &VerifierState<C1, C2> {
+        &self.state
+    }
+
+    /// verifies the IVC proof, see `VerifierState::verify_self`
+    pub fn verify_self(&self) -> Result<(), Error> {
+        self.state.verify_self()
+    }
+}
+
+/// NovaVerifier is a verify-only light client of Nova+CycleFold, built from the VerifierParams
+/// alone. It does not depend on the FCircuit nor on the commitment schemes, and never builds the
+/// AugmentedFCircuit, so a verifier node does not need to hold the prover params. It performs
+/// the same checks as `Nova::verify`, see `VerifierState::verify_self`.
+#[derive(Debug, Clone)]
+pub struct NovaVerifier<C1: CurveGroup, C2: CurveGroup> {
+    pub vp: VerifierParams<C1, C2>,
+}
+
+impl<C1, C2> NovaVerifier<C1, C2>
+where
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    C2: CurveGroup,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField + Absorb,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+{
+    pub fn new(vp: VerifierParams<C1, C2>) -> Self {
+        Self { vp }
+    }
+
+    /// verifies the given last state, instances and proof data, returning the same result as
+    /// `Nova::verify` with the same VerifierParams
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify(
+        &self,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: (CommittedInstance<C1>, Witness<C1>),
+        incoming_instance: (CommittedInstance<C1>, Witness<C1>),
+        cyclefold_instance: (CommittedInstance<C2>, Witness<C2>),
+        proof_data: IVCProofData,
+    ) -> Result<(), Error> {
+        VerifierState::new(
+            self.vp.clone(),
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+            None,
+        )
+        .verify_self()
+    }
+}
+
+/// witnesses and instances resulting from a proven step, stored when the `debug` feature is
+/// enabled so that their commitments can be checked with `Nova::debug_verify_all_commitments`
+#[cfg(feature = "debug")]
+#[derive(Clone, Debug)]
+struct DebugStep<C1: CurveGroup, C2: CurveGroup> {
+    W_i: Witness<C1>,
+    U_i: CommittedInstance<C1>,
+    w_i: Witness<C1>,
+    u_i: CommittedInstance<C1>,
+    cf_W_i: Witness<C2>,
+    cf_U_i: CommittedInstance<C2>,
+}
+
+/// recomputes the commitments to the witness `W` and checks that they match the ones of the
+/// committed instance `U`
+#[cfg(feature = "debug")]
+fn check_commitments<C: CurveGroup, CS: CommitmentScheme<C>>(
+    params: &CS::ProverParams,
+    W: &Witness<C>,
+    U: &CommittedInstance<C>,
+) -> Result<(), Error> {
+    let cmE = if is_zero_vec(&W.E) {
+        C::zero()
+    } else {
+        CS::commit(params, &W.E, &W.rE)?
+    };
+    let cmW = CS::commit(params, &W.W, &W.rW)?;
+    if U.cmE != cmE || U.cmW != cmW {
+        return Err(Error::CommitmentVerificationFail);
+    }
+    Ok(())
+}
+
+/// CircuitSize is the number of constraints and of witness variables of a circuit, see
+/// `Nova::circuit_size_breakdown`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct CircuitSize {
+    pub constraints: usize,
+    pub witness_variables: usize,
+}
+
+/// SetupPlan is the estimation of the resources needed by the setup of the commitment keys of
+/// Nova for a FCircuit, computed by `Nova::plan_setup` without allocating the keys.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct SetupPlan {
+    /// length to be given to the setup of the commitment scheme over C1, which is the size of
+    /// the AugmentedFCircuit checked by `init`
+    pub required_len: usize,
+    /// length to be given to the setup of the commitment scheme over C2, for the CycleFold
+    /// circuit
+    pub cf_required_len: usize,
+    /// length of the key over C1 produced by the setup, padded to the next power of two
+    pub key_len: usize,
+    /// length of the key over C2 produced by the setup, padded to the next power of two
+    pub cf_key_len: usize,
+    /// estimated memory in bytes taken by the points of both keys
+    pub memory: usize,
+    /// estimated time of the setup of both keys, extrapolated from the time of a few scalar
+    /// multiplications on each curve, one per point of the keys
+    pub time: Duration,
+}
+
+/// Implements Nova+CycleFold's IVC, described in [Nova](https://eprint.iacr.org/2021/370.pdf) and
+/// [CycleFold](https://eprint.iacr.org/2023/1192.pdf), following the FoldingScheme trait
+#[derive(Clone, Debug)]
+pub struct Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+{
+    _gc1: PhantomData<GC1>,
+    _c2: PhantomData<C2>,
+    _gc2: PhantomData<GC2>,
+    /// R1CS of the Augmented Function circuit
+    pub r1cs: R1CS<C1::ScalarField>,
+    /// R1CS of the CycleFold circuit
+    pub cf_r1cs: R1CS<C2::ScalarField>,
+    pub poseidon_config: PoseidonConfig<C1::ScalarField>,
+    /// decomposition into limbs of the non-native field elements of the AugmentedFCircuit
+    pub limb_config: LimbConfig,
+    /// CommitmentScheme::ProverParams over C1
+    pub cs_params: CS1::ProverParams,
+    /// CycleFold CommitmentScheme::ProverParams, over C2
+    pub cf_cs_params: CS2::ProverParams,
+    /// F circuit, the circuit that is being folded
+    pub F: FC,
+    pub i: C1::ScalarField,
+    /// initial state
+    pub z_0: Vec<C1::ScalarField>,
+    /// current i-th state
+    pub z_i: Vec<C1::ScalarField>,
+    /// Nova instances
+    pub w_i: Witness<C1>,
+    pub u_i: CommittedInstance<C1>,
+    pub W_i: Witness<C1>,
+    pub U_i: CommittedInstance<C1>,
+
+    /// CycleFold running instance
+    pub cf_W_i: Witness<C2>,
+    pub cf_U_i: CommittedInstance<C2>,
+
+    /// beacon values absorbed into the folding challenge of each of the proven steps, when the
+    /// FCircuit is in beacon mode (see `FCircuit::beacon_mode`)
+    pub beacons: Vec<C1::ScalarField>,
+    /// checkpoints of the states of each step, see `state_checkpoint`. Empty unless enabled
+    /// through `with_checkpoints`
+    pub checkpoints: Vec<C1::ScalarField>,
+    /// application metadata bound to the IVC, see `with_metadata`
+    pub metadata: Vec<u8>,
+    /// domain of the transcript of the folding challenge of the last step, which is bound to the
+    /// IVC, and the labels of the rotations that led to it, see `rotate_transcript_domain`
+    pub domain: C1::ScalarField,
+    pub domain_labels: Vec<Vec<u8>>,
+    /// label of the rotation to apply at the next step
+    pub pending_domain_label: Option<Vec<u8>>,
+    /// public outputs of each one of the proven steps, when the FCircuit exposes IO (see
+    /// `FCircuit::io_len`), and their accumulator (see `io_accumulator`)
+    pub io: Vec<Vec<C1::ScalarField>>,
+    pub io_acc: C1::ScalarField,
+
+    /// stats of the proven steps
+    #[cfg(feature = "stats")]
+    stats: Vec<StepStats>,
+
+    /// witnesses and instances of the proven steps
+    #[cfg(feature = "debug")]
+    debug_steps: Vec<DebugStep<C1, C2>>,
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> FoldingScheme<C1, C2, FC>
+    for Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    CS1: CommitmentScheme<C1>,
+    CS2: CommitmentScheme<C2>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    type PreprocessorParam = (Self::ProverParam, FC);
+    type ProverParam = ProverParams<C1, C2, CS1, CS2>;
+    type VerifierParam = VerifierParams<C1, C2>;
+    type CommittedInstanceWithWitness = (CommittedInstance<C1>, Witness<C1>);
+    type CFCommittedInstanceWithWitness = (CommittedInstance<C2>, Witness<C2>);
+    type ProofData = IVCProofData;
+
+    fn preprocess(
+        prep_param: &Self::PreprocessorParam,
+    ) -> Result<(Self::ProverParam, Self::VerifierParam), Error> {
+        let (prover_params, F_circuit) = prep_param;
+
+        let (r1cs, cf_r1cs) =
+            get_r1cs::<C1, GC1, C2, GC2, FC>(&prover_params.poseidon_config, F_circuit.clone())?;
+
+        let verifier_params = VerifierParams::<C1, C2> {
+            poseidon_config: prover_params.poseidon_config.clone(),
+            r1cs,
+            cf_r1cs,
+            state_len: F_circuit.state_len(),
+        };
+        Ok((prover_params.clone(), verifier_params))
+    }
+
+    /// Initializes the Nova+CycleFold's IVC for the given parameters and initial state `z_0`.
+    fn init(pp: &Self::ProverParam, F: FC, z_0: Vec<C1::ScalarField>) -> Result<Self, Error> {
+        Self::init_with_limb_config(
+            pp,
+            F,
+            z_0,
+            LimbConfig::default_for::<C1::ScalarField, C1::BaseField>(),
+        )
+    }
+
+    /// Implements IVC.P of Nova+CycleFold.
+    /// prove_step is transactional: the state of the scheme (instances, witnesses, state and step
+    /// counter) is only updated once all the operations of the step succeeded, so if an error is
+    /// returned the scheme is left as it was before the call, and the step can be retried.
+    fn prove_step(&mut self, external_inputs: FC::ExternalInputs) -> Result<(), Error> {
+        let augmented_F_circuit: AugmentedFCircuit<C1, C2, GC2, FC>;
+
+        if self.z_i.len() != self.F.state_len() {
+            return Err(Error::StateLengthMismatch {
+                expected: self.F.state_len(),
+                got: self.z_i.len(),
+            });
+        }
+        // the AugmentedFCircuit takes the field elements of the external inputs
+        let external_inputs_elems = external_inputs.to_field_elements();
+        if external_inputs_elems.len() != self.F.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "F.external_inputs_len()",
+                a_len: self.F.external_inputs_len(),
+                b: "external_inputs.len()",
+                b_len: external_inputs_elems.len(),
+            });
         }
 
         if self.i > C1::ScalarField::from_le_bytes_mod_order(&usize::MAX.to_le_bytes()) {
@@ -366,42 +1347,102 @@ where
         i_bytes.copy_from_slice(&self.i.into_bigint().to_bytes_le()[..8]);
         let i_usize: usize = usize::from_le_bytes(i_bytes);
 
+        #[cfg(feature = "stats")]
+        let step_start = Instant::now();
         let z_i1 = self
             .F
             .step_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
+        // public outputs of the step, accumulated into io_{i+1} = H(io_i, IO)
+        let io_len = self.F.io_len();
+        let io = self
+            .F
+            .step_io_native(i_usize, self.z_i.clone(), external_inputs.clone())?;
+        if io.len() != io_len {
+            return Err(Error::NotExpectedLength {
+                got: io.len(),
+                expected: io_len,
+            });
+        }
+        let io_acc_i1 = if io_len > 0 {
+            Some(io_accumulator(&self.poseidon_config, self.io_acc, &io)?)
+        } else {
+            None
+        };
 
+        #[cfg(feature = "stats")]
+        let commit_start = Instant::now();
         // compute T and cmT for AugmentedFCircuit
         let (T, cmT) = self.compute_cmT()?;
+        #[cfg(feature = "stats")]
+        let mut commit_time = commit_start.elapsed();
+
+        // in beacon mode, the beacon value of the step is the first external input
+        let beacon = if self.F.beacon_mode() {
+            Some(*external_inputs_elems.first().ok_or(Error::Empty)?)
+        } else {
+            None
+        };
+
+        // domain of the transcript of this step, rotated if there is a pending rotation
+        let domain_rotation = self
+            .pending_domain_label
+            .as_ref()
+            .map(|label| metadata_digest(&self.poseidon_config, label))
+            .transpose()?;
+        let domain_i1 = match &self.pending_domain_label {
+            Some(label) => rotated_domain(&self.poseidon_config, self.domain, label)?,
+            None => self.domain,
+        };
 
         // r_bits is the r used to the RLC of the F' instances
-        let r_bits = ChallengeGadget::<C1>::get_challenge_native(
+        let r_bits = ChallengeGadget::<C1>::get_challenge_native_with_domain(
             &self.poseidon_config,
+            Some(domain_i1),
             self.U_i.clone(),
             self.u_i.clone(),
             cmT,
+            beacon,
         )?;
         let r_Fr = C1::ScalarField::from_bigint(BigInteger::from_bits_le(&r_bits))
             .ok_or(Error::OutOfBounds)?;
         let r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&r_bits))
             .ok_or(Error::OutOfBounds)?;
 
+        #[cfg(feature = "stats")]
+        let fold_start = Instant::now();
         // fold Nova instances
         let (W_i1, U_i1): (Witness<C1>, CommittedInstance<C1>) = NIFS::<C1, CS1>::fold_instances(
             r_Fr, &self.W_i, &self.U_i, &self.w_i, &self.u_i, &T, cmT,
         )?;
+        #[cfg(feature = "stats")]
+        let fold_time = fold_start.elapsed();
 
         // folded instance output (public input, x)
-        // u_{i+1}.x[0] = H(i+1, z_0, z_{i+1}, U_{i+1})
-        let u_i1_x = U_i1.hash(
+        // u_{i+1}.x[0] = H(i+1, z_0, z_{i+1}, b_{i+1}, U_{i+1})
+        let metadata = metadata_digest(&self.poseidon_config, &self.metadata)?;
+        let u_i1_x = U_i1.hash_with_bindings(
             &self.poseidon_config,
             self.i + C1::ScalarField::one(),
             self.z_0.clone(),
             z_i1.clone(),
+            &IVCBindings {
+                metadata,
+                domain: domain_i1,
+                io_acc: io_acc_i1,
+            },
         )?;
         // u_{i+1}.x[1] = H(cf_U_{i+1})
         let cf_u_i1_x: C1::ScalarField;
+        // CycleFold running instance for the next iteration
+        let cf_W_i1: Witness<C2>;
+        let cf_U_i1: CommittedInstance<C2>;
+        // the base case does not fold any CycleFold instance
+        #[cfg(feature = "stats")]
+        let mut cyclefold_time = Duration::ZERO;
 
         if self.i == C1::ScalarField::zero() {
+            cf_W_i1 = self.cf_W_i.clone();
+            cf_U_i1 = self.cf_U_i.clone();
             cf_u_i1_x = self.cf_U_i.hash_cyclefold(&self.poseidon_config)?;
             // base case
             augmented_F_circuit = AugmentedFCircuit::<C1, C2, GC2, FC> {
@@ -411,7 +1452,11 @@ where
                 i_usize: Some(0),
                 z_0: Some(self.z_0.clone()), // = z_i
                 z_i: Some(self.z_i.clone()),
-                external_inputs: Some(external_inputs.clone()),
+                metadata: Some(metadata),
+                domain: Some(self.domain),
+                domain_rotation,
+                io_acc: Some(self.io_acc),
+                external_inputs: Some(external_inputs_elems.clone()),
                 u_i_cmW: Some(self.u_i.cmW), // = dummy
                 U_i: Some(self.U_i.clone()), // = dummy
                 U_i1_cmE: Some(U_i1.cmE),
@@ -430,6 +1475,12 @@ where
             #[cfg(test)]
             NIFS::<C1, CS1>::verify_folded_instance(r_Fr, &self.U_i, &self.u_i, &U_i1, &cmT)?;
         } else {
+            // the CycleFold running instance has to be the one committed in the public input of
+            // the incoming instance, u_i.x[1] = H(cf_U_i)
+            if self.u_i.x.get(1) != Some(&self.cf_U_i.hash_cyclefold(&self.poseidon_config)?) {
+                return Err(Error::CycleFold(CycleFoldError::CommitmentMismatch));
+            }
+
             // CycleFold part:
             // get the vector used as public inputs 'x' in the CycleFold circuit
             // cyclefold circuit for cmW
@@ -464,6 +1515,8 @@ where
                 x: Some(cfE_u_i_x.clone()),
             };
 
+            #[cfg(feature = "stats")]
+            let cyclefold_start = Instant::now();
             // fold self.cf_U_i + cfW_U -> folded running with cfW
             let (_cfW_w_i, cfW_u_i, cfW_W_i1, cfW_U_i1, cfW_cmT, _) = self.fold_cyclefold_circuit(
                 self.cf_W_i.clone(), // CycleFold running instance witness
@@ -472,8 +1525,14 @@ where
                 cfW_circuit,
             )?;
             // fold [the output from folding self.cf_U_i + cfW_U] + cfE_U = folded_running_with_cfW + cfE
-            let (_cfE_w_i, cfE_u_i, cf_W_i1, cf_U_i1, cf_cmT, _) =
+            let (_cfE_w_i, cfE_u_i, cfE_W_i1, cfE_U_i1, cf_cmT, _) =
                 self.fold_cyclefold_circuit(cfW_W_i1, cfW_U_i1.clone(), cfE_u_i_x, cfE_circuit)?;
+            cf_W_i1 = cfE_W_i1;
+            cf_U_i1 = cfE_U_i1;
+            #[cfg(feature = "stats")]
+            {
+                cyclefold_time = cyclefold_start.elapsed();
+            }
 
             cf_u_i1_x = cf_U_i1.hash_cyclefold(&self.poseidon_config)?;
 
@@ -484,7 +1543,11 @@ where
                 i_usize: Some(i_usize),
                 z_0: Some(self.z_0.clone()),
                 z_i: Some(self.z_i.clone()),
-                external_inputs: Some(external_inputs.clone()),
+                metadata: Some(metadata),
+                domain: Some(self.domain),
+                domain_rotation,
+                io_acc: Some(self.io_acc),
+                external_inputs: Some(external_inputs_elems.clone()),
                 u_i_cmW: Some(self.u_i.cmW),
                 U_i: Some(self.U_i.clone()),
                 U_i1_cmE: Some(U_i1.cmE),
@@ -501,57 +1564,143 @@ where
                 cf_x: Some(cf_u_i1_x),
             };
 
-            self.cf_W_i = cf_W_i1;
-            self.cf_U_i = cf_U_i1;
-
             #[cfg(test)]
             {
                 self.cf_r1cs.check_instance_relation(&_cfW_w_i, &cfW_u_i)?;
                 self.cf_r1cs.check_instance_relation(&_cfE_w_i, &cfE_u_i)?;
                 self.cf_r1cs
-                    .check_relaxed_instance_relation(&self.cf_W_i, &self.cf_U_i)?;
+                    .check_relaxed_instance_relation(&cf_W_i1, &cf_U_i1)?;
             }
         }
 
+        #[cfg(feature = "stats")]
+        let witness_start = Instant::now();
         let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        self.limb_config.set(&cs);
 
         augmented_F_circuit.generate_constraints(cs.clone())?;
 
         #[cfg(test)]
         assert!(cs.is_satisfied().unwrap());
 
+        #[cfg(feature = "stats")]
+        let constraints = cs.num_constraints();
+
         let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
-        let (w_i1, x_i1) = extract_w_x::<C1::ScalarField>(&cs);
+        let (w_i1_vec, x_i1) = extract_w_x::<C1::ScalarField>(&cs);
         if x_i1[0] != u_i1_x || x_i1[1] != cf_u_i1_x {
             return Err(Error::NotEqual);
         }
 
         #[cfg(test)]
         if x_i1.len() != 2 {
-            return Err(Error::NotExpectedLength(x_i1.len(), 2));
+            return Err(Error::NotExpectedLength {
+                got: x_i1.len(),
+                expected: 2,
+            });
+        }
+
+        let w_i1 = Witness::<C1>::new(w_i1_vec, self.r1cs.A.n_rows);
+        #[cfg(feature = "stats")]
+        let witness_time = witness_start.elapsed();
+        #[cfg(feature = "stats")]
+        let commit_start = Instant::now();
+        let u_i1 = w_i1.commit::<CS1>(&self.cs_params, x_i1)?;
+        #[cfg(feature = "stats")]
+        {
+            commit_time += commit_start.elapsed();
+        }
+
+        #[cfg(test)]
+        {
+            self.r1cs.check_instance_relation(&w_i1, &u_i1)?;
+            self.r1cs.check_relaxed_instance_relation(&W_i1, &U_i1)?;
         }
 
-        // set values for next iteration
+        let checkpoint = self
+            .checkpoints
+            .last()
+            .map(|&prev| {
+                state_checkpoint(
+                    &self.poseidon_config,
+                    prev,
+                    self.i + C1::ScalarField::one(),
+                    &z_i1,
+                )
+            })
+            .transpose()?;
+
+        // set values for next iteration. This is done once all the fallible operations of the
+        // step succeeded, so that on error the state is left unchanged.
         self.i += C1::ScalarField::one();
         self.z_i = z_i1;
-        self.w_i = Witness::<C1>::new(w_i1, self.r1cs.A.n_rows);
-        self.u_i = self.w_i.commit::<CS1>(&self.cs_params, x_i1)?;
+        self.w_i = w_i1;
+        self.u_i = u_i1;
         self.W_i = W_i1;
         self.U_i = U_i1;
+        self.cf_W_i = cf_W_i1;
+        self.cf_U_i = cf_U_i1;
+        self.beacons.extend(beacon);
+        self.checkpoints.extend(checkpoint);
+        self.domain = domain_i1;
+        self.domain_labels.extend(self.pending_domain_label.take());
+        if let Some(io_acc_i1) = io_acc_i1 {
+            self.io_acc = io_acc_i1;
+            self.io.push(io);
+        }
+        #[cfg(feature = "debug")]
+        self.debug_steps.push(DebugStep {
+            W_i: self.W_i.clone(),
+            U_i: self.U_i.clone(),
+            w_i: self.w_i.clone(),
+            u_i: self.u_i.clone(),
+            cf_W_i: self.cf_W_i.clone(),
+            cf_U_i: self.cf_U_i.clone(),
+        });
+        #[cfg(feature = "stats")]
+        self.stats.push(StepStats {
+            step: i_usize,
+            constraints,
+            witness_time,
+            fold_time,
+            cyclefold_time,
+            commit_time,
+            total_time: step_start.elapsed(),
+        });
 
-        #[cfg(test)]
+        Ok(())
+    }
+
+    /// Proves the steps as `FoldingScheme::prove_steps`, checking the length of all the external
+    /// inputs before proving any step, so that on a length mismatch no step is proven. On an
+    /// error of a step, the previous steps are kept proven and the failing step is not applied
+    /// (see `prove_step`). Each step is proven as by `prove_step`, no buffers are reused.
+    fn prove_steps(&mut self, external_inputs: Vec<FC::ExternalInputs>) -> Result<(), Error> {
+        if let Some(len) = external_inputs
+            .iter()
+            .map(|inputs| inputs.to_field_elements().len())
+            .find(|len| *len != self.F.external_inputs_len())
         {
-            self.r1cs.check_instance_relation(&self.w_i, &self.u_i)?;
-            self.r1cs
-                .check_relaxed_instance_relation(&self.W_i, &self.U_i)?;
+            return Err(Error::NotSameLength {
+                a: "F.external_inputs_len()",
+                a_len: self.F.external_inputs_len(),
+                b: "external_inputs.len()",
+                b_len: len,
+            });
+        }
+        for inputs in external_inputs {
+            self.prove_step(inputs)?;
         }
-
         Ok(())
     }
 
     fn state(&self) -> Vec<C1::ScalarField> {
         self.z_i.clone()
     }
+    fn num_steps(&self) -> u64 {
+        // the step counter is incremented by one at each step, so it fits in the lowest limb
+        self.i.into_bigint().as_ref()[0]
+    }
     fn instances(
         &self,
     ) -> (
@@ -565,6 +1714,13 @@ where
             (self.cf_U_i.clone(), self.cf_W_i.clone()),
         )
     }
+    fn proof_data(&self) -> Self::ProofData {
+        IVCProofData::new(
+            &self.poseidon_config,
+            self.metadata.clone(),
+            self.domain_labels.clone(),
+        )
+    }
 
     /// Implements IVC.V of Nova+CycleFold
     fn verify(
@@ -575,48 +1731,97 @@ where
         running_instance: Self::CommittedInstanceWithWitness,
         incoming_instance: Self::CommittedInstanceWithWitness,
         cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
     ) -> Result<(), Error> {
-        let (U_i, W_i) = running_instance;
-        let (u_i, w_i) = incoming_instance;
-        let (cf_U_i, cf_W_i) = cyclefold_instance;
-
-        if u_i.x.len() != 2 || U_i.x.len() != 2 {
-            return Err(Error::IVCVerificationFail);
-        }
-
-        // check that u_i's output points to the running instance
-        // u_i.X[0] == H(i, z_0, z_i, U_i)
-        let expected_u_i_x = U_i.hash(&vp.poseidon_config, num_steps, z_0, z_i.clone())?;
-        if expected_u_i_x != u_i.x[0] {
-            return Err(Error::IVCVerificationFail);
-        }
-        // u_i.X[1] == H(cf_U_i)
-        let expected_cf_u_i_x = cf_U_i.hash_cyclefold(&vp.poseidon_config)?;
-        if expected_cf_u_i_x != u_i.x[1] {
-            return Err(Error::IVCVerificationFail);
-        }
-
-        // check u_i.cmE==0, u_i.u==1 (=u_i is a un-relaxed instance)
-        if !u_i.cmE.is_zero() || !u_i.u.is_one() {
-            return Err(Error::IVCVerificationFail);
-        }
-
-        // check R1CS satisfiability
-        vp.r1cs.check_instance_relation(&w_i, &u_i)?;
-        // check RelaxedR1CS satisfiability
-        vp.r1cs.check_relaxed_instance_relation(&W_i, &U_i)?;
-
-        // check CycleFold RelaxedR1CS satisfiability
-        vp.cf_r1cs
-            .check_relaxed_instance_relation(&cf_W_i, &cf_U_i)?;
+        VerifierState::new(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+            None,
+        )
+        .verify_self()
+    }
 
-        Ok(())
+    /// Implements IVC.V of Nova+CycleFold as `verify`, where the public input of the incoming
+    /// instance also binds the accumulator of the given public outputs of the steps, for
+    /// FCircuits that expose IO. Empty `io` vectors correspond to FCircuits without IO, for
+    /// which this is equivalent to `verify`.
+    fn verify_with_io(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::CommittedInstanceWithWitness,
+        incoming_instance: Self::CommittedInstanceWithWitness,
+        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
+        io: Vec<Vec<C1::ScalarField>>,
+    ) -> Result<(), Error> {
+        let io_acc = if io.iter().any(|io| !io.is_empty()) {
+            if C1::ScalarField::from(io.len() as u64) != num_steps {
+                return Err(Error::StepCountMismatch {
+                    expected: num_steps.into_bigint().as_ref()[0],
+                    got: io.len() as u64,
+                });
+            }
+            let mut io_acc = C1::ScalarField::zero();
+            for io in io.iter() {
+                io_acc = io_accumulator(&vp.poseidon_config, io_acc, io)?;
+            }
+            Some(io_acc)
+        } else {
+            None
+        };
+        VerifierState::new(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+            io_acc,
+        )
+        .verify_self()
     }
-}
 
-impl<C1, GC1, C2, GC2, FC, CS1, CS2> Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
-where
-    C1: CurveGroup,
+    /// Implements IVC.V of Nova+CycleFold, performing the same checks as `verify` but recording
+    /// the result of each one of them into a VerificationReport, see
+    /// `VerifierState::verify_self_report`.
+    fn verify_report(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::CommittedInstanceWithWitness,
+        incoming_instance: Self::CommittedInstanceWithWitness,
+        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
+    ) -> VerificationReport {
+        VerifierState::new(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+            None,
+        )
+        .verify_self_report()
+    }
+}
+
+impl<C1, GC1, C2, GC2, FC, CS1, CS2> Nova<C1, GC1, C2, GC2, FC, CS1, CS2>
+where
+    C1: CurveGroup,
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
     C2: CurveGroup,
     GC2: CurveVar<C2, CF2<C2>>,
@@ -628,6 +1833,258 @@ where
     <C2 as Group>::ScalarField: Absorb,
     C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
 {
+    /// returns the number of constraints of the AugmentedFCircuit, this is, of the FCircuit
+    /// together with the in-circuit verification of the folding
+    pub fn num_constraints(&self) -> usize {
+        self.r1cs.A.n_rows
+    }
+
+    /// returns the number of witness variables of the AugmentedFCircuit
+    pub fn num_witness_variables(&self) -> usize {
+        self.r1cs.A.n_cols - 1 - self.r1cs.l
+    }
+
+    /// returns the size of the AugmentedFCircuit split between the FCircuit and the folding
+    /// overhead, as `(fcircuit, overhead)`. The FCircuit size is the one of its step constraints,
+    /// synthesized over a zero state and zero external inputs, without counting the variables of
+    /// the state and the external inputs, which are allocated by the AugmentedFCircuit.
+    pub fn circuit_size_breakdown(&self) -> Result<(CircuitSize, CircuitSize), Error> {
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        self.limb_config.set(&cs);
+        let z_i = Vec::<FpVar<C1::ScalarField>>::new_witness(cs.clone(), || {
+            Ok(vec![C1::ScalarField::zero(); self.F.state_len()])
+        })?;
+        let external_inputs = Vec::<FpVar<C1::ScalarField>>::new_witness(cs.clone(), || {
+            Ok(vec![C1::ScalarField::zero(); self.F.external_inputs_len()])
+        })?;
+        let external_inputs = FC::ExternalInputs::var_from_field_elements(external_inputs)?;
+        let inputs_len = cs.num_witness_variables();
+        self.F
+            .generate_step_constraints(cs.clone(), 0, z_i, external_inputs)?;
+
+        let fcircuit = CircuitSize {
+            constraints: cs.num_constraints(),
+            witness_variables: cs.num_witness_variables() - inputs_len,
+        };
+        let overhead = CircuitSize {
+            constraints: self.num_constraints().saturating_sub(fcircuit.constraints),
+            witness_variables: self
+                .num_witness_variables()
+                .saturating_sub(fcircuit.witness_variables),
+        };
+        Ok((fcircuit, overhead))
+    }
+
+    /// returns a copy of the current state without the prover params, the FCircuit and the
+    /// prover-only data, which can be sent to a verifier and checked through
+    /// `VerifierState::verify_self`.
+    pub fn clone_for_verification(&self) -> VerifierState<C1, C2> {
+        let vp = VerifierParams {
+            poseidon_config: self.poseidon_config.clone(),
+            r1cs: self.r1cs.clone(),
+            cf_r1cs: self.cf_r1cs.clone(),
+            state_len: self.F.state_len(),
+        };
+        VerifierState {
+            checkpoints: self.checkpoints.clone(),
+            ..VerifierState::new(
+                vp,
+                self.z_0.clone(),
+                self.z_i.clone(),
+                self.i,
+                (self.U_i.clone(), self.W_i.clone()),
+                (self.u_i.clone(), self.w_i.clone()),
+                (self.cf_U_i.clone(), self.cf_W_i.clone()),
+                IVCProofData::new(
+                    &self.poseidon_config,
+                    self.metadata.clone(),
+                    self.domain_labels.clone(),
+                ),
+                (self.F.io_len() > 0).then_some(self.io_acc),
+            )
+        }
+    }
+
+    /// returns the IVCBindings of the current state, which are public inputs of the Decider
+    pub fn bindings(&self) -> Result<IVCBindings<C1::ScalarField>, Error> {
+        Ok(IVCBindings {
+            metadata: metadata_digest(&self.poseidon_config, &self.metadata)?,
+            domain: self.domain,
+            io_acc: (self.F.io_len() > 0).then_some(self.io_acc),
+        })
+    }
+
+    /// attaches the given application metadata (eg. a job ID or a timestamp) to the IVC. The
+    /// digest of the metadata is hashed together with the state into the public input of each
+    /// step, so the IVC proof does not verify with a different metadata. Must be called before
+    /// the first step.
+    pub fn with_metadata(mut self, metadata: Vec<u8>) -> Result<Self, Error> {
+        if !self.i.is_zero() {
+            return Err(Error::NotSupported {
+                feature: "attaching metadata after the first step",
+            });
+        }
+        self.metadata = metadata;
+        Ok(self)
+    }
+
+    /// enables the checkpoints of the states of each step (see `state_checkpoint`), which are
+    /// needed by `check_shared_prefix_trusted`. They cost a hash per step and a field element per
+    /// step in the state, so they are disabled by default. Must be called before the first step.
+    pub fn with_checkpoints(mut self) -> Result<Self, Error> {
+        if !self.i.is_zero() {
+            return Err(Error::NotSupported {
+                feature: "enabling checkpoints after the first step",
+            });
+        }
+        self.checkpoints = vec![state_checkpoint(
+            &self.poseidon_config,
+            C1::ScalarField::zero(),
+            C1::ScalarField::zero(),
+            &self.z_0,
+        )?];
+        Ok(self)
+    }
+
+    /// rotates the domain of the transcript of the folding challenges with the given label, eg.
+    /// when moving to the next phase of a multi-phase protocol, so that the challenges of the
+    /// following steps are separated from the ones of the previous steps. The new domain is
+    /// chained to the previous one (see `rotated_domain`), and the challenges keep absorbing the
+    /// instances, so they still bind to the prior state. The rotation is applied at the next
+    /// step, in-circuit, and the resulting domain is bound to the IVC, so the verifier needs the
+    /// labels of the rotations (see `VerifierState::domain_labels`). Returns
+    /// `Error::DomainAlreadyRotated` if the domain has already been rotated since the last step.
+    pub fn rotate_transcript_domain(&mut self, label: &[u8]) -> Result<(), Error> {
+        if self.pending_domain_label.is_some() {
+            return Err(Error::DomainAlreadyRotated);
+        }
+        self.pending_domain_label = Some(label.to_vec());
+        Ok(())
+    }
+
+    /// verifies two IVC proofs, and checks that their checkpoints match up to step `k`, this is,
+    /// that both provers claim to have gone through the same states until step `k` (eg. IVCs
+    /// forked at step `k`). Returns `Error::SharedPrefixMismatch` with the first step at which the
+    /// checkpoints differ.
+    ///
+    /// Only the IVC proofs are verified: the checkpoints are computed by the prover and are not
+    /// bound by the proofs (see `VerifierState::verify_checkpoints`), so a dishonest prover can
+    /// claim any prefix. This is only meaningful when the provers of both proofs are trusted.
+    /// Both IVCs need the checkpoints enabled, see `with_checkpoints`.
+    pub fn check_shared_prefix_trusted(
+        proof_a: &VerifierState<C1, C2>,
+        proof_b: &VerifierState<C1, C2>,
+        k: usize,
+    ) -> Result<(), Error>
+    where
+        <C2 as CurveGroup>::BaseField: Absorb,
+    {
+        for proof in [proof_a, proof_b] {
+            proof.verify_self()?;
+            proof.verify_checkpoints()?;
+            if k >= proof.checkpoints.len() {
+                return Err(Error::OutOfBounds);
+            }
+        }
+        // the checkpoints are chained, so for honest provers the same checkpoint at k implies the
+        // same prefix
+        if proof_a.checkpoints[k] != proof_b.checkpoints[k] {
+            let j = (0..k)
+                .find(|&j| proof_a.checkpoints[j] != proof_b.checkpoints[j])
+                .unwrap_or(k);
+            return Err(Error::SharedPrefixMismatch { step: j });
+        }
+        Ok(())
+    }
+
+    /// checks that both Nova instances use the same commitment keys (see
+    /// `CommitmentScheme::params_eq`), returning `Error::IncompatibleParams` otherwise. The
+    /// instances committed with different keys can not be folded together.
+    pub fn check_params_compatible(&self, other: &Self) -> Result<(), Error> {
+        if !CS1::params_eq(&self.cs_params, &other.cs_params) {
+            return Err(Error::IncompatibleParams { scheme: "CS1" });
+        }
+        if !CS2::params_eq(&self.cf_cs_params, &other.cf_cs_params) {
+            return Err(Error::IncompatibleParams { scheme: "CS2" });
+        }
+        Ok(())
+    }
+
+    /// as `check_shared_prefix_trusted`, but over the Nova instances, also checking that both use
+    /// the same commitment keys, which is not possible from the `VerifierState`s
+    pub fn check_shared_prefix_trusted_with(&self, other: &Self, k: usize) -> Result<(), Error>
+    where
+        <C2 as CurveGroup>::BaseField: Absorb,
+    {
+        self.check_params_compatible(other)?;
+        Self::check_shared_prefix_trusted(
+            &self.clone_for_verification(),
+            &other.clone_for_verification(),
+            k,
+        )
+    }
+
+    /// returns the public IO of the IVC, `[i, z_0, z_i]`
+    pub fn public_inputs(&self) -> Vec<C1::ScalarField> {
+        [vec![self.i], self.z_0.clone(), self.z_i.clone()].concat()
+    }
+
+    /// returns a commitment to the public IO of the IVC (see `public_inputs`) with the
+    /// commitment scheme CS1, which is distinct from the commitments to the witnesses, so that
+    /// the public IO can be opened independently of them (eg. on-chain, for selective
+    /// disclosure). Its opening proofs are computed with `CS1::prove` over `public_inputs()`.
+    pub fn public_io_commitment(&self) -> Result<C1, Error> {
+        CS1::commit(
+            &self.cs_params,
+            &self.public_inputs(),
+            &C1::ScalarField::zero(),
+        )
+    }
+
+    /// seals the IVC after its last step, consuming the Nova instance. The returned FinalizedNova
+    /// allows to verify the IVC proof but not to fold further steps into it.
+    pub fn finalize(self) -> FinalizedNova<C1, C2> {
+        FinalizedNova {
+            state: self.clone_for_verification(),
+        }
+    }
+
+    /// recommits to the witnesses of every proven step (the folded running instance, the incoming
+    /// instance and the CycleFold running instance) and checks that the commitments match the ones
+    /// of the stored committed instances. Since the folded commitments are computed
+    /// homomorphically, this catches bugs in the folding or the commitment computation. Requires
+    /// the `debug` feature, which stores the witnesses of all the steps.
+    #[cfg(feature = "debug")]
+    pub fn debug_verify_all_commitments(&self) -> Result<(), Error> {
+        for step in self.debug_steps.iter() {
+            check_commitments::<C1, CS1>(&self.cs_params, &step.W_i, &step.U_i)?;
+            check_commitments::<C1, CS1>(&self.cs_params, &step.w_i, &step.u_i)?;
+            check_commitments::<C2, CS2>(&self.cf_cs_params, &step.cf_W_i, &step.cf_U_i)?;
+        }
+        Ok(())
+    }
+
+    /// returns the stats of each of the steps proven so far
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> Vec<StepStats> {
+        self.stats.clone()
+    }
+
+    /// exports the R1CS of the AugmentedFCircuit into the given directory, in the circom `.r1cs`
+    /// binary format (`augmented_circuit.r1cs`), together with a JSON describing the wires layout
+    /// to be used by a witness calculator (`augmented_circuit_wires.json`).
+    #[cfg(feature = "fs")]
+    pub fn export_circom(&self, dir: &Path) -> Result<(), Error> {
+        fs::create_dir_all(dir)?;
+        let file = fs::File::create(dir.join("augmented_circuit.r1cs"))?;
+        write_r1cs(&self.r1cs, BufWriter::new(file))?;
+        fs::write(
+            dir.join("augmented_circuit_wires.json"),
+            wires_layout_json(&self.r1cs),
+        )?;
+        Ok(())
+    }
+
     // computes T and cmT for the AugmentedFCircuit
     fn compute_cmT(&self) -> Result<(Vec<C1::ScalarField>, C1), Error> {
         NIFS::<C1, CS1>::compute_cmT(
@@ -675,6 +2132,340 @@ where
     for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
     for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
 {
+    /// initializes the IVC as `init`, with the given decomposition into limbs of the elements of
+    /// the non-native field `C1::BaseField` in the AugmentedFCircuit (the CycleFold instances and
+    /// the folding challenge), which can reduce its number of constraints for some curves. The
+    /// verifier params have to be obtained with `preprocess_with_limb_config` with the same
+    /// config.
+    pub fn init_with_limb_config(
+        pp: &ProverParams<C1, C2, CS1, CS2>,
+        F: FC,
+        z_0: Vec<C1::ScalarField>,
+        limb_config: LimbConfig,
+    ) -> Result<Self, Error> {
+        limb_config.check::<C1::ScalarField, C1::BaseField>()?;
+        if z_0.len() != F.state_len() {
+            return Err(Error::StateLengthMismatch {
+                expected: F.state_len(),
+                got: z_0.len(),
+            });
+        }
+
+        // prepare the circuit to obtain its R1CS
+        let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+        let cs2 = ConstraintSystem::<C1::BaseField>::new_ref();
+        limb_config.set(&cs);
+
+        let augmented_F_circuit =
+            AugmentedFCircuit::<C1, C2, GC2, FC>::empty(&pp.poseidon_config, F.clone());
+        let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
+
+        augmented_F_circuit.generate_constraints(cs.clone())?;
+        cs.finalize();
+        let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let r1cs = extract_r1cs::<C1::ScalarField>(&cs);
+        // both the witness and the error term vectors are committed with the params
+        let circuit_size = required_len(&r1cs);
+        if circuit_size > pp.max_circuit_size() {
+            return Err(Error::CircuitTooLarge {
+                got: circuit_size,
+                max: pp.max_circuit_size(),
+            });
+        }
+
+        cf_circuit.generate_constraints(cs2.clone())?;
+        cs2.finalize();
+        let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+        let cf_r1cs = extract_r1cs::<C1::BaseField>(&cs2);
+        let cf_circuit_size = required_len(&cf_r1cs);
+        if cf_circuit_size > pp.max_cf_circuit_size() {
+            return Err(Error::CycleFoldCircuitTooLarge {
+                got: cf_circuit_size,
+                max: pp.max_cf_circuit_size(),
+            });
+        }
+
+        // setup the dummy instances
+        let (w_dummy, u_dummy) = r1cs.dummy_instance();
+        let (cf_w_dummy, cf_u_dummy) = cf_r1cs.dummy_instance();
+
+        // W_dummy=W_0 is a 'dummy witness', all zeroes, but with the size corresponding to the
+        // R1CS that we're working with. The IVC state is set by `reset`.
+        let mut nova = Self {
+            _gc1: PhantomData,
+            _c2: PhantomData,
+            _gc2: PhantomData,
+            r1cs,
+            cf_r1cs,
+            poseidon_config: pp.poseidon_config.clone(),
+            limb_config,
+            cs_params: pp.cs_params.clone(),
+            cf_cs_params: pp.cf_cs_params.clone(),
+            F,
+            i: C1::ScalarField::zero(),
+            z_0: Vec::new(),
+            z_i: Vec::new(),
+            w_i: w_dummy.clone(),
+            u_i: u_dummy.clone(),
+            W_i: w_dummy,
+            U_i: u_dummy,
+            // cyclefold running instance
+            cf_W_i: cf_w_dummy,
+            cf_U_i: cf_u_dummy,
+            beacons: Vec::new(),
+            checkpoints: Vec::new(),
+            metadata: Vec::new(),
+            domain: C1::ScalarField::zero(),
+            domain_labels: Vec::new(),
+            pending_domain_label: None,
+            io: Vec::new(),
+            io_acc: C1::ScalarField::zero(),
+            #[cfg(feature = "stats")]
+            stats: Vec::new(),
+            #[cfg(feature = "debug")]
+            debug_steps: Vec::new(),
+        };
+        nova.reset(z_0)?;
+        Ok(nova)
+    }
+
+    /// starts a new IVC from the initial state `z_0`, with the same params and FCircuit: the
+    /// instances are set to the dummy ones, and the step counter, the checkpoints, the beacons,
+    /// the IO and the transcript domain are set to the values of `init`. The metadata, whether
+    /// the checkpoints are enabled and a pending rotation of the transcript domain are kept, and
+    /// apply to the new IVC.
+    pub(crate) fn reset(&mut self, z_0: Vec<C1::ScalarField>) -> Result<(), Error> {
+        let (w_dummy, u_dummy) = self.r1cs.dummy_instance();
+        let (cf_w_dummy, cf_u_dummy) = self.cf_r1cs.dummy_instance();
+        if !self.checkpoints.is_empty() {
+            self.checkpoints = vec![state_checkpoint(
+                &self.poseidon_config,
+                C1::ScalarField::zero(),
+                C1::ScalarField::zero(),
+                &z_0,
+            )?];
+        }
+        self.i = C1::ScalarField::zero();
+        self.z_0 = z_0.clone();
+        self.z_i = z_0;
+        self.w_i = w_dummy.clone();
+        self.u_i = u_dummy.clone();
+        self.W_i = w_dummy;
+        self.U_i = u_dummy;
+        self.cf_W_i = cf_w_dummy;
+        self.cf_U_i = cf_u_dummy;
+        self.beacons = Vec::new();
+        self.domain = C1::ScalarField::zero();
+        self.domain_labels = Vec::new();
+        self.io = Vec::new();
+        self.io_acc = C1::ScalarField::zero();
+        Ok(())
+    }
+
+    /// verifies the IVC as `verify`, and additionally checks that `state_commitment` is the
+    /// commitment `H(z_i, nonce)` to the last state, see `state_commitment`. This links a
+    /// previously published commitment to the verified state, but it does not hide the state:
+    /// the verifier gets `z_i` in the clear, and the commitment is not part of the public input
+    /// of the IVC.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify_with_state_commitment(
+        vp: VerifierParams<C1, C2>,
+        z_0: Vec<C1::ScalarField>,
+        z_i: Vec<C1::ScalarField>,
+        num_steps: C1::ScalarField,
+        running_instance: (CommittedInstance<C1>, Witness<C1>),
+        incoming_instance: (CommittedInstance<C1>, Witness<C1>),
+        cyclefold_instance: (CommittedInstance<C2>, Witness<C2>),
+        proof_data: IVCProofData,
+        state_commitment: C1::ScalarField,
+        nonce: C1::ScalarField,
+    ) -> Result<(), Error> {
+        if self::state_commitment(&vp.poseidon_config, &z_i, nonce)? != state_commitment {
+            return Err(Error::StateCommitmentMismatch);
+        }
+        Self::verify(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+    }
+
+    /// returns the SetupPlan of the commitment keys needed to fold `F_circuit` with the given
+    /// Poseidon config, building the R1CS of the circuits but without allocating the keys
+    pub fn plan_setup(
+        poseidon_config: &PoseidonConfig<C1::ScalarField>,
+        F_circuit: &FC,
+    ) -> Result<SetupPlan, Error> {
+        let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(poseidon_config, F_circuit.clone())?;
+        // both the witness and the error term vectors are committed with the keys, see `init`
+        let (required_len, cf_required_len) = (required_len(&r1cs), required_len(&cf_r1cs));
+        let (key_len, cf_key_len) = (
+            required_len.next_power_of_two(),
+            cf_required_len.next_power_of_two(),
+        );
+
+        const SAMPLES: u64 = 8;
+        let start = Instant::now();
+        let mut acc = C1::zero();
+        for i in 0..SAMPLES {
+            acc += C1::generator() * -C1::ScalarField::from(i + 1);
+        }
+        core::hint::black_box(acc);
+        let point_time = start.elapsed() / SAMPLES as u32;
+        let start = Instant::now();
+        let mut cf_acc = C2::zero();
+        for i in 0..SAMPLES {
+            cf_acc += C2::generator() * -C2::ScalarField::from(i + 1);
+        }
+        core::hint::black_box(cf_acc);
+        let cf_point_time = start.elapsed() / SAMPLES as u32;
+
+        Ok(SetupPlan {
+            required_len,
+            cf_required_len,
+            key_len,
+            cf_key_len,
+            memory: key_len * core::mem::size_of::<C1::Affine>()
+                + cf_key_len * core::mem::size_of::<C2::Affine>(),
+            time: point_time * key_len as u32 + cf_point_time * cf_key_len as u32,
+        })
+    }
+
+    /// preprocesses the params as `preprocess`, with the R1CS of the AugmentedFCircuit built with
+    /// the given limb config, see `init_with_limb_config`
+    pub fn preprocess_with_limb_config(
+        prover_params: &ProverParams<C1, C2, CS1, CS2>,
+        F_circuit: &FC,
+        limb_config: LimbConfig,
+    ) -> Result<(ProverParams<C1, C2, CS1, CS2>, VerifierParams<C1, C2>), Error> {
+        limb_config.check::<C1::ScalarField, C1::BaseField>()?;
+        let (r1cs, cf_r1cs) = get_r1cs_with_limb_config::<C1, GC1, C2, GC2, FC>(
+            &prover_params.poseidon_config,
+            F_circuit.clone(),
+            limb_config,
+        )?;
+
+        let verifier_params = VerifierParams::<C1, C2> {
+            poseidon_config: prover_params.poseidon_config.clone(),
+            r1cs,
+            cf_r1cs,
+            state_len: F_circuit.state_len(),
+        };
+        Ok((prover_params.clone(), verifier_params))
+    }
+
+    /// returns the estimated gas of verifying on-chain the folding of a step, see
+    /// `gas::estimate_step_gas`. It is a static per-fold cost: it only depends on the number of
+    /// public inputs of the AugmentedFCircuit, so it is the same for all the steps of the IVC and
+    /// does not depend on the values of any step.
+    pub fn fold_gas_estimate(&self) -> GasEstimate {
+        estimate_step_gas::<C1>(self.r1cs.l)
+    }
+
+    /// verifies the IVC at its current step as `verify`, with the given verifier params and the
+    /// number of steps `num_steps` that the caller expects to have been folded. Returns
+    /// `Error::StepCountMismatch` if it differs from the number of folded steps.
+    pub fn verify_num_steps(
+        &self,
+        vp: VerifierParams<C1, C2>,
+        num_steps: u64,
+    ) -> Result<(), Error> {
+        if num_steps != self.num_steps() {
+            return Err(Error::StepCountMismatch {
+                expected: self.num_steps(),
+                got: num_steps,
+            });
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = self.instances();
+        Self::verify(
+            vp,
+            self.z_0.clone(),
+            self.z_i.clone(),
+            C1::ScalarField::from(num_steps),
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            self.proof_data(),
+        )
+    }
+
+    /// writes the folding state of the prover (limb config, step counter, states, instances and
+    /// witnesses), from which the IVC can be resumed with `deserialize_state`. The R1CS, the
+    /// params and the FCircuit are not written, since they are rebuilt by `deserialize_state`.
+    pub fn serialize_state<W: Write>(&self, mut writer: W) -> Result<(), Error> {
+        (self.limb_config.bits_per_limb as u64).serialize_compressed(&mut writer)?;
+        (self.limb_config.num_limbs as u64).serialize_compressed(&mut writer)?;
+        self.i.serialize_compressed(&mut writer)?;
+        self.z_0.serialize_compressed(&mut writer)?;
+        self.z_i.serialize_compressed(&mut writer)?;
+        self.w_i.serialize_compressed(&mut writer)?;
+        self.u_i.serialize_compressed(&mut writer)?;
+        self.W_i.serialize_compressed(&mut writer)?;
+        self.U_i.serialize_compressed(&mut writer)?;
+        self.cf_W_i.serialize_compressed(&mut writer)?;
+        self.cf_U_i.serialize_compressed(&mut writer)?;
+        self.beacons.serialize_compressed(&mut writer)?;
+        self.checkpoints.serialize_compressed(&mut writer)?;
+        self.metadata.serialize_compressed(&mut writer)?;
+        self.domain.serialize_compressed(&mut writer)?;
+        self.domain_labels.serialize_compressed(&mut writer)?;
+        self.pending_domain_label
+            .serialize_compressed(&mut writer)?;
+        self.io.serialize_compressed(&mut writer)?;
+        self.io_acc.serialize_compressed(&mut writer)?;
+        Ok(())
+    }
+
+    /// reads a folding state written by `serialize_state`, and returns the scheme ready to
+    /// continue proving steps from it. The given prover params and FCircuit must be the same
+    /// ones used by the prover that wrote the state.
+    pub fn deserialize_state<R: Read>(
+        mut reader: R,
+        pp: &ProverParams<C1, C2, CS1, CS2>,
+        F: FC,
+    ) -> Result<Self, Error> {
+        let limb_config = LimbConfig {
+            bits_per_limb: u64::deserialize_compressed(&mut reader)? as usize,
+            num_limbs: u64::deserialize_compressed(&mut reader)? as usize,
+        };
+        let i = C1::ScalarField::deserialize_compressed(&mut reader)?;
+        let z_0 = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        let mut nova = Self::init_with_limb_config(pp, F, z_0, limb_config)?;
+        nova.i = i;
+        nova.z_i = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        nova.w_i = Witness::<C1>::deserialize_compressed(&mut reader)?;
+        nova.u_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        nova.W_i = Witness::<C1>::deserialize_compressed(&mut reader)?;
+        nova.U_i = CommittedInstance::<C1>::deserialize_compressed(&mut reader)?;
+        nova.cf_W_i = Witness::<C2>::deserialize_compressed(&mut reader)?;
+        nova.cf_U_i = CommittedInstance::<C2>::deserialize_compressed(&mut reader)?;
+        nova.beacons = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        nova.checkpoints = Vec::<C1::ScalarField>::deserialize_compressed(&mut reader)?;
+        nova.metadata = Vec::<u8>::deserialize_compressed(&mut reader)?;
+        nova.domain = C1::ScalarField::deserialize_compressed(&mut reader)?;
+        nova.domain_labels = Vec::<Vec<u8>>::deserialize_compressed(&mut reader)?;
+        nova.pending_domain_label = Option::<Vec<u8>>::deserialize_compressed(&mut reader)?;
+        nova.io = Vec::<Vec<C1::ScalarField>>::deserialize_compressed(&mut reader)?;
+        nova.io_acc = C1::ScalarField::deserialize_compressed(&mut reader)?;
+
+        // check that the read state fits the R1CS rebuilt from the given params and FCircuit
+        if nova.z_i.len() != nova.F.state_len() {
+            return Err(Error::StateLengthMismatch {
+                expected: nova.F.state_len(),
+                got: nova.z_i.len(),
+            });
+        }
+        check_instance_len(&nova.r1cs, &nova.w_i, &nova.u_i)?;
+        check_instance_len(&nova.r1cs, &nova.W_i, &nova.U_i)?;
+        check_instance_len(&nova.cf_r1cs, &nova.cf_W_i, &nova.cf_U_i)?;
+        Ok(nova)
+    }
+
     // folds the given cyclefold circuit and its instances
     #[allow(clippy::type_complexity)]
     fn fold_cyclefold_circuit(
@@ -695,17 +2486,22 @@ where
         Error,
     > {
         let cs2 = ConstraintSystem::<C1::BaseField>::new_ref();
-        cf_circuit.generate_constraints(cs2.clone())?;
+        cf_circuit
+            .generate_constraints(cs2.clone())
+            .map_err(|e| Error::CycleFold(CycleFoldError::SynthesisError(e)))?;
 
         let cs2 = cs2.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
         let (cf_w_i, cf_x_i) = extract_w_x::<C1::BaseField>(&cs2);
         if cf_x_i != cf_u_i_x {
-            return Err(Error::NotEqual);
+            return Err(Error::CycleFold(CycleFoldError::PublicInputMismatch));
         }
 
         #[cfg(test)]
         if cf_x_i.len() != CF_IO_LEN {
-            return Err(Error::NotExpectedLength(cf_x_i.len(), CF_IO_LEN));
+            return Err(Error::NotExpectedLength {
+                got: cf_x_i.len(),
+                expected: CF_IO_LEN,
+            });
         }
 
         // fold cyclefold instances
@@ -714,16 +2510,23 @@ where
             cf_w_i.commit::<CS2>(&self.cf_cs_params, cf_x_i.clone())?;
 
         // compute T* and cmT* for CycleFoldCircuit
-        let (cf_T, cf_cmT) = self.compute_cf_cmT(&cf_w_i, &cf_u_i, &cf_W_i, &cf_U_i)?;
+        let (cf_T, cf_cmT) = self
+            .compute_cf_cmT(&cf_w_i, &cf_u_i, &cf_W_i, &cf_U_i)
+            .map_err(|e| Error::CycleFold(CycleFoldError::CrossTermFailed(e.to_string())))?;
 
         let cf_r_bits = CycleFoldChallengeGadget::<C2, GC2>::get_challenge_native(
             &self.poseidon_config,
             cf_U_i.clone(),
             cf_u_i.clone(),
             cf_cmT,
-        )?;
-        let cf_r_Fq = C1::BaseField::from_bigint(BigInteger::from_bits_le(&cf_r_bits))
-            .ok_or(Error::OutOfBounds)?;
+        )
+        .map_err(|e| Error::CycleFold(CycleFoldError::ChallengeDerivationFailed(e.to_string())))?;
+        let cf_r_Fq =
+            C1::BaseField::from_bigint(BigInteger::from_bits_le(&cf_r_bits)).ok_or_else(|| {
+                Error::CycleFold(CycleFoldError::ChallengeDerivationFailed(
+                    "challenge out of bounds".to_string(),
+                ))
+            })?;
 
         let (cf_W_i1, cf_U_i1) = NIFS::<C2, CS2>::fold_instances(
             cf_r_Fq, &cf_W_i, &cf_U_i, &cf_w_i, &cf_u_i, &cf_T, cf_cmT,
@@ -750,6 +2553,35 @@ pub fn get_r1cs<C1, GC1, C2, GC2, FC>(
     poseidon_config: &PoseidonConfig<C1::ScalarField>,
     F_circuit: FC,
 ) -> Result<(R1CS<C1::ScalarField>, R1CS<C2::ScalarField>), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    get_r1cs_with_limb_config::<C1, GC1, C2, GC2, FC>(
+        poseidon_config,
+        F_circuit,
+        LimbConfig::default_for::<C1::ScalarField, C1::BaseField>(),
+    )
+}
+
+/// helper method to get the R1CS for both the AugmentedFCircuit, with the given limb config (see
+/// `Nova::init_with_limb_config`), and the CycleFold circuit
+#[allow(clippy::type_complexity)]
+pub fn get_r1cs_with_limb_config<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuit: FC,
+    limb_config: LimbConfig,
+) -> Result<(R1CS<C1::ScalarField>, R1CS<C2::ScalarField>), Error>
 where
     C1: CurveGroup,
     GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
@@ -766,14 +2598,26 @@ where
 {
     let augmented_F_circuit =
         AugmentedFCircuit::<C1, C2, GC2, FC>::empty(poseidon_config, F_circuit);
+    let cs = ConstraintSystem::<C1::ScalarField>::new_ref();
+    limb_config.set(&cs);
+    augmented_F_circuit.generate_constraints(cs.clone())?;
+    cs.finalize();
+    let cs = cs.into_inner().ok_or(Error::NoInnerConstraintSystem)?;
+    let r1cs = extract_r1cs::<C1::ScalarField>(&cs);
+
     let cf_circuit = CycleFoldCircuit::<C1, GC1>::empty();
-    let r1cs = get_r1cs_from_cs::<C1::ScalarField>(augmented_F_circuit)?;
     let cf_r1cs = get_r1cs_from_cs::<C2::ScalarField>(cf_circuit)?;
     Ok((r1cs, cf_r1cs))
 }
 
-/// helper method to get the pedersen params length for both the AugmentedFCircuit and the
-/// CycleFold circuit
+/// returns the length of the commitment params needed to commit to the witness and the error
+/// term vectors of the given R1CS, `max(n_rows, n_cols - 1 - l)`
+pub fn required_len<F: PrimeField>(r1cs: &R1CS<F>) -> usize {
+    r1cs.A.n_rows.max(r1cs.A.n_cols - 1 - r1cs.l)
+}
+
+/// helper method to get the commitment params length for both the AugmentedFCircuit and the
+/// CycleFold circuit, see `required_len`
 pub fn get_cs_params_len<C1, GC1, C2, GC2, FC>(
     poseidon_config: &PoseidonConfig<C1::ScalarField>,
     F_circuit: FC,
@@ -793,7 +2637,52 @@ where
     for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
 {
     let (r1cs, cf_r1cs) = get_r1cs::<C1, GC1, C2, GC2, FC>(poseidon_config, F_circuit)?;
-    Ok((r1cs.A.n_rows, cf_r1cs.A.n_rows))
+    Ok((required_len(&r1cs), required_len(&cf_r1cs)))
+}
+
+/// helper method to get the maximum commitment params length required by the given FCircuits,
+/// for both the AugmentedFCircuit and the CycleFold circuit, so that a single pair of commitment
+/// params can be setup and shared by all of them.
+pub fn max_required_len<C1, GC1, C2, GC2, FC>(
+    poseidon_config: &PoseidonConfig<C1::ScalarField>,
+    F_circuits: &[&FC],
+) -> Result<(usize, usize), Error>
+where
+    C1: CurveGroup,
+    GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+    C2: CurveGroup,
+    GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+    FC: FCircuit<C1::ScalarField>,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+    for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+{
+    let mut max_len = (0, 0);
+    for F_circuit in F_circuits {
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<C1, GC1, C2, GC2, FC>(poseidon_config, (*F_circuit).clone())?;
+        max_len = (max_len.0.max(cs_len), max_len.1.max(cf_cs_len));
+    }
+    Ok(max_len)
+}
+
+/// number of elements of the IVC state that encode a commitment, see
+/// `commitment_to_state_elements`
+pub const COMMITMENT_STATE_LEN: usize = 2;
+
+/// returns the encoding of a commitment as elements of the IVC state, to be used when the
+/// initial state `z_0` contains a commitment (eg. to a larger initial dataset) instead of raw
+/// values. The commitment must be a point of the second curve C2, whose base field is the scalar
+/// field of C1 over which the state is defined, and it is encoded as its affine coordinates
+/// `[x, y]` (`COMMITMENT_STATE_LEN` elements), with the point at infinity encoded as `[0, 0]`.
+/// Since these elements are hashed into `u_i.x[0]` as any other element of the state, an IVC
+/// proof only verifies for the commitment encoded in its `z_0`.
+pub fn commitment_to_state_elements<C2: CurveGroup>(cm: &C2) -> Vec<C2::BaseField> {
+    get_cm_coordinates(cm)
 }
 
 /// returns the coordinates of a commitment point. This is compatible with the arkworks
@@ -812,10 +2701,57 @@ pub mod tests {
     use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as Projective};
     use ark_grumpkin::{constraints::GVar as GVar2, Projective as Projective2};
     use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
+    use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
 
     use crate::commitment::pedersen::Pedersen;
-    use crate::frontend::tests::CubicFCircuit;
-    use crate::transcript::poseidon::poseidon_test_config;
+    use ark_std::rand::RngCore;
+    use ark_std::UniformRand;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::commitment::pedersen::Params as PedersenParams;
+    use crate::frontend::tests::{CubicFCircuit, CustomFCircuit};
+    use crate::frontend::BoundedFCircuit;
+    use crate::transcript::{
+        poseidon::{poseidon_test_config, PoseidonTranscript},
+        Transcript,
+    };
+
+    /// Nova over BN254/Grumpkin with Pedersen commitments on both sides of the curve cycle, which
+    /// are the params returned by `test_nova_params`. Only for tests.
+    pub(crate) type TestNova<FC> =
+        Nova<Projective, GVar, Projective2, GVar2, FC, Pedersen<Projective>, Pedersen<Projective2>>;
+
+    /// returns the Nova prover & verifier params for the given FCircuit, using Pedersen
+    /// commitments on both sides of the curve cycle. Only for tests.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn test_nova_params<FC: FCircuit<Fr>>(
+        F_circuit: FC,
+    ) -> (
+        ProverParams<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>>,
+        VerifierParams<Projective, Projective2>,
+    ) {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let (cs_len, cf_cs_len) = get_cs_params_len::<Projective, GVar, Projective2, GVar2, FC>(
+            &poseidon_config,
+            F_circuit.clone(),
+        )
+        .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+            };
+        TestNova::<FC>::preprocess(&(prover_params, F_circuit)).unwrap()
+    }
 
     /// This test tests the Nova+CycleFold IVC, and by consequence it is also testing the
     /// AugmentedFCircuit
@@ -854,16 +2790,1365 @@ pub mod tests {
         );
     }
 
-    // test_ivc allowing to choose the CommitmentSchemes
-    fn test_ivc_opt<CS1: CommitmentScheme<Projective>, CS2: CommitmentScheme<Projective2>>(
-        poseidon_config: PoseidonConfig<Fr>,
-        cs_params: CS1::ProverParams,
-        cf_cs_params: CS2::ProverParams,
-        F_circuit: CubicFCircuit<Fr>,
-    ) {
-        type NOVA<CS1, CS2> =
-            Nova<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>, CS1, CS2>;
-
+    #[test]
+    fn test_small_cyclefold_commitment_params() {
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            KZG<'static, Bn254>,
+            Pedersen<Projective2>,
+        >;
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        // the CycleFold circuit is much smaller than the AugmentedFCircuit
+        assert!(cf_cs_len < cs_len);
+        let (kzg_pk, _): (KZGProverKey<Projective>, KZGVerifierKey<Bn254>) =
+            KZG::<Bn254>::setup(&mut rng, cs_len).unwrap();
+
+        // a Pedersen key shorter than the CycleFold circuit is rejected
+        let small_len = cf_cs_len.next_power_of_two() / 2;
+        let (small_pedersen_params, _) =
+            Pedersen::<Projective2>::setup(&mut rng, small_len).unwrap();
+        let prover_params = ProverParams::<_, _, KZG<Bn254>, Pedersen<Projective2>> {
+            poseidon_config: poseidon_config.clone(),
+            cs_params: kzg_pk.clone(),
+            cf_cs_params: small_pedersen_params,
+        };
+        assert!(matches!(
+            NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]),
+            Err(Error::CycleFoldCircuitTooLarge { .. })
+        ));
+
+        // a Pedersen key of the CycleFold circuit size suffices, while KZG is used on the main
+        // curve
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        test_ivc_opt::<KZG<Bn254>, Pedersen<Projective2>>(
+            poseidon_config,
+            kzg_pk,
+            cf_pedersen_params,
+            F_circuit,
+        );
+    }
+
+    #[test]
+    fn test_max_required_len() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let F_circuit_a = CustomFCircuit::<Fr>::new(10).unwrap();
+        let F_circuit_b = CustomFCircuit::<Fr>::new(2000).unwrap();
+
+        let (max_len, max_cf_len) =
+            max_required_len::<Projective, GVar, Projective2, GVar2, CustomFCircuit<Fr>>(
+                &poseidon_config,
+                &[&F_circuit_a, &F_circuit_b],
+            )
+            .unwrap();
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, max_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, max_cf_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+            };
+
+        // the shared params fit both circuits, and can be used to fold both of them
+        for F_circuit in [F_circuit_a, F_circuit_b] {
+            let (cs_len, cf_cs_len) =
+                get_cs_params_len::<Projective, GVar, Projective2, GVar2, CustomFCircuit<Fr>>(
+                    &poseidon_config,
+                    F_circuit,
+                )
+                .unwrap();
+            assert!(Pedersen::<Projective>::fits(
+                &prover_params.cs_params,
+                cs_len
+            ));
+            assert!(Pedersen::<Projective2>::fits(
+                &prover_params.cf_cs_params,
+                cf_cs_len
+            ));
+
+            let mut nova = TestNova::<CustomFCircuit<Fr>>::init(
+                &prover_params,
+                F_circuit,
+                vec![Fr::from(3_u32)],
+            )
+            .unwrap();
+            nova.prove_step(()).unwrap();
+            nova.prove_step(()).unwrap();
+        }
+
+        // params not fitting the required length
+        assert!(!Pedersen::<Projective>::fits(
+            &prover_params.cs_params,
+            prover_params.cs_params.generators.len() + 1
+        ));
+    }
+
+    #[test]
+    fn test_max_circuit_size() {
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let F_circuit = CustomFCircuit::<Fr>::new(10).unwrap();
+        let (r1cs, cf_r1cs) = get_r1cs::<Projective, GVar, Projective2, GVar2, CustomFCircuit<Fr>>(
+            &poseidon_config,
+            F_circuit,
+        )
+        .unwrap();
+        let circuit_size = required_len(&r1cs);
+
+        // params of exactly the size of the circuit
+        let (mut pedersen_params, _) =
+            Pedersen::<Projective>::setup(&mut rng, circuit_size).unwrap();
+        pedersen_params.generators.truncate(circuit_size);
+        let (cf_pedersen_params, _) =
+            Pedersen::<Projective2>::setup(&mut rng, cf_r1cs.A.n_rows).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+            };
+        assert_eq!(prover_params.max_circuit_size(), circuit_size);
+
+        type NOVA = TestNova<CustomFCircuit<Fr>>;
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.prove_step(()).unwrap();
+        nova.prove_step(()).unwrap();
+
+        // a circuit above the bound
+        let F_circuit = CustomFCircuit::<Fr>::new(10 + circuit_size).unwrap();
+        let res = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]);
+        assert!(matches!(
+            res,
+            Err(Error::CircuitTooLarge { got: size, max }) if size > max && max == circuit_size
+        ));
+    }
+
+    /// FailingPedersen wraps the Pedersen commitment scheme, allowing to make the commitments fail
+    /// once `commits_left` commitments have been computed. Used to inject errors in the prover.
+    #[derive(Clone, Debug)]
+    struct FailingPedersen;
+    #[derive(Clone, Debug)]
+    struct FailingPedersenParams {
+        params: PedersenParams<Projective>,
+        commits_left: Arc<AtomicUsize>,
+    }
+    impl CommitmentScheme<Projective> for FailingPedersen {
+        type ProverParams = FailingPedersenParams;
+        type VerifierParams = PedersenParams<Projective>;
+        type Proof = <Pedersen<Projective> as CommitmentScheme<Projective>>::Proof;
+        type ProverChallenge =
+            <Pedersen<Projective> as CommitmentScheme<Projective>>::ProverChallenge;
+        type Challenge = <Pedersen<Projective> as CommitmentScheme<Projective>>::Challenge;
+
+        fn setup(
+            rng: impl RngCore,
+            len: usize,
+        ) -> Result<(Self::ProverParams, Self::VerifierParams), Error> {
+            let (params, vk) = Pedersen::<Projective>::setup(rng, len)?;
+            let params = FailingPedersenParams {
+                params,
+                commits_left: Arc::new(AtomicUsize::new(usize::MAX)),
+            };
+            Ok((params, vk))
+        }
+        fn fits(params: &Self::ProverParams, len: usize) -> bool {
+            Pedersen::<Projective>::fits(&params.params, len)
+        }
+        fn max_len(params: &Self::ProverParams) -> usize {
+            Pedersen::<Projective>::max_len(&params.params)
+        }
+        fn params_eq(a: &Self::ProverParams, b: &Self::ProverParams) -> bool {
+            Pedersen::<Projective>::params_eq(&a.params, &b.params)
+        }
+        fn commit(params: &Self::ProverParams, v: &[Fr], blind: &Fr) -> Result<Projective, Error> {
+            if params.commits_left.load(Ordering::SeqCst) == 0 {
+                return Err(Error::Other("injected commitment failure".to_string()));
+            }
+            params.commits_left.fetch_sub(1, Ordering::SeqCst);
+            Pedersen::<Projective>::commit(&params.params, v, blind)
+        }
+        fn prove(
+            params: &Self::ProverParams,
+            transcript: &mut impl Transcript<Projective>,
+            cm: &Projective,
+            v: &[Fr],
+            blind: &Fr,
+            rng: Option<&mut dyn RngCore>,
+        ) -> Result<Self::Proof, Error> {
+            Pedersen::<Projective>::prove(&params.params, transcript, cm, v, blind, rng)
+        }
+        fn prove_with_challenge(
+            params: &Self::ProverParams,
+            challenge: Self::ProverChallenge,
+            v: &[Fr],
+            blind: &Fr,
+            rng: Option<&mut dyn RngCore>,
+        ) -> Result<Self::Proof, Error> {
+            Pedersen::<Projective>::prove_with_challenge(&params.params, challenge, v, blind, rng)
+        }
+        fn verify(
+            params: &Self::VerifierParams,
+            transcript: &mut impl Transcript<Projective>,
+            cm: &Projective,
+            proof: &Self::Proof,
+        ) -> Result<(), Error> {
+            Pedersen::<Projective>::verify(params, transcript, cm, proof)
+        }
+        fn verify_with_challenge(
+            params: &Self::VerifierParams,
+            challenge: Self::Challenge,
+            cm: &Projective,
+            proof: &Self::Proof,
+        ) -> Result<(), Error> {
+            Pedersen::<Projective>::verify_with_challenge(params, challenge, cm, proof)
+        }
+    }
+
+    #[test]
+    fn test_prove_step_rollback_on_error() {
+        type NOVA = Nova<
+            Projective,
+            GVar,
+            Projective2,
+            GVar2,
+            CubicFCircuit<Fr>,
+            FailingPedersen,
+            Pedersen<Projective2>,
+        >;
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap();
+        let (cs_params, _) = FailingPedersen::setup(&mut rng, cs_len).unwrap();
+        let (cf_cs_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, FailingPedersen, Pedersen<Projective2>> {
+                poseidon_config: poseidon_config.clone(),
+                cs_params: cs_params.clone(),
+                cf_cs_params,
+            };
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        nova.prove_step(()).unwrap();
+        nova.prove_step(()).unwrap();
+
+        // let the commitment to cmT succeed, and make the commitment to the new witness fail, which
+        // happens after the CycleFold instances have already been folded
+        let nova_before = nova.clone();
+        cs_params.commits_left.store(1, Ordering::SeqCst);
+        assert!(nova.prove_step(()).is_err());
+
+        // the state is the one from before the failed step
+        assert_eq!(nova.i, nova_before.i);
+        assert_eq!(nova.z_i, nova_before.z_i);
+        assert_eq!(nova.w_i, nova_before.w_i);
+        assert_eq!(nova.u_i, nova_before.u_i);
+        assert_eq!(nova.W_i, nova_before.W_i);
+        assert_eq!(nova.U_i, nova_before.U_i);
+        assert_eq!(nova.cf_W_i, nova_before.cf_W_i);
+        assert_eq!(nova.cf_U_i, nova_before.cf_U_i);
+
+        // retrying the step succeeds
+        cs_params.commits_left.store(usize::MAX, Ordering::SeqCst);
+        nova.prove_step(()).unwrap();
+        assert_eq!(nova.i, Fr::from(3_u32));
+
+        let verifier_params = VerifierParams::<Projective, Projective2> {
+            poseidon_config,
+            r1cs: nova.r1cs.clone(),
+            cf_r1cs: nova.cf_r1cs.clone(),
+            state_len: nova.F.state_len(),
+        };
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_prove_steps() {
+        use crate::frontend::FnFCircuit;
+
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let num_steps = 4;
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..num_steps {
+            nova.prove_step(()).unwrap();
+        }
+        let mut nova_batch = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        nova_batch.prove_steps(vec![(); num_steps]).unwrap();
+
+        assert_eq!(nova_batch.i, nova.i);
+        assert_eq!(nova_batch.state(), nova.state());
+        assert_eq!(nova_batch.instances(), nova.instances());
+
+        // a wrong length of any of the external inputs does not prove any step
+        let sum_circuit = FnFCircuit::<Fr>::new(
+            1,
+            |_i, z_i, external_inputs| Ok(vec![z_i[0] + external_inputs[0]]),
+            |_cs, _i, z_i, external_inputs| Ok(vec![&z_i[0] + &external_inputs[0]]),
+        )
+        .with_external_inputs_len(1);
+        let (sum_prover_params, _) = test_nova_params(sum_circuit.clone());
+        let mut nova_sum = TestNova::init(&sum_prover_params, sum_circuit, z_0.clone()).unwrap();
+        assert!(matches!(
+            nova_sum.prove_steps(vec![vec![Fr::from(1_u32)], vec![]]),
+            Err(Error::NotSameLength {
+                a_len: 1,
+                b_len: 0,
+                ..
+            })
+        ));
+        assert_eq!(nova_sum.i, Fr::zero());
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova_batch.instances();
+        let proof_data = nova_batch.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova_batch.z_i,
+            nova_batch.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_ivc_limb_configs() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let configs = [
+            LimbConfig::default_for::<Fr, ark_bn254::Fq>(),
+            LimbConfig {
+                bits_per_limb: 64,
+                num_limbs: 4,
+            },
+        ];
+
+        // commitment params large enough for the AugmentedFCircuit of both configs
+        let (mut cs_len, mut cf_cs_len) = (0, 0);
+        for config in configs {
+            let (r1cs, cf_r1cs) = get_r1cs_with_limb_config::<
+                Projective,
+                GVar,
+                Projective2,
+                GVar2,
+                CubicFCircuit<Fr>,
+            >(&poseidon_config, F_circuit, config)
+            .unwrap();
+            cs_len = cs_len.max(r1cs.A.n_rows.max(r1cs.A.n_cols));
+            cf_cs_len = cf_cs_len.max(cf_r1cs.A.n_rows.max(cf_r1cs.A.n_cols));
+        }
+        let (pedersen_params, _) = Pedersen::<Projective>::setup(&mut rng, cs_len).unwrap();
+        let (cf_pedersen_params, _) = Pedersen::<Projective2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params: pedersen_params,
+                cf_cs_params: cf_pedersen_params,
+            };
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut num_constraints = vec![];
+        for config in configs {
+            let (prover_params, verifier_params) =
+                NOVA::preprocess_with_limb_config(&prover_params, &F_circuit, config).unwrap();
+            let mut nova =
+                NOVA::init_with_limb_config(&prover_params, F_circuit, z_0.clone(), config)
+                    .unwrap();
+            for _ in 0..3 {
+                nova.prove_step(()).unwrap();
+            }
+            assert_eq!(nova.r1cs, verifier_params.r1cs);
+
+            // the limb config is kept when resuming from the serialized state
+            let mut bytes = vec![];
+            nova.serialize_state(&mut bytes).unwrap();
+            let resumed =
+                NOVA::deserialize_state(bytes.as_slice(), &prover_params, F_circuit).unwrap();
+            assert_eq!(resumed.limb_config, config);
+            assert_eq!(resumed.r1cs, nova.r1cs);
+
+            let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+            let proof_data = nova.proof_data();
+            NOVA::verify(
+                verifier_params,
+                z_0.clone(),
+                nova.z_i.clone(),
+                nova.i,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+            )
+            .unwrap();
+            num_constraints.push(nova.num_constraints());
+        }
+        // the limbs change the AugmentedFCircuit
+        assert_ne!(num_constraints[0], num_constraints[1]);
+
+        // a config that does not fit the non-native field is rejected
+        let invalid = LimbConfig {
+            bits_per_limb: 64,
+            num_limbs: 5,
+        };
+        assert!(matches!(
+            NOVA::init_with_limb_config(&prover_params, F_circuit, z_0, invalid),
+            Err(Error::InvalidLimbConfig {
+                bits_per_limb: 64,
+                num_limbs: 5,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_num_steps() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        assert_eq!(nova.num_steps(), 0);
+        for _ in 0..7 {
+            nova.prove_step(()).unwrap();
+        }
+        assert_eq!(nova.num_steps(), 7);
+
+        nova.verify_num_steps(verifier_params.clone(), 7).unwrap();
+        assert!(matches!(
+            nova.verify_num_steps(verifier_params, 6),
+            Err(Error::StepCountMismatch {
+                expected: 7,
+                got: 6
+            })
+        ));
+    }
+
+    #[test]
+    fn test_verify_with_state_commitment() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut rng = ark_std::test_rng();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let nonce = Fr::rand(&mut rng);
+        let commitment =
+            state_commitment(&prover_params.poseidon_config, &nova.state(), nonce).unwrap();
+        // the commitment depends on the nonce
+        assert_ne!(
+            commitment,
+            state_commitment(
+                &prover_params.poseidon_config,
+                &nova.state(),
+                nonce + Fr::one()
+            )
+            .unwrap()
+        );
+
+        let verify = |nonce: Fr| {
+            let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+            let proof_data = nova.proof_data();
+            NOVA::verify_with_state_commitment(
+                verifier_params.clone(),
+                z_0.clone(),
+                nova.state(),
+                nova.i,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+                commitment,
+                nonce,
+            )
+        };
+        verify(nonce).unwrap();
+        assert!(matches!(
+            verify(Fr::rand(&mut rng)),
+            Err(Error::StateCommitmentMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_plan_setup() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let plan = NOVA::plan_setup(&poseidon_config, &F_circuit).unwrap();
+        assert!(plan.key_len >= plan.required_len && plan.cf_key_len >= plan.cf_required_len);
+        assert!(plan.memory > 0);
+        // get_cs_params_len returns the same lengths
+        assert_eq!(
+            get_cs_params_len::<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>>(
+                &poseidon_config,
+                F_circuit,
+            )
+            .unwrap(),
+            (plan.required_len, plan.cf_required_len)
+        );
+
+        // the key lengths are the ones produced by the setup of both commitment schemes
+        let (cs_params, _) = Pedersen::<Projective>::setup(&mut rng, plan.required_len).unwrap();
+        let (cf_cs_params, _) =
+            Pedersen::<Projective2>::setup(&mut rng, plan.cf_required_len).unwrap();
+        assert_eq!(cs_params.generators.len(), plan.key_len);
+        assert_eq!(cf_cs_params.generators.len(), plan.cf_key_len);
+        let (kzg_pk, _) = KZG::<Bn254>::setup(&mut rng, plan.required_len).unwrap();
+        assert_eq!(KZG::<Bn254>::max_len(&kzg_pk), plan.key_len);
+
+        // and they are enough to fold the circuit
+        let prover_params =
+            ProverParams::<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>> {
+                poseidon_config,
+                cs_params,
+                cf_cs_params,
+            };
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.prove_step(()).unwrap();
+    }
+
+    #[test]
+    fn test_circuit_size_breakdown() {
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+        let nova =
+            TestNova::<CubicFCircuit<Fr>>::init(&prover_params, F_circuit, vec![Fr::from(3_u32)])
+                .unwrap();
+
+        let (fcircuit, overhead) = nova.circuit_size_breakdown().unwrap();
+        // z_i^3 takes 2 multiplications
+        assert_eq!(
+            fcircuit,
+            CircuitSize {
+                constraints: 2,
+                witness_variables: 2
+            }
+        );
+        assert_eq!(
+            fcircuit.constraints + overhead.constraints,
+            nova.num_constraints()
+        );
+        assert_eq!(
+            fcircuit.witness_variables + overhead.witness_variables,
+            nova.num_witness_variables()
+        );
+        assert_eq!(nova.num_witness_variables(), nova.w_i.W.len());
+    }
+
+    #[test]
+    fn test_clone_for_verification() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+
+        let verifier_state = nova.clone_for_verification();
+        assert_eq!(verifier_state.i, nova.i);
+        assert_eq!(verifier_state.z_i, nova.z_i);
+        verifier_state.verify_self().unwrap();
+
+        // a tampered state must not verify
+        let mut tampered_state = verifier_state.clone();
+        tampered_state.z_i[0] += Fr::one();
+        assert!(tampered_state.verify_self().is_err());
+    }
+
+    #[cfg(feature = "debug")]
+    #[test]
+    fn test_debug_verify_all_commitments() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        assert_eq!(nova.debug_steps.len(), 3);
+        nova.debug_verify_all_commitments().unwrap();
+
+        // corrupt the folded commitment of an intermediate step
+        nova.debug_steps[1].U_i.cmW += Projective::generator();
+        assert!(nova.debug_verify_all_commitments().is_err());
+    }
+
+    #[test]
+    fn test_public_io_commitment() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let public_inputs = nova.public_inputs();
+        assert_eq!(public_inputs, vec![nova.i, nova.z_0[0], nova.z_i[0]]);
+        let cm = nova.public_io_commitment().unwrap();
+
+        // open the commitment to the public inputs
+        let transcript_p = &mut PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let proof = Pedersen::<Projective>::prove(
+            &prover_params.cs_params,
+            transcript_p,
+            &cm,
+            &public_inputs,
+            &Fr::zero(),
+            None,
+        )
+        .unwrap();
+        let transcript_v = &mut PoseidonTranscript::<Projective>::new(&poseidon_config);
+        Pedersen::<Projective>::verify(&prover_params.cs_params, transcript_v, &cm, &proof)
+            .unwrap();
+
+        // the commitment is bound to the public inputs
+        let mut other_public_inputs = public_inputs;
+        other_public_inputs[0] += Fr::one();
+        let transcript_p = &mut PoseidonTranscript::<Projective>::new(&poseidon_config);
+        let proof = Pedersen::<Projective>::prove(
+            &prover_params.cs_params,
+            transcript_p,
+            &cm,
+            &other_public_inputs,
+            &Fr::zero(),
+            None,
+        )
+        .unwrap();
+        let transcript_v = &mut PoseidonTranscript::<Projective>::new(&poseidon_config);
+        assert!(Pedersen::<Projective>::verify(
+            &prover_params.cs_params,
+            transcript_v,
+            &cm,
+            &proof
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_finalize() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (i, z_i) = (nova.i, nova.z_i.clone());
+
+        // finalize consumes the Nova instance, so `prove_step` can not be called anymore, and
+        // FinalizedNova only exposes the proof and its verification
+        let finalized = nova.finalize();
+        assert_eq!(finalized.ivc_proof().i, i);
+        assert_eq!(finalized.ivc_proof().z_i, z_i);
+        finalized.verify_self().unwrap();
+
+        // the proof obtained from the finalized instance verifies as any other VerifierState
+        let mut tampered_state = finalized.ivc_proof().clone();
+        tampered_state.z_i[0] += Fr::one();
+        assert!(tampered_state.verify_self().is_err());
+    }
+
+    #[test]
+    fn test_init_state_length_mismatch() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32), Fr::from(4_u32)];
+        match NOVA::init(&prover_params, F_circuit, z_0) {
+            Err(Error::StateLengthMismatch { expected, got }) => {
+                assert_eq!(expected, 1);
+                assert_eq!(got, 2);
+            }
+            _ => panic!("expected Error::StateLengthMismatch"),
+        }
+    }
+
+    #[test]
+    fn test_structured_error() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.z_i = vec![Fr::from(3_u32), Fr::from(4_u32)];
+        let err = nova.prove_step(()).unwrap_err();
+
+        // the fields of the error can be used to build a custom message
+        let msg = match err {
+            Error::StateLengthMismatch { expected, got } => {
+                format!(
+                    "longitud del estado: esperada {}, recibida {}",
+                    expected, got
+                )
+            }
+            e => panic!("expected Error::StateLengthMismatch, got {:?}", e),
+        };
+        assert_eq!(msg, "longitud del estado: esperada 1, recibida 2");
+    }
+
+    #[test]
+    fn test_check_shared_prefix_trusted() {
+        use crate::frontend::FnFCircuit;
+
+        type NOVA = TestNova<FnFCircuit<Fr>>;
+        // z_{i+1} = z_i + x, where x is the external input of the step
+        let F_circuit = FnFCircuit::<Fr>::new(
+            1,
+            |_i, z_i, external_inputs| Ok(vec![z_i[0] + external_inputs[0]]),
+            |_cs, _i, z_i, external_inputs| Ok(vec![&z_i[0] + &external_inputs[0]]),
+        )
+        .with_external_inputs_len(1);
+        let (prover_params, _) = test_nova_params(F_circuit.clone());
+
+        let prove_branch = |nova: &NOVA, inputs: &[u32]| {
+            let mut nova = nova.clone();
+            for x in inputs {
+                nova.prove_step(vec![Fr::from(*x)]).unwrap();
+            }
+            nova.clone_for_verification()
+        };
+
+        let nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        // the checkpoints are disabled by default, and can only be enabled before the first step
+        assert!(nova.checkpoints.is_empty());
+        let mut stepped = nova.clone();
+        stepped.prove_step(vec![Fr::from(1_u32)]).unwrap();
+        assert!(stepped.checkpoints.is_empty());
+        assert!(stepped.with_checkpoints().is_err());
+        let nova = nova.with_checkpoints().unwrap();
+        // two branches forked after a 3-step prefix
+        let mut prefix_nova = nova.clone();
+        for x in [1_u32, 2, 3] {
+            prefix_nova.prove_step(vec![Fr::from(x)]).unwrap();
+        }
+        let branch_a = prove_branch(&prefix_nova, &[4, 5]);
+        let branch_b = prove_branch(&prefix_nova, &[6]);
+        NOVA::check_shared_prefix_trusted(&branch_a, &branch_b, 3).unwrap();
+        assert!(matches!(
+            NOVA::check_shared_prefix_trusted(&branch_a, &branch_b, 4),
+            Err(Error::SharedPrefixMismatch { step: 4 })
+        ));
+
+        // a branch diverging at step 2 does not share the 3-step prefix
+        let branch_c = prove_branch(&nova, &[1, 7, 3, 4]);
+        NOVA::check_shared_prefix_trusted(&branch_a, &branch_c, 1).unwrap();
+        assert!(matches!(
+            NOVA::check_shared_prefix_trusted(&branch_a, &branch_c, 3),
+            Err(Error::SharedPrefixMismatch { step: 2 })
+        ));
+
+        // checkpoints not matching the state of the proof are rejected
+        let mut tampered = branch_b.clone();
+        tampered.checkpoints.pop();
+        assert!(matches!(
+            NOVA::check_shared_prefix_trusted(&branch_a, &tampered, 3),
+            Err(Error::CheckpointMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_with_min_security() {
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.prove_step(()).unwrap();
+        nova.prove_step(()).unwrap();
+        let state = nova.clone_for_verification();
+
+        // the challenges of N_BITS_RO=128 bits give a soundness error of 2^-127
+        assert_eq!(state.vp.security_bits(), N_BITS_RO - 1);
+        state.verify_with_min_security(100).unwrap();
+        state.verify_with_min_security(N_BITS_RO - 1).unwrap();
+        assert!(matches!(
+            state.verify_with_min_security(N_BITS_RO),
+            Err(Error::InsufficientSecurity {
+                bits: 127,
+                required: 128
+            })
+        ));
+    }
+
+    #[test]
+    fn test_verify_poseidon_config_mismatch() {
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        nova.prove_step(()).unwrap();
+        nova.prove_step(()).unwrap();
+        let state = nova.clone_for_verification();
+        assert_eq!(
+            state.poseidon_config_digest,
+            poseidon_config_digest(&poseidon_test_config::<Fr>())
+        );
+        state.verify_self().unwrap();
+
+        // the verifier uses its own params, with a different Poseidon config
+        let mut state_with_other_config = state.clone();
+        state_with_other_config.vp.poseidon_config.ark[0][0] += Fr::one();
+        assert!(matches!(
+            state_with_other_config.verify_self(),
+            Err(Error::ParamsMismatch {
+                params: "Poseidon config"
+            })
+        ));
+
+        // the same happens through `verify`, which takes the digest from the proof data
+        let mut other_verifier_params = verifier_params;
+        other_verifier_params.poseidon_config.ark[0][0] += Fr::one();
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        assert!(matches!(
+            NOVA::verify(
+                other_verifier_params,
+                vec![Fr::from(3_u32)],
+                nova.z_i.clone(),
+                nova.i,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                nova.proof_data(),
+            ),
+            Err(Error::ParamsMismatch {
+                params: "Poseidon config"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_check_shared_prefix_trusted_incompatible_params() {
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+        let z_0 = vec![Fr::from(3_u32)];
+
+        // commitment keys of the same length, generated from a different rng
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut other_prover_params = prover_params.clone();
+        other_prover_params.cs_params = Pedersen::<Projective>::setup(
+            &mut rng,
+            Pedersen::<Projective>::max_len(&prover_params.cs_params),
+        )
+        .unwrap()
+        .0;
+        assert!(!Pedersen::<Projective>::params_eq(
+            &prover_params.cs_params,
+            &other_prover_params.cs_params
+        ));
+
+        let prove = |prover_params: &ProverParams<_, _, _, _>| {
+            let mut nova = NOVA::init(prover_params, F_circuit, z_0.clone())
+                .unwrap()
+                .with_checkpoints()
+                .unwrap();
+            for _ in 0..3 {
+                nova.prove_step(()).unwrap();
+            }
+            nova
+        };
+        let nova_a = prove(&prover_params);
+        let nova_b = prove(&prover_params);
+        let nova_c = prove(&other_prover_params);
+        nova_a.check_shared_prefix_trusted_with(&nova_b, 3).unwrap();
+
+        // the states alone do not carry the commitment keys, so the mismatch is only detected
+        // over the Nova instances
+        NOVA::check_shared_prefix_trusted(
+            &nova_a.clone_for_verification(),
+            &nova_c.clone_for_verification(),
+            3,
+        )
+        .unwrap();
+        assert!(matches!(
+            nova_a.check_shared_prefix_trusted_with(&nova_c, 3),
+            Err(Error::IncompatibleParams { scheme: "CS1" })
+        ));
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        use ark_std::rand::{rngs::StdRng, SeedableRng};
+
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params_a, verifier_params_a) = test_nova_params(F_circuit);
+
+        // rotated commitment keys, of the same length
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut prover_params_b = prover_params_a.clone();
+        prover_params_b.cs_params = Pedersen::<Projective>::setup(
+            &mut rng,
+            Pedersen::<Projective>::max_len(&prover_params_a.cs_params),
+        )
+        .unwrap()
+        .0;
+        let (_, verifier_params_b) =
+            NOVA::preprocess(&(prover_params_b.clone(), F_circuit)).unwrap();
+
+        let prove = |prover_params: &ProverParams<_, _, _, _>, z_0: Vec<Fr>| {
+            let mut nova = NOVA::init(prover_params, F_circuit, z_0).unwrap();
+            for _ in 0..3 {
+                nova.prove_step(()).unwrap();
+            }
+            nova.clone_for_verification()
+        };
+        // 3 steps under the keys A, and the next 3 steps under the keys B
+        let proof_a = prove(&prover_params_a, vec![Fr::from(3_u32)]);
+        let proof_b = prove(&prover_params_b, proof_a.z_i.clone());
+        let params = [verifier_params_a.clone(), verifier_params_b];
+        VerifierState::verify_chain(&params, &[proof_a.clone(), proof_b.clone()]).unwrap();
+
+        // the chain matches a single IVC of 6 steps
+        let mut nova = NOVA::init(&prover_params_a, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..6 {
+            nova.prove_step(()).unwrap();
+        }
+        assert_eq!(proof_b.z_i, nova.z_i);
+
+        // a generation that does not continue from the previous one
+        let proof_c = prove(&prover_params_a, vec![Fr::from(4_u32)]);
+        assert!(matches!(
+            VerifierState::verify_chain(&params, &[proof_a.clone(), proof_c]),
+            Err(Error::ProofChainBroken { generation: 1 })
+        ));
+
+        // a proof whose params are not the ones of its generation
+        let mut other_params = verifier_params_a;
+        other_params.poseidon_config.full_rounds += 1;
+        assert!(matches!(
+            VerifierState::verify_chain(&[other_params, params[1].clone()], &[proof_a, proof_b]),
+            Err(Error::ChainParamsMismatch { generation: 0 })
+        ));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_verify_or_dump() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let proof = nova.clone_for_verification();
+
+        let dir = std::env::temp_dir().join("sonobe_test_verify_or_dump");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bundle.json");
+        let _ = fs::remove_file(&path);
+
+        // a valid proof does not write any bundle
+        proof.verify_or_dump(&path).unwrap();
+        assert!(!path.exists());
+
+        // a proof with a wrong last state
+        let mut bad_proof = proof.clone();
+        bad_proof.z_i = vec![Fr::from(4_u32)];
+        assert!(matches!(
+            bad_proof.verify_or_dump(&path),
+            Err(Error::IVCVerificationFail)
+        ));
+        let bundle: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(bundle["failing_check"], "running_instance_hash");
+        assert_eq!(bundle["error"], Error::IVCVerificationFail.to_string());
+        let params_digest: String = verifier_params
+            .digest()
+            .unwrap()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        assert_eq!(bundle["params_digest"], params_digest);
+        assert_eq!(bundle["report"]["passed"], false);
+        assert_eq!(bundle["report"]["checks"].as_array().unwrap().len(), 8);
+        assert_eq!(
+            bundle["proof"].as_str().unwrap().len(),
+            2 * bad_proof.proof_to_bytes().unwrap().len()
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_serialize_state() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let resume = |nova: &NOVA| {
+            let mut bytes = Vec::new();
+            nova.serialize_state(&mut bytes).unwrap();
+            // the state is restored with a copy of the params, as it would be on another machine
+            NOVA::deserialize_state(bytes.as_slice(), &prover_params.clone(), F_circuit).unwrap()
+        };
+
+        // serialize before any fold
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)])
+            .unwrap()
+            .with_checkpoints()
+            .unwrap();
+        let mut resumed = resume(&nova);
+        assert_eq!(resumed.instances(), nova.instances());
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+            resumed.prove_step(()).unwrap();
+        }
+        assert_eq!(resumed.instances(), nova.instances());
+
+        // serialize in the middle of the IVC
+        let mut resumed = resume(&nova);
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+            resumed.prove_step(()).unwrap();
+        }
+        assert_eq!(resumed.i, nova.i);
+        assert_eq!(resumed.z_i, nova.z_i);
+        assert_eq!(resumed.checkpoints, nova.checkpoints);
+        assert_eq!(resumed.instances(), nova.instances());
+
+        let (running_instance, incoming_instance, cyclefold_instance) = resumed.instances();
+        let proof_data = resumed.proof_data();
+        NOVA::verify(
+            verifier_params,
+            resumed.z_0.clone(),
+            resumed.z_i.clone(),
+            resumed.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+
+        // a truncated state can not be restored
+        let mut bytes = Vec::new();
+        nova.serialize_state(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() / 2);
+        assert!(NOVA::deserialize_state(bytes.as_slice(), &prover_params, F_circuit).is_err());
+    }
+
+    #[test]
+    fn test_ivc_metadata() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)])
+            .unwrap()
+            .with_metadata(b"job-42".to_vec())
+            .unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        // metadata can not be attached after the first step
+        assert!(nova.clone().with_metadata(vec![]).is_err());
+
+        let ivc_proof = nova.clone_for_verification();
+        ivc_proof.verify_self().unwrap();
+
+        // altering the metadata invalidates the proof
+        let mut tampered = ivc_proof.clone();
+        tampered.metadata = b"job-43".to_vec();
+        assert!(tampered.verify_self().is_err());
+        let mut tampered = ivc_proof;
+        tampered.metadata = vec![];
+        assert!(tampered.verify_self().is_err());
+
+        // `verify` takes the metadata from the proof data
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        assert_eq!(proof_data.metadata, b"job-42".to_vec());
+        let verify = |proof_data: IVCProofData| {
+            NOVA::verify(
+                verifier_params.clone(),
+                vec![Fr::from(3_u32)],
+                nova.z_i.clone(),
+                nova.i,
+                running_instance.clone(),
+                incoming_instance.clone(),
+                cyclefold_instance.clone(),
+                proof_data,
+            )
+        };
+        verify(proof_data.clone()).unwrap();
+        assert!(verify(IVCProofData {
+            metadata: b"job-43".to_vec(),
+            ..proof_data
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_structure() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+        }
+        let state = nova.clone_for_verification();
+        state.validate_structure().unwrap();
+
+        // point which is not on the curve (y^2 != x^3 + 3)
+        let mut invalid_state = state.clone();
+        invalid_state.U_i.cmW = Projective::new_unchecked(
+            ark_bn254::Fq::one(),
+            ark_bn254::Fq::one(),
+            ark_bn254::Fq::one(),
+        );
+        match invalid_state.validate_structure() {
+            Err(Error::InvalidPoint { name }) => assert_eq!(name, "U_i.cmW"),
+            _ => panic!("expected Error::InvalidPoint"),
+        }
+
+        // witness of a wrong length
+        let mut invalid_state = state;
+        invalid_state.W_i.W.pop();
+        assert!(matches!(
+            invalid_state.validate_structure(),
+            Err(Error::NotExpectedLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_commitment_valued_z_0() {
+        use crate::frontend::FnFCircuit;
+        use ark_bn254::Fq;
+        use ark_r1cs_std::fields::{fp::FpVar, FieldVar};
+
+        type NOVA = TestNova<FnFCircuit<Fr>>;
+
+        // z_0 = [cm_x, cm_y, counter], each step keeps the commitment and increments the counter
+        let F_circuit = FnFCircuit::<Fr>::new(
+            COMMITMENT_STATE_LEN + 1,
+            |_i, z_i, _external_inputs| Ok(vec![z_i[0], z_i[1], z_i[2] + Fr::one()]),
+            |_cs, _i, z_i, _external_inputs| {
+                Ok(vec![z_i[0].clone(), z_i[1].clone(), &z_i[2] + FpVar::one()])
+            },
+        );
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        // commitment over C2 to the initial dataset
+        let dataset: Vec<Fq> = (0..8).map(|i| Fq::from(i as u32)).collect();
+        let cm =
+            Pedersen::<Projective2>::commit(&prover_params.cf_cs_params, &dataset, &Fq::zero())
+                .unwrap();
+        let z_0 = [commitment_to_state_elements(&cm), vec![Fr::zero()]].concat();
+        assert_eq!(z_0.len(), COMMITMENT_STATE_LEN + 1);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(vec![]).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params.clone(),
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+            proof_data.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            nova.z_i[..COMMITMENT_STATE_LEN],
+            commitment_to_state_elements(&cm)[..]
+        );
+
+        // the proof does not verify for a commitment to a different dataset
+        let other_cm = Pedersen::<Projective2>::commit(
+            &prover_params.cf_cs_params,
+            &dataset[1..],
+            &Fq::zero(),
+        )
+        .unwrap();
+        let other_z_0 = [commitment_to_state_elements(&other_cm), vec![Fr::zero()]].concat();
+        assert!(NOVA::verify(
+            verifier_params,
+            other_z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_public_inputs_count() {
+        // circuits with different state lengths are verified through the same path
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        verify_public_inputs_count_opt(F_circuit, vec![Fr::from(3_u32)]);
+        let F_circuit =
+            <BoundedFCircuit<CubicFCircuit<Fr>> as FCircuit<Fr>>::new(((), 10)).unwrap();
+        verify_public_inputs_count_opt(F_circuit, vec![Fr::from(3_u32), Fr::zero()]);
+    }
+
+    fn verify_public_inputs_count_opt<FC: FCircuit<Fr, ExternalInputs = ()>>(
+        F_circuit: FC,
+        z_0: Vec<Fr>,
+    ) {
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let mut nova = TestNova::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        TestNova::<FC>::verify(
+            verifier_params.clone(),
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+            proof_data.clone(),
+        )
+        .unwrap();
+
+        // a last state of a different length than the state of the FCircuit
+        let z_i = [nova.z_i.clone(), vec![Fr::one()]].concat();
+        match TestNova::<FC>::verify(
+            verifier_params.clone(),
+            z_0.clone(),
+            z_i,
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+            proof_data.clone(),
+        ) {
+            Err(Error::StateLengthMismatch { expected, got }) => {
+                assert_eq!(expected, z_0.len());
+                assert_eq!(got, z_0.len() + 1);
+            }
+            _ => panic!("expected Error::StateLengthMismatch"),
+        }
+
+        // an incoming instance with a wrong number of public inputs
+        let (mut u_i, w_i) = incoming_instance;
+        u_i.x.push(Fr::one());
+        match NOVA::<FC>::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            (u_i, w_i),
+            cyclefold_instance,
+            proof_data,
+        ) {
+            Err(Error::PublicInputCountMismatch { expected, got }) => {
+                assert_eq!(expected, 2);
+                assert_eq!(got, 3);
+            }
+            _ => panic!("expected Error::PublicInputCountMismatch"),
+        }
+    }
+
+    #[test]
+    fn test_verifier_params_digest() {
+        // keccak256 of the empty input, to check that the digest is keccak256 and not SHA3-256
+        assert_eq!(
+            hex_string(&Keccak256::digest(b"").into()),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (_, verifier_params) = test_nova_params(F_circuit);
+        let digest = verifier_params.digest().unwrap();
+        let expected: [u8; 32] = Keccak256::digest(verifier_params.to_bytes().unwrap()).into();
+        assert_eq!(digest, expected);
+        // the digest is deterministic
+        assert_eq!(digest, verifier_params.clone().digest().unwrap());
+
+        // params of a different circuit have a different digest
+        let F_circuit =
+            <BoundedFCircuit<CubicFCircuit<Fr>> as FCircuit<Fr>>::new(((), 10)).unwrap();
+        let (_, other_verifier_params) = test_nova_params(F_circuit);
+        assert_ne!(digest, other_verifier_params.digest().unwrap());
+    }
+
+    fn hex_string(bytes: &[u8; 32]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    // test_ivc allowing to choose the CommitmentSchemes
+    fn test_ivc_opt<CS1: CommitmentScheme<Projective>, CS2: CommitmentScheme<Projective2>>(
+        poseidon_config: PoseidonConfig<Fr>,
+        cs_params: CS1::ProverParams,
+        cf_cs_params: CS2::ProverParams,
+        F_circuit: CubicFCircuit<Fr>,
+    ) {
+        type NOVA<CS1, CS2> =
+            Nova<Projective, GVar, Projective2, GVar2, CubicFCircuit<Fr>, CS1, CS2>;
+
         let prover_params = ProverParams::<Projective, Projective2, CS1, CS2> {
             poseidon_config: poseidon_config.clone(),
             cs_params,
@@ -875,7 +4160,7 @@ pub mod tests {
 
         let num_steps: usize = 3;
         for _ in 0..num_steps {
-            nova.prove_step(vec![]).unwrap();
+            nova.prove_step(()).unwrap();
         }
         assert_eq!(Fr::from(num_steps as u32), nova.i);
 
@@ -883,8 +4168,10 @@ pub mod tests {
             poseidon_config,
             r1cs: nova.clone().r1cs,
             cf_r1cs: nova.clone().cf_r1cs,
+            state_len: nova.F.state_len(),
         };
         let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
         NOVA::<CS1, CS2>::verify(
             verifier_params,
             z_0,
@@ -893,7 +4180,356 @@ pub mod tests {
             running_instance,
             incoming_instance,
             cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_serialize_params() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let mut pp_bytes = vec![];
+        prover_params.serialize_compressed(&mut pp_bytes).unwrap();
+        assert_eq!(pp_bytes.len(), prover_params.compressed_size());
+        let mut vp_bytes = vec![];
+        verifier_params.serialize_compressed(&mut vp_bytes).unwrap();
+        assert_eq!(vp_bytes.len(), verifier_params.compressed_size());
+
+        type PP =
+            ProverParams<Projective, Projective2, Pedersen<Projective>, Pedersen<Projective2>>;
+        let pp = PP::deserialize_compressed(&pp_bytes[..]).unwrap();
+        let vp = VerifierParams::<Projective, Projective2>::deserialize_compressed(&vp_bytes[..])
+            .unwrap();
+        assert_eq!(pp.cs_params, prover_params.cs_params);
+        assert_eq!(pp.cf_cs_params, prover_params.cf_cs_params);
+        assert_eq!(
+            poseidon_config_digest(&pp.poseidon_config),
+            poseidon_config_digest(&prover_params.poseidon_config)
+        );
+        assert_eq!(vp.r1cs, verifier_params.r1cs);
+        assert_eq!(vp.cf_r1cs, verifier_params.cf_r1cs);
+        assert_eq!(vp.state_len, verifier_params.state_len);
+
+        // re-serializing the deserialized params gives the same bytes
+        let mut bytes = vec![];
+        pp.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes, pp_bytes);
+        let mut bytes = vec![];
+        vp.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes, vp_bytes);
+
+        // the deserialized verifier params verify the proofs of the deserialized prover params
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&pp, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            vp,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_nova_verifier() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        // the light verifier only holds the verifier params
+        drop(prover_params);
+        let verifier = NovaVerifier::new(verifier_params.clone());
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        verifier
+            .verify(
+                z_0.clone(),
+                nova.z_i.clone(),
+                nova.i,
+                running_instance.clone(),
+                incoming_instance.clone(),
+                cyclefold_instance.clone(),
+                proof_data.clone(),
+            )
+            .unwrap();
+
+        // a wrong last state is rejected both by the light verifier and by Nova::verify
+        let z_i = vec![nova.z_i[0] + Fr::one()];
+        assert!(verifier
+            .verify(
+                z_0.clone(),
+                z_i.clone(),
+                nova.i,
+                running_instance.clone(),
+                incoming_instance.clone(),
+                cyclefold_instance.clone(),
+                proof_data.clone(),
+            )
+            .is_err());
+        assert!(NOVA::verify(
+            verifier_params,
+            z_0,
+            z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_rotate_transcript_domain() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut rng = ark_std::test_rng();
+        let poseidon_config = poseidon_test_config::<Fr>();
+
+        // the challenges for the same instances are independent before and after the rotation,
+        // and are determined by the rotation label
+        let U_i = CommittedInstance::<Projective> {
+            cmE: Projective::rand(&mut rng),
+            u: Fr::rand(&mut rng),
+            cmW: Projective::rand(&mut rng),
+            x: vec![Fr::rand(&mut rng), Fr::rand(&mut rng)],
+        };
+        let u_i = CommittedInstance::<Projective> {
+            cmE: Projective::zero(),
+            u: Fr::one(),
+            cmW: Projective::rand(&mut rng),
+            x: vec![Fr::rand(&mut rng), Fr::rand(&mut rng)],
+        };
+        let cmT = Projective::rand(&mut rng);
+        let challenge = |domain: Fr| {
+            ChallengeGadget::<Projective>::get_challenge_native_with_domain(
+                &poseidon_config,
+                Some(domain),
+                U_i.clone(),
+                u_i.clone(),
+                cmT,
+                None,
+            )
+            .unwrap()
+        };
+        let domain_0 = Fr::zero();
+        let domain_1 = rotated_domain(&poseidon_config, domain_0, b"phase 2").unwrap();
+        assert_ne!(challenge(domain_0), challenge(domain_1));
+        assert_eq!(
+            challenge(domain_1),
+            challenge(rotated_domain(&poseidon_config, domain_0, b"phase 2").unwrap())
+        );
+        assert_ne!(
+            challenge(domain_1),
+            challenge(rotated_domain(&poseidon_config, domain_0, b"phase 3").unwrap())
+        );
+        // the rotations are chained
+        assert_ne!(
+            rotated_domain(&poseidon_config, domain_1, b"phase 2").unwrap(),
+            domain_1
+        );
+
+        // the IVC verifies across the rotation, and the steps after it fold with different
+        // challenges than the ones without the rotation
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+        }
+        let mut rotated = nova.clone();
+        rotated.rotate_transcript_domain(b"phase 2").unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+            rotated.prove_step(()).unwrap();
+        }
+        assert_eq!(rotated.z_i, nova.z_i);
+        assert_ne!(rotated.U_i, nova.U_i);
+        // the domain can only be rotated once per step
+        rotated.rotate_transcript_domain(b"phase 3").unwrap();
+        assert!(matches!(
+            rotated.rotate_transcript_domain(b"phase 4"),
+            Err(Error::DomainAlreadyRotated)
+        ));
+        rotated.prove_step(()).unwrap();
+
+        // the domain is bound to the IVC, so the proof verifies with the labels of the rotations,
+        // and not with other labels nor without them
+        let state = rotated.clone_for_verification();
+        assert_eq!(
+            state.domain_labels,
+            vec![b"phase 2".to_vec(), b"phase 3".to_vec()]
+        );
+        state.verify_self().unwrap();
+        let mut other_label = state.clone();
+        other_label.domain_labels[0] = b"phase B".to_vec();
+        assert!(matches!(
+            other_label.verify_self(),
+            Err(Error::IVCVerificationFail)
+        ));
+        let (running_instance, incoming_instance, cyclefold_instance) = rotated.instances();
+        let proof_data = rotated.proof_data();
+        assert_eq!(proof_data.domain_labels, state.domain_labels);
+        NOVA::verify(
+            verifier_params.clone(),
+            z_0.clone(),
+            rotated.z_i.clone(),
+            rotated.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+            proof_data.clone(),
         )
         .unwrap();
+        assert!(NOVA::verify(
+            verifier_params,
+            z_0,
+            rotated.z_i,
+            rotated.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            IVCProofData {
+                domain_labels: vec![],
+                ..proof_data
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_cyclefold_commitment_mismatch() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let mut rng = ark_std::test_rng();
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+        }
+
+        // corrupt the CycleFold running instance, which no longer matches its hash in u_i.x[1]
+        nova.cf_U_i.cmW += Projective2::rand(&mut rng);
+        assert!(matches!(
+            nova.prove_step(()),
+            Err(Error::CycleFold(CycleFoldError::CommitmentMismatch))
+        ));
+        assert_eq!(nova.i, Fr::from(2_u32));
+    }
+
+    /// CubicFCircuit exposing the square of the state as the public output of each step
+    #[derive(Clone, Copy, Debug)]
+    pub(crate) struct SquareIOFCircuit<F: PrimeField>(CubicFCircuit<F>);
+    impl<F: PrimeField> FCircuit<F> for SquareIOFCircuit<F> {
+        type Params = ();
+        type ExternalInputs = ();
+        fn new(params: Self::Params) -> Result<Self, Error> {
+            Ok(Self(CubicFCircuit::new(params)?))
+        }
+        fn state_len(&self) -> usize {
+            1
+        }
+        fn external_inputs_len(&self) -> usize {
+            0
+        }
+        fn io_len(&self) -> usize {
+            1
+        }
+        fn step_io_native(
+            &self,
+            _i: usize,
+            z_i: Vec<F>,
+            _external_inputs: (),
+        ) -> Result<Vec<F>, Error> {
+            Ok(vec![z_i[0] * z_i[0]])
+        }
+        fn generate_step_io_constraints(
+            &self,
+            _cs: ConstraintSystemRef<F>,
+            _i: usize,
+            z_i: Vec<FpVar<F>>,
+            _external_inputs: (),
+        ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+            Ok(vec![z_i[0].clone() * z_i[0].clone()])
+        }
+        fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: ()) -> Result<Vec<F>, Error> {
+            self.0.step_native(i, z_i, external_inputs)
+        }
+        fn generate_step_constraints(
+            &self,
+            cs: ConstraintSystemRef<F>,
+            i: usize,
+            z_i: Vec<FpVar<F>>,
+            external_inputs: (),
+        ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+            self.0
+                .generate_step_constraints(cs, i, z_i, external_inputs)
+        }
+    }
+
+    #[test]
+    fn test_verify_with_io() {
+        type NOVA = TestNova<SquareIOFCircuit<Fr>>;
+        let F_circuit = SquareIOFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        let mut io = vec![];
+        for _ in 0..3 {
+            io.push(vec![nova.z_i[0] * nova.z_i[0]]);
+            nova.prove_step(()).unwrap();
+        }
+        assert_eq!(nova.io, io);
+        nova.clone_for_verification().verify_self().unwrap();
+
+        let verify = |io: Vec<Vec<Fr>>| {
+            let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+            let proof_data = nova.proof_data();
+            NOVA::verify_with_io(
+                verifier_params.clone(),
+                z_0.clone(),
+                nova.z_i.clone(),
+                nova.i,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+                io,
+            )
+        };
+        verify(io.clone()).unwrap();
+
+        // a different IO, a missing step or no IO at all do not verify
+        let mut wrong_io = io.clone();
+        wrong_io[1][0] += Fr::one();
+        assert!(verify(wrong_io).is_err());
+        assert!(matches!(
+            verify(io[..2].to_vec()),
+            Err(Error::StepCountMismatch {
+                expected: 3,
+                got: 2
+            })
+        ));
+        assert!(verify(vec![]).is_err());
     }
 }
@@ -0,0 +1,198 @@
+/// Merkle tree over the components of the Nova verifier params (see `VerifierParams::to_leaves`),
+/// which allows light clients to pin only the Merkle root of the params, and to receive the
+/// params together with their inclusion proofs alongside the IVC proof to be verified.
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use sha3::{Digest, Keccak256};
+
+use super::{VerifierParams, VerifierState};
+use crate::Error;
+
+pub type Hash = [u8; 32];
+
+/// MerkleProof proves the inclusion of the `index`-th leaf in the tree, through the siblings of
+/// the path from the leaf to the root.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub siblings: Vec<Hash>,
+}
+
+// leaves and inner nodes are hashed with different prefixes, so that an inner node can not be
+// passed as a leaf
+fn hash_leaf(leaf: &[u8]) -> Hash {
+    Keccak256::new()
+        .chain_update([0_u8])
+        .chain_update(leaf)
+        .finalize()
+        .into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    Keccak256::new()
+        .chain_update([1_u8])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+/// returns the layers of the tree, from the hashed leaves to the root, where the leaves are padded
+/// with zero hashes up to the next power of two
+fn layers(leaves: &[Vec<u8>]) -> Vec<Vec<Hash>> {
+    let mut layer: Vec<Hash> = leaves.iter().map(|leaf| hash_leaf(leaf)).collect();
+    layer.resize(layer.len().next_power_of_two(), [0_u8; 32]);
+    let mut layers = vec![layer];
+    while layers[layers.len() - 1].len() > 1 {
+        let next = layers[layers.len() - 1]
+            .chunks(2)
+            .map(|pair| hash_node(&pair[0], &pair[1]))
+            .collect();
+        layers.push(next);
+    }
+    layers
+}
+
+impl MerkleProof {
+    /// returns the root of the tree defined by the given leaf and the proof
+    pub fn compute_root(&self, leaf: &[u8]) -> Hash {
+        let mut index = self.index;
+        let mut node = hash_leaf(leaf);
+        for sibling in self.siblings.iter() {
+            node = if index % 2 == 0 {
+                hash_node(&node, sibling)
+            } else {
+                hash_node(sibling, &node)
+            };
+            index /= 2;
+        }
+        node
+    }
+}
+
+impl<C1: CurveGroup, C2: CurveGroup> VerifierParams<C1, C2> {
+    /// returns the Merkle root of the leaves of the params
+    pub fn merkle_root(&self) -> Result<Hash, Error> {
+        let layers = layers(&self.to_leaves()?);
+        Ok(layers[layers.len() - 1][0])
+    }
+
+    /// returns the inclusion proofs of each of the leaves of the params
+    pub fn merkle_proofs(&self) -> Result<Vec<MerkleProof>, Error> {
+        let leaves = self.to_leaves()?;
+        let layers = layers(&leaves);
+        Ok((0..leaves.len())
+            .map(|index| MerkleProof {
+                index,
+                siblings: layers[..layers.len() - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(depth, layer)| layer[(index >> depth) ^ 1])
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// checks that each of the leaves of the params is included in the tree of the given root
+    /// at its position, returning `Error::VerifierParamsInclusionFail` otherwise
+    pub fn check_inclusion(&self, root: &Hash, proofs: &[MerkleProof]) -> Result<(), Error> {
+        let leaves = self.to_leaves()?;
+        if proofs.len() != leaves.len() {
+            return Err(Error::NotSameLength {
+                a: "proofs.len()",
+                a_len: proofs.len(),
+                b: "leaves.len()",
+                b_len: leaves.len(),
+            });
+        }
+        for (index, (leaf, proof)) in leaves.iter().zip(proofs).enumerate() {
+            if proof.index != index || proof.compute_root(leaf) != *root {
+                return Err(Error::VerifierParamsInclusionFail { index });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<C1, C2> VerifierState<C1, C2>
+where
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    C2: CurveGroup,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField + Absorb,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+{
+    /// verifies the state against the pinned Merkle `root` of the verifier params: first checks
+    /// the inclusion of the params of the state through the given proofs, and then verifies the
+    /// state with them
+    pub fn verify_with_params_root(
+        &self,
+        root: &Hash,
+        proofs: &[MerkleProof],
+    ) -> Result<(), Error> {
+        self.vp.check_inclusion(root, proofs)?;
+        self.verify_self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::One;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_verify_with_params_root() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let state = nova.clone_for_verification();
+
+        // the light client only stores the root, the params and their proofs come with the proof
+        let root = verifier_params.merkle_root().unwrap();
+        let proofs = state.vp.merkle_proofs().unwrap();
+        state.verify_with_params_root(&root, &proofs).unwrap();
+
+        // tampered inclusion proof
+        let mut tampered_proofs = proofs.clone();
+        tampered_proofs[1].siblings[0][0] ^= 1;
+        assert!(matches!(
+            state.verify_with_params_root(&root, &tampered_proofs),
+            Err(Error::VerifierParamsInclusionFail { index: 1 })
+        ));
+
+        // proof of a leaf at a different position
+        let mut tampered_proofs = proofs.clone();
+        tampered_proofs.swap(0, 2);
+        assert!(state
+            .verify_with_params_root(&root, &tampered_proofs)
+            .is_err());
+
+        // tampered params, whose leaves are not included in the pinned root
+        let mut tampered_state = state.clone();
+        let row = tampered_state
+            .vp
+            .r1cs
+            .A
+            .coeffs
+            .iter_mut()
+            .find(|row| !row.is_empty())
+            .unwrap();
+        row[0].0 += Fr::one();
+        assert!(matches!(
+            tampered_state.verify_with_params_root(&root, &proofs),
+            Err(Error::VerifierParamsInclusionFail { index: 1 })
+        ));
+    }
+}
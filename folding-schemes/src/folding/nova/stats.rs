@@ -0,0 +1,111 @@
+/// Per-step proving stats of Nova, recorded by `Nova::prove_step` when the `stats` feature is
+/// enabled, and exported through `Nova::stats`.
+use ark_std::fmt::Write;
+use std::time::Duration;
+
+/// StepStats contains the metrics of a single `prove_step` call.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct StepStats {
+    /// index of the step, starting from 0
+    pub step: usize,
+    /// number of constraints of the AugmentedFCircuit synthesized at the step
+    pub constraints: usize,
+    /// time spent synthesizing the AugmentedFCircuit and extracting the new witness from it
+    pub witness_time: Duration,
+    /// time spent folding the Nova instances (NIFS)
+    pub fold_time: Duration,
+    /// time spent synthesizing, committing and folding the CycleFold circuits, which is zero at
+    /// the first step
+    pub cyclefold_time: Duration,
+    /// time spent computing the commitments of the step (cmT and the commitment to the new
+    /// witness)
+    pub commit_time: Duration,
+    /// total time of the step, which also includes the native step and the hashes, not covered
+    /// by the phases above
+    pub total_time: Duration,
+}
+
+impl StepStats {
+    /// returns the time of the step not covered by any of the phases
+    pub fn other_time(&self) -> Duration {
+        self.total_time.saturating_sub(
+            self.witness_time + self.fold_time + self.cyclefold_time + self.commit_time,
+        )
+    }
+}
+
+/// returns the given stats as CSV, with a header row followed by a row per step, where the times
+/// are in microseconds
+pub fn to_csv(stats: &[StepStats]) -> String {
+    let mut csv = String::from(
+        "step,constraints,commit_time_us,fold_time_us,witness_time_us,cyclefold_time_us,total_time_us\n",
+    );
+    for s in stats {
+        // writing into a String never fails
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{}",
+            s.step,
+            s.constraints,
+            s.commit_time.as_micros(),
+            s.fold_time.as_micros(),
+            s.witness_time.as_micros(),
+            s.cyclefold_time.as_micros(),
+            s.total_time.as_micros()
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_step_stats() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+
+        let mut nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+        let num_steps = 5;
+        for _ in 0..num_steps {
+            nova.prove_step(()).unwrap();
+        }
+
+        let stats = nova.stats();
+        assert_eq!(stats.len(), num_steps);
+        for (i, s) in stats.iter().enumerate() {
+            assert_eq!(s.step, i);
+            assert_eq!(s.constraints, nova.r1cs.A.n_rows);
+            assert!(s.commit_time > Duration::ZERO);
+            assert!(s.fold_time > Duration::ZERO);
+            assert!(s.witness_time > Duration::ZERO);
+            // only the steps after the base case fold CycleFold instances
+            assert_eq!(s.cyclefold_time > Duration::ZERO, i > 0);
+            // the phases are disjoint parts of the step
+            assert!(
+                s.witness_time + s.fold_time + s.cyclefold_time + s.commit_time <= s.total_time
+            );
+            assert_eq!(
+                s.other_time() + s.witness_time + s.fold_time + s.cyclefold_time + s.commit_time,
+                s.total_time
+            );
+        }
+
+        let csv = to_csv(&stats);
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows.len(), num_steps + 1);
+        assert_eq!(
+            rows[0],
+            "step,constraints,commit_time_us,fold_time_us,witness_time_us,cyclefold_time_us,total_time_us"
+        );
+        assert!(rows[1].starts_with(&format!("0,{},", nova.r1cs.A.n_rows)));
+    }
+}
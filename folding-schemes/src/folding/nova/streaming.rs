@@ -0,0 +1,315 @@
+/// This file implements a streaming verifier for the Nova+CycleFold IVC, which reads the IVC
+/// proof (the committed instances and their witnesses) from a `Read` and verifies it
+/// incrementally, without holding the full proof in memory: the committed instances are checked
+/// first, and then each witness is read and checked against its R1CS row by row, keeping in
+/// memory only the `z` vector of the witness being checked.
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::{CurveGroup, Group};
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use std::io::{Read, Write};
+
+use super::{CommittedInstance, IVCBindings, IVCProofData, VerifierParams, VerifierState, Witness};
+use crate::ccs::r1cs::R1CS;
+use crate::Error;
+
+/// writes the IVC proof (running, incoming and CycleFold instances, together with their
+/// witnesses, and the Poseidon config digest of the proof data) in the layout expected by
+/// `verify_streaming`: first the digest, then the three committed instances, and then the three
+/// witnesses. The metadata and domain labels of the proof data are given to `verify_streaming`
+/// through its `bindings`.
+pub fn write_ivc_proof<C1: CurveGroup, C2: CurveGroup, W: Write>(
+    mut writer: W,
+    running_instance: &(CommittedInstance<C1>, Witness<C1>),
+    incoming_instance: &(CommittedInstance<C1>, Witness<C1>),
+    cyclefold_instance: &(CommittedInstance<C2>, Witness<C2>),
+    proof_data: &IVCProofData,
+) -> Result<(), Error> {
+    proof_data
+        .poseidon_config_digest
+        .serialize_compressed(&mut writer)?;
+    write_committed_instance(&mut writer, &running_instance.0)?;
+    write_committed_instance(&mut writer, &incoming_instance.0)?;
+    write_committed_instance(&mut writer, &cyclefold_instance.0)?;
+    write_witness(&mut writer, &running_instance.1)?;
+    write_witness(&mut writer, &incoming_instance.1)?;
+    write_witness(&mut writer, &cyclefold_instance.1)?;
+    Ok(())
+}
+
+/// Implements IVC.V of Nova+CycleFold reading the IVC proof from the given reader, which is
+/// expected to follow the layout of `write_ivc_proof`. It performs the same checks as
+/// `VerifierState::verify_self`, where `bindings` are the ones of the IVC (see
+/// `Nova::bindings`).
+pub fn verify_streaming<C1, C2, R: Read>(
+    vp: &VerifierParams<C1, C2>,
+    z_0: Vec<C1::ScalarField>, // initial state
+    z_i: Vec<C1::ScalarField>, // last state
+    num_steps: C1::ScalarField,
+    bindings: &IVCBindings<C1::ScalarField>,
+    mut reader: R,
+) -> Result<(), Error>
+where
+    C1: CurveGroup,
+    C2: CurveGroup,
+    <C1 as CurveGroup>::BaseField: PrimeField,
+    <C2 as CurveGroup>::BaseField: PrimeField + Absorb,
+    <C1 as Group>::ScalarField: Absorb,
+    <C2 as Group>::ScalarField: Absorb,
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+{
+    let poseidon_config_digest = <[u8; 32]>::deserialize_compressed(&mut reader)?;
+    let U_i = read_committed_instance::<C1, _>(&mut reader)?;
+    let u_i = read_committed_instance::<C1, _>(&mut reader)?;
+    let cf_U_i = read_committed_instance::<C2, _>(&mut reader)?;
+
+    for (_, check) in VerifierState::instance_checks(
+        vp,
+        poseidon_config_digest,
+        num_steps,
+        &z_0,
+        &z_i,
+        *bindings,
+        &U_i,
+        &u_i,
+        &cf_U_i,
+    ) {
+        check()?;
+    }
+
+    // check RelaxedR1CS satisfiability
+    check_relaxed_relation_streaming(&vp.r1cs, U_i.u, &U_i.x, false, &mut reader)?;
+    // check R1CS satisfiability, since u_i.u==1 this is the relaxed relation with E==0
+    check_relaxed_relation_streaming(&vp.r1cs, u_i.u, &u_i.x, true, &mut reader)?;
+    // check CycleFold RelaxedR1CS satisfiability
+    check_relaxed_relation_streaming(&vp.cf_r1cs, cf_U_i.u, &cf_U_i.x, false, &mut reader)?;
+
+    Ok(())
+}
+
+fn write_committed_instance<C: CurveGroup, W: Write>(
+    mut writer: W,
+    ci: &CommittedInstance<C>,
+) -> Result<(), Error> {
+    ci.cmE.serialize_compressed(&mut writer)?;
+    ci.u.serialize_compressed(&mut writer)?;
+    ci.cmW.serialize_compressed(&mut writer)?;
+    ci.x.serialize_compressed(&mut writer)?;
+    Ok(())
+}
+
+fn read_committed_instance<C: CurveGroup, R: Read>(
+    mut reader: R,
+) -> Result<CommittedInstance<C>, Error> {
+    Ok(CommittedInstance {
+        cmE: C::deserialize_compressed(&mut reader)?,
+        u: C::ScalarField::deserialize_compressed(&mut reader)?,
+        cmW: C::deserialize_compressed(&mut reader)?,
+        x: Vec::<C::ScalarField>::deserialize_compressed(&mut reader)?,
+    })
+}
+
+fn write_witness<C: CurveGroup, W: Write>(mut writer: W, w: &Witness<C>) -> Result<(), Error> {
+    w.W.serialize_compressed(&mut writer)?;
+    w.rW.serialize_compressed(&mut writer)?;
+    w.E.serialize_compressed(&mut writer)?;
+    w.rE.serialize_compressed(&mut writer)?;
+    Ok(())
+}
+
+/// reads a witness from the reader and checks that it satisfies the relaxed R1CS relation
+/// `Az ∘ Bz = u⋅Cz + E`, for `z = (u, x, W)`. `W` is read into `z`, while `E` is read and checked
+/// one element (row) at a time. When `unrelaxed` is set, `E` is also checked to be zero, so that
+/// the witness satisfies the plain R1CS relation.
+fn check_relaxed_relation_streaming<F: PrimeField, R: Read>(
+    r1cs: &R1CS<F>,
+    u: F,
+    x: &[F],
+    unrelaxed: bool,
+    mut reader: R,
+) -> Result<(), Error> {
+    let W_len = u64::deserialize_compressed(&mut reader)? as usize;
+    if 1 + x.len() + W_len != r1cs.A.n_cols {
+        return Err(Error::NotExpectedLength {
+            got: 1 + x.len() + W_len,
+            expected: r1cs.A.n_cols,
+        });
+    }
+    let mut z: Vec<F> = Vec::with_capacity(r1cs.A.n_cols);
+    z.push(u);
+    z.extend_from_slice(x);
+    for _ in 0..W_len {
+        z.push(F::deserialize_compressed(&mut reader)?);
+    }
+    let _rW = F::deserialize_compressed(&mut reader)?;
+
+    let E_len = u64::deserialize_compressed(&mut reader)? as usize;
+    if E_len != r1cs.A.n_rows {
+        return Err(Error::NotExpectedLength {
+            got: E_len,
+            expected: r1cs.A.n_rows,
+        });
+    }
+    for row in 0..r1cs.A.n_rows {
+        let E_row = F::deserialize_compressed(&mut reader)?;
+        if unrelaxed && !E_row.is_zero() {
+            return Err(Error::NotSatisfied);
+        }
+        let Az = row_dot(&r1cs.A.coeffs[row], &z);
+        let Bz = row_dot(&r1cs.B.coeffs[row], &z);
+        let Cz = row_dot(&r1cs.C.coeffs[row], &z);
+        if Az * Bz != u * Cz + E_row {
+            return Err(Error::NotSatisfied);
+        }
+    }
+    let _rE = F::deserialize_compressed(&mut reader)?;
+    Ok(())
+}
+
+fn row_dot<F: PrimeField>(row: &[(F, usize)], z: &[F]) -> F {
+    row.iter().map(|(coeff, col)| *coeff * z[*col]).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_std::One;
+    use std::io::Cursor;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::utils::vec::{hadamard, mat_vec_mul_sparse, vec_sub};
+    use crate::FoldingScheme;
+
+    /// BoundedReader wraps a reader, allowing to read at most `max_read` bytes at once
+    struct BoundedReader<R: Read> {
+        inner: R,
+        max_read: usize,
+    }
+    impl<R: Read> Read for BoundedReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            assert!(buf.len() <= self.max_read);
+            self.inner.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_verify_streaming() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+
+        let mut proof_bytes = Vec::new();
+        write_ivc_proof(
+            &mut proof_bytes,
+            &running_instance,
+            &incoming_instance,
+            &cyclefold_instance,
+            &proof_data,
+        )
+        .unwrap();
+
+        // the proof is read at most 32 bytes at a time (a single field element or point)
+        let reader = BoundedReader {
+            inner: Cursor::new(proof_bytes.clone()),
+            max_read: 32,
+        };
+        verify_streaming(
+            &verifier_params,
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            &nova.bindings().unwrap(),
+            reader,
+        )
+        .unwrap();
+
+        // a wrong state is rejected
+        assert!(verify_streaming(
+            &verifier_params,
+            z_0.clone(),
+            z_0.clone(),
+            nova.i,
+            &IVCBindings::default(),
+            Cursor::new(proof_bytes.clone()),
+        )
+        .is_err());
+
+        // a modified witness is rejected
+        let mut wrong_running_instance = running_instance.clone();
+        wrong_running_instance.1.W[0] += Fr::one();
+        let mut wrong_proof_bytes = Vec::new();
+        write_ivc_proof(
+            &mut wrong_proof_bytes,
+            &wrong_running_instance,
+            &incoming_instance,
+            &cyclefold_instance,
+            &proof_data,
+        )
+        .unwrap();
+        assert!(verify_streaming(
+            &verifier_params,
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            &IVCBindings::default(),
+            Cursor::new(wrong_proof_bytes),
+        )
+        .is_err());
+
+        // a modified incoming witness with an error term compensating it satisfies the relaxed
+        // relation, but not the R1CS relation of the incoming instance
+        let (u_i, mut w_i) = incoming_instance.clone();
+        w_i.W[0] += Fr::one();
+        let z = [vec![u_i.u], u_i.x.clone(), w_i.W.clone()].concat();
+        let r1cs = &verifier_params.r1cs;
+        w_i.E = vec_sub(
+            &hadamard(
+                &mat_vec_mul_sparse(&r1cs.A, &z).unwrap(),
+                &mat_vec_mul_sparse(&r1cs.B, &z).unwrap(),
+            )
+            .unwrap(),
+            &mat_vec_mul_sparse(&r1cs.C, &z).unwrap(),
+        )
+        .unwrap();
+        let mut w_i_bytes = Vec::new();
+        write_witness(&mut w_i_bytes, &w_i).unwrap();
+        check_relaxed_relation_streaming(r1cs, u_i.u, &u_i.x, false, Cursor::new(&w_i_bytes))
+            .unwrap();
+        assert!(matches!(
+            check_relaxed_relation_streaming(r1cs, u_i.u, &u_i.x, true, Cursor::new(&w_i_bytes)),
+            Err(Error::NotSatisfied)
+        ));
+
+        let mut wrong_proof_bytes = Vec::new();
+        write_ivc_proof(
+            &mut wrong_proof_bytes,
+            &running_instance,
+            &(u_i, w_i),
+            &cyclefold_instance,
+            &proof_data,
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_streaming(
+                &verifier_params,
+                z_0,
+                nova.z_i,
+                nova.i,
+                &IVCBindings::default(),
+                Cursor::new(wrong_proof_bytes),
+            ),
+            Err(Error::NotSatisfied)
+        ));
+    }
+}
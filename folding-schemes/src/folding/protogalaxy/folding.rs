@@ -54,24 +54,24 @@ where
         Error,
     > {
         if vec_instances.len() != vec_w.len() {
-            return Err(Error::NotSameLength(
-                "vec_instances.len()".to_string(),
-                vec_instances.len(),
-                "vec_w.len()".to_string(),
-                vec_w.len(),
-            ));
+            return Err(Error::NotSameLength {
+                a: "vec_instances.len()",
+                a_len: vec_instances.len(),
+                b: "vec_w.len()",
+                b_len: vec_w.len(),
+            });
         }
         let d = 2; // for the moment hardcoded to 2 since it only supports R1CS
         let k = vec_instances.len();
         let t = instance.betas.len();
         let n = r1cs.A.n_cols;
         if w.w.len() != n {
-            return Err(Error::NotSameLength(
-                "w.w.len()".to_string(),
-                w.w.len(),
-                "n".to_string(),
-                n,
-            ));
+            return Err(Error::NotSameLength {
+                a: "w.w.len()",
+                a_len: w.w.len(),
+                b: "n",
+                b_len: n,
+            });
         }
         if log2(n) as usize != t {
             return Err(Error::NotEqual);
@@ -124,12 +124,12 @@ where
                     .iter()
                     .map(|wj| {
                         if wj.w.len() != n {
-                            return Err(Error::NotSameLength(
-                                "wj.w.len()".to_string(),
-                                wj.w.len(),
-                                "n".to_string(),
-                                n,
-                            ));
+                            return Err(Error::NotSameLength {
+                                a: "wj.w.len()",
+                                a_len: wj.w.len(),
+                                b: "n",
+                                b_len: n,
+                            });
                         }
                         Ok(wj.w.clone())
                     })
@@ -393,12 +393,12 @@ mod tests {
         w: &Witness<C::ScalarField>,
     ) -> Result<(), Error> {
         if instance.betas.len() != log2(w.w.len()) as usize {
-            return Err(Error::NotSameLength(
-                "instance.betas.len()".to_string(),
-                instance.betas.len(),
-                "log2(w.w.len())".to_string(),
-                log2(w.w.len()) as usize,
-            ));
+            return Err(Error::NotSameLength {
+                a: "instance.betas.len()",
+                a_len: instance.betas.len(),
+                b: "log2(w.w.len())",
+                b_len: log2(w.w.len()) as usize,
+            });
         }
 
         let f_w = eval_f(r1cs, &w.w)?; // f(w)
@@ -0,0 +1,165 @@
+/// Implements a builder for the IVC verification, which allows to provide the public inputs of
+/// the verification (`z_0`, `z_i` and the number of steps) one element at a time, in any order.
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+
+use crate::frontend::FCircuit;
+use crate::Error;
+use crate::FoldingScheme;
+
+/// VerificationBuilder collects the public inputs of the IVC verification as they become
+/// available (eg. when each element is received from a different source), and once all of them
+/// have been provided it runs the `FoldingScheme::verify` method.
+#[derive(Debug, Clone)]
+pub struct VerificationBuilder<C1: CurveGroup> {
+    z_0: Vec<Option<C1::ScalarField>>,
+    z_i: Vec<Option<C1::ScalarField>>,
+    num_steps: Option<C1::ScalarField>,
+}
+
+impl<C1: CurveGroup> VerificationBuilder<C1> {
+    /// returns a new VerificationBuilder for a state of `state_len` elements
+    pub fn new(state_len: usize) -> Self {
+        Self {
+            z_0: vec![None; state_len],
+            z_i: vec![None; state_len],
+            num_steps: None,
+        }
+    }
+
+    /// sets the `index`-th element of the initial state `z_0`
+    pub fn set_z_0(&mut self, index: usize, value: C1::ScalarField) -> Result<(), Error> {
+        let elem = self.z_0.get_mut(index).ok_or(Error::OutOfBounds)?;
+        *elem = Some(value);
+        Ok(())
+    }
+
+    /// sets the `index`-th element of the last state `z_i`
+    pub fn set_z_i(&mut self, index: usize, value: C1::ScalarField) -> Result<(), Error> {
+        let elem = self.z_i.get_mut(index).ok_or(Error::OutOfBounds)?;
+        *elem = Some(value);
+        Ok(())
+    }
+
+    /// sets the number of steps between the initial state and the last state
+    pub fn set_num_steps(&mut self, num_steps: C1::ScalarField) {
+        self.num_steps = Some(num_steps);
+    }
+
+    /// returns true if all the public inputs have been provided
+    pub fn is_complete(&self) -> bool {
+        self.num_steps.is_some()
+            && self.z_0.iter().all(Option::is_some)
+            && self.z_i.iter().all(Option::is_some)
+    }
+
+    /// returns the public inputs `(z_0, z_i, num_steps)`, or `Error::MissingPublicInput` pointing
+    /// to the first element that has not been provided yet.
+    #[allow(clippy::type_complexity)]
+    pub fn finalize(
+        self,
+    ) -> Result<(Vec<C1::ScalarField>, Vec<C1::ScalarField>, C1::ScalarField), Error> {
+        let z_0 = collect_inputs("z_0", self.z_0)?;
+        let z_i = collect_inputs("z_i", self.z_i)?;
+        let num_steps = self.num_steps.ok_or(Error::MissingPublicInput {
+            name: "num_steps",
+            index: 0,
+        })?;
+        Ok((z_0, z_i, num_steps))
+    }
+
+    /// finalizes the builder and verifies the given instances and proof data with the collected
+    /// public inputs
+    pub fn verify<C2, FC, FS>(
+        self,
+        vp: FS::VerifierParam,
+        running_instance: FS::CommittedInstanceWithWitness,
+        incoming_instance: FS::CommittedInstanceWithWitness,
+        cyclefold_instance: FS::CFCommittedInstanceWithWitness,
+        proof_data: FS::ProofData,
+    ) -> Result<(), Error>
+    where
+        C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+        C2: CurveGroup,
+        C2::BaseField: PrimeField,
+        FC: FCircuit<C1::ScalarField>,
+        FS: FoldingScheme<C1, C2, FC>,
+    {
+        let (z_0, z_i, num_steps) = self.finalize()?;
+        FS::verify(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+    }
+}
+
+fn collect_inputs<F: PrimeField>(
+    name: &'static str,
+    inputs: Vec<Option<F>>,
+) -> Result<Vec<F>, Error> {
+    inputs
+        .into_iter()
+        .enumerate()
+        .map(|(i, v)| v.ok_or(Error::MissingPublicInput { name, index: i }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective as Projective};
+    use ark_grumpkin::Projective as Projective2;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+
+    type NOVA = TestNova<CubicFCircuit<Fr>>;
+
+    #[test]
+    fn test_verification_builder() {
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+
+        // provide the public inputs out of order
+        let mut builder = VerificationBuilder::<Projective>::new(F_circuit.state_len());
+        builder.set_num_steps(nova.i);
+        builder.set_z_i(0, nova.z_i[0]).unwrap();
+        assert!(!builder.is_complete());
+        assert!(builder.set_z_0(1, z_0[0]).is_err());
+
+        // finalizing before all the inputs have been provided must fail
+        match builder.clone().finalize() {
+            Err(Error::MissingPublicInput { name, index }) => {
+                assert_eq!(name, "z_0");
+                assert_eq!(index, 0);
+            }
+            _ => panic!("expected Error::MissingPublicInput"),
+        }
+
+        builder.set_z_0(0, z_0[0]).unwrap();
+        assert!(builder.is_complete());
+        builder
+            .verify::<Projective2, CubicFCircuit<Fr>, NOVA>(
+                verifier_params,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+            )
+            .unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+/// Implements the VerificationReport, a structured record of the checks performed by a
+/// verification, with the result and the time spent in each one of them.
+use std::time::{Duration, Instant};
+
+use crate::Error;
+
+/// VerificationCheck contains the result of a single verification check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationCheck {
+    pub name: String,
+    pub passed: bool,
+    pub duration: Duration,
+    /// error returned by the check, if it did not pass
+    pub error: Option<String>,
+}
+
+/// VerificationReport contains the list of checks performed by a verification in the order in
+/// which they were performed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub checks: Vec<VerificationCheck>,
+}
+
+impl VerificationReport {
+    pub fn new() -> Self {
+        Self { checks: vec![] }
+    }
+
+    /// runs the given check, recording its result and duration into the report. Returns true if
+    /// the check passed.
+    pub fn check(&mut self, name: &str, f: impl FnOnce() -> Result<(), Error>) -> bool {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        let passed = result.is_ok();
+        self.checks.push(VerificationCheck {
+            name: name.to_string(),
+            passed,
+            duration,
+            error: result.err().map(|e| e.to_string()),
+        });
+        passed
+    }
+
+    /// returns true if the report contains checks and all of them passed
+    pub fn passed(&self) -> bool {
+        !self.checks.is_empty() && self.checks.iter().all(|c| c.passed)
+    }
+
+    /// returns the total time spent in the checks of the report
+    pub fn duration(&self) -> Duration {
+        self.checks.iter().map(|c| c.duration).sum()
+    }
+
+    /// returns the report serialized as JSON, with the durations in microseconds
+    pub fn to_json(&self) -> String {
+        let checks = self
+            .checks
+            .iter()
+            .map(|c| {
+                format!(
+                    "{{\"name\":\"{}\",\"passed\":{},\"duration_us\":{},\"error\":{}}}",
+                    escape_json(&c.name),
+                    c.passed,
+                    c.duration.as_micros(),
+                    c.error
+                        .as_ref()
+                        .map(|e| format!("\"{}\"", escape_json(e)))
+                        .unwrap_or("null".to_string()),
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        format!(
+            "{{\"passed\":{},\"duration_us\":{},\"checks\":[{}]}}",
+            self.passed(),
+            self.duration().as_micros(),
+            checks
+        )
+    }
+}
+
+fn escape_json(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_nova_verify_report() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+
+        let report = NOVA::verify_report(
+            verifier_params.clone(),
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance.clone(),
+            incoming_instance.clone(),
+            cyclefold_instance.clone(),
+            proof_data.clone(),
+        );
+        assert!(report.passed());
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "poseidon_config",
+                "instances_io_len",
+                "running_instance_hash",
+                "cyclefold_instance_hash",
+                "incoming_instance_unrelaxed",
+                "incoming_instance_r1cs",
+                "running_instance_relaxed_r1cs",
+                "cyclefold_instance_relaxed_r1cs",
+            ]
+        );
+        assert!(report.to_json().starts_with("{\"passed\":true,"));
+
+        // with a wrong state, only the hash check fails
+        let report = NOVA::verify_report(
+            verifier_params,
+            z_0.clone(),
+            z_0,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        );
+        assert!(!report.passed());
+        let failed: Vec<&str> = report
+            .checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(failed, vec!["running_instance_hash"]);
+        assert!(report
+            .to_json()
+            .contains("\"error\":\"IVC verification failed\""));
+    }
+}
@@ -0,0 +1,187 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// BeaconFCircuit wraps an FCircuit to run it in beacon mode (see `FCircuit::beacon_mode`),
+/// where the folding challenge of each step is derived from an external beacon value (eg. from a
+/// VDF or a public randomness beacon) in addition to the Fiat-Shamir transcript.
+///
+/// The beacon value of each step is given as the first external input, before the external
+/// inputs of the inner FCircuit, and it is accumulated into the state through a Poseidon hash
+/// chain: `acc_{i+1} = H(acc_i, beacon_i)`. Since the in-circuit challenge absorbs the same
+/// value, a valid IVC proof for `z_i` binds the challenges to the beacon values, which are
+/// recorded in `Nova::beacons` and can be checked with `verify_beacons`. The accumulator is the
+/// last element of the state, and it should be set to 0 in `z_0`.
+#[derive(Clone, Debug)]
+pub struct BeaconFCircuit<F: PrimeField, FC> {
+    pub inner: FC,
+    pub poseidon_config: PoseidonConfig<F>,
+}
+
+/// returns the accumulator resulting of appending the `beacon` value to the accumulator `acc`
+pub fn accumulate_beacon<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    acc: F,
+    beacon: F,
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(poseidon_config, vec![acc, beacon]).map_err(|e| Error::CRHFail {
+        reason: e.to_string(),
+    })
+}
+
+impl<F: PrimeField + Absorb, FC: FCircuit<F>> BeaconFCircuit<F, FC> {
+    /// checks that the beacon values used in the steps from `z_0` to `z_i` are the given
+    /// `beacons`, by recomputing the accumulator of the state. Returns `Error::BeaconMismatch`
+    /// otherwise.
+    pub fn verify_beacons(&self, beacons: &[F], z_0: &[F], z_i: &[F]) -> Result<(), Error> {
+        let acc_0 = *z_0.last().ok_or(Error::Empty)?;
+        let acc_i = *z_i.last().ok_or(Error::Empty)?;
+        let acc = beacons.iter().try_fold(acc_0, |acc, beacon| {
+            accumulate_beacon(&self.poseidon_config, acc, *beacon)
+        })?;
+        if acc != acc_i {
+            return Err(Error::BeaconMismatch);
+        }
+        Ok(())
+    }
+}
+
+impl<F: PrimeField + Absorb, FC: FCircuit<F>> FCircuit<F> for BeaconFCircuit<F, FC> {
+    /// parameters of the inner FCircuit, and the Poseidon config used for the accumulator
+    type Params = (FC::Params, PoseidonConfig<F>);
+    /// the beacon value followed by the field elements of the inner external inputs
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (inner_params, poseidon_config) = params;
+        Ok(Self {
+            inner: FC::new(inner_params)?,
+            poseidon_config,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len() + 1
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.inner.external_inputs_len() + 1
+    }
+    fn beacon_mode(&self) -> bool {
+        true
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let (acc, z_i) = z_i.split_last().ok_or(Error::Empty)?;
+        let (beacon, inner_inputs) = external_inputs.split_first().ok_or(Error::Empty)?;
+        let inner_inputs = FC::ExternalInputs::from_field_elements(inner_inputs.to_vec())?;
+        let mut z_i1 = self.inner.step_native(i, z_i.to_vec(), inner_inputs)?;
+        z_i1.push(accumulate_beacon(&self.poseidon_config, *acc, *beacon)?);
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let (acc, z_i) = z_i.split_last().ok_or(SynthesisError::Unsatisfiable)?;
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (beacon, inner_inputs) = external_inputs
+            .split_first()
+            .ok_or(SynthesisError::Unsatisfiable)?;
+        let crh_params =
+            CRHParametersVar::<F>::new_constant(cs.clone(), self.poseidon_config.clone())?;
+        let acc_i1 = CRHGadget::<F>::evaluate(&crh_params, &[acc.clone(), beacon.clone()])?;
+        let inner_inputs = FC::ExternalInputs::var_from_field_elements(inner_inputs.to_vec())?;
+        let mut z_i1 = self
+            .inner
+            .generate_step_constraints(cs, i, z_i.to_vec(), inner_inputs)?;
+        z_i1.push(acc_i1);
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::Zero;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    type FC = BeaconFCircuit<Fr, CubicFCircuit<Fr>>;
+    type NOVA = TestNova<FC>;
+
+    #[test]
+    fn test_beacon_fcircuit() {
+        let F_circuit = FC::new(((), poseidon_test_config::<Fr>())).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32), Fr::zero()];
+        let beacons: Vec<Fr> = [11_u32, 22, 33].into_iter().map(Fr::from).collect();
+        let prove = |beacons: &[Fr]| {
+            let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+            for beacon in beacons {
+                nova.prove_step(vec![*beacon]).unwrap();
+            }
+            nova
+        };
+
+        // the same beacons yield the same proof
+        let nova = prove(&beacons);
+        let nova2 = prove(&beacons);
+        assert_eq!(nova.beacons, beacons);
+        assert_eq!(nova.z_i, nova2.z_i);
+        assert_eq!(nova.U_i, nova2.U_i);
+        assert_eq!(nova.u_i, nova2.u_i);
+        assert_eq!(nova.cf_U_i, nova2.cf_U_i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+        F_circuit
+            .verify_beacons(&nova.beacons, &z_0, &nova.z_i)
+            .unwrap();
+
+        // a different beacon changes the folding challenge, and so the folded instance
+        let other = prove(&[beacons[0], beacons[1], Fr::from(44_u32)]);
+        assert_ne!(other.U_i, nova.U_i);
+        assert!(matches!(
+            F_circuit.verify_beacons(&other.beacons, &z_0, &nova.z_i),
+            Err(Error::BeaconMismatch)
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::{fp::FpVar, FieldVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use core::cmp::Ordering;
+
+use super::{ExternalInputsVar, FCircuit};
+use crate::Error;
+
+/// BoundedFCircuit wraps an FCircuit and caps the number of steps that can be folded. It extends
+/// the state of the inner FCircuit with an extra element, the step counter, which is enforced
+/// in-circuit to be smaller than `max_steps` at each step and then incremented. Since the state
+/// is bound to the folded instances, a valid IVC proof guarantees that no more than `max_steps`
+/// steps were computed, independently of the `i` value that is given to the verifier.
+///
+/// The step counter is the last element of the state, and it should be set to 0 in `z_0`.
+#[derive(Clone, Copy, Debug)]
+pub struct BoundedFCircuit<FC> {
+    pub inner: FC,
+    pub max_steps: usize,
+}
+
+impl<F: PrimeField, FC: FCircuit<F>> FCircuit<F> for BoundedFCircuit<FC> {
+    /// parameters of the inner FCircuit, and the maximum number of steps
+    type Params = (FC::Params, usize);
+    type ExternalInputs = FC::ExternalInputs;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (inner_params, max_steps) = params;
+        Ok(Self {
+            inner: FC::new(inner_params)?,
+            max_steps,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len() + 1
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.inner.external_inputs_len()
+    }
+    // the external inputs are the ones of the inner FCircuit, so its beacon is kept
+    fn beacon_mode(&self) -> bool {
+        self.inner.beacon_mode()
+    }
+    // the IO of the step is the one of the inner FCircuit, computed over its part of the state
+    fn io_len(&self) -> usize {
+        self.inner.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Self::ExternalInputs,
+    ) -> Result<Vec<F>, Error> {
+        let (_, z_i) = z_i.split_last().ok_or(Error::Empty)?;
+        self.inner.step_io_native(i, z_i.to_vec(), external_inputs)
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: ExternalInputsVar<F, Self>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let (_, z_i) = z_i.split_last().ok_or(SynthesisError::Unsatisfiable)?;
+        self.inner
+            .generate_step_io_constraints(cs, i, z_i.to_vec(), external_inputs)
+    }
+    fn step_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Self::ExternalInputs,
+    ) -> Result<Vec<F>, Error> {
+        let (counter, z_i) = z_i.split_last().ok_or(Error::Empty)?;
+        if counter.into_bigint() >= F::BigInt::from(self.max_steps as u64) {
+            return Err(Error::StepBoundReached {
+                max_steps: self.max_steps,
+            });
+        }
+        let mut z_i1 = self.inner.step_native(i, z_i.to_vec(), external_inputs)?;
+        z_i1.push(*counter + F::one());
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: ExternalInputsVar<F, Self>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let (counter, z_i) = z_i.split_last().ok_or(SynthesisError::Unsatisfiable)?;
+        // enforce counter < max_steps
+        counter.enforce_cmp(
+            &FpVar::constant(F::from(self.max_steps as u64)),
+            Ordering::Less,
+            false,
+        )?;
+        let mut z_i1 =
+            self.inner
+                .generate_step_constraints(cs, i, z_i.to_vec(), external_inputs)?;
+        z_i1.push(counter + FpVar::one());
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_bounded_fcircuit_constraints() {
+        let F_circuit = <BoundedFCircuit<CubicFCircuit<Fr>> as FCircuit<Fr>>::new(((), 2)).unwrap();
+
+        // counter below the bound
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i = vec![Fr::from(3_u32), Fr::from(1_u32)];
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 1, z_iVar, ())
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            z_i1Var.value().unwrap(),
+            F_circuit.step_native(1, z_i, ()).unwrap()
+        );
+
+        // counter at the bound
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i = vec![Fr::from(3_u32), Fr::from(2_u32)];
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        F_circuit
+            .generate_step_constraints(cs.clone(), 2, z_iVar, ())
+            .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+        assert!(F_circuit.step_native(2, z_i, ()).is_err());
+    }
+
+    #[test]
+    fn test_bounded_fcircuit_ivc() {
+        type FC = BoundedFCircuit<CubicFCircuit<Fr>>;
+        type NOVA = TestNova<FC>;
+
+        let max_steps = 3;
+        let F_circuit = <FC as FCircuit<Fr>>::new(((), max_steps)).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32), Fr::from(0_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..max_steps {
+            nova.prove_step(()).unwrap();
+        }
+        assert_eq!(nova.z_i[1], Fr::from(max_steps as u32));
+
+        // the step after reaching the bound can not be proven
+        assert!(nova.prove_step(()).is_err());
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use core::cmp::Ordering;
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// BoundedInputFCircuit wraps an FCircuit and enforces in-circuit, at each step, that every
+/// external input `x` of the step lies within the bounds `lo <= x <= hi`. It extends the state
+/// of the inner FCircuit with the two bounds, which are carried unchanged from step to step, so
+/// that a valid IVC proof guarantees that all the folded inputs were within the bounds set in
+/// `z_0`.
+///
+/// The bounds are the last two elements of the state, `[.., lo, hi]`. The comparisons are done
+/// over the integers, so the bounds and the inputs must be at most `(p-1)/2`.
+#[derive(Clone, Copy, Debug)]
+pub struct BoundedInputFCircuit<FC> {
+    pub inner: FC,
+}
+
+impl<F: PrimeField, FC: FCircuit<F>> FCircuit<F> for BoundedInputFCircuit<FC> {
+    /// parameters of the inner FCircuit
+    type Params = FC::Params;
+    /// the field elements of the inner external inputs, which are checked against the bounds
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        Ok(Self {
+            inner: FC::new(params)?,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len() + 2
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.inner.external_inputs_len()
+    }
+    // the external inputs are the ones of the inner FCircuit, so its beacon is kept
+    fn beacon_mode(&self) -> bool {
+        self.inner.beacon_mode()
+    }
+    // the IO of the step is the one of the inner FCircuit, computed over its part of the state
+    fn io_len(&self) -> usize {
+        self.inner.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if z_i.len() < 2 {
+            return Err(Error::Empty);
+        }
+        let (z_i, _) = z_i.split_at(z_i.len() - 2);
+        self.inner.step_io_native(
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::from_field_elements(external_inputs)?,
+        )
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() < 2 {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (z_i, _) = z_i.split_at(z_i.len() - 2);
+        self.inner.generate_step_io_constraints(
+            cs,
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::var_from_field_elements(external_inputs)?,
+        )
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if z_i.len() < 2 {
+            return Err(Error::Empty);
+        }
+        let (z_i, bounds) = z_i.split_at(z_i.len() - 2);
+        let (lo, hi) = (bounds[0].into_bigint(), bounds[1].into_bigint());
+        let max = F::MODULUS_MINUS_ONE_DIV_TWO;
+        if lo > max || hi > max {
+            return Err(Error::OutOfBounds);
+        }
+        for (j, x) in external_inputs.iter().enumerate() {
+            let x = x.into_bigint();
+            if x < lo || x > hi {
+                return Err(Error::ExternalInputOutOfBounds { index: j });
+            }
+        }
+        let mut z_i1 = self.inner.step_native(
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::from_field_elements(external_inputs)?,
+        )?;
+        z_i1.extend_from_slice(bounds);
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() < 2 {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (z_i, bounds) = z_i.split_at(z_i.len() - 2);
+        // enforce lo <= x <= hi, which also enforces that x, lo and hi are at most (p-1)/2
+        for x in external_inputs.iter() {
+            x.enforce_cmp(&bounds[0], Ordering::Greater, true)?;
+            x.enforce_cmp(&bounds[1], Ordering::Less, true)?;
+        }
+        let mut z_i1 = self.inner.generate_step_constraints(
+            cs,
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::var_from_field_elements(external_inputs)?,
+        )?;
+        z_i1.extend_from_slice(bounds);
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+    use core::marker::PhantomData;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::FoldingScheme;
+
+    /// SumFCircuit adds the external input to its state, `z_{i+1} = z_i + w_i`
+    #[derive(Clone, Copy, Debug)]
+    struct SumFCircuit<F: PrimeField> {
+        _f: PhantomData<F>,
+    }
+    impl<F: PrimeField> FCircuit<F> for SumFCircuit<F> {
+        type Params = ();
+        type ExternalInputs = Vec<F>;
+
+        fn new(_params: Self::Params) -> Result<Self, Error> {
+            Ok(Self { _f: PhantomData })
+        }
+        fn state_len(&self) -> usize {
+            1
+        }
+        fn external_inputs_len(&self) -> usize {
+            1
+        }
+        fn step_native(
+            &self,
+            _i: usize,
+            z_i: Vec<F>,
+            external_inputs: Vec<F>,
+        ) -> Result<Vec<F>, Error> {
+            Ok(vec![z_i[0] + external_inputs[0]])
+        }
+        fn generate_step_constraints(
+            &self,
+            _cs: ConstraintSystemRef<F>,
+            _i: usize,
+            z_i: Vec<FpVar<F>>,
+            external_inputs: Vec<FpVar<F>>,
+        ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+            Ok(vec![&z_i[0] + &external_inputs[0]])
+        }
+    }
+
+    type FC = BoundedInputFCircuit<SumFCircuit<Fr>>;
+
+    // returns whether the constraints of a step with the given input are satisfied
+    fn is_satisfied(F_circuit: &FC, z_i: &[Fr], input: Fr) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.to_vec())).unwrap();
+        let inputsVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(vec![input])).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, inputsVar)
+            .unwrap();
+        if let Ok(z_i1) = F_circuit.step_native(0, z_i.to_vec(), vec![input]) {
+            assert_eq!(z_i1Var.value().unwrap(), z_i1);
+        }
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn test_bounded_input_fcircuit_constraints() {
+        let F_circuit = <FC as FCircuit<Fr>>::new(()).unwrap();
+        let z_i = vec![Fr::from(0_u32), Fr::from(10_u32), Fr::from(20_u32)];
+
+        // inputs within the bounds, including the bounds themselves
+        for x in [10_u32, 15, 20] {
+            assert!(is_satisfied(&F_circuit, &z_i, Fr::from(x)));
+            assert!(F_circuit
+                .step_native(0, z_i.clone(), vec![Fr::from(x)])
+                .is_ok());
+        }
+        // inputs out of the bounds, including a "negative" one
+        for x in [Fr::from(9_u32), Fr::from(21_u32), -Fr::from(15_u32)] {
+            assert!(!is_satisfied(&F_circuit, &z_i, x));
+            assert!(matches!(
+                F_circuit.step_native(0, z_i.clone(), vec![x]),
+                Err(Error::ExternalInputOutOfBounds { index: 0 })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_bounded_input_fcircuit_ivc() {
+        type NOVA = TestNova<FC>;
+
+        let F_circuit = <FC as FCircuit<Fr>>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(0_u32), Fr::from(10_u32), Fr::from(20_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for x in [10_u32, 13, 20] {
+            nova.prove_step(vec![Fr::from(x)]).unwrap();
+        }
+        assert_eq!(nova.z_i, vec![Fr::from(43_u32), z_0[1], z_0[2]]);
+
+        // an input out of the bounds can not be proven
+        assert!(nova.prove_step(vec![Fr::from(21_u32)]).is_err());
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
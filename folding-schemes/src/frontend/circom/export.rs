@@ -0,0 +1,146 @@
+/// Exports R1CS structures into the iden3 binary `.r1cs` format used by circom and its tooling
+/// (snarkjs, circom-compat, etc), following
+/// https://github.com/iden3/r1csfile/blob/master/doc/r1cs_bin_format.md
+use ark_ff::{BigInteger, PrimeField};
+use std::io::Write;
+
+use crate::ccs::r1cs::R1CS;
+use crate::Error;
+
+pub const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+pub const R1CS_VERSION: u32 = 1;
+
+const SECTION_HEADER: u32 = 1;
+const SECTION_CONSTRAINTS: u32 = 2;
+const SECTION_WIRE2LABEL: u32 = 3;
+
+/// writes the given R1CS in the circom `.r1cs` binary format. The wires layout is the same than
+/// the one used by arkworks (and circom): `z = (1, x, w)`, where the public inputs `x` are
+/// exported as circom public inputs, and the rest of the wires as internal (non-input) wires.
+pub fn write_r1cs<F: PrimeField, W: Write>(r1cs: &R1CS<F>, mut writer: W) -> Result<(), Error> {
+    let n_wires = r1cs.A.n_cols;
+    let n_constraints = r1cs.A.n_rows;
+    let prime = F::MODULUS.to_bytes_le();
+
+    let mut header: Vec<u8> = Vec::new();
+    header.extend((prime.len() as u32).to_le_bytes());
+    header.extend(&prime);
+    header.extend((n_wires as u32).to_le_bytes());
+    header.extend(0_u32.to_le_bytes()); // public outputs
+    header.extend((r1cs.l as u32).to_le_bytes()); // public inputs
+    header.extend(0_u32.to_le_bytes()); // private inputs
+    header.extend((n_wires as u64).to_le_bytes()); // labels
+    header.extend((n_constraints as u32).to_le_bytes());
+
+    let mut constraints: Vec<u8> = Vec::new();
+    for i in 0..n_constraints {
+        for m in [&r1cs.A, &r1cs.B, &r1cs.C] {
+            let row = &m.coeffs[i];
+            constraints.extend((row.len() as u32).to_le_bytes());
+            for (coeff, col) in row {
+                constraints.extend((*col as u32).to_le_bytes());
+                constraints.extend(coeff.into_bigint().to_bytes_le());
+            }
+        }
+    }
+
+    // each wire is mapped to the label with its same index
+    let wire2label: Vec<u8> = (0..n_wires as u64).flat_map(|i| i.to_le_bytes()).collect();
+
+    writer.write_all(R1CS_MAGIC)?;
+    writer.write_all(&R1CS_VERSION.to_le_bytes())?;
+    writer.write_all(&3_u32.to_le_bytes())?; // number of sections
+    for (section_type, section) in [
+        (SECTION_HEADER, header),
+        (SECTION_CONSTRAINTS, constraints),
+        (SECTION_WIRE2LABEL, wire2label),
+    ] {
+        writer.write_all(&section_type.to_le_bytes())?;
+        writer.write_all(&(section.len() as u64).to_le_bytes())?;
+        writer.write_all(&section)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// returns a JSON describing the wires layout of the given R1CS, to be used by the witness
+/// calculator. The ranges are given as `[start, end)` wire indexes.
+pub fn wires_layout_json<F: PrimeField>(r1cs: &R1CS<F>) -> String {
+    format!(
+        "{{\"n_wires\":{},\"n_constraints\":{},\"one\":0,\"public_inputs\":[1,{}],\"witness\":[{},{}]}}",
+        r1cs.A.n_cols,
+        r1cs.A.n_rows,
+        r1cs.l + 1,
+        r1cs.l + 1,
+        r1cs.A.n_cols,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_circom::circom::r1cs_reader;
+    use std::fs;
+    use std::io::BufReader;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::{tests::CubicFCircuit, FCircuit};
+    use crate::FoldingScheme;
+
+    fn read_u32(bytes: &[u8], pos: &mut usize) -> u32 {
+        let v = u32::from_le_bytes(bytes[*pos..*pos + 4].try_into().unwrap());
+        *pos += 4;
+        v
+    }
+    fn read_u64(bytes: &[u8], pos: &mut usize) -> u64 {
+        let v = u64::from_le_bytes(bytes[*pos..*pos + 8].try_into().unwrap());
+        *pos += 8;
+        v
+    }
+
+    #[test]
+    fn test_export_circom() {
+        type NOVA = TestNova<CubicFCircuit<Fr>>;
+        let F_circuit = CubicFCircuit::<Fr>::new(()).unwrap();
+        let (prover_params, _) = test_nova_params(F_circuit);
+        let nova = NOVA::init(&prover_params, F_circuit, vec![Fr::from(3_u32)]).unwrap();
+
+        let dir = std::env::temp_dir().join("sonobe_test_export_circom");
+        nova.export_circom(&dir).unwrap();
+
+        // parse the header of the exported .r1cs
+        let bytes = fs::read(dir.join("augmented_circuit.r1cs")).unwrap();
+        assert_eq!(&bytes[..4], R1CS_MAGIC);
+        let mut pos = 4;
+        assert_eq!(read_u32(&bytes, &mut pos), R1CS_VERSION);
+        assert_eq!(read_u32(&bytes, &mut pos), 3); // n_sections
+        assert_eq!(read_u32(&bytes, &mut pos), SECTION_HEADER);
+        let _header_len = read_u64(&bytes, &mut pos);
+        let n8 = read_u32(&bytes, &mut pos) as usize;
+        assert_eq!(n8, 32);
+        assert_eq!(
+            bytes[pos..pos + n8].to_vec(),
+            <Fr as PrimeField>::MODULUS.to_bytes_le()
+        );
+        pos += n8;
+        assert_eq!(read_u32(&bytes, &mut pos) as usize, nova.r1cs.A.n_cols); // n_wires
+        assert_eq!(read_u32(&bytes, &mut pos), 0); // n_pub_out
+        assert_eq!(read_u32(&bytes, &mut pos) as usize, nova.r1cs.l); // n_pub_in
+        assert_eq!(read_u32(&bytes, &mut pos), 0); // n_prv_in
+        assert_eq!(read_u64(&bytes, &mut pos) as usize, nova.r1cs.A.n_cols); // n_labels
+        assert_eq!(read_u32(&bytes, &mut pos) as usize, nova.r1cs.A.n_rows); // n_constraints
+
+        // the file can be read back by the circom r1cs reader
+        let reader = BufReader::new(fs::File::open(dir.join("augmented_circuit.r1cs")).unwrap());
+        let r1cs_file = r1cs_reader::R1CSFile::<Fr>::new(reader).unwrap();
+        let circom_r1cs = r1cs_reader::R1CS::<Fr>::from(r1cs_file);
+        assert_eq!(circom_r1cs.constraints.len(), nova.r1cs.A.n_rows);
+        assert_eq!(circom_r1cs.num_variables, nova.r1cs.A.n_cols);
+
+        let layout = fs::read_to_string(dir.join("augmented_circuit_wires.json")).unwrap();
+        assert_eq!(layout, wires_layout_json(&nova.r1cs));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -11,6 +11,7 @@ use std::path::PathBuf;
 use crate::frontend::FCircuit;
 use crate::Error;
 
+pub mod export;
 pub mod utils;
 use utils::CircomWrapper;
 
@@ -26,6 +27,8 @@ pub struct CircomFCircuit<F: PrimeField> {
 impl<F: PrimeField> FCircuit<F> for CircomFCircuit<F> {
     /// (r1cs_path, wasm_path, state_len, external_inputs_len)
     type Params = (PathBuf, PathBuf, usize, usize);
+    /// the `external_inputs` signal of the circom circuit
+    type ExternalInputs = Vec<F>;
 
     fn new(params: Self::Params) -> Result<Self, Error> {
         let (r1cs_path, wasm_path, state_len, external_inputs_len) = params;
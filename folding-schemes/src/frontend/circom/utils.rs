@@ -61,8 +61,9 @@ impl<F: PrimeField> CircomWrapper<F> {
             .map(|big_int| {
                 self.num_bigint_to_ark_bigint(big_int)
                     .and_then(|ark_big_int| {
-                        F::from_bigint(ark_big_int)
-                            .ok_or_else(|| Error::Other("could not get F from bigint".to_string()))
+                        F::from_bigint(ark_big_int).ok_or_else(|| {
+                            Error::BigIntConversionError("could not get F from bigint".to_string())
+                        })
                     })
             })
             .collect()
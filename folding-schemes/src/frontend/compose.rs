@@ -0,0 +1,311 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// Compose is the sequential composition of two FCircuits, whose step applies the step of
+/// `first` and then the step of `second` over its output, `z_{i+1} = B(A(z_i))`, both natively
+/// and in-circuit. The state of both FCircuits must have the same length, which is the length of
+/// the state of the composition.
+///
+/// The external inputs of each step are the external inputs of `first` followed by the ones of
+/// `second`, so the beacon mode of `first` is kept.
+#[derive(Clone, Copy, Debug)]
+pub struct Compose<A, B> {
+    pub first: A,
+    pub second: B,
+}
+
+impl<F: PrimeField, A: FCircuit<F>, B: FCircuit<F>> FCircuit<F> for Compose<A, B> {
+    /// parameters of the first and the second FCircuits
+    type Params = (A::Params, B::Params);
+    /// the field elements of the external inputs of `first` followed by the ones of `second`
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (first, second) = (A::new(params.0)?, B::new(params.1)?);
+        if first.state_len() != second.state_len() {
+            return Err(Error::NotSameLength {
+                a: "first.state_len()",
+                a_len: first.state_len(),
+                b: "second.state_len()",
+                b_len: second.state_len(),
+            });
+        }
+        Ok(Self { first, second })
+    }
+    fn state_len(&self) -> usize {
+        self.first.state_len()
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.first.external_inputs_len() + self.second.external_inputs_len()
+    }
+    fn beacon_mode(&self) -> bool {
+        self.first.beacon_mode()
+    }
+    // the IO of the step is the IO of `first` over `z_i` followed by the IO of `second` over the
+    // output of `first`
+    fn io_len(&self) -> usize {
+        self.first.io_len() + self.second.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let (first_inputs, second_inputs) =
+            external_inputs.split_at(self.first.external_inputs_len());
+        let mut io = self.first.step_io_native(
+            i,
+            z_i.clone(),
+            A::ExternalInputs::from_field_elements(first_inputs.to_vec())?,
+        )?;
+        if self.second.io_len() > 0 {
+            let z_mid = self.first.step_native(
+                i,
+                z_i,
+                A::ExternalInputs::from_field_elements(first_inputs.to_vec())?,
+            )?;
+            io.extend(self.second.step_io_native(
+                i,
+                z_mid,
+                B::ExternalInputs::from_field_elements(second_inputs.to_vec())?,
+            )?);
+        }
+        Ok(io)
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (first_inputs, second_inputs) =
+            external_inputs.split_at(self.first.external_inputs_len());
+        let mut io = self.first.generate_step_io_constraints(
+            cs.clone(),
+            i,
+            z_i.clone(),
+            A::ExternalInputs::var_from_field_elements(first_inputs.to_vec())?,
+        )?;
+        // the IO of `second` is over the output of `first`, whose constraints are generated
+        // again, since the IO constraints only get `z_i`
+        if self.second.io_len() > 0 {
+            let z_mid = self.first.generate_step_constraints(
+                cs.clone(),
+                i,
+                z_i,
+                A::ExternalInputs::var_from_field_elements(first_inputs.to_vec())?,
+            )?;
+            io.extend(self.second.generate_step_io_constraints(
+                cs,
+                i,
+                z_mid,
+                B::ExternalInputs::var_from_field_elements(second_inputs.to_vec())?,
+            )?);
+        }
+        Ok(io)
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let (first_inputs, second_inputs) =
+            external_inputs.split_at(self.first.external_inputs_len());
+        let z_mid = self.first.step_native(
+            i,
+            z_i,
+            A::ExternalInputs::from_field_elements(first_inputs.to_vec())?,
+        )?;
+        self.second.step_native(
+            i,
+            z_mid,
+            B::ExternalInputs::from_field_elements(second_inputs.to_vec())?,
+        )
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (first_inputs, second_inputs) =
+            external_inputs.split_at(self.first.external_inputs_len());
+        let z_mid = self.first.generate_step_constraints(
+            cs.clone(),
+            i,
+            z_i,
+            A::ExternalInputs::var_from_field_elements(first_inputs.to_vec())?,
+        )?;
+        self.second.generate_step_constraints(
+            cs,
+            i,
+            z_mid,
+            B::ExternalInputs::var_from_field_elements(second_inputs.to_vec())?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, fields::FieldVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+    use core::marker::PhantomData;
+
+    use crate::folding::nova::tests::{test_nova_params, SquareIOFCircuit, TestNova};
+    use crate::frontend::{examples::PoseidonFCircuit, tests::CubicFCircuit, BoundedFCircuit};
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    /// AddConstFCircuit adds a constant to its state, `z_{i+1} = z_i + c`
+    #[derive(Clone, Copy, Debug)]
+    struct AddConstFCircuit<F: PrimeField> {
+        c: u64,
+        _f: PhantomData<F>,
+    }
+    impl<F: PrimeField> FCircuit<F> for AddConstFCircuit<F> {
+        type Params = u64;
+        type ExternalInputs = ();
+
+        fn new(c: Self::Params) -> Result<Self, Error> {
+            Ok(Self { c, _f: PhantomData })
+        }
+        fn state_len(&self) -> usize {
+            1
+        }
+        fn external_inputs_len(&self) -> usize {
+            0
+        }
+        fn step_native(
+            &self,
+            _i: usize,
+            z_i: Vec<F>,
+            _external_inputs: (),
+        ) -> Result<Vec<F>, Error> {
+            Ok(vec![z_i[0] + F::from(self.c)])
+        }
+        fn generate_step_constraints(
+            &self,
+            _cs: ConstraintSystemRef<F>,
+            _i: usize,
+            z_i: Vec<FpVar<F>>,
+            _external_inputs: (),
+        ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+            Ok(vec![&z_i[0] + FpVar::constant(F::from(self.c))])
+        }
+    }
+
+    type FC = Compose<PoseidonFCircuit<Fr>, AddConstFCircuit<Fr>>;
+
+    #[test]
+    fn test_compose_constraints() {
+        let F_circuit = FC::new((poseidon_test_config::<Fr>(), 7)).unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i = vec![Fr::from(3_u32)];
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, vec![])
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            z_i1Var.value().unwrap(),
+            F_circuit.step_native(0, z_i, vec![]).unwrap()
+        );
+
+        // the intermediate states must have the same length
+        assert!(matches!(
+            <Compose<BoundedFCircuit<CubicFCircuit<Fr>>, CubicFCircuit<Fr>> as FCircuit<Fr>>::new(
+                (((), 3), ())
+            ),
+            Err(Error::NotSameLength {
+                a_len: 2,
+                b_len: 1,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_compose_io() {
+        // the IO of `second` is the square of the output of `first`
+        let F_circuit =
+            Compose::<AddConstFCircuit<Fr>, SquareIOFCircuit<Fr>>::new((7, ())).unwrap();
+        assert_eq!(F_circuit.io_len(), 1);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i = vec![Fr::from(3_u32)];
+        let io = F_circuit.step_io_native(0, z_i.clone(), vec![]).unwrap();
+        assert_eq!(io, vec![Fr::from(100_u32)]);
+
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let ioVar = F_circuit
+            .generate_step_io_constraints(cs.clone(), 0, z_iVar, vec![])
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(ioVar.value().unwrap(), io);
+    }
+
+    #[test]
+    fn test_compose_ivc() {
+        type NOVA = TestNova<FC>;
+
+        let F_circuit = FC::new((poseidon_test_config::<Fr>(), 7)).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        let num_steps = 3;
+        for _ in 0..num_steps {
+            nova.prove_step(vec![]).unwrap();
+        }
+
+        // each step hashes the state and then adds the constant
+        let mut expected = z_0.clone();
+        for i in 0..num_steps {
+            expected = F_circuit.first.step_native(i, expected, ()).unwrap();
+            expected = vec![expected[0] + Fr::from(7_u32)];
+        }
+        assert_eq!(nova.z_i, expected);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
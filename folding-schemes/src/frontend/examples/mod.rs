@@ -0,0 +1,8 @@
+/// FCircuit implementations that can be used as examples or for benchmarking the folding schemes.
+pub mod poseidon;
+pub mod sort;
+pub mod synthetic;
+
+pub use poseidon::PoseidonFCircuit;
+pub use sort::SortFCircuit;
+pub use synthetic::SyntheticFCircuit;
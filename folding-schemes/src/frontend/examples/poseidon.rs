@@ -0,0 +1,152 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use crate::frontend::FCircuit;
+use crate::Error;
+
+/// PoseidonFCircuit is an FCircuit whose step hashes its state, `z_{i+1} = [H(z_i)]`, so that
+/// after `n` steps the state is the `n`-th iteration of the Poseidon hash over `z_0`.
+#[derive(Clone, Debug)]
+pub struct PoseidonFCircuit<F: PrimeField> {
+    pub poseidon_config: PoseidonConfig<F>,
+}
+
+impl<F: PrimeField + Absorb> FCircuit<F> for PoseidonFCircuit<F> {
+    /// Poseidon config used for the hash
+    type Params = PoseidonConfig<F>;
+    type ExternalInputs = ();
+
+    fn new(poseidon_config: Self::Params) -> Result<Self, Error> {
+        Ok(Self { poseidon_config })
+    }
+    fn state_len(&self) -> usize {
+        1
+    }
+    fn external_inputs_len(&self) -> usize {
+        0
+    }
+    fn step_native(&self, _i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
+        let h = CRH::<F>::evaluate(&self.poseidon_config, z_i).map_err(|e| Error::CRHFail {
+            reason: e.to_string(),
+        })?;
+        Ok(vec![h])
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        let h = CRHGadget::<F>::evaluate(&crh_params, &z_i)?;
+        Ok(vec![h])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{CurveGroup, Group};
+    use ark_r1cs_std::{groups::GroupOpsBounds, prelude::CurveVar, ToConstraintFieldGadget};
+
+    use crate::commitment::{pedersen::Pedersen, CommitmentScheme};
+    use crate::folding::nova::{circuits::CF2, get_cs_params_len, Nova, ProverParams};
+    use crate::transcript::poseidon::cycle_configs;
+    use crate::FoldingScheme;
+
+    /// folds the PoseidonFCircuit over the cycle (C1, C2), with C1 being the primary curve, and
+    /// verifies the resulting IVC proof
+    fn test_poseidon_fcircuit_ivc<C1, GC1, C2, GC2>()
+    where
+        C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+        GC1: CurveVar<C1, CF2<C1>> + ToConstraintFieldGadget<CF2<C1>>,
+        C2: CurveGroup,
+        GC2: CurveVar<C2, CF2<C2>> + ToConstraintFieldGadget<CF2<C2>>,
+        <C1 as CurveGroup>::BaseField: PrimeField,
+        <C2 as CurveGroup>::BaseField: PrimeField,
+        <C1 as Group>::ScalarField: Absorb,
+        <C2 as Group>::ScalarField: Absorb,
+        for<'a> &'a GC1: GroupOpsBounds<'a, C1, GC1>,
+        for<'a> &'a GC2: GroupOpsBounds<'a, C2, GC2>,
+    {
+        type NOVA<C1, GC1, C2, GC2> = Nova<
+            C1,
+            GC1,
+            C2,
+            GC2,
+            PoseidonFCircuit<<C1 as Group>::ScalarField>,
+            Pedersen<C1>,
+            Pedersen<C2>,
+        >;
+        let mut rng = ark_std::test_rng();
+        // the Poseidon configs of each field of the cycle, for either orientation
+        let (poseidon_config, _) = cycle_configs::<C1, C2>().unwrap();
+        let F_circuit = PoseidonFCircuit::new(poseidon_config.clone()).unwrap();
+
+        let (cs_len, cf_cs_len) =
+            get_cs_params_len::<C1, GC1, C2, GC2, _>(&poseidon_config, F_circuit.clone()).unwrap();
+        let (cs_params, _) = Pedersen::<C1>::setup(&mut rng, cs_len).unwrap();
+        let (cf_cs_params, _) = Pedersen::<C2>::setup(&mut rng, cf_cs_len).unwrap();
+        let prover_params = ProverParams::<C1, C2, Pedersen<C1>, Pedersen<C2>> {
+            poseidon_config,
+            cs_params,
+            cf_cs_params,
+        };
+        let (prover_params, verifier_params) =
+            NOVA::<C1, GC1, C2, GC2>::preprocess(&(prover_params, F_circuit.clone())).unwrap();
+
+        let z_0 = vec![C1::ScalarField::from(3_u32)];
+        let mut nova =
+            NOVA::<C1, GC1, C2, GC2>::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        let mut z_i = z_0.clone();
+        for i in 0..3 {
+            nova.prove_step(()).unwrap();
+            z_i = F_circuit.step_native(i, z_i, ()).unwrap();
+        }
+        assert_eq!(nova.z_i, z_i);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::<C1, GC1, C2, GC2>::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_poseidon_fcircuit_pallas_first() {
+        test_poseidon_fcircuit_ivc::<
+            ark_pallas::Projective,
+            ark_pallas::constraints::GVar,
+            ark_vesta::Projective,
+            ark_vesta::constraints::GVar,
+        >();
+    }
+
+    #[test]
+    fn test_poseidon_fcircuit_vesta_first() {
+        test_poseidon_fcircuit_ivc::<
+            ark_vesta::Projective,
+            ark_vesta::constraints::GVar,
+            ark_pallas::Projective,
+            ark_pallas::constraints::GVar,
+        >();
+    }
+}
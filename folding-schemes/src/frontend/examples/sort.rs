@@ -0,0 +1,103 @@
+use ark_crypto_primitives::{
+    crh::poseidon::constraints::CRHParametersVar,
+    sponge::{poseidon::PoseidonConfig, Absorb},
+};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, eq::EqGadget, fields::fp::FpVar, R1CSVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use crate::frontend::gadgets::sort::{is_permutation, is_sorted};
+use crate::frontend::FCircuit;
+use crate::Error;
+
+/// SortFCircuit is an FCircuit whose step sorts its state, proving that `z_{i+1}` is sorted and
+/// that it is a permutation of `z_i`. The values of the state must be smaller or equal than
+/// `(p-1)/2`, as required by the comparison gadgets.
+#[derive(Clone, Debug)]
+pub struct SortFCircuit<F: PrimeField> {
+    pub poseidon_config: PoseidonConfig<F>,
+    pub state_len: usize,
+}
+
+impl<F: PrimeField + Absorb> FCircuit<F> for SortFCircuit<F> {
+    /// Poseidon config used for the permutation challenge, and length of the state
+    type Params = (PoseidonConfig<F>, usize);
+    type ExternalInputs = ();
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (poseidon_config, state_len) = params;
+        Ok(Self {
+            poseidon_config,
+            state_len,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.state_len
+    }
+    fn external_inputs_len(&self) -> usize {
+        0
+    }
+    fn step_native(&self, _i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
+        let mut z_i1 = z_i;
+        z_i1.sort();
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        // the sorted values are computed natively and provided as witness
+        let z_i1 = Vec::<FpVar<F>>::new_witness(cs.clone(), || {
+            let mut v = z_i.value()?;
+            v.sort();
+            Ok(v)
+        })?;
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        is_sorted(&z_i1)?.enforce_equal(&Boolean::TRUE)?;
+        is_permutation(&crh_params, &z_i, &z_i1)?.enforce_equal(&Boolean::TRUE)?;
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::gadgets::sort::is_sorted_native;
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_sort_fcircuit() {
+        type NOVA = TestNova<SortFCircuit<Fr>>;
+        let F_circuit = SortFCircuit::<Fr>::new((poseidon_test_config::<Fr>(), 4)).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0: Vec<Fr> = [7_u32, 3, 9, 1].into_iter().map(Fr::from).collect();
+        assert!(!is_sorted_native(&z_0));
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(()).unwrap();
+        }
+        assert!(is_sorted_native(&nova.z_i));
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::{fp::FpVar, FieldVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use core::marker::PhantomData;
+
+use crate::frontend::FCircuit;
+use crate::Error;
+
+/// SyntheticFCircuit is an FCircuit with a configurable number of constraints per step, which is
+/// useful to benchmark the folding schemes over different circuit sizes. Each step computes a
+/// chain of `n_constraints` multiplications over the single element of the state,
+/// `z_{i+1} = (...((z_i^2 + 1)^2 + 1)...)`, where each multiplication takes one constraint.
+#[derive(Clone, Copy, Debug)]
+pub struct SyntheticFCircuit<F: PrimeField> {
+    pub n_constraints: usize,
+    _f: PhantomData<F>,
+}
+
+impl<F: PrimeField> FCircuit<F> for SyntheticFCircuit<F> {
+    /// number of constraints of the step
+    type Params = usize;
+    type ExternalInputs = ();
+
+    fn new(n_constraints: Self::Params) -> Result<Self, Error> {
+        Ok(Self {
+            n_constraints,
+            _f: PhantomData,
+        })
+    }
+    fn state_len(&self) -> usize {
+        1
+    }
+    fn external_inputs_len(&self) -> usize {
+        0
+    }
+    fn step_native(&self, _i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
+        let mut x = *z_i.first().ok_or(Error::Empty)?;
+        for _ in 0..self.n_constraints {
+            x = x.square() + F::one();
+        }
+        Ok(vec![x])
+    }
+    fn generate_step_constraints(
+        &self,
+        _cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let mut x = z_i.first().ok_or(SynthesisError::Unsatisfiable)?.clone();
+        for _ in 0..self.n_constraints {
+            // the multiplication adds one constraint, while the addition of a constant is free
+            x = &x * &x + FpVar::one();
+        }
+        Ok(vec![x])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn test_synthetic_fcircuit_num_constraints() {
+        for n_constraints in [0, 1, 10, 1000] {
+            let F_circuit = SyntheticFCircuit::<Fr>::new(n_constraints).unwrap();
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let z_i = vec![Fr::from(3_u32)];
+            let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+            let z_i1Var = F_circuit
+                .generate_step_constraints(cs.clone(), 0, z_iVar, ())
+                .unwrap();
+            assert!(cs.is_satisfied().unwrap());
+            assert_eq!(cs.num_constraints(), n_constraints);
+            assert_eq!(
+                z_i1Var.value().unwrap(),
+                F_circuit.step_native(0, z_i, ()).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use ark_std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use super::FCircuit;
+use crate::Error;
+
+/// closure computing the native step, with the same signature as `FCircuit::step_native`
+pub type NativeStepFn<F> = Arc<dyn Fn(usize, Vec<F>, Vec<F>) -> Result<Vec<F>, Error>>;
+
+/// closure generating the step constraints, with the same signature as
+/// `FCircuit::generate_step_constraints`
+pub type ConstraintsStepFn<F> = Arc<
+    dyn Fn(
+        ConstraintSystemRef<F>,
+        usize,
+        Vec<FpVar<F>>,
+        Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError>,
+>;
+
+/// FnFCircuit builds an FCircuit from two closures, one computing the native step and the other
+/// one generating its constraints, which is handy to prototype steps without implementing the
+/// FCircuit trait. Both closures must compute the same function.
+#[derive(Clone)]
+pub struct FnFCircuit<F: PrimeField> {
+    pub state_len: usize,
+    pub external_inputs_len: usize,
+    native_fn: NativeStepFn<F>,
+    constraints_fn: ConstraintsStepFn<F>,
+}
+
+impl<F: PrimeField> Debug for FnFCircuit<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnFCircuit")
+            .field("state_len", &self.state_len)
+            .field("external_inputs_len", &self.external_inputs_len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: PrimeField> FnFCircuit<F> {
+    /// returns a new FnFCircuit for a state of `state_len` elements and without external inputs
+    pub fn new(
+        state_len: usize,
+        native_fn: impl Fn(usize, Vec<F>, Vec<F>) -> Result<Vec<F>, Error> + 'static,
+        constraints_fn: impl Fn(
+                ConstraintSystemRef<F>,
+                usize,
+                Vec<FpVar<F>>,
+                Vec<FpVar<F>>,
+            ) -> Result<Vec<FpVar<F>>, SynthesisError>
+            + 'static,
+    ) -> Self {
+        Self {
+            state_len,
+            external_inputs_len: 0,
+            native_fn: Arc::new(native_fn),
+            constraints_fn: Arc::new(constraints_fn),
+        }
+    }
+
+    /// sets the number of external inputs that the closures receive at each step
+    pub fn with_external_inputs_len(mut self, external_inputs_len: usize) -> Self {
+        self.external_inputs_len = external_inputs_len;
+        self
+    }
+}
+
+impl<F: PrimeField> FCircuit<F> for FnFCircuit<F> {
+    /// the FnFCircuit itself, since it is built from closures through `FnFCircuit::new`
+    type Params = Self;
+    /// the `external_inputs_len` field elements received by the closures
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        Ok(params)
+    }
+    fn state_len(&self) -> usize {
+        self.state_len
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.external_inputs_len
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        (self.native_fn)(i, z_i, external_inputs)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        (self.constraints_fn)(cs, i, z_i, external_inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::fields::FieldVar;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_fn_fcircuit() {
+        type NOVA = TestNova<FnFCircuit<Fr>>;
+
+        // z_{i+1} = z_i + 1
+        let F_circuit = FnFCircuit::<Fr>::new(
+            1,
+            |_i, z_i, _external_inputs| Ok(vec![z_i[0] + Fr::from(1_u32)]),
+            |_cs, _i, z_i, _external_inputs| Ok(vec![&z_i[0] + FpVar::one()]),
+        );
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        let num_steps = 3;
+        for _ in 0..num_steps {
+            nova.prove_step(vec![]).unwrap();
+        }
+        assert_eq!(nova.z_i, vec![Fr::from(3_u32 + num_steps)]);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,266 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::FCircuit;
+use crate::Error;
+
+/// Transition of a finite state machine: being at `state` and receiving `input`, the machine
+/// moves to `next_state`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Transition<F: PrimeField> {
+    pub state: F,
+    pub input: F,
+    pub next_state: F,
+}
+
+/// FsmFCircuit is an FCircuit that proves the execution of a finite state machine defined by a
+/// committed transition table. The state is `[table_commitment, fsm_state]`, where
+/// `table_commitment = H(table)` is set in `z_0` and kept unchanged by every step, and at each
+/// step the machine moves from `fsm_state` to the next state given by the table for the step
+/// input.
+///
+/// The external inputs of each step are the input of the machine followed by the transition
+/// table (see `FsmFCircuit::external_inputs`), which is checked against the commitment. The table
+/// must contain at most one transition for each `(state, input)` pair, and a step whose
+/// `(state, input)` is not in the table can not be proven.
+#[derive(Clone, Debug)]
+pub struct FsmFCircuit<F: PrimeField> {
+    pub poseidon_config: PoseidonConfig<F>,
+    pub table_len: usize,
+}
+
+impl<F: PrimeField + Absorb> FsmFCircuit<F> {
+    /// returns the commitment to the given transition table, to be placed as the first element
+    /// of `z_0`
+    pub fn table_commitment(&self, table: &[Transition<F>]) -> Result<F, Error> {
+        CRH::<F>::evaluate(&self.poseidon_config, self.encode_table(table)?).map_err(|e| {
+            Error::CRHFail {
+                reason: e.to_string(),
+            }
+        })
+    }
+
+    /// returns the encoding of the transition table as `[state, input, next_state]` triples
+    pub fn encode_table(&self, table: &[Transition<F>]) -> Result<Vec<F>, Error> {
+        if table.len() != self.table_len {
+            return Err(Error::NotSameLength {
+                a: "table.len()",
+                a_len: table.len(),
+                b: "table_len",
+                b_len: self.table_len,
+            });
+        }
+        Ok(table
+            .iter()
+            .flat_map(|t| [t.state, t.input, t.next_state])
+            .collect())
+    }
+
+    /// returns the external inputs of a step receiving `input`
+    pub fn external_inputs(&self, table: &[Transition<F>], input: F) -> Result<Vec<F>, Error> {
+        Ok([vec![input], self.encode_table(table)?].concat())
+    }
+}
+
+impl<F: PrimeField + Absorb> FCircuit<F> for FsmFCircuit<F> {
+    /// Poseidon config used for the table commitment, and number of transitions of the table
+    type Params = (PoseidonConfig<F>, usize);
+    /// the input of the machine followed by the encoded transition table
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (poseidon_config, table_len) = params;
+        Ok(Self {
+            poseidon_config,
+            table_len,
+        })
+    }
+    fn state_len(&self) -> usize {
+        2
+    }
+    fn external_inputs_len(&self) -> usize {
+        1 + 3 * self.table_len
+    }
+    fn step_native(
+        &self,
+        _i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotExpectedLength {
+                got: external_inputs.len(),
+                expected: self.external_inputs_len(),
+            });
+        }
+        let (input, table) = (external_inputs[0], &external_inputs[1..]);
+        let table_commitment =
+            CRH::<F>::evaluate(&self.poseidon_config, table.to_vec()).map_err(|e| {
+                Error::CRHFail {
+                    reason: e.to_string(),
+                }
+            })?;
+        if table_commitment != z_i[0] {
+            return Err(Error::TransitionTableCommitmentMismatch);
+        }
+        let mut matches = table.chunks(3).filter(|t| t[0] == z_i[1] && t[1] == input);
+        match (matches.next(), matches.next()) {
+            (Some(t), None) => Ok(vec![z_i[0], t[2]]),
+            _ => Err(Error::InvalidTransition),
+        }
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() || external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (input, table) = (&external_inputs[0], &external_inputs[1..]);
+        // check the transition table against the table commitment of the state
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        let table_commitment = CRHGadget::<F>::evaluate(&crh_params, table)?;
+        table_commitment.enforce_equal(&z_i[0])?;
+
+        // exactly one transition of the table matches (fsm_state, input), and the next state is
+        // the one of the matching transition
+        let mut n_matches = FpVar::<F>::zero();
+        let mut next_state = FpVar::<F>::zero();
+        for t in table.chunks(3) {
+            let is_match = t[0].is_eq(&z_i[1])?.and(&t[1].is_eq(input)?)?;
+            let is_match = FpVar::from(is_match);
+            n_matches += &is_match;
+            next_state += is_match * &t[2];
+        }
+        n_matches.enforce_equal(&FpVar::one())?;
+        Ok(vec![z_i[0].clone(), next_state])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    // 3-state machine which stays at the same state on input 0, and moves to the next state
+    // (mod 3) on input 1
+    fn test_table() -> Vec<Transition<Fr>> {
+        (0..3_u32)
+            .flat_map(|s| {
+                [
+                    Transition {
+                        state: Fr::from(s),
+                        input: Fr::from(0_u32),
+                        next_state: Fr::from(s),
+                    },
+                    Transition {
+                        state: Fr::from(s),
+                        input: Fr::from(1_u32),
+                        next_state: Fr::from((s + 1) % 3),
+                    },
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fsm_fcircuit_constraints() {
+        let table = test_table();
+        let F_circuit =
+            FsmFCircuit::<Fr>::new((poseidon_test_config::<Fr>(), table.len())).unwrap();
+        let cm = F_circuit.table_commitment(&table).unwrap();
+
+        for (input, expected) in [(1_u32, Some(2_u32)), (0, Some(1)), (2, None)] {
+            let z_i = vec![cm, Fr::from(1_u32)];
+            let external_inputs = F_circuit.external_inputs(&table, Fr::from(input)).unwrap();
+            let z_i1 = F_circuit.step_native(0, z_i.clone(), external_inputs.clone());
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+            let external_inputsVar =
+                Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(external_inputs)).unwrap();
+            let z_i1Var = F_circuit
+                .generate_step_constraints(cs.clone(), 0, z_iVar, external_inputsVar)
+                .unwrap();
+            match expected {
+                Some(next_state) => {
+                    assert_eq!(z_i1.unwrap(), vec![cm, Fr::from(next_state)]);
+                    assert!(cs.is_satisfied().unwrap());
+                    assert_eq!(z_i1Var.value().unwrap(), vec![cm, Fr::from(next_state)]);
+                }
+                None => {
+                    assert!(matches!(z_i1, Err(Error::InvalidTransition)));
+                    assert!(!cs.is_satisfied().unwrap());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fsm_fcircuit_ivc() {
+        type NOVA = TestNova<FsmFCircuit<Fr>>;
+
+        let table = test_table();
+        let F_circuit =
+            FsmFCircuit::<Fr>::new((poseidon_test_config::<Fr>(), table.len())).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![F_circuit.table_commitment(&table).unwrap(), Fr::from(0_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        // 0 -1-> 1 -1-> 2 -0-> 2 -1-> 0 -1-> 1
+        for input in [1_u32, 1, 0, 1, 1] {
+            nova.prove_step(F_circuit.external_inputs(&table, Fr::from(input)).unwrap())
+                .unwrap();
+        }
+        assert_eq!(nova.z_i[1], Fr::from(1_u32));
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i.clone(),
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+
+        // an input without transition in the table can not be proven
+        let i = nova.i;
+        assert!(nova
+            .prove_step(F_circuit.external_inputs(&table, Fr::from(2_u32)).unwrap())
+            .is_err());
+        assert_eq!(nova.i, i);
+    }
+}
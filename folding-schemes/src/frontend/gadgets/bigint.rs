@@ -0,0 +1,150 @@
+use ark_ff::{One, PrimeField};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
+    select::CondSelectGadget,
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::BigUint;
+
+use crate::folding::circuits::nonnative::uint::{BoundedBigUint, NonNativeUintVar};
+use crate::Error;
+
+/// BigIntConfig defines the modular arithmetic over the integers modulo `modulus`, which does not
+/// need to be prime nor to fit in the field (eg. an RSA modulus), where the operands are unsigned
+/// integers of `n_bits` bits. The integers are represented in the circuit as `NonNativeUintVar`s,
+/// decomposed into limbs of `NonNativeUintVar::bits_per_limb` bits, or of the bits of the
+/// `LimbConfig` of the constraint system.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BigIntConfig {
+    pub modulus: BigUint,
+    pub n_bits: usize,
+}
+
+impl BigIntConfig {
+    /// returns a new config, checking that the modulus is greater than 1 and fits in `n_bits`
+    pub fn new(modulus: BigUint, n_bits: usize) -> Result<Self, Error> {
+        if modulus <= BigUint::one() || modulus.bits() > n_bits as u64 {
+            return Err(Error::OutOfBounds);
+        }
+        Ok(Self { modulus, n_bits })
+    }
+
+    /// allocates the integer `x`, enforcing that it fits in `n_bits` bits
+    pub fn alloc<F: PrimeField>(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        x: &BigUint,
+        mode: AllocationMode,
+    ) -> Result<NonNativeUintVar<F>, SynthesisError> {
+        if x.bits() > self.n_bits as u64 {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        NonNativeUintVar::new_variable(cs, || Ok(BoundedBigUint(x.clone(), self.n_bits)), mode)
+    }
+}
+
+/// returns `(a + b) mod modulus`
+pub fn add_mod<F: PrimeField>(
+    config: &BigIntConfig,
+    a: &NonNativeUintVar<F>,
+    b: &NonNativeUintVar<F>,
+) -> Result<NonNativeUintVar<F>, SynthesisError> {
+    a.add_no_align(b).modulo_by(&config.modulus)
+}
+
+/// returns `(a * b) mod modulus`
+pub fn mul_mod<F: PrimeField>(
+    config: &BigIntConfig,
+    a: &NonNativeUintVar<F>,
+    b: &NonNativeUintVar<F>,
+) -> Result<NonNativeUintVar<F>, SynthesisError> {
+    a.mul_no_align(b)?.modulo_by(&config.modulus)
+}
+
+/// returns `a^e mod modulus`, where `e` is given by its little-endian bits, using
+/// square-and-multiply
+pub fn pow_mod<F: PrimeField>(
+    config: &BigIntConfig,
+    a: &NonNativeUintVar<F>,
+    e: &[Boolean<F>],
+) -> Result<NonNativeUintVar<F>, SynthesisError> {
+    // the results of the modular operations are allocated with the bit length of the modulus, so
+    // that they can be selected between each other, and in the constraint system of `a`, so that
+    // they have the same limbs
+    let mut r = NonNativeUintVar::new_constant(
+        a.cs(),
+        BoundedBigUint(BigUint::one(), config.modulus.bits() as usize),
+    )?;
+    for bit in e.iter().rev() {
+        r = mul_mod(config, &r, &r)?;
+        let ra = mul_mod(config, &r, a)?;
+        r = NonNativeUintVar::conditionally_select(bit, &ra, &r)?;
+    }
+    Ok(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::test_rng;
+    use num_bigint::RandBigInt;
+
+    #[test]
+    fn test_bigint_mod_arithmetic() {
+        let rng = &mut test_rng();
+        let n_bits = 512;
+        // an odd 512-bit modulus, as the ones of RSA
+        let modulus = rng.gen_biguint(n_bits as u64) | BigUint::one() | (BigUint::one() << 511);
+        let config = BigIntConfig::new(modulus.clone(), n_bits).unwrap();
+        let a = rng.gen_biguint_below(&modulus);
+        let b = rng.gen_biguint_below(&modulus);
+        let e = rng.gen_biguint(16);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = config
+            .alloc(cs.clone(), &a, AllocationMode::Witness)
+            .unwrap();
+        let bVar = config
+            .alloc(cs.clone(), &b, AllocationMode::Witness)
+            .unwrap();
+        let eVar = Vec::<Boolean<Fr>>::new_witness(cs.clone(), || {
+            Ok((0..16).map(|i| e.bit(i)).collect::<Vec<_>>())
+        })
+        .unwrap();
+
+        let abVar = mul_mod(&config, &aVar, &bVar).unwrap();
+        assert_eq!(abVar.value().unwrap(), (&a * &b) % &modulus);
+        let a_plus_bVar = add_mod(&config, &aVar, &bVar).unwrap();
+        assert_eq!(a_plus_bVar.value().unwrap(), (&a + &b) % &modulus);
+        let a_pow_eVar = pow_mod(&config, &aVar, &eVar).unwrap();
+        assert_eq!(a_pow_eVar.value().unwrap(), a.modpow(&e, &modulus));
+        assert!(cs.is_satisfied().unwrap());
+
+        // a wrong product does not satisfy the circuit
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = config
+            .alloc(cs.clone(), &a, AllocationMode::Witness)
+            .unwrap();
+        let bVar = config
+            .alloc(cs.clone(), &b, AllocationMode::Witness)
+            .unwrap();
+        let abVar = mul_mod(&config, &aVar, &bVar).unwrap();
+        let expected = config
+            .alloc(
+                cs.clone(),
+                &((&a * &b + BigUint::one()) % &modulus),
+                AllocationMode::Witness,
+            )
+            .unwrap();
+        abVar.enforce_equal_unaligned(&expected).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // the modulus must fit in the configured bit width
+        assert!(BigIntConfig::new(modulus, n_bits - 1).is_err());
+    }
+}
@@ -0,0 +1,237 @@
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::fp::FpVar,
+    groups::{CurveVar, GroupOpsBounds},
+    ToBitsGadget, ToConstraintFieldGadget,
+};
+use ark_relations::r1cs::SynthesisError;
+use num_bigint::BigUint;
+
+/// ecdsa_verify enforces that `(R, s)` is a valid ECDSA signature of the message hash `e` under
+/// the public key `pk`, over a curve C whose base field is the native field of the circuit, such
+/// as the CycleFold companion curve (eg. Grumpkin for a BN254 circuit), so that the curve points
+/// are native to the circuit.
+///
+/// Instead of the usual check `r == (e⋅s^{-1} G + r⋅s^{-1} pk).x mod n`, which would require
+/// non-native arithmetic modulo the order `n` of C, it checks the equivalent `s R == e G + r pk`
+/// for `r = R.x`, where all the scalar arithmetic is done through scalar multiplications by the
+/// bits of the scalars. As a consequence, the signature has to contain the full point `R` instead
+/// of only its x coordinate, and the modulus of C's base field must be smaller than the order of
+/// C (so that `R.x mod n == R.x`), which holds for Grumpkin.
+/// `s` is given as its little-endian bits, since it lives in C's scalar field.
+pub fn ecdsa_verify<C, GC>(
+    pk: &GC,
+    e: &FpVar<C::BaseField>,
+    R: &GC,
+    s: &[Boolean<C::BaseField>],
+) -> Result<(), SynthesisError>
+where
+    C: CurveGroup,
+    C::BaseField: PrimeField,
+    GC: CurveVar<C, C::BaseField> + ToConstraintFieldGadget<C::BaseField>,
+    for<'a> &'a GC: GroupOpsBounds<'a, C, GC>,
+{
+    let base_field_modulus: BigUint = C::BaseField::MODULUS.into();
+    let scalar_field_modulus: BigUint = C::ScalarField::MODULUS.into();
+    if base_field_modulus >= scalar_field_modulus {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    // R != 0 and s != 0
+    R.is_zero()?.enforce_equal(&Boolean::FALSE)?;
+    Boolean::kary_or(s)?.enforce_equal(&Boolean::TRUE)?;
+
+    // r = R.x
+    let r_bits = R.to_constraint_field()?[0].to_bits_le()?;
+    let e_bits = e.to_bits_le()?;
+
+    let g = GC::constant(C::generator());
+    let lhs = R.scalar_mul_le(s.iter())?;
+    let rhs = g.scalar_mul_le(e_bits.iter())? + pk.scalar_mul_le(r_bits.iter())?;
+    lhs.enforce_equal(&rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ec::{AffineRepr, Group};
+    use ark_ff::{BigInteger, Field};
+    use ark_grumpkin::{
+        constraints::GVar as GVar2, Affine as Affine2, Fr as ScalarField2,
+        Projective as Projective2,
+    };
+    use ark_r1cs_std::{alloc::AllocVar, fields::FieldVar, R1CSVar};
+    use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
+    use ark_std::{UniformRand, Zero};
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::FCircuit;
+    use crate::{Error, FoldingScheme};
+
+    const S_LIMB_BITS: usize = 128;
+
+    /// returns (pk, e, R, s) for a random key and message, where the signature is computed over
+    /// Grumpkin and the message hash `e` is an element of Grumpkin's base field
+    fn sign(rng: &mut impl ark_std::rand::RngCore) -> (Projective2, Fr, Projective2, ScalarField2) {
+        let sk = ScalarField2::rand(rng);
+        let pk = Projective2::generator() * sk;
+        let e = Fr::rand(rng);
+
+        let k = ScalarField2::rand(rng);
+        let R = Projective2::generator() * k;
+        let r =
+            ScalarField2::from_le_bytes_mod_order(&R.into_affine().x.into_bigint().to_bytes_le());
+        let e_scalar = ScalarField2::from_le_bytes_mod_order(&e.into_bigint().to_bytes_le());
+        let s = k.inverse().unwrap() * (e_scalar + r * sk);
+        (pk, e, R, s)
+    }
+
+    /// splits the scalar `s` into two limbs of S_LIMB_BITS bits, to be used as elements of Fr
+    fn s_to_limbs(s: ScalarField2) -> Vec<Fr> {
+        let bits = s.into_bigint().to_bits_le();
+        bits.chunks(S_LIMB_BITS)
+            .map(|limb| Fr::from_bigint(BigInteger::from_bits_le(limb)).unwrap())
+            .collect()
+    }
+
+    fn s_limbs_to_bits(limbs: &[FpVar<Fr>]) -> Result<Vec<Boolean<Fr>>, SynthesisError> {
+        Ok([
+            limbs[0].to_bits_le()?[..S_LIMB_BITS].to_vec(),
+            limbs[1].to_bits_le()?[..S_LIMB_BITS].to_vec(),
+        ]
+        .concat())
+    }
+
+    /// allocates the point with the given coordinates. If the coordinates do not correspond to a
+    /// point in the curve (eg. when building the circuit with dummy values), the generator is
+    /// allocated instead, which will not satisfy the coordinates equality constraints.
+    fn alloc_point(
+        cs: ConstraintSystemRef<Fr>,
+        x: &FpVar<Fr>,
+        y: &FpVar<Fr>,
+    ) -> Result<GVar2, SynthesisError> {
+        let p = GVar2::new_witness(cs, || {
+            let p = Affine2::new_unchecked(
+                x.value().unwrap_or_default(),
+                y.value().unwrap_or_default(),
+            );
+            if p.is_on_curve() {
+                Ok(p.into_group())
+            } else {
+                Ok(Projective2::generator())
+            }
+        })?;
+        let p_coords = p.to_constraint_field()?;
+        p_coords[0].enforce_equal(x)?;
+        p_coords[1].enforce_equal(y)?;
+        Ok(p)
+    }
+
+    /// EcdsaFCircuit verifies an ECDSA signature at each step, counting the number of verified
+    /// signatures in its state. The external inputs are `[pk.x, pk.y, e, R.x, R.y, s_0, s_1]`.
+    #[derive(Clone, Copy, Debug)]
+    struct EcdsaFCircuit;
+    impl FCircuit<Fr> for EcdsaFCircuit {
+        type Params = ();
+        type ExternalInputs = Vec<Fr>;
+        fn new(_params: Self::Params) -> Result<Self, Error> {
+            Ok(Self)
+        }
+        fn state_len(&self) -> usize {
+            1
+        }
+        fn external_inputs_len(&self) -> usize {
+            7
+        }
+        fn step_native(
+            &self,
+            _i: usize,
+            z_i: Vec<Fr>,
+            _external_inputs: Vec<Fr>,
+        ) -> Result<Vec<Fr>, Error> {
+            Ok(vec![z_i[0] + Fr::from(1_u32)])
+        }
+        fn generate_step_constraints(
+            &self,
+            cs: ConstraintSystemRef<Fr>,
+            _i: usize,
+            z_i: Vec<FpVar<Fr>>,
+            external_inputs: Vec<FpVar<Fr>>,
+        ) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+            let pk = alloc_point(cs.clone(), &external_inputs[0], &external_inputs[1])?;
+            let R = alloc_point(cs.clone(), &external_inputs[3], &external_inputs[4])?;
+            let s = s_limbs_to_bits(&external_inputs[5..7])?;
+            ecdsa_verify::<Projective2, GVar2>(&pk, &external_inputs[2], &R, &s)?;
+            Ok(vec![z_i[0].clone() + FpVar::one()])
+        }
+    }
+
+    fn external_inputs(pk: Projective2, e: Fr, R: Projective2, s: ScalarField2) -> Vec<Fr> {
+        let pk = pk.into_affine();
+        let R = R.into_affine();
+        [vec![pk.x, pk.y, e, R.x, R.y], s_to_limbs(s)].concat()
+    }
+
+    #[test]
+    fn test_ecdsa_verify_gadget() {
+        let mut rng = ark_std::test_rng();
+        let (pk, e, R, s) = sign(&mut rng);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let pkVar = GVar2::new_witness(cs.clone(), || Ok(pk)).unwrap();
+        let eVar = FpVar::<Fr>::new_witness(cs.clone(), || Ok(e)).unwrap();
+        let RVar = GVar2::new_witness(cs.clone(), || Ok(R)).unwrap();
+        let sVar = s_limbs_to_bits(
+            &Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(s_to_limbs(s))).unwrap(),
+        )
+        .unwrap();
+        ecdsa_verify::<Projective2, GVar2>(&pkVar, &eVar, &RVar, &sVar).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+
+        // a signature of a different message is not valid
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let pkVar = GVar2::new_witness(cs.clone(), || Ok(pk)).unwrap();
+        let eVar = FpVar::<Fr>::new_witness(cs.clone(), || Ok(e + Fr::from(1_u32))).unwrap();
+        let RVar = GVar2::new_witness(cs.clone(), || Ok(R)).unwrap();
+        let sVar = s_limbs_to_bits(
+            &Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(s_to_limbs(s))).unwrap(),
+        )
+        .unwrap();
+        ecdsa_verify::<Projective2, GVar2>(&pkVar, &eVar, &RVar, &sVar).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_ecdsa_fcircuit_ivc() {
+        type NOVA = TestNova<EcdsaFCircuit>;
+        let mut rng = ark_std::test_rng();
+        let F_circuit = EcdsaFCircuit::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::zero()];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..2 {
+            let (pk, e, R, s) = sign(&mut rng);
+            nova.prove_step(external_inputs(pk, e, R, s)).unwrap();
+        }
+        assert_eq!(nova.z_i, vec![Fr::from(2_u32)]);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,228 @@
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    alloc::{AllocVar, AllocationMode},
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use num_bigint::BigUint;
+
+use crate::Error;
+
+/// FixedPointConfig defines an unsigned fixed-point representation of `n_bits` bits, where the
+/// lowest `frac_bits` bits are the fractional part, so that the field element `x` represents the
+/// number `x / 2^frac_bits`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedPointConfig {
+    pub frac_bits: usize,
+    pub n_bits: usize,
+}
+
+impl FixedPointConfig {
+    /// returns a new config, checking that the product of two values fits in the field F, so
+    /// that the multiplications do not overflow the field
+    pub fn new<F: PrimeField>(frac_bits: usize, n_bits: usize) -> Result<Self, Error> {
+        if frac_bits > n_bits || 2 * n_bits >= F::MODULUS_BIT_SIZE as usize {
+            return Err(Error::OutOfBounds);
+        }
+        Ok(Self { frac_bits, n_bits })
+    }
+
+    /// returns the fixed-point representation of the integer `x`
+    pub fn from_integer<F: PrimeField>(&self, x: u64) -> Result<F, Error> {
+        let x = BigUint::from(x) << self.frac_bits;
+        if x.bits() > self.n_bits as u64 {
+            return Err(Error::FixedPointOverflow { bits: self.n_bits });
+        }
+        Ok(F::from(x))
+    }
+}
+
+/// checks natively that `x` fits in `n_bits` bits
+fn check_bit_length<F: PrimeField>(x: &F, n_bits: usize) -> Result<(), Error> {
+    if BigUint::from(x.into_bigint()).bits() > n_bits as u64 {
+        return Err(Error::FixedPointOverflow { bits: n_bits });
+    }
+    Ok(())
+}
+
+/// values derived from constants are allocated as constants, since they have no constraint system
+fn allocation_mode<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> AllocationMode {
+    if cs.is_none() {
+        AllocationMode::Constant
+    } else {
+        AllocationMode::Witness
+    }
+}
+
+/// enforces that `x` fits in `n_bits` bits, by decomposing it into `n_bits` bits
+fn enforce_bit_length<F: PrimeField>(x: &FpVar<F>, n_bits: usize) -> Result<(), SynthesisError> {
+    let cs = x.cs();
+    let mode = allocation_mode(&cs);
+    let bits = Vec::<Boolean<F>>::new_variable(
+        cs,
+        || Ok(x.value().unwrap_or_default().into_bigint().to_bits_le()[..n_bits].to_vec()),
+        mode,
+    )?;
+    Boolean::le_bits_to_fp_var(&bits)?.enforce_equal(x)
+}
+
+/// returns `a + b`, or `Error::FixedPointOverflow` if the result does not fit in the config
+pub fn add_native<F: PrimeField>(config: &FixedPointConfig, a: F, b: F) -> Result<F, Error> {
+    check_bit_length(&a, config.n_bits)?;
+    check_bit_length(&b, config.n_bits)?;
+    let c = a + b;
+    check_bit_length(&c, config.n_bits)?;
+    Ok(c)
+}
+
+/// returns `x / 2^frac_bits` rounded down, where `x` is a value at twice the scale of the config
+/// (eg. the product of two values), or `Error::FixedPointOverflow` if the result does not fit in
+/// the config
+pub fn truncate_native<F: PrimeField>(config: &FixedPointConfig, x: F) -> Result<F, Error> {
+    let q = F::from(BigUint::from(x.into_bigint()) >> config.frac_bits);
+    check_bit_length(&q, config.n_bits)?;
+    Ok(q)
+}
+
+/// returns `a * b` truncated to the scale of the config
+pub fn mul_native<F: PrimeField>(config: &FixedPointConfig, a: F, b: F) -> Result<F, Error> {
+    check_bit_length(&a, config.n_bits)?;
+    check_bit_length(&b, config.n_bits)?;
+    truncate_native(config, a * b)
+}
+
+/// add returns `a + b`, enforcing that the result fits in `n_bits`. The inputs are expected to
+/// fit in `n_bits`, as the outputs of the other gadgets do.
+pub fn add<F: PrimeField>(
+    config: &FixedPointConfig,
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let c = a + b;
+    enforce_bit_length(&c, config.n_bits)?;
+    Ok(c)
+}
+
+/// truncate returns `x / 2^frac_bits` rounded down, where `x` is at twice the scale of the config.
+/// The quotient `q` and remainder `r` are provided as witnesses, enforcing `x == q⋅2^frac_bits + r`
+/// with `r < 2^frac_bits` and `q < 2^n_bits`, which also checks that the result does not
+/// overflow. Since `2⋅n_bits` is smaller than the field size, the decomposition is unique.
+pub fn truncate<F: PrimeField>(
+    config: &FixedPointConfig,
+    x: &FpVar<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let cs = x.cs();
+    let mode = allocation_mode(&cs);
+    let x_value = BigUint::from(x.value().unwrap_or_default().into_bigint());
+    let q = FpVar::new_variable(
+        cs.clone(),
+        || Ok(F::from(&x_value >> config.frac_bits)),
+        mode,
+    )?;
+    let r = FpVar::new_variable(
+        cs,
+        || {
+            Ok(F::from(
+                &x_value % (BigUint::from(1_u8) << config.frac_bits),
+            ))
+        },
+        mode,
+    )?;
+    enforce_bit_length(&q, config.n_bits)?;
+    enforce_bit_length(&r, config.frac_bits)?;
+    let scale = FpVar::constant(F::from(BigUint::from(1_u8) << config.frac_bits));
+    (&q * scale + r).enforce_equal(x)?;
+    Ok(q)
+}
+
+/// mul returns `a * b` truncated to the scale of the config. The inputs are expected to fit in
+/// `n_bits`, as the outputs of the other gadgets do.
+pub fn mul<F: PrimeField>(
+    config: &FixedPointConfig,
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    truncate(config, &(a * b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    #[test]
+    fn test_fixed_point_mul() {
+        // 16 fractional bits, values up to 2^32
+        let config = FixedPointConfig::new::<Fr>(16, 32).unwrap();
+        let scale = 1_u64 << 16;
+        // 2.5 * 3.25 = 8.125
+        let a = Fr::from(5 * scale / 2);
+        let b = Fr::from(13 * scale / 4);
+        let c = mul_native(&config, a, b).unwrap();
+        assert_eq!(c, Fr::from(65 * scale / 8));
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = FpVar::new_witness(cs.clone(), || Ok(a)).unwrap();
+        let bVar = FpVar::new_witness(cs.clone(), || Ok(b)).unwrap();
+        let cVar = mul(&config, &aVar, &bVar).unwrap();
+        assert_eq!(cVar.value().unwrap(), c);
+        assert!(cs.is_satisfied().unwrap());
+
+        // the product is truncated: (1 + 2^-16) * (1 + 2^-16) = 1 + 2^-15 + 2^-32, where the
+        // last term is dropped
+        let a = Fr::from(scale + 1);
+        let c = mul_native(&config, a, a).unwrap();
+        assert_eq!(c, Fr::from(scale + 2));
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = FpVar::new_witness(cs.clone(), || Ok(a)).unwrap();
+        let cVar = mul(&config, &aVar, &aVar).unwrap();
+        assert_eq!(cVar.value().unwrap(), c);
+        assert!(cs.is_satisfied().unwrap());
+
+        // addition
+        let d = add_native(&config, c, Fr::from(scale)).unwrap();
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let cVar = FpVar::new_witness(cs.clone(), || Ok(c)).unwrap();
+        let oneVar = FpVar::new_witness(cs.clone(), || Ok(Fr::from(scale))).unwrap();
+        let dVar = add(&config, &cVar, &oneVar).unwrap();
+        assert_eq!(dVar.value().unwrap(), d);
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_fixed_point_overflow() {
+        let config = FixedPointConfig::new::<Fr>(16, 32).unwrap();
+        // the integer part has 16 bits, so 2^15 * 2^2 = 2^17 and 2^15 + 2^15 = 2^16 overflow
+        let a = config.from_integer::<Fr>(1 << 15).unwrap();
+        let b = config.from_integer::<Fr>(4).unwrap();
+        assert!(matches!(
+            mul_native(&config, a, b),
+            Err(Error::FixedPointOverflow { bits: 32 })
+        ));
+        assert!(matches!(
+            add_native(&config, a, a),
+            Err(Error::FixedPointOverflow { bits: 32 })
+        ));
+        assert!(add_native(&config, a, b).is_ok());
+        assert!(config.from_integer::<Fr>(1 << 16).is_err());
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = FpVar::new_witness(cs.clone(), || Ok(a)).unwrap();
+        let bVar = FpVar::new_witness(cs.clone(), || Ok(b)).unwrap();
+        mul(&config, &aVar, &bVar).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let aVar = FpVar::new_witness(cs.clone(), || Ok(a)).unwrap();
+        add(&config, &aVar, &aVar).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        // products not fitting in the field are rejected by the config
+        assert!(FixedPointConfig::new::<Fr>(16, 127).is_err());
+        assert!(FixedPointConfig::new::<Fr>(33, 32).is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_poly_commit::kzg10::VerifierKey;
+use ark_r1cs_std::{
+    boolean::Boolean, eq::EqGadget, fields::FieldVar, groups::CurveVar, pairing::PairingVar,
+};
+use ark_relations::r1cs::SynthesisError;
+
+/// native field of the circuits that verify KZG openings over the pairing-friendly curve E, ie. the
+/// base field of E
+pub type KZGConstraintField<E> = <<E as Pairing>::G1 as CurveGroup>::BaseField;
+
+/// kzg_verify enforces that `proof` is a valid KZG opening of the commitment `cm` to the value
+/// `eval` at the point `z`, for the verifier key `vk`, ie. that
+/// `e(cm - eval⋅g, h) == e(proof, β⋅h - z⋅h)`, which is checked as a single product of pairings.
+///
+/// The pairing is computed in-circuit with the gadget `P`, so the base field of the
+/// pairing-friendly curve E has to be the native field of the circuit (eg. BLS12-377 in a circuit
+/// over BW6-761's scalar field), so that the points are native to the circuit. `z` and `eval` are
+/// given as their little-endian bits, since they live in E's scalar field. The points of `vk` are
+/// constants of the circuit.
+pub fn kzg_verify<E, P>(
+    vk: &VerifierKey<E>,
+    cm: &P::G1Var,
+    z: &[Boolean<KZGConstraintField<E>>],
+    eval: &[Boolean<KZGConstraintField<E>>],
+    proof: &P::G1Var,
+) -> Result<(), SynthesisError>
+where
+    E: Pairing,
+    P: PairingVar<E>,
+{
+    let g = P::G1Var::constant(vk.g.into());
+    let h = P::G2Var::constant(vk.h.into());
+    let beta_h = P::G2Var::constant(vk.beta_h.into());
+
+    let lhs = cm.clone() - g.scalar_mul_le(eval.iter())?;
+    let rhs = beta_h - h.scalar_mul_le(z.iter())?;
+
+    // e(cm - eval⋅g, h) ⋅ e(-proof, β⋅h - z⋅h) == 1
+    let e = P::product_of_pairings(
+        &[P::prepare_g1(&lhs)?, P::prepare_g1(&proof.negate()?)?],
+        &[P::prepare_g2(&h)?, P::prepare_g2(&rhs)?],
+    )?;
+    e.enforce_equal(&P::GTVar::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_377::{
+        constraints::{G1Var, PairingVar as Bls12_377PairingVar},
+        Bls12_377, Fq, Fr,
+    };
+    use ark_ff::{BigInteger, PrimeField};
+    use ark_r1cs_std::alloc::AllocVar;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::{One, UniformRand};
+
+    use crate::commitment::{kzg::KZG, CommitmentScheme};
+
+    /// returns whether the in-circuit check of the opening `(z, eval, proof)` of `cm` is satisfied
+    fn check_opening(
+        vk: &VerifierKey<Bls12_377>,
+        cm: <Bls12_377 as Pairing>::G1,
+        z: Fr,
+        eval: Fr,
+        proof: <Bls12_377 as Pairing>::G1,
+    ) -> bool {
+        let cs = ConstraintSystem::<Fq>::new_ref();
+        let cmVar = G1Var::new_witness(cs.clone(), || Ok(cm)).unwrap();
+        let zVar = Vec::<Boolean<Fq>>::new_witness(cs.clone(), || Ok(z.into_bigint().to_bits_le()))
+            .unwrap();
+        let evalVar =
+            Vec::<Boolean<Fq>>::new_witness(cs.clone(), || Ok(eval.into_bigint().to_bits_le()))
+                .unwrap();
+        let proofVar = G1Var::new_witness(cs.clone(), || Ok(proof)).unwrap();
+        kzg_verify::<Bls12_377, Bls12_377PairingVar>(vk, &cmVar, &zVar, &evalVar, &proofVar)
+            .unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn test_kzg_verify() {
+        let mut rng = ark_std::test_rng();
+        let n = 8;
+        let (pk, vk) = KZG::<Bls12_377>::setup(&mut rng, n).unwrap();
+        let v: Vec<Fr> = std::iter::repeat_with(|| Fr::rand(&mut rng))
+            .take(n)
+            .collect();
+        let cm = KZG::<Bls12_377>::commit(&pk, &v, &Fr::from(0_u32)).unwrap();
+        let z = Fr::rand(&mut rng);
+        let proof =
+            KZG::<Bls12_377>::prove_with_challenge(&pk, z, &v, &Fr::from(0_u32), None).unwrap();
+        KZG::<Bls12_377>::verify_with_challenge(&vk, z, &cm, &proof).unwrap();
+
+        assert!(check_opening(&vk, cm, z, proof.eval, proof.proof));
+
+        // a wrong evaluation, point or commitment does not satisfy the constraints
+        assert!(!check_opening(
+            &vk,
+            cm,
+            z,
+            proof.eval + Fr::one(),
+            proof.proof
+        ));
+        assert!(!check_opening(
+            &vk,
+            cm,
+            z + Fr::one(),
+            proof.eval,
+            proof.proof
+        ));
+        assert!(!check_opening(&vk, cm + vk.g, z, proof.eval, proof.proof));
+    }
+}
@@ -0,0 +1,11 @@
+/// Gadgets to be used inside the FCircuit implementations.
+pub mod bigint;
+pub mod ecdsa;
+pub mod fixed_point;
+pub mod kzg_verify;
+pub mod sort;
+
+pub use bigint::{add_mod, mul_mod, pow_mod, BigIntConfig};
+pub use ecdsa::ecdsa_verify;
+pub use kzg_verify::kzg_verify;
+pub use sort::{is_permutation, is_sorted};
@@ -0,0 +1,116 @@
+use ark_crypto_primitives::crh::{
+    poseidon::constraints::{CRHGadget, CRHParametersVar},
+    CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+};
+use ark_relations::r1cs::SynthesisError;
+use core::cmp::Ordering;
+
+/// returns true if the given values are sorted in non-decreasing order, comparing them as
+/// integers. Native counterpart of the `is_sorted` gadget.
+pub fn is_sorted_native<F: PrimeField>(v: &[F]) -> bool {
+    v.windows(2)
+        .all(|w| w[0].into_bigint() <= w[1].into_bigint())
+}
+
+/// returns true if `b` is a permutation of `a`. Native counterpart of the `is_permutation`
+/// gadget.
+pub fn is_permutation_native<F: PrimeField>(a: &[F], b: &[F]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort();
+    b.sort();
+    a == b
+}
+
+/// is_sorted returns a Boolean which is true if the given values are sorted in non-decreasing
+/// order. The comparison gadgets require the values to be smaller or equal than `(p-1)/2`, the
+/// circuit is unsatisfiable otherwise.
+pub fn is_sorted<F: PrimeField>(v: &[FpVar<F>]) -> Result<Boolean<F>, SynthesisError> {
+    let mut sorted = Boolean::TRUE;
+    for w in v.windows(2) {
+        // w[0] <= w[1]
+        sorted = sorted.and(&w[0].is_cmp(&w[1], Ordering::Less, true)?)?;
+    }
+    Ok(sorted)
+}
+
+/// is_permutation returns a Boolean which is true if `b` is a permutation of `a`. It checks that
+/// `Π (r - a_i) == Π (r - b_i)`, where the challenge `r = H(a, b)` is computed in-circuit after
+/// both vectors are fixed, so by the Schwartz-Zippel lemma the check only passes for a
+/// non-permutation with negligible probability.
+pub fn is_permutation<F: PrimeField + Absorb>(
+    crh_params: &CRHParametersVar<F>,
+    a: &[FpVar<F>],
+    b: &[FpVar<F>],
+) -> Result<Boolean<F>, SynthesisError> {
+    if a.len() != b.len() {
+        return Ok(Boolean::FALSE);
+    }
+    let r = CRHGadget::<F>::evaluate(crh_params, &[a, b].concat())?;
+    let prod_a = a.iter().fold(FpVar::one(), |acc, a_i| acc * (&r - a_i));
+    let prod_b = b.iter().fold(FpVar::one(), |acc, b_i| acc * (&r - b_i));
+    prod_a.is_eq(&prod_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::transcript::poseidon::poseidon_test_config;
+
+    fn to_fr(v: &[u32]) -> Vec<Fr> {
+        v.iter().map(|x| Fr::from(*x)).collect()
+    }
+
+    #[test]
+    fn test_is_sorted() {
+        for (v, expected) in [
+            (to_fr(&[1, 2, 2, 5, 9]), true),
+            (to_fr(&[]), true),
+            (to_fr(&[3]), true),
+            (to_fr(&[1, 5, 2, 9]), false),
+            (to_fr(&[9, 1]), false),
+        ] {
+            assert_eq!(is_sorted_native(&v), expected);
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let vVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(v)).unwrap();
+            let sorted = is_sorted(&vVar).unwrap();
+            sorted.enforce_equal(&Boolean::TRUE).unwrap();
+            assert_eq!(sorted.value().unwrap(), expected);
+            assert_eq!(cs.is_satisfied().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_is_permutation() {
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let a = to_fr(&[4, 1, 3, 1]);
+        for (b, expected) in [
+            (to_fr(&[1, 1, 3, 4]), true),
+            (to_fr(&[1, 3, 3, 4]), false),
+            (to_fr(&[1, 3, 4]), false),
+        ] {
+            assert_eq!(is_permutation_native(&a, &b), expected);
+
+            let cs = ConstraintSystem::<Fr>::new_ref();
+            let crh_params =
+                CRHParametersVar::<Fr>::new_constant(cs.clone(), poseidon_config.clone()).unwrap();
+            let aVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(a.clone())).unwrap();
+            let bVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(b)).unwrap();
+            let is_perm = is_permutation(&crh_params, &aVar, &bVar).unwrap();
+            assert_eq!(is_perm.value().unwrap(), expected);
+            assert!(cs.is_satisfied().unwrap());
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// LoggingFCircuit wraps an FCircuit and lets each step emit a log entry, which is accumulated
+/// into the folded state through a Poseidon hash chain: `acc_{i+1} = H(acc_i, log_i)`. Since the
+/// accumulator is part of the state, a valid IVC proof for `z_i` commits to all the log entries
+/// emitted in the steps, which can later be proven through `LogInclusionProof`.
+///
+/// The log entry of each step is given as the last `log_len` elements of the external inputs,
+/// after the external inputs of the inner FCircuit. The accumulator is the last element of the
+/// state, and it should be set to 0 in `z_0`.
+#[derive(Clone, Debug)]
+pub struct LoggingFCircuit<F: PrimeField, FC> {
+    pub inner: FC,
+    pub poseidon_config: PoseidonConfig<F>,
+    pub log_len: usize,
+}
+
+/// LogInclusionProof proves that a log entry was emitted at some step, by providing the value of
+/// the accumulator before the entry and all the entries emitted afterwards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LogInclusionProof<F: PrimeField> {
+    pub acc_before: F,
+    pub entry: Vec<F>,
+    pub entries_after: Vec<Vec<F>>,
+}
+
+/// returns the accumulator resulting of appending the log `entry` to the accumulator `acc`
+pub fn accumulate_log<F: PrimeField + Absorb>(
+    poseidon_config: &PoseidonConfig<F>,
+    acc: F,
+    entry: &[F],
+) -> Result<F, Error> {
+    CRH::<F>::evaluate(poseidon_config, [vec![acc], entry.to_vec()].concat()).map_err(|e| {
+        Error::CRHFail {
+            reason: e.to_string(),
+        }
+    })
+}
+
+impl<F: PrimeField + Absorb, FC: FCircuit<F>> LoggingFCircuit<F, FC> {
+    /// computes the accumulator of the given log entries, starting from the 0 accumulator
+    pub fn logs_accumulator(&self, logs: &[Vec<F>]) -> Result<F, Error> {
+        logs.iter().try_fold(F::zero(), |acc, entry| {
+            accumulate_log(&self.poseidon_config, acc, entry)
+        })
+    }
+
+    /// returns the inclusion proof of the `index`-th of the given log entries
+    pub fn prove_log_inclusion(
+        &self,
+        logs: &[Vec<F>],
+        index: usize,
+    ) -> Result<LogInclusionProof<F>, Error> {
+        let entry = logs.get(index).ok_or(Error::OutOfBounds)?.clone();
+        Ok(LogInclusionProof {
+            acc_before: self.logs_accumulator(&logs[..index])?,
+            entry,
+            entries_after: logs[index + 1..].to_vec(),
+        })
+    }
+
+    /// checks that the given proof leads to the accumulator `acc`, which is the last element of
+    /// the state of the IVC
+    pub fn verify_log_inclusion(
+        &self,
+        acc: F,
+        proof: &LogInclusionProof<F>,
+    ) -> Result<bool, Error> {
+        let mut computed_acc =
+            accumulate_log(&self.poseidon_config, proof.acc_before, &proof.entry)?;
+        for entry in proof.entries_after.iter() {
+            computed_acc = accumulate_log(&self.poseidon_config, computed_acc, entry)?;
+        }
+        Ok(computed_acc == acc)
+    }
+}
+
+impl<F: PrimeField + Absorb, FC: FCircuit<F>> FCircuit<F> for LoggingFCircuit<F, FC> {
+    /// parameters of the inner FCircuit, the Poseidon config used for the accumulator, and the
+    /// length of the log entries
+    type Params = (FC::Params, PoseidonConfig<F>, usize);
+    /// the field elements of the inner external inputs followed by the log entry
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (inner_params, poseidon_config, log_len) = params;
+        Ok(Self {
+            inner: FC::new(inner_params)?,
+            poseidon_config,
+            log_len,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len() + 1
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.inner.external_inputs_len() + self.log_len
+    }
+    // the external inputs of the inner FCircuit go first, so its beacon is kept
+    fn beacon_mode(&self) -> bool {
+        self.inner.beacon_mode()
+    }
+    // the IO of the step is the one of the inner FCircuit, over its part of the state and of the
+    // external inputs
+    fn io_len(&self) -> usize {
+        self.inner.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let (_, z_i) = z_i.split_last().ok_or(Error::Empty)?;
+        let (inner_inputs, _) = external_inputs.split_at(self.inner.external_inputs_len());
+        self.inner.step_io_native(
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::from_field_elements(inner_inputs.to_vec())?,
+        )
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let (_, z_i) = z_i.split_last().ok_or(SynthesisError::Unsatisfiable)?;
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (inner_inputs, _) = external_inputs.split_at(self.inner.external_inputs_len());
+        self.inner.generate_step_io_constraints(
+            cs,
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::var_from_field_elements(inner_inputs.to_vec())?,
+        )
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let (acc, z_i) = z_i.split_last().ok_or(Error::Empty)?;
+        let (inner_inputs, entry) = external_inputs.split_at(self.inner.external_inputs_len());
+        let mut z_i1 = self.inner.step_native(
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::from_field_elements(inner_inputs.to_vec())?,
+        )?;
+        z_i1.push(accumulate_log(&self.poseidon_config, *acc, entry)?);
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        let (acc, z_i) = z_i.split_last().ok_or(SynthesisError::Unsatisfiable)?;
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (inner_inputs, entry) = external_inputs.split_at(self.inner.external_inputs_len());
+        let crh_params =
+            CRHParametersVar::<F>::new_constant(cs.clone(), self.poseidon_config.clone())?;
+        let acc_i1 =
+            CRHGadget::<F>::evaluate(&crh_params, &[vec![acc.clone()], entry.to_vec()].concat())?;
+        let mut z_i1 = self.inner.generate_step_constraints(
+            cs,
+            i,
+            z_i.to_vec(),
+            FC::ExternalInputs::var_from_field_elements(inner_inputs.to_vec())?,
+        )?;
+        z_i1.push(acc_i1);
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::Zero;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    type FC = LoggingFCircuit<Fr, CubicFCircuit<Fr>>;
+
+    #[test]
+    fn test_logging_fcircuit_constraints() {
+        let F_circuit = FC::new(((), poseidon_test_config::<Fr>(), 2)).unwrap();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_i = vec![Fr::from(3_u32), Fr::from(7_u32)];
+        let log = vec![Fr::from(1_u32), Fr::from(2_u32)];
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        let logVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(log.clone())).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, logVar)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            z_i1Var.value().unwrap(),
+            F_circuit.step_native(0, z_i, log).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_logging_fcircuit_ivc() {
+        type NOVA = TestNova<FC>;
+
+        let F_circuit = FC::new(((), poseidon_test_config::<Fr>(), 1)).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32), Fr::zero()];
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        let logs: Vec<Vec<Fr>> = (0..3).map(|i| vec![Fr::from(100 + i as u32)]).collect();
+        for log in logs.iter() {
+            nova.prove_step(log.clone()).unwrap();
+        }
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i.clone(),
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+
+        // the accumulator in the verified state commits to the emitted logs
+        let acc = nova.z_i[1];
+        assert_eq!(acc, F_circuit.logs_accumulator(&logs).unwrap());
+
+        let proof = F_circuit.prove_log_inclusion(&logs, 1).unwrap();
+        assert!(F_circuit.verify_log_inclusion(acc, &proof).unwrap());
+
+        // a log entry that was not emitted can not be proven
+        let mut wrong_proof = proof.clone();
+        wrong_proof.entry = vec![Fr::from(42_u32)];
+        assert!(!F_circuit.verify_log_inclusion(acc, &wrong_proof).unwrap());
+    }
+}
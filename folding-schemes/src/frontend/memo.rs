@@ -0,0 +1,231 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::{ExternalInputs, ExternalInputsVar, FCircuit};
+use crate::Error;
+
+/// inputs of a native step: the step index, the state and the field elements of the external
+/// inputs
+type StepInputs<F> = (usize, Vec<F>, Vec<F>);
+type StepCache<F> = HashMap<StepInputs<F>, Vec<F>>;
+
+/// MemoFCircuit wraps a deterministic FCircuit and caches the outputs of its native step, keyed
+/// by the step index, the state and the external inputs, so that a step is computed only once
+/// when it is first previewed through `step_native` and then proven through `prove_step`. The
+/// cache is shared between the clones of the MemoFCircuit, so the instance kept by the caller
+/// shares it with the one given to the folding scheme.
+///
+/// The cache is never evicted, see `clear_cache`. The constraints are the ones of the inner
+/// FCircuit.
+#[derive(Clone, Debug)]
+pub struct MemoFCircuit<F: PrimeField, FC> {
+    pub inner: FC,
+    cache: Arc<Mutex<StepCache<F>>>,
+}
+
+impl<F: PrimeField, FC: FCircuit<F>> MemoFCircuit<F, FC> {
+    /// returns the number of cached native steps
+    pub fn cache_len(&self) -> Result<usize, Error> {
+        Ok(self.lock_cache()?.len())
+    }
+
+    /// removes all the cached native steps
+    pub fn clear_cache(&self) -> Result<(), Error> {
+        self.lock_cache()?.clear();
+        Ok(())
+    }
+
+    fn lock_cache(&self) -> Result<MutexGuard<'_, StepCache<F>>, Error> {
+        self.cache.lock().map_err(|_| Error::CachePoisoned)
+    }
+}
+
+impl<F: PrimeField, FC: FCircuit<F>> FCircuit<F> for MemoFCircuit<F, FC> {
+    /// parameters of the inner FCircuit
+    type Params = FC::Params;
+    type ExternalInputs = FC::ExternalInputs;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        Ok(Self {
+            inner: FC::new(params)?,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len()
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.inner.external_inputs_len()
+    }
+    fn beacon_mode(&self) -> bool {
+        self.inner.beacon_mode()
+    }
+    // the IO is not cached, since it is computed once per step
+    fn io_len(&self) -> usize {
+        self.inner.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Self::ExternalInputs,
+    ) -> Result<Vec<F>, Error> {
+        self.inner.step_io_native(i, z_i, external_inputs)
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: ExternalInputsVar<F, Self>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        self.inner
+            .generate_step_io_constraints(cs, i, z_i, external_inputs)
+    }
+    fn step_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Self::ExternalInputs,
+    ) -> Result<Vec<F>, Error> {
+        let key = (i, z_i, external_inputs.to_field_elements());
+        if let Some(z_i1) = self.lock_cache()?.get(&key) {
+            return Ok(z_i1.clone());
+        }
+        // the lock is not held while computing the step, since it can be expensive
+        let z_i1 = self
+            .inner
+            .step_native(key.0, key.1.clone(), external_inputs)?;
+        self.lock_cache()?.insert(key, z_i1.clone());
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: ExternalInputsVar<F, Self>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        self.inner
+            .generate_step_constraints(cs, i, z_i, external_inputs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::FoldingScheme;
+
+    /// CountingFCircuit is the CubicFCircuit, counting the calls to its native step
+    #[derive(Clone, Debug)]
+    struct CountingFCircuit {
+        cubic: CubicFCircuit<Fr>,
+        calls: Arc<AtomicUsize>,
+    }
+    impl FCircuit<Fr> for CountingFCircuit {
+        type Params = Arc<AtomicUsize>;
+        type ExternalInputs = ();
+
+        fn new(calls: Self::Params) -> Result<Self, Error> {
+            Ok(Self {
+                cubic: CubicFCircuit::new(())?,
+                calls,
+            })
+        }
+        fn state_len(&self) -> usize {
+            self.cubic.state_len()
+        }
+        fn external_inputs_len(&self) -> usize {
+            self.cubic.external_inputs_len()
+        }
+        fn step_native(
+            &self,
+            i: usize,
+            z_i: Vec<Fr>,
+            external_inputs: (),
+        ) -> Result<Vec<Fr>, Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.cubic.step_native(i, z_i, external_inputs)
+        }
+        fn generate_step_constraints(
+            &self,
+            cs: ConstraintSystemRef<Fr>,
+            i: usize,
+            z_i: Vec<FpVar<Fr>>,
+            external_inputs: (),
+        ) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+            self.cubic
+                .generate_step_constraints(cs, i, z_i, external_inputs)
+        }
+    }
+
+    type FC = MemoFCircuit<Fr, CountingFCircuit>;
+
+    #[test]
+    fn test_memo_fcircuit_step_native() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let F_circuit = FC::new(calls.clone()).unwrap();
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let z_1 = F_circuit.step_native(0, z_0.clone(), ()).unwrap();
+        assert_eq!(F_circuit.step_native(0, z_0.clone(), ()).unwrap(), z_1);
+        // the clones share the cache
+        assert_eq!(
+            F_circuit.clone().step_native(0, z_0.clone(), ()).unwrap(),
+            z_1
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // a different input is computed by the inner FCircuit
+        F_circuit.step_native(1, z_1.clone(), ()).unwrap();
+        F_circuit.step_native(1, z_0, ()).unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(F_circuit.cache_len().unwrap(), 3);
+
+        F_circuit.clear_cache().unwrap();
+        assert_eq!(F_circuit.cache_len().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_memo_fcircuit_preview_then_prove() {
+        type NOVA = TestNova<FC>;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let F_circuit = FC::new(calls.clone()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        let num_steps = 3;
+        for i in 0..num_steps {
+            // preview the next state, and then prove the step
+            let preview = F_circuit.step_native(i, nova.z_i.clone(), ()).unwrap();
+            nova.prove_step(()).unwrap();
+            assert_eq!(nova.z_i, preview);
+        }
+        // the native step was computed once per step
+        assert_eq!(calls.load(Ordering::SeqCst), num_steps);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
@@ -4,7 +4,115 @@ use ark_r1cs_std::fields::fp::FpVar;
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
 use ark_std::fmt::Debug;
 
+pub mod beacon;
+pub mod bounded;
+pub mod bounded_input;
+#[cfg(feature = "fs")]
 pub mod circom;
+pub mod compose;
+pub mod examples;
+pub mod fn_circuit;
+pub mod fsm;
+pub mod gadgets;
+pub mod logging;
+pub mod memo;
+pub mod multiplier;
+pub mod parallel;
+pub mod parallel_tracks;
+pub mod program;
+pub mod smt;
+
+pub use beacon::BeaconFCircuit;
+pub use bounded::BoundedFCircuit;
+pub use bounded_input::BoundedInputFCircuit;
+pub use compose::Compose;
+pub use fn_circuit::FnFCircuit;
+pub use fsm::FsmFCircuit;
+pub use logging::LoggingFCircuit;
+pub use memo::MemoFCircuit;
+pub use multiplier::StepMultiplier;
+pub use parallel::ParallelFCircuit;
+pub use parallel_tracks::ParallelTracksFCircuit;
+pub use program::ProgramFCircuit;
+pub use smt::SmtUpdateFCircuit;
+
+/// ExternalInputs is implemented by the types of the external inputs that an FCircuit takes at
+/// each step (see `FCircuit::ExternalInputs`). The folding schemes handle the external inputs as
+/// field elements, which are converted from and into the typed inputs through this trait.
+pub trait ExternalInputs<F: PrimeField>: Clone + Debug {
+    /// in-circuit counterpart of the external inputs
+    type Var: Clone + Debug;
+
+    /// returns the field elements representing the external inputs
+    fn to_field_elements(&self) -> Vec<F>;
+
+    /// returns the external inputs represented by the given field elements
+    fn from_field_elements(elems: Vec<F>) -> Result<Self, Error>;
+
+    /// returns the in-circuit external inputs represented by the given allocated field elements,
+    /// compatible with `from_field_elements`
+    fn var_from_field_elements(elems: Vec<FpVar<F>>) -> Result<Self::Var, SynthesisError>;
+}
+
+/// in-circuit external inputs of the FCircuit `FC`
+pub type ExternalInputsVar<F, FC> = <<FC as FCircuit<F>>::ExternalInputs as ExternalInputs<F>>::Var;
+
+/// no external inputs
+impl<F: PrimeField> ExternalInputs<F> for () {
+    type Var = ();
+
+    fn to_field_elements(&self) -> Vec<F> {
+        vec![]
+    }
+    fn from_field_elements(elems: Vec<F>) -> Result<Self, Error> {
+        if !elems.is_empty() {
+            return Err(Error::NotExpectedLength {
+                got: elems.len(),
+                expected: 0,
+            });
+        }
+        Ok(())
+    }
+    fn var_from_field_elements(elems: Vec<FpVar<F>>) -> Result<Self::Var, SynthesisError> {
+        if !elems.is_empty() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        Ok(())
+    }
+}
+
+/// external inputs given as a list of field elements, of length `FCircuit::external_inputs_len`
+impl<F: PrimeField> ExternalInputs<F> for Vec<F> {
+    type Var = Vec<FpVar<F>>;
+
+    fn to_field_elements(&self) -> Vec<F> {
+        self.clone()
+    }
+    fn from_field_elements(elems: Vec<F>) -> Result<Self, Error> {
+        Ok(elems)
+    }
+    fn var_from_field_elements(elems: Vec<FpVar<F>>) -> Result<Self::Var, SynthesisError> {
+        Ok(elems)
+    }
+}
+
+/// a fixed number of external inputs
+impl<F: PrimeField, const N: usize> ExternalInputs<F> for [F; N] {
+    type Var = [FpVar<F>; N];
+
+    fn to_field_elements(&self) -> Vec<F> {
+        self.to_vec()
+    }
+    fn from_field_elements(elems: Vec<F>) -> Result<Self, Error> {
+        let got = elems.len();
+        elems
+            .try_into()
+            .map_err(|_| Error::NotExpectedLength { got, expected: N })
+    }
+    fn var_from_field_elements(elems: Vec<FpVar<F>>) -> Result<Self::Var, SynthesisError> {
+        elems.try_into().map_err(|_| SynthesisError::Unsatisfiable)
+    }
+}
 
 /// FCircuit defines the trait of the circuit of the F function, which is the one being folded (ie.
 /// inside the agmented F' function).
@@ -12,6 +120,9 @@ pub mod circom;
 /// the step.
 pub trait FCircuit<F: PrimeField>: Clone + Debug {
     type Params: Debug;
+    /// type of the external inputs of each step, which are not part of the state. FCircuits that
+    /// do not use external inputs set it to `()`.
+    type ExternalInputs: ExternalInputs<F>;
 
     /// returns a new FCircuit instance
     fn new(params: Self::Params) -> Result<Self, Error>;
@@ -20,10 +131,49 @@ pub trait FCircuit<F: PrimeField>: Clone + Debug {
     /// FCircuit inputs.
     fn state_len(&self) -> usize;
 
-    /// returns the number of elements in the external inputs used by the FCircuit. External inputs
-    /// are optional, and in case no external inputs are used, this method should return 0.
+    /// returns the number of field elements of the external inputs used by the FCircuit (see
+    /// `ExternalInputs::to_field_elements`). External inputs are optional, and in case no external
+    /// inputs are used, this method should return 0.
     fn external_inputs_len(&self) -> usize;
 
+    /// returns true if the first external input of each step is a beacon value (eg. from a public
+    /// randomness beacon), which is then absorbed into the transcript of the folding challenge of
+    /// the step. Such FCircuits need to bind the beacon values to the state in order for them to
+    /// be checked by the verifier, see `BeaconFCircuit`.
+    fn beacon_mode(&self) -> bool {
+        false
+    }
+
+    /// returns the number of public outputs (IO) exposed by each step, which are computed by
+    /// `step_io_native` separately from the next state, and accumulated into the public input of
+    /// the folded instances so that the verifier can check them without the state (see
+    /// `FoldingScheme::verify_with_io`). Defaults to 0, ie. the FCircuit does not expose IO.
+    fn io_len(&self) -> usize {
+        0
+    }
+
+    /// computes the public outputs of the step for the given z_i, of length `io_len`
+    fn step_io_native(
+        &self,
+        _i: usize,
+        _z_i: Vec<F>,
+        _external_inputs: Self::ExternalInputs,
+    ) -> Result<Vec<F>, Error> {
+        Ok(vec![])
+    }
+
+    /// generates the constraints computing the public outputs of the step for the given z_i,
+    /// compatible with `step_io_native`
+    fn generate_step_io_constraints(
+        &self,
+        _cs: ConstraintSystemRef<F>,
+        _i: usize,
+        _z_i: Vec<FpVar<F>>,
+        _external_inputs: ExternalInputsVar<F, Self>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        Ok(vec![])
+    }
+
     /// computes the next state values in place, assigning z_{i+1} into z_i, and computing the new
     /// z_{i+1}
     fn step_native(
@@ -32,7 +182,7 @@ pub trait FCircuit<F: PrimeField>: Clone + Debug {
         &self,
         i: usize,
         z_i: Vec<F>,
-        external_inputs: Vec<F>, // inputs that are not part of the state
+        external_inputs: Self::ExternalInputs, // inputs that are not part of the state
     ) -> Result<Vec<F>, Error>;
 
     /// generates the constraints for the step of F for the given z_i
@@ -43,7 +193,7 @@ pub trait FCircuit<F: PrimeField>: Clone + Debug {
         cs: ConstraintSystemRef<F>,
         i: usize,
         z_i: Vec<FpVar<F>>,
-        external_inputs: Vec<FpVar<F>>, // inputs that are not part of the state
+        external_inputs: ExternalInputsVar<F, Self>, // inputs that are not part of the state
     ) -> Result<Vec<FpVar<F>>, SynthesisError>;
 }
 
@@ -67,6 +217,7 @@ pub mod tests {
     }
     impl<F: PrimeField> FCircuit<F> for CubicFCircuit<F> {
         type Params = ();
+        type ExternalInputs = ();
         fn new(_params: Self::Params) -> Result<Self, Error> {
             Ok(Self { _f: PhantomData })
         }
@@ -80,7 +231,7 @@ pub mod tests {
             &self,
             _i: usize,
             z_i: Vec<F>,
-            _external_inputs: Vec<F>,
+            _external_inputs: (),
         ) -> Result<Vec<F>, Error> {
             Ok(vec![z_i[0] * z_i[0] * z_i[0] + z_i[0] + F::from(5_u32)])
         }
@@ -89,7 +240,7 @@ pub mod tests {
             cs: ConstraintSystemRef<F>,
             _i: usize,
             z_i: Vec<FpVar<F>>,
-            _external_inputs: Vec<FpVar<F>>,
+            _external_inputs: (),
         ) -> Result<Vec<FpVar<F>>, SynthesisError> {
             let five = FpVar::<F>::new_constant(cs.clone(), F::from(5u32))?;
             let z_i = z_i[0].clone();
@@ -107,6 +258,7 @@ pub mod tests {
     }
     impl<F: PrimeField> FCircuit<F> for CustomFCircuit<F> {
         type Params = usize;
+        type ExternalInputs = ();
 
         fn new(params: Self::Params) -> Result<Self, Error> {
             Ok(Self {
@@ -124,7 +276,7 @@ pub mod tests {
             &self,
             _i: usize,
             z_i: Vec<F>,
-            _external_inputs: Vec<F>,
+            _external_inputs: (),
         ) -> Result<Vec<F>, Error> {
             let mut z_i1 = F::one();
             for _ in 0..self.n_constraints - 1 {
@@ -137,7 +289,7 @@ pub mod tests {
             cs: ConstraintSystemRef<F>,
             _i: usize,
             z_i: Vec<FpVar<F>>,
-            _external_inputs: Vec<FpVar<F>>,
+            _external_inputs: (),
         ) -> Result<Vec<FpVar<F>>, SynthesisError> {
             let mut z_i1 = FpVar::<F>::new_witness(cs.clone(), || Ok(F::one()))?;
             for _ in 0..self.n_constraints - 1 {
@@ -170,9 +322,13 @@ pub mod tests {
             let z_i1 = Vec::<FpVar<F>>::new_input(cs.clone(), || {
                 Ok(self.z_i1.unwrap_or(vec![F::zero()]))
             })?;
+            let external_inputs = Vec::<FpVar<F>>::new_witness(cs.clone(), || {
+                Ok(vec![F::zero(); self.FC.external_inputs_len()])
+            })?;
+            let external_inputs = FC::ExternalInputs::var_from_field_elements(external_inputs)?;
             let computed_z_i1 =
                 self.FC
-                    .generate_step_constraints(cs.clone(), 0, z_i.clone(), vec![])?;
+                    .generate_step_constraints(cs.clone(), 0, z_i.clone(), external_inputs)?;
 
             computed_z_i1.enforce_equal(&z_i1)?;
             Ok(())
@@ -202,7 +358,7 @@ pub mod tests {
         let wrapper_circuit = WrapperCircuit::<Fr, CustomFCircuit<Fr>> {
             FC: custom_circuit,
             z_i: Some(z_i.clone()),
-            z_i1: Some(custom_circuit.step_native(0, z_i, vec![]).unwrap()),
+            z_i1: Some(custom_circuit.step_native(0, z_i, ()).unwrap()),
         };
         wrapper_circuit.generate_constraints(cs.clone()).unwrap();
         assert_eq!(cs.num_constraints(), n_constraints);
@@ -0,0 +1,239 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// StepMultiplier wraps an FCircuit and applies `N` steps of it at each folding step, chaining
+/// the state of each inner step into the next one, so that the overhead of the folding is
+/// amortized over `N` steps of the inner FCircuit. The `i`-th folding step runs the inner steps
+/// `i*N` to `i*N + N - 1`.
+///
+/// The external inputs of each step are the concatenation of the external inputs of the `N`
+/// inner steps. The beacon mode of the inner FCircuit is not kept, since only one beacon value is
+/// absorbed per folding step.
+#[derive(Clone, Copy, Debug)]
+pub struct StepMultiplier<FC, const N: usize> {
+    pub inner: FC,
+}
+
+impl<F: PrimeField, FC: FCircuit<F>, const N: usize> FCircuit<F> for StepMultiplier<FC, N> {
+    /// parameters of the inner FCircuit
+    type Params = FC::Params;
+    /// the field elements of the external inputs of the `N` inner steps
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        if N == 0 {
+            return Err(Error::ZeroInnerSteps);
+        }
+        Ok(Self {
+            inner: FC::new(params)?,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.inner.state_len()
+    }
+    fn external_inputs_len(&self) -> usize {
+        N * self.inner.external_inputs_len()
+    }
+    // the IO of the step is the concatenation of the IO of the `N` inner steps, each over the
+    // state of its inner step
+    fn io_len(&self) -> usize {
+        N * self.inner.io_len()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        if self.inner.io_len() == 0 {
+            return Ok(vec![]);
+        }
+        let inputs_len = self.inner.external_inputs_len();
+        let mut io = Vec::with_capacity(self.io_len());
+        let mut z = z_i;
+        for j in 0..N {
+            let inputs = FC::ExternalInputs::from_field_elements(
+                external_inputs[j * inputs_len..(j + 1) * inputs_len].to_vec(),
+            )?;
+            io.extend(
+                self.inner
+                    .step_io_native(i * N + j, z.clone(), inputs.clone())?,
+            );
+            if j + 1 < N {
+                z = self.inner.step_native(i * N + j, z, inputs)?;
+            }
+        }
+        Ok(io)
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        if self.inner.io_len() == 0 {
+            return Ok(vec![]);
+        }
+        // the states of the inner steps are recomputed, since the IO constraints only get `z_i`
+        let inputs_len = self.inner.external_inputs_len();
+        let mut io = Vec::with_capacity(self.io_len());
+        let mut z = z_i;
+        for j in 0..N {
+            let inputs = FC::ExternalInputs::var_from_field_elements(
+                external_inputs[j * inputs_len..(j + 1) * inputs_len].to_vec(),
+            )?;
+            io.extend(self.inner.generate_step_io_constraints(
+                cs.clone(),
+                i * N + j,
+                z.clone(),
+                inputs.clone(),
+            )?);
+            if j + 1 < N {
+                z = self
+                    .inner
+                    .generate_step_constraints(cs.clone(), i * N + j, z, inputs)?;
+            }
+        }
+        Ok(io)
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let inputs_len = self.inner.external_inputs_len();
+        (0..N).try_fold(z_i, |z, j| {
+            self.inner.step_native(
+                i * N + j,
+                z,
+                FC::ExternalInputs::from_field_elements(
+                    external_inputs[j * inputs_len..(j + 1) * inputs_len].to_vec(),
+                )?,
+            )
+        })
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let inputs_len = self.inner.external_inputs_len();
+        (0..N).try_fold(z_i, |z, j| {
+            self.inner.generate_step_constraints(
+                cs.clone(),
+                i * N + j,
+                z,
+                FC::ExternalInputs::var_from_field_elements(
+                    external_inputs[j * inputs_len..(j + 1) * inputs_len].to_vec(),
+                )?,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::{alloc::AllocVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::tests::CubicFCircuit;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_step_multiplier_constraints() {
+        let inner = CubicFCircuit::<Fr>::new(()).unwrap();
+        let F_circuit = <StepMultiplier<CubicFCircuit<Fr>, 10> as FCircuit<Fr>>::new(()).unwrap();
+        assert_eq!(F_circuit.state_len(), inner.state_len());
+
+        let z_i = vec![Fr::from(3_u32)];
+        let mut expected = z_i.clone();
+        for j in 0..10 {
+            expected = inner.step_native(j, expected, ()).unwrap();
+        }
+        assert_eq!(
+            F_circuit.step_native(0, z_i.clone(), vec![]).unwrap(),
+            expected
+        );
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i.clone())).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, vec![])
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(z_i1Var.value().unwrap(), expected);
+
+        // the constraints are the ones of the 10 inner steps
+        let inner_cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(inner_cs.clone(), || Ok(z_i)).unwrap();
+        inner
+            .generate_step_constraints(inner_cs.clone(), 0, z_iVar, ())
+            .unwrap();
+        assert_eq!(cs.num_constraints(), 10 * inner_cs.num_constraints());
+    }
+
+    #[test]
+    fn test_step_multiplier_ivc() {
+        type FC = StepMultiplier<CubicFCircuit<Fr>, 3>;
+        type NOVA = TestNova<FC>;
+
+        let F_circuit = <FC as FCircuit<Fr>>::new(()).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit);
+
+        let z_0 = vec![Fr::from(3_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..2 {
+            nova.prove_step(vec![]).unwrap();
+        }
+
+        // 2 folding steps run 6 steps of the inner FCircuit
+        let inner = CubicFCircuit::<Fr>::new(()).unwrap();
+        let mut expected = z_0.clone();
+        for j in 0..6 {
+            expected = inner.step_native(j, expected, ()).unwrap();
+        }
+        assert_eq!(nova.z_i, expected);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
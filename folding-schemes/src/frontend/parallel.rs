@@ -0,0 +1,412 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::fp::{AllocatedFp, FpVar},
+    R1CSVar,
+};
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSystem, ConstraintSystemRef, LinearCombination, SynthesisError,
+    SynthesisMode, Variable,
+};
+use ark_std::fmt::Debug;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+use super::FCircuit;
+use crate::Error;
+
+/// SubFCircuit defines an independent sub-component of the step of a `ParallelFCircuit`, which
+/// takes its own slice of the state and returns its next value.
+pub trait SubFCircuit<F: PrimeField>: Clone + Debug + Send + Sync {
+    /// returns the number of elements of the state used (and returned) by the sub-circuit
+    fn state_len(&self) -> usize;
+
+    /// computes the next value of the slice of the state of the sub-circuit
+    fn step_native(&self, i: usize, z_i: Vec<F>) -> Result<Vec<F>, Error>;
+
+    /// generates the constraints computing the next value of the slice of the state of the
+    /// sub-circuit
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError>;
+}
+
+/// ParallelFCircuit is an FCircuit whose step is composed of independent sub-circuits, each one
+/// operating over its own slice of the state, in the order of `sub_circuits`. Both the native
+/// step and the constraints of the sub-circuits are computed in parallel.
+///
+/// Since the arkworks ConstraintSystem can not be shared between threads, each sub-circuit is
+/// synthesized into its own ConstraintSystem, and the resulting constraints and witnesses are
+/// then merged into the ConstraintSystem of the step, replacing the inputs of the sub-circuits by
+/// the variables of the state. `generate_step_constraints_serial` synthesizes the same
+/// constraints sequentially.
+#[derive(Clone, Debug)]
+pub struct ParallelFCircuit<F: PrimeField, SC: SubFCircuit<F>> {
+    pub sub_circuits: Vec<SC>,
+    _f: core::marker::PhantomData<F>,
+}
+
+/// constraints and witness of a sub-circuit synthesized in its own ConstraintSystem
+struct SynthesizedSubCircuit<F: PrimeField> {
+    matrices: ConstraintMatrices<F>,
+    /// empty in setup mode
+    witness: Vec<F>,
+    /// indexes of the witness variables of the outputs
+    outputs: Vec<usize>,
+}
+
+/// returns the index of the witness variable of the given FpVar, if it is one
+fn witness_index<F: PrimeField>(z: &FpVar<F>) -> Option<usize> {
+    match z {
+        FpVar::Var(z) => match z.variable {
+            Variable::Witness(index) => Some(index),
+            _ => None,
+        },
+        FpVar::Constant(_) => None,
+    }
+}
+
+impl<F: PrimeField, SC: SubFCircuit<F>> ParallelFCircuit<F, SC> {
+    /// returns the offsets of the slices of the state of each sub-circuit
+    fn offsets(&self) -> Vec<usize> {
+        self.sub_circuits
+            .iter()
+            .scan(0, |offset, sc| {
+                let start = *offset;
+                *offset += sc.state_len();
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// synthesizes the constraints of the step sequentially into the given ConstraintSystem,
+    /// which is equivalent to `generate_step_constraints`
+    pub fn generate_step_constraints_serial(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut z_i1 = Vec::with_capacity(z_i.len());
+        for (sc, offset) in self.sub_circuits.iter().zip(self.offsets()) {
+            z_i1.extend(sc.generate_step_constraints(
+                cs.clone(),
+                i,
+                z_i[offset..offset + sc.state_len()].to_vec(),
+            )?);
+        }
+        Ok(z_i1)
+    }
+
+    /// synthesizes the sub-circuit into a new ConstraintSystem, where its inputs are the first
+    /// witness variables
+    fn synthesize(
+        sc: &SC,
+        setup_mode: bool,
+        i: usize,
+        z_i: Option<Vec<F>>,
+    ) -> Result<SynthesizedSubCircuit<F>, SynthesisError> {
+        let cs = ConstraintSystem::<F>::new_ref();
+        if setup_mode {
+            cs.set_mode(SynthesisMode::Setup);
+        }
+        let z_iVar = (0..sc.state_len())
+            .map(|j| {
+                FpVar::new_witness(cs.clone(), || {
+                    z_i.as_ref()
+                        .map(|z_i| z_i[j])
+                        .ok_or(SynthesisError::AssignmentMissing)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let z_i1 = sc.generate_step_constraints(cs.clone(), i, z_iVar)?;
+        if z_i1.len() != sc.state_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        // the outputs need to be witness variables in order to be mapped into the
+        // ConstraintSystem of the step
+        let mut outputs = Vec::with_capacity(z_i1.len());
+        for z in z_i1 {
+            let index = match witness_index(&z) {
+                Some(index) => index,
+                None => {
+                    let out = FpVar::new_witness(cs.clone(), || z.value())?;
+                    out.enforce_equal(&z)?;
+                    witness_index(&out).ok_or(SynthesisError::Unsatisfiable)?
+                }
+            };
+            outputs.push(index);
+        }
+
+        cs.finalize();
+        let matrices = cs.to_matrices().ok_or(SynthesisError::MissingCS)?;
+        let witness = cs
+            .borrow()
+            .ok_or(SynthesisError::MissingCS)?
+            .witness_assignment
+            .clone();
+        Ok(SynthesizedSubCircuit {
+            matrices,
+            witness,
+            outputs,
+        })
+    }
+
+    /// merges the synthesized sub-circuit into the given ConstraintSystem, where `z_i` are the
+    /// inputs of the sub-circuit, and returns its outputs
+    fn merge(
+        cs: ConstraintSystemRef<F>,
+        sub: SynthesizedSubCircuit<F>,
+        z_i: &[FpVar<F>],
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        // each variable of the sub-circuit is mapped to a term (coefficient, variable) of the
+        // ConstraintSystem of the step
+        let mut terms: Vec<(F, Variable)> = vec![(F::one(), Variable::One)];
+        for z in z_i {
+            terms.push(match z {
+                FpVar::Var(z) => (F::one(), z.variable),
+                FpVar::Constant(c) => (*c, Variable::One),
+            });
+        }
+        for j in z_i.len()..sub.matrices.num_witness_variables {
+            let variable = cs.new_witness_variable(|| {
+                sub.witness
+                    .get(j)
+                    .copied()
+                    .ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            terms.push((F::one(), variable));
+        }
+
+        // the sub-circuit has no instance variables other than the constant 1, so the columns of
+        // its matrices are [1, witness variables]
+        let lc = |row: &[(F, usize)]| {
+            row.iter()
+                .fold(LinearCombination::<F>::zero(), |lc, (coeff, col)| {
+                    let (c, v) = terms[*col];
+                    lc + (*coeff * c, v)
+                })
+        };
+        for ((a, b), c) in sub
+            .matrices
+            .a
+            .iter()
+            .zip(sub.matrices.b.iter())
+            .zip(sub.matrices.c.iter())
+        {
+            cs.enforce_constraint(lc(a), lc(b), lc(c))?;
+        }
+
+        Ok(sub
+            .outputs
+            .iter()
+            .map(|j| {
+                let (c, v) = terms[1 + j];
+                if v == Variable::One {
+                    FpVar::Constant(c)
+                } else {
+                    FpVar::Var(AllocatedFp::new(
+                        sub.witness.get(*j).copied(),
+                        v,
+                        cs.clone(),
+                    ))
+                }
+            })
+            .collect())
+    }
+}
+
+impl<F: PrimeField, SC: SubFCircuit<F>> FCircuit<F> for ParallelFCircuit<F, SC> {
+    /// the sub-circuits of the step
+    type Params = Vec<SC>;
+    type ExternalInputs = ();
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        Ok(Self {
+            sub_circuits: params,
+            _f: core::marker::PhantomData,
+        })
+    }
+    fn state_len(&self) -> usize {
+        self.sub_circuits.iter().map(|sc| sc.state_len()).sum()
+    }
+    fn external_inputs_len(&self) -> usize {
+        0
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, _external_inputs: ()) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        let z_i1 = self
+            .sub_circuits
+            .par_iter()
+            .zip(self.offsets())
+            .map(|(sc, offset)| sc.step_native(i, z_i[offset..offset + sc.state_len()].to_vec()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(z_i1.concat())
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        _external_inputs: (),
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let setup_mode = cs.is_in_setup_mode();
+        // the values are extracted before the parallel synthesis, since the variables can not be
+        // shared between threads
+        let z_i_values = self
+            .offsets()
+            .into_iter()
+            .zip(self.sub_circuits.iter())
+            .map(|(offset, sc)| z_i[offset..offset + sc.state_len()].value().ok())
+            .collect::<Vec<_>>();
+        let synthesized = self
+            .sub_circuits
+            .par_iter()
+            .zip(z_i_values)
+            .map(|(sc, z_i)| Self::synthesize(sc, setup_mode, i, z_i))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut z_i1 = Vec::with_capacity(z_i.len());
+        for ((sub, sc), offset) in synthesized
+            .into_iter()
+            .zip(self.sub_circuits.iter())
+            .zip(self.offsets())
+        {
+            z_i1.extend(Self::merge(
+                cs.clone(),
+                sub,
+                &z_i[offset..offset + sc.state_len()],
+            )?);
+        }
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_ff::Field;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::FoldingScheme;
+
+    /// sub-circuit computing z_{i+1} = z_i^exp + i
+    #[derive(Clone, Debug)]
+    struct PowSubFCircuit {
+        exp: usize,
+    }
+    impl SubFCircuit<Fr> for PowSubFCircuit {
+        fn state_len(&self) -> usize {
+            1
+        }
+        fn step_native(&self, i: usize, z_i: Vec<Fr>) -> Result<Vec<Fr>, Error> {
+            Ok(vec![z_i[0].pow([self.exp as u64]) + Fr::from(i as u64)])
+        }
+        fn generate_step_constraints(
+            &self,
+            cs: ConstraintSystemRef<Fr>,
+            i: usize,
+            z_i: Vec<FpVar<Fr>>,
+        ) -> Result<Vec<FpVar<Fr>>, SynthesisError> {
+            let i = FpVar::new_witness(cs, || Ok(Fr::from(i as u64)))?;
+            let mut z = z_i[0].clone();
+            for _ in 1..self.exp {
+                z *= &z_i[0];
+            }
+            Ok(vec![z + i])
+        }
+    }
+
+    fn test_circuit() -> ParallelFCircuit<Fr, PowSubFCircuit> {
+        ParallelFCircuit::new(vec![PowSubFCircuit { exp: 3 }, PowSubFCircuit { exp: 2 }]).unwrap()
+    }
+
+    #[test]
+    fn test_parallel_vs_serial_constraints() {
+        let F_circuit = test_circuit();
+        let z_i = vec![Fr::from(3_u32), Fr::from(5_u32)];
+        let z_i1 = F_circuit.step_native(2, z_i.clone(), ()).unwrap();
+        assert_eq!(z_i1, vec![Fr::from(27 + 2_u32), Fr::from(25 + 2_u32)]);
+
+        let cs_parallel = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar =
+            Vec::<FpVar<Fr>>::new_witness(cs_parallel.clone(), || Ok(z_i.clone())).unwrap();
+        let z_i1_parallel = F_circuit
+            .generate_step_constraints(cs_parallel.clone(), 2, z_iVar, ())
+            .unwrap();
+
+        let cs_serial = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs_serial.clone(), || Ok(z_i.clone())).unwrap();
+        let z_i1_serial = F_circuit
+            .generate_step_constraints_serial(cs_serial.clone(), 2, z_iVar)
+            .unwrap();
+
+        assert!(cs_parallel.is_satisfied().unwrap());
+        assert!(cs_serial.is_satisfied().unwrap());
+        assert_eq!(z_i1_parallel.value().unwrap(), z_i1);
+        assert_eq!(z_i1_serial.value().unwrap(), z_i1);
+
+        // both paths synthesize the same constraints, where the parallel one adds an equality
+        // constraint for each output that is not a witness variable (`z + i` is a linear
+        // combination)
+        cs_parallel.finalize();
+        cs_serial.finalize();
+        assert_eq!(
+            cs_parallel.num_constraints(),
+            cs_serial.num_constraints() + F_circuit.state_len()
+        );
+
+        // a wrong output does not satisfy the merged constraints
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 2, z_iVar, ())
+            .unwrap();
+        let wrong = FpVar::new_witness(cs.clone(), || Ok(Fr::from(1_u32))).unwrap();
+        z_i1Var[0].enforce_equal(&wrong).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_parallel_fcircuit_ivc() {
+        type NOVA = TestNova<ParallelFCircuit<Fr, PowSubFCircuit>>;
+
+        let F_circuit = test_circuit();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let z_0 = vec![Fr::from(3_u32), Fr::from(5_u32)];
+        let mut nova = NOVA::init(&prover_params, F_circuit, z_0.clone()).unwrap();
+        for _ in 0..3 {
+            nova.prove_step(()).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i,
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+    }
+}
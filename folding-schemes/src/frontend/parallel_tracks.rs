@@ -0,0 +1,261 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::{ExternalInputs, FCircuit};
+use crate::Error;
+
+/// ParallelTracksFCircuit folds N independent FCircuits (the tracks) in lockstep, so that a
+/// single IVC proof covers all of them. Its state is the concatenation of the states of the
+/// tracks, in the order of `tracks`, and each step advances all the tracks, `z_{i+1} =
+/// F_1(z_i^1) || ... || F_N(z_i^N)`.
+///
+/// The external inputs of each step are the concatenation of the external inputs of the tracks,
+/// so the beacon mode of the first track is kept.
+#[derive(Clone, Debug)]
+pub struct ParallelTracksFCircuit<T> {
+    pub tracks: T,
+}
+
+impl<F: PrimeField, FC: FCircuit<F>, const N: usize> ParallelTracksFCircuit<[FC; N]> {
+    /// returns the slices of `v` of each track, where `len` returns the length of the slice of
+    /// the given track
+    fn split<'a, V>(&self, v: &'a [V], len: impl Fn(&FC) -> usize) -> Vec<&'a [V]> {
+        let mut offset = 0;
+        self.tracks
+            .iter()
+            .map(|track| {
+                let slice = &v[offset..offset + len(track)];
+                offset += len(track);
+                slice
+            })
+            .collect()
+    }
+
+    /// returns the last state of the given track, in the concatenated state `z_i`
+    pub fn track_state<'a>(&self, z_i: &'a [F], track: usize) -> &'a [F] {
+        self.split(z_i, FC::state_len)[track]
+    }
+}
+
+impl<F: PrimeField, FC: FCircuit<F>, const N: usize> FCircuit<F>
+    for ParallelTracksFCircuit<[FC; N]>
+{
+    /// parameters of each one of the tracks
+    type Params = [FC::Params; N];
+    /// the field elements of the external inputs of the tracks, in the order of `tracks`
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let tracks = params
+            .into_iter()
+            .map(FC::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        let tracks = tracks
+            .try_into()
+            .map_err(|tracks: Vec<FC>| Error::NotExpectedLength {
+                got: tracks.len(),
+                expected: N,
+            })?;
+        Ok(Self { tracks })
+    }
+    fn state_len(&self) -> usize {
+        self.tracks.iter().map(FC::state_len).sum()
+    }
+    fn external_inputs_len(&self) -> usize {
+        self.tracks.iter().map(FC::external_inputs_len).sum()
+    }
+    fn beacon_mode(&self) -> bool {
+        self.tracks.first().is_some_and(FC::beacon_mode)
+    }
+    // the IO of the step is the concatenation of the IO of the tracks, in the order of `tracks`
+    fn io_len(&self) -> usize {
+        self.tracks.iter().map(FC::io_len).sum()
+    }
+    fn step_io_native(
+        &self,
+        i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let mut io = Vec::with_capacity(self.io_len());
+        for ((track, z), inputs) in self
+            .tracks
+            .iter()
+            .zip(self.split(&z_i, FC::state_len))
+            .zip(self.split(&external_inputs, FC::external_inputs_len))
+        {
+            io.extend(track.step_io_native(
+                i,
+                z.to_vec(),
+                FC::ExternalInputs::from_field_elements(inputs.to_vec())?,
+            )?);
+        }
+        Ok(io)
+    }
+    fn generate_step_io_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() || external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut io = Vec::with_capacity(self.io_len());
+        for ((track, z), inputs) in self
+            .tracks
+            .iter()
+            .zip(self.split(&z_i, FC::state_len))
+            .zip(self.split(&external_inputs, FC::external_inputs_len))
+        {
+            io.extend(track.generate_step_io_constraints(
+                cs.clone(),
+                i,
+                z.to_vec(),
+                FC::ExternalInputs::var_from_field_elements(inputs.to_vec())?,
+            )?);
+        }
+        Ok(io)
+    }
+    fn step_native(&self, i: usize, z_i: Vec<F>, external_inputs: Vec<F>) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotSameLength {
+                a: "external_inputs.len()",
+                a_len: external_inputs.len(),
+                b: "external_inputs_len()",
+                b_len: self.external_inputs_len(),
+            });
+        }
+        let mut z_i1 = Vec::with_capacity(z_i.len());
+        for ((track, z), inputs) in self
+            .tracks
+            .iter()
+            .zip(self.split(&z_i, FC::state_len))
+            .zip(self.split(&external_inputs, FC::external_inputs_len))
+        {
+            let z_next = track.step_native(
+                i,
+                z.to_vec(),
+                FC::ExternalInputs::from_field_elements(inputs.to_vec())?,
+            )?;
+            if z_next.len() != track.state_len() {
+                return Err(Error::NotExpectedLength {
+                    got: z_next.len(),
+                    expected: track.state_len(),
+                });
+            }
+            z_i1.extend(z_next);
+        }
+        Ok(z_i1)
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() || external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut z_i1 = Vec::with_capacity(z_i.len());
+        for ((track, z), inputs) in self
+            .tracks
+            .iter()
+            .zip(self.split(&z_i, FC::state_len))
+            .zip(self.split(&external_inputs, FC::external_inputs_len))
+        {
+            let z_next = track.generate_step_constraints(
+                cs.clone(),
+                i,
+                z.to_vec(),
+                FC::ExternalInputs::var_from_field_elements(inputs.to_vec())?,
+            )?;
+            if z_next.len() != track.state_len() {
+                return Err(SynthesisError::Unsatisfiable);
+            }
+            z_i1.extend(z_next);
+        }
+        Ok(z_i1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::frontend::examples::PoseidonFCircuit;
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    #[test]
+    fn test_parallel_tracks_hash_chains() {
+        type FC = ParallelTracksFCircuit<[PoseidonFCircuit<Fr>; 3]>;
+        type NOVA = TestNova<FC>;
+
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = FC::new([
+            poseidon_config.clone(),
+            poseidon_config.clone(),
+            poseidon_config.clone(),
+        ])
+        .unwrap();
+        assert_eq!(F_circuit.state_len(), 3);
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        // three hash chains, starting at different values
+        let z_0 = vec![Fr::from(1_u32), Fr::from(2_u32), Fr::from(3_u32)];
+        let n_steps = 3;
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        for _ in 0..n_steps {
+            nova.prove_step(vec![]).unwrap();
+        }
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0.clone(),
+            nova.z_i.clone(),
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+
+        // the final state of each track is the one of its own hash chain
+        let hash_chain = PoseidonFCircuit::<Fr>::new(poseidon_config).unwrap();
+        for (track, z) in z_0.iter().enumerate() {
+            let mut expected = vec![*z];
+            for i in 0..n_steps {
+                expected = hash_chain.step_native(i, expected, ()).unwrap();
+            }
+            assert_eq!(F_circuit.track_state(&nova.z_i, track), expected.as_slice());
+        }
+    }
+}
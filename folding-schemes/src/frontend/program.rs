@@ -0,0 +1,303 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::FCircuit;
+use crate::Error;
+
+/// Instruction of the programs executed by the ProgramFCircuit, which operate over a single
+/// accumulator
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Instruction<F: PrimeField> {
+    /// acc = acc + v
+    Add(F),
+    /// acc = acc * v
+    Mul(F),
+}
+
+impl<F: PrimeField> Instruction<F> {
+    /// returns the `[opcode, operand]` encoding of the instruction, where the opcode is 0 for
+    /// `Add` and 1 for `Mul`
+    pub fn to_field_elements(&self) -> [F; 2] {
+        match self {
+            Self::Add(v) => [F::zero(), *v],
+            Self::Mul(v) => [F::one(), *v],
+        }
+    }
+}
+
+/// ProgramFCircuit is an FCircuit whose step is defined by data: at each step it executes a
+/// program, given through the external inputs, over the accumulator in the state. The state is
+/// `[program_commitment, acc]`, where `program_commitment = H(program)` is set in `z_0` and kept
+/// unchanged by every step, and each step enforces that the executed program matches it.
+///
+/// Since the program is not part of the circuit, a single set of folding params (and a single
+/// verifier) covers any program of up to `program_len` instructions, while the IVC proof is bound
+/// to the program committed in `z_0`: a proof of the execution of a program does not verify
+/// against the commitment of a different program.
+#[derive(Clone, Debug)]
+pub struct ProgramFCircuit<F: PrimeField> {
+    pub poseidon_config: PoseidonConfig<F>,
+    pub program_len: usize,
+}
+
+impl<F: PrimeField + Absorb> ProgramFCircuit<F> {
+    /// returns the commitment to the given program, to be placed as the first element of `z_0`
+    pub fn program_commitment(&self, program: &[Instruction<F>]) -> Result<F, Error> {
+        CRH::<F>::evaluate(&self.poseidon_config, self.encode_program(program)?).map_err(|e| {
+            Error::CRHFail {
+                reason: e.to_string(),
+            }
+        })
+    }
+
+    /// returns the external inputs encoding the given program, to be used at each step
+    pub fn encode_program(&self, program: &[Instruction<F>]) -> Result<Vec<F>, Error> {
+        if program.len() != self.program_len {
+            return Err(Error::NotSameLength {
+                a: "program.len()",
+                a_len: program.len(),
+                b: "program_len",
+                b_len: self.program_len,
+            });
+        }
+        Ok(program.iter().flat_map(|i| i.to_field_elements()).collect())
+    }
+}
+
+impl<F: PrimeField + Absorb> FCircuit<F> for ProgramFCircuit<F> {
+    /// Poseidon config used for the program commitment, and number of instructions of the programs
+    type Params = (PoseidonConfig<F>, usize);
+    /// the encoded program, see `ProgramFCircuit::encode_program`
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (poseidon_config, program_len) = params;
+        Ok(Self {
+            poseidon_config,
+            program_len,
+        })
+    }
+    fn state_len(&self) -> usize {
+        2
+    }
+    fn external_inputs_len(&self) -> usize {
+        2 * self.program_len
+    }
+    fn step_native(
+        &self,
+        _i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotExpectedLength {
+                got: external_inputs.len(),
+                expected: self.external_inputs_len(),
+            });
+        }
+        let program_commitment = CRH::<F>::evaluate(&self.poseidon_config, external_inputs.clone())
+            .map_err(|e| Error::CRHFail {
+                reason: e.to_string(),
+            })?;
+        if program_commitment != z_i[0] {
+            return Err(Error::ProgramCommitmentMismatch);
+        }
+        let mut acc = z_i[1];
+        for instruction in external_inputs.chunks(2) {
+            let (opcode, v) = (instruction[0], instruction[1]);
+            acc = if opcode.is_zero() {
+                acc + v
+            } else if opcode.is_one() {
+                acc * v
+            } else {
+                return Err(Error::UnknownOpcode {
+                    opcode: opcode.to_string(),
+                });
+            };
+        }
+        Ok(vec![z_i[0], acc])
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() || external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        // check the executed program against the program commitment of the state
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        let program_commitment = CRHGadget::<F>::evaluate(&crh_params, &external_inputs)?;
+        program_commitment.enforce_equal(&z_i[0])?;
+
+        let mut acc = z_i[1].clone();
+        for instruction in external_inputs.chunks(2) {
+            let (opcode, v) = (&instruction[0], &instruction[1]);
+            // opcode is a bit: 0 for Add, 1 for Mul
+            opcode.mul_equals(&(opcode - FpVar::one()), &FpVar::zero())?;
+            // acc = acc + v + opcode * (acc * v - acc - v)
+            let add = &acc + v;
+            let mul = &acc * v;
+            acc = &add + opcode * (mul - &add);
+        }
+        Ok(vec![z_i[0].clone(), acc])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_r1cs_std::R1CSVar;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    type NOVA = TestNova<ProgramFCircuit<Fr>>;
+
+    #[test]
+    fn test_program_fcircuit_constraints() {
+        let F_circuit = ProgramFCircuit::<Fr>::new((poseidon_test_config::<Fr>(), 2)).unwrap();
+        let program = [
+            Instruction::Mul(Fr::from(2_u32)),
+            Instruction::Add(Fr::from(3_u32)),
+        ];
+        let cm = F_circuit.program_commitment(&program).unwrap();
+        let z_i = vec![cm, Fr::from(5_u32)];
+        let external_inputs = F_circuit.encode_program(&program).unwrap();
+
+        let z_i1 = F_circuit
+            .step_native(0, z_i.clone(), external_inputs.clone())
+            .unwrap();
+        assert_eq!(z_i1, vec![cm, Fr::from(13_u32)]);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let external_inputsVar =
+            Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(external_inputs)).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, external_inputsVar)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(z_i1Var.value().unwrap(), z_i1);
+
+        // executing a program different than the committed one fails
+        let other_program = F_circuit
+            .encode_program(&[
+                Instruction::Add(Fr::from(2_u32)),
+                Instruction::Add(Fr::from(3_u32)),
+            ])
+            .unwrap();
+        let z_i = vec![cm, Fr::from(5_u32)];
+        assert!(F_circuit
+            .step_native(0, z_i.clone(), other_program.clone())
+            .is_err());
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(z_i)).unwrap();
+        let external_inputsVar =
+            Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(other_program)).unwrap();
+        F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, external_inputsVar)
+            .unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn test_program_fcircuit_ivc() {
+        let F_circuit = ProgramFCircuit::<Fr>::new((poseidon_test_config::<Fr>(), 2)).unwrap();
+        // the same params and verifier params are used for both programs
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let programs = [
+            [
+                Instruction::Mul(Fr::from(2_u32)),
+                Instruction::Add(Fr::from(3_u32)),
+            ],
+            [
+                Instruction::Add(Fr::from(7_u32)),
+                Instruction::Mul(Fr::from(5_u32)),
+            ],
+        ];
+        let mut proofs = vec![];
+        for program in programs.iter() {
+            let z_0 = vec![
+                F_circuit.program_commitment(program).unwrap(),
+                Fr::from(1_u32),
+            ];
+            let external_inputs = F_circuit.encode_program(program).unwrap();
+            let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+            for _ in 0..3 {
+                nova.prove_step(external_inputs.clone()).unwrap();
+            }
+            let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+            let proof_data = nova.proof_data();
+            NOVA::verify(
+                verifier_params.clone(),
+                z_0.clone(),
+                nova.z_i.clone(),
+                nova.i,
+                running_instance.clone(),
+                incoming_instance.clone(),
+                cyclefold_instance.clone(),
+                proof_data.clone(),
+            )
+            .unwrap();
+            proofs.push((
+                z_0,
+                nova.z_i,
+                nova.i,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+            ));
+        }
+
+        // the proof of the first program does not verify against the commitment of the second one
+        let (
+            mut z_0,
+            mut z_i,
+            i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        ) = proofs[0].clone();
+        z_0[0] = proofs[1].0[0];
+        z_i[0] = proofs[1].0[0];
+        assert!(NOVA::verify(
+            verifier_params,
+            z_0,
+            z_i,
+            i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use ark_crypto_primitives::crh::{
+    poseidon::{
+        constraints::{CRHGadget, CRHParametersVar},
+        CRH,
+    },
+    CRHScheme, CRHSchemeGadget,
+};
+use ark_crypto_primitives::sponge::{poseidon::PoseidonConfig, Absorb};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    alloc::AllocVar,
+    boolean::Boolean,
+    eq::EqGadget,
+    fields::{fp::FpVar, FieldVar},
+    select::CondSelectGadget,
+    R1CSVar,
+};
+use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+
+use super::FCircuit;
+use crate::Error;
+
+/// SmtUpdateFCircuit is an FCircuit that proves the update of a leaf of a Poseidon Merkle tree of
+/// the given depth, where the state is the root of the tree `[root]`. At each step the leaf at
+/// `index` changes from `old_value` to `new_value`, and the root of the updated tree becomes the
+/// next state. The empty leaves of the tree are zero, and the inner nodes are `H(left, right)`.
+///
+/// The external inputs of each step are `[index, old_value, new_value, siblings]` (see
+/// `SmtUpdateFCircuit::external_inputs`), where `siblings` are the `depth` siblings of the path
+/// of the leaf, from the leaf level to the root level. The path is checked against the root of
+/// the state with `old_value`, so inserting a leaf is an update of a leaf with `old_value = 0`.
+#[derive(Clone, Debug)]
+pub struct SmtUpdateFCircuit<F: PrimeField> {
+    pub poseidon_config: PoseidonConfig<F>,
+    pub depth: usize,
+}
+
+impl<F: PrimeField + Absorb> SmtUpdateFCircuit<F> {
+    /// returns the external inputs of a step updating the leaf at `index` from `old_value` to
+    /// `new_value`, where `siblings` are the siblings of the path of the leaf
+    pub fn external_inputs(
+        &self,
+        index: usize,
+        old_value: F,
+        new_value: F,
+        siblings: &[F],
+    ) -> Result<Vec<F>, Error> {
+        if siblings.len() != self.depth {
+            return Err(Error::NotExpectedLength {
+                got: siblings.len(),
+                expected: self.depth,
+            });
+        }
+        Ok([
+            vec![F::from(index as u64), old_value, new_value],
+            siblings.to_vec(),
+        ]
+        .concat())
+    }
+
+    /// returns the root of the tree computed from the leaf `value` at the position given by the
+    /// `path` bits (little-endian) and its siblings
+    fn root_native(&self, path: &[bool], value: F, siblings: &[F]) -> Result<F, Error> {
+        path.iter()
+            .zip(siblings.iter())
+            .try_fold(value, |node, (bit, sibling)| {
+                let (left, right) = if *bit {
+                    (*sibling, node)
+                } else {
+                    (node, *sibling)
+                };
+                CRH::<F>::evaluate(&self.poseidon_config, vec![left, right]).map_err(|e| {
+                    Error::CRHFail {
+                        reason: e.to_string(),
+                    }
+                })
+            })
+    }
+}
+
+impl<F: PrimeField + Absorb> FCircuit<F> for SmtUpdateFCircuit<F> {
+    /// Poseidon config used for the inner nodes, and depth of the tree
+    type Params = (PoseidonConfig<F>, usize);
+    /// the position, the old and new leaves and the siblings of the path, see
+    /// `SmtUpdateFCircuit::external_inputs`
+    type ExternalInputs = Vec<F>;
+
+    fn new(params: Self::Params) -> Result<Self, Error> {
+        let (poseidon_config, depth) = params;
+        Ok(Self {
+            poseidon_config,
+            depth,
+        })
+    }
+    fn state_len(&self) -> usize {
+        1
+    }
+    fn external_inputs_len(&self) -> usize {
+        3 + self.depth
+    }
+    fn step_native(
+        &self,
+        _i: usize,
+        z_i: Vec<F>,
+        external_inputs: Vec<F>,
+    ) -> Result<Vec<F>, Error> {
+        if z_i.len() != self.state_len() {
+            return Err(Error::NotExpectedLength {
+                got: z_i.len(),
+                expected: self.state_len(),
+            });
+        }
+        if external_inputs.len() != self.external_inputs_len() {
+            return Err(Error::NotExpectedLength {
+                got: external_inputs.len(),
+                expected: self.external_inputs_len(),
+            });
+        }
+        let (index, old_value, new_value, siblings) = (
+            external_inputs[0],
+            external_inputs[1],
+            external_inputs[2],
+            &external_inputs[3..],
+        );
+        let index_bits = index.into_bigint().to_bits_le();
+        if index_bits[self.depth..].iter().any(|b| *b) {
+            return Err(Error::OutOfBounds);
+        }
+        let path = &index_bits[..self.depth];
+
+        if self.root_native(path, old_value, siblings)? != z_i[0] {
+            return Err(Error::MerkleRootMismatch);
+        }
+        Ok(vec![self.root_native(path, new_value, siblings)?])
+    }
+    fn generate_step_constraints(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        _i: usize,
+        z_i: Vec<FpVar<F>>,
+        external_inputs: Vec<FpVar<F>>,
+    ) -> Result<Vec<FpVar<F>>, SynthesisError> {
+        if z_i.len() != self.state_len() || external_inputs.len() != self.external_inputs_len() {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let (index, old_value, new_value, siblings) = (
+            &external_inputs[0],
+            &external_inputs[1],
+            &external_inputs[2],
+            &external_inputs[3..],
+        );
+
+        // decompose the index into the `depth` bits of the path
+        let path = (0..self.depth)
+            .map(|j| {
+                Boolean::new_witness(cs.clone(), || Ok(index.value()?.into_bigint().get_bit(j)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut index_from_path = FpVar::<F>::zero();
+        let mut power = F::one();
+        for bit in path.iter() {
+            index_from_path += FpVar::from(bit.clone()) * power;
+            power.double_in_place();
+        }
+        index_from_path.enforce_equal(index)?;
+
+        let crh_params = CRHParametersVar::<F>::new_constant(cs, self.poseidon_config.clone())?;
+        let mut old_node = old_value.clone();
+        let mut new_node = new_value.clone();
+        for (bit, sibling) in path.iter().zip(siblings.iter()) {
+            // both paths share the siblings, so only the nodes of the leaf side change
+            for node in [&mut old_node, &mut new_node] {
+                let left = FpVar::conditionally_select(bit, sibling, node)?;
+                let right = FpVar::conditionally_select(bit, node, sibling)?;
+                *node = CRHGadget::<F>::evaluate(&crh_params, &[left, right])?;
+            }
+        }
+        old_node.enforce_equal(&z_i[0])?;
+        Ok(vec![new_node])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+    use ark_std::Zero;
+
+    use crate::folding::nova::tests::{test_nova_params, TestNova};
+    use crate::transcript::poseidon::poseidon_test_config;
+    use crate::FoldingScheme;
+
+    /// returns the levels of the tree with the given leaves, from the leaves to the root
+    fn tree_levels(poseidon_config: &PoseidonConfig<Fr>, leaves: &[Fr]) -> Vec<Vec<Fr>> {
+        let mut levels = vec![leaves.to_vec()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| CRH::<Fr>::evaluate(poseidon_config, pair.to_vec()).unwrap())
+                .collect();
+            levels.push(level);
+        }
+        levels
+    }
+
+    fn siblings(levels: &[Vec<Fr>], index: usize) -> Vec<Fr> {
+        levels[..levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| nodes[(index >> level) ^ 1])
+            .collect()
+    }
+
+    #[test]
+    fn test_smt_update_fcircuit_constraints() {
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = SmtUpdateFCircuit::<Fr>::new((poseidon_config.clone(), 3)).unwrap();
+        let mut leaves = vec![Fr::zero(); 8];
+        let levels = tree_levels(&poseidon_config, &leaves);
+        let external_inputs = F_circuit
+            .external_inputs(5, Fr::zero(), Fr::from(42_u32), &siblings(&levels, 5))
+            .unwrap();
+        let z_i1 = F_circuit
+            .step_native(0, vec![levels[3][0]], external_inputs.clone())
+            .unwrap();
+        leaves[5] = Fr::from(42_u32);
+        assert_eq!(z_i1, vec![tree_levels(&poseidon_config, &leaves)[3][0]]);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let z_iVar = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(vec![levels[3][0]])).unwrap();
+        let external_inputsVar =
+            Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(external_inputs)).unwrap();
+        let z_i1Var = F_circuit
+            .generate_step_constraints(cs.clone(), 0, z_iVar, external_inputsVar)
+            .unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(z_i1Var.value().unwrap(), z_i1);
+    }
+
+    #[test]
+    fn test_smt_update_fcircuit_ivc() {
+        type NOVA = TestNova<SmtUpdateFCircuit<Fr>>;
+
+        let poseidon_config = poseidon_test_config::<Fr>();
+        let F_circuit = SmtUpdateFCircuit::<Fr>::new((poseidon_config.clone(), 3)).unwrap();
+        let (prover_params, verifier_params) = test_nova_params(F_circuit.clone());
+
+        let mut leaves = vec![Fr::zero(); 8];
+        let z_0 = vec![tree_levels(&poseidon_config, &leaves)[3][0]];
+        let mut nova = NOVA::init(&prover_params, F_circuit.clone(), z_0.clone()).unwrap();
+        // insert two leaves and update one of them
+        for (index, new_value) in [(2, 7_u32), (5, 11), (2, 13)] {
+            let levels = tree_levels(&poseidon_config, &leaves);
+            let external_inputs = F_circuit
+                .external_inputs(
+                    index,
+                    leaves[index],
+                    Fr::from(new_value),
+                    &siblings(&levels, index),
+                )
+                .unwrap();
+            nova.prove_step(external_inputs).unwrap();
+            leaves[index] = Fr::from(new_value);
+        }
+        assert_eq!(nova.z_i, vec![tree_levels(&poseidon_config, &leaves)[3][0]]);
+
+        let (running_instance, incoming_instance, cyclefold_instance) = nova.instances();
+        let proof_data = nova.proof_data();
+        NOVA::verify(
+            verifier_params,
+            z_0,
+            nova.z_i.clone(),
+            nova.i,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+        .unwrap();
+
+        // an update with a wrong old value can not be proven
+        let levels = tree_levels(&poseidon_config, &leaves);
+        let external_inputs = F_circuit
+            .external_inputs(5, Fr::zero(), Fr::from(1_u32), &siblings(&levels, 5))
+            .unwrap();
+        assert!(matches!(
+            nova.prove_step(external_inputs),
+            Err(Error::MerkleRootMismatch)
+        ));
+    }
+}
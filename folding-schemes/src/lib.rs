@@ -9,6 +9,7 @@ use ark_std::rand::CryptoRng;
 use ark_std::{fmt::Debug, rand::RngCore};
 use thiserror::Error;
 
+use crate::folding::verification_report::VerificationReport;
 use crate::frontend::FCircuit;
 
 pub mod ccs;
@@ -32,6 +33,8 @@ pub enum Error {
     ArithError(#[from] utils::espresso::virtual_polynomial::ArithErrors),
     #[error(transparent)]
     ProtoGalaxy(folding::protogalaxy::ProtoGalaxyError),
+    #[error(transparent)]
+    CycleFold(folding::nova::cyclefold::CycleFoldError),
     #[error("std::io::Error")]
     IOError(#[from] std::io::Error),
 
@@ -42,6 +45,38 @@ pub enum Error {
     SNARKVerificationFail,
     #[error("IVC verification failed")]
     IVCVerificationFail,
+    #[error("Security level of {bits} bits is below the required {required} bits")]
+    InsufficientSecurity { bits: usize, required: usize },
+    #[error("Public input count mismatch, expected {expected}, got {got}")]
+    PublicInputCountMismatch { expected: usize, got: usize },
+    #[error("ABI encoded public input {index} does not match the public input of the proof")]
+    PublicInputAbiMismatch { index: usize },
+    #[error("Inclusion proof of the verifier params leaf {index} does not match the root")]
+    VerifierParamsInclusionFail { index: usize },
+    #[error("The executed program does not match the program commitment")]
+    ProgramCommitmentMismatch,
+    #[error("The transition table does not match the transition table commitment")]
+    TransitionTableCommitmentMismatch,
+    #[error("The transition table does not contain a transition for the given state and input")]
+    InvalidTransition,
+    #[error("The Merkle path does not match the root of the state")]
+    MerkleRootMismatch,
+    #[error("The beacon values do not match the beacon accumulator of the state")]
+    BeaconMismatch,
+    #[error("The state checkpoint {index} does not match the state of the proof")]
+    CheckpointMismatch { index: usize },
+    #[error("The state commitment does not match the state and the nonce")]
+    StateCommitmentMismatch,
+    #[error("The checkpoints of the proofs differ at step {step}")]
+    SharedPrefixMismatch { step: usize },
+    #[error("The transcript domain has already been rotated since the last step")]
+    DomainAlreadyRotated,
+    #[error("Fixed-point value does not fit in {bits} bits")]
+    FixedPointOverflow { bits: usize },
+    #[error("Invalid curve point: {name}")]
+    InvalidPoint { name: String },
+    #[error("Invalid JSON: {0}")]
+    JSONError(String),
     #[error("R1CS instance is expected to not be relaxed")]
     R1CSUnrelaxedFail,
     #[error("Could not find the inner ConstraintSystem")]
@@ -54,12 +89,19 @@ pub enum Error {
     // Comparators errors
     #[error("Not equal")]
     NotEqual,
-    #[error("Vectors should have the same length ({0}: {1}, {2}: {3})")]
-    NotSameLength(String, usize, String, usize),
-    #[error("Vector's length ({0}) is not the expected ({1})")]
-    NotExpectedLength(usize, usize),
-    #[error("Vector ({0}) length ({1}) is not a power of two")]
-    NotPowerOfTwo(String, usize),
+    #[error("Vectors should have the same length ({a}: {a_len}, {b}: {b_len})")]
+    NotSameLength {
+        a: &'static str,
+        a_len: usize,
+        b: &'static str,
+        b_len: usize,
+    },
+    #[error("Vector's length ({got}) is not the expected ({expected})")]
+    NotExpectedLength { got: usize, expected: usize },
+    #[error("State length ({got}) is not the expected by the FCircuit ({expected})")]
+    StateLengthMismatch { expected: usize, got: usize },
+    #[error("Vector ({name}) length ({len}) is not a power of two")]
+    NotPowerOfTwo { name: &'static str, len: usize },
     #[error("Can not be empty")]
     Empty,
     #[error("Value out of bounds")]
@@ -70,26 +112,86 @@ pub enum Error {
     NotEnoughSteps,
 
     // Commitment errors
-    #[error("Pedersen parameters length is not sufficient (generators.len={0} < vector.len={1} unsatisfied)")]
-    PedersenParamsLen(usize, usize),
+    #[error("Pedersen parameters length is not sufficient (generators.len={generators} < vector.len={len} unsatisfied)")]
+    PedersenParamsLen { generators: usize, len: usize },
+    #[error("Circuit too large for the commitment params (size={got} > max_circuit_size={max})")]
+    CircuitTooLarge { max: usize, got: usize },
+    #[error("CycleFold circuit too large for the CycleFold commitment params (size={got} > max_circuit_size={max})")]
+    CycleFoldCircuitTooLarge { max: usize, got: usize },
+    #[error("Can not compute the inverse of zero")]
+    ZeroInverse,
     #[error("Blinding factor not 0 for Commitment without hiding")]
     BlindingNotZero,
     #[error("Commitment verification failed")]
     CommitmentVerificationFail,
+    #[error("No masks left for the split commitment, see SplitParams::add_masks")]
+    SplitMasksExhausted,
+    #[error("Split commitment mask length ({mask_len}) is smaller than the vector length ({len})")]
+    SplitMaskTooShort { len: usize, mask_len: usize },
+    #[error("The commitment params of the {scheme} are not the same")]
+    IncompatibleParams { scheme: &'static str },
+    #[error("The {params} of the verifier params does not match the one used by the prover")]
+    ParamsMismatch { params: &'static str },
+    #[error(
+        "The params of generation {generation} of the proof chain do not match the given ones"
+    )]
+    ChainParamsMismatch { generation: usize },
 
     // Other
+    #[error("No Poseidon preset defined for the field with modulus {modulus}")]
+    NoPoseidonPreset { modulus: num_bigint::BigUint },
+    #[error("Poseidon preset {preset} is for a {preset_bits}-bit field, but the given field has {field_bits} bits")]
+    PoseidonPresetFieldMismatch {
+        preset: String,
+        preset_bits: u32,
+        field_bits: u32,
+    },
+    #[error("Poseidon preset {preset} ships the constants of another field, the given field has modulus {modulus}")]
+    PoseidonPresetModulusMismatch {
+        preset: String,
+        modulus: num_bigint::BigUint,
+    },
+    #[error("StepMultiplier needs at least one inner step")]
+    ZeroInnerSteps,
+    #[error("The step cache of the MemoFCircuit is poisoned")]
+    CachePoisoned,
+    #[error("Unknown opcode {opcode}")]
+    UnknownOpcode { opcode: String },
+    #[error("CRH evaluation failed: {reason}")]
+    CRHFail { reason: String },
     #[error("{0}")]
     Other(String),
     #[error("Randomness for blinding not found")]
     MissingRandomness,
-    #[error("Missing value: {0}")]
-    MissingValue(String),
-    #[error("Feature '{0}' not supported yet")]
-    NotSupportedYet(String),
-    #[error("Feature '{0}' is not supported and it will not be")]
-    NotSupported(String),
+    #[error("Missing value: {name}")]
+    MissingValue { name: &'static str },
+    #[error("Missing public input: {name}[{index}]")]
+    MissingPublicInput { name: &'static str, index: usize },
+    #[error("Feature '{feature}' not supported yet")]
+    NotSupportedYet { feature: &'static str },
+    #[error("Feature '{feature}' is not supported and it will not be")]
+    NotSupported { feature: &'static str },
     #[error("max i-th step reached (usize limit reached)")]
     MaxStep,
+    #[error("Step bound reached, the circuit allows at most {max_steps} steps")]
+    StepBoundReached { max_steps: usize },
+    #[error("External input {index} is out of the bounds of the state")]
+    ExternalInputOutOfBounds { index: usize },
+    #[error("The proof chain is broken at generation {generation}, its initial state is not the last state of the previous generation")]
+    ProofChainBroken { generation: usize },
+    #[error("{num_limbs} limbs of {bits_per_limb} bits do not represent the {target_bits} bits of the non-native field")]
+    InvalidLimbConfig {
+        bits_per_limb: usize,
+        num_limbs: usize,
+        target_bits: usize,
+    },
+    #[error("Limbs of {bits_per_limb} bits overflow the {native_bits} bits of the native field")]
+    LimbOverflow {
+        bits_per_limb: usize,
+        native_bits: usize,
+    },
+    #[error("The number of steps {got} does not match the {expected} folded steps")]
+    StepCountMismatch { expected: u64, got: u64 },
     #[error("Circom Witness calculation error: {0}")]
     WitnessCalculationError(String),
     #[error("BigInt to PrimeField conversion error: {0}")]
@@ -113,6 +215,9 @@ where
     type VerifierParam: Debug;
     type CommittedInstanceWithWitness: Debug;
     type CFCommittedInstanceWithWitness: Debug; // CycleFold CommittedInstance & Witness
+    /// data of the IVC proof besides the states and the instances, which the verifier does not
+    /// take from its own params (eg. the digest of the prover's Poseidon config)
+    type ProofData: Debug;
 
     fn preprocess(
         prep_param: &Self::PreprocessorParam,
@@ -124,11 +229,27 @@ where
         z_0: Vec<C1::ScalarField>, // initial state
     ) -> Result<Self, Error>;
 
-    fn prove_step(&mut self, external_inputs: Vec<C1::ScalarField>) -> Result<(), Error>;
+    /// proves a step of the FCircuit, which takes the given external inputs
+    fn prove_step(&mut self, external_inputs: FC::ExternalInputs) -> Result<(), Error>;
+
+    /// proves a step for each of the given external inputs, in order, which results in the same
+    /// state and instances as calling `prove_step` with each of them. By default it stops at the
+    /// first failing step, leaving the previous steps proven. It is a convenience over calling
+    /// `prove_step` in a loop, and it does not reduce the cost of each step.
+    fn prove_steps(&mut self, external_inputs: Vec<FC::ExternalInputs>) -> Result<(), Error> {
+        for inputs in external_inputs {
+            self.prove_step(inputs)?;
+        }
+        Ok(())
+    }
 
     // returns the state at the current step
     fn state(&self) -> Vec<C1::ScalarField>;
 
+    /// returns the number of steps folded so far, which is the `num_steps` to be passed to
+    /// `verify` together with the current state and instances
+    fn num_steps(&self) -> u64;
+
     // returns the instances at the current step, in the following order:
     // (running_instance, incoming_instance, cyclefold_instance)
     fn instances(
@@ -139,6 +260,11 @@ where
         Self::CFCommittedInstanceWithWitness,
     );
 
+    /// returns the data of the IVC proof at the current step that is not contained in the
+    /// instances, to be passed to `verify` together with them
+    fn proof_data(&self) -> Self::ProofData;
+
+    #[allow(clippy::too_many_arguments)]
     fn verify(
         vp: Self::VerifierParam,
         z_0: Vec<C1::ScalarField>, // initial state
@@ -148,7 +274,70 @@ where
         running_instance: Self::CommittedInstanceWithWitness,
         incoming_instance: Self::CommittedInstanceWithWitness,
         cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
     ) -> Result<(), Error>;
+
+    /// verifies the IVC as `verify`, and additionally checks that the public outputs of the steps
+    /// are the given `io`, one vector per step, for FCircuits that expose IO (see
+    /// `FCircuit::io_len`). By default only FCircuits without IO are supported.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_with_io(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::CommittedInstanceWithWitness,
+        incoming_instance: Self::CommittedInstanceWithWitness,
+        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
+        io: Vec<Vec<C1::ScalarField>>,
+    ) -> Result<(), Error> {
+        if io.iter().any(|io| !io.is_empty()) {
+            return Err(Error::NotSupported {
+                feature: "verifying the public outputs of the steps",
+            });
+        }
+        Self::verify(
+            vp,
+            z_0,
+            z_i,
+            num_steps,
+            running_instance,
+            incoming_instance,
+            cyclefold_instance,
+            proof_data,
+        )
+    }
+
+    /// runs the same verification as `verify`, but instead of stopping at the first failing check,
+    /// it returns a VerificationReport with the result and timing of each of the checks performed.
+    /// By default the report contains a single check with the result of `verify`.
+    #[allow(clippy::too_many_arguments)]
+    fn verify_report(
+        vp: Self::VerifierParam,
+        z_0: Vec<C1::ScalarField>, // initial state
+        z_i: Vec<C1::ScalarField>, // last state
+        num_steps: C1::ScalarField,
+        running_instance: Self::CommittedInstanceWithWitness,
+        incoming_instance: Self::CommittedInstanceWithWitness,
+        cyclefold_instance: Self::CFCommittedInstanceWithWitness,
+        proof_data: Self::ProofData,
+    ) -> VerificationReport {
+        let mut report = VerificationReport::new();
+        report.check("verify", || {
+            Self::verify(
+                vp,
+                z_0,
+                z_i,
+                num_steps,
+                running_instance,
+                incoming_instance,
+                cyclefold_instance,
+                proof_data,
+            )
+        });
+        report
+    }
 }
 
 pub trait Decider<
@@ -173,11 +362,15 @@ pub trait Decider<
         folding_scheme: FS,
     ) -> Result<Self::Proof, Error>;
 
+    /// verifies the Decider proof of the IVC with `i` steps from `z_0` to `z_i`, where
+    /// `public_input` are the other values bound to the IVC (eg. Nova's IVCBindings)
+    #[allow(clippy::too_many_arguments)]
     fn verify(
         vp: Self::VerifierParam,
         i: C1::ScalarField,
         z_0: Vec<C1::ScalarField>,
         z_i: Vec<C1::ScalarField>,
+        public_input: &Self::PublicInput,
         running_instance: &Self::CommittedInstance,
         incoming_instance: &Self::CommittedInstance,
         proof: &Self::Proof,
@@ -193,12 +386,16 @@ where
     C2::BaseField: PrimeField,
 {
     type Proof;
+    type PublicInput: Debug;
     type CommittedInstance: Clone + Debug;
 
+    /// prepares the calldata of the IVC with `i` steps from `z_0` to `z_i`, where `public_input`
+    /// are the other values bound to the IVC, as in `Decider::verify`
     fn prepare_calldata(
         i: C1::ScalarField,
         z_0: Vec<C1::ScalarField>,
         z_i: Vec<C1::ScalarField>,
+        public_input: &Self::PublicInput,
         running_instance: &Self::CommittedInstance,
         incoming_instance: &Self::CommittedInstance,
         proof: Self::Proof,
@@ -0,0 +1,117 @@
+/// DualTranscript wraps a field-native transcript (eg. Poseidon) to produce the challenges both
+/// as field elements, to be used by the in-circuit verifiers, and as bytes, to be used by the
+/// keccak based on-chain verifiers.
+///
+/// Both forms are bound to the same challenge: each challenge is squeezed from the inner
+/// transcript as a field element `c`, and its byte form is `keccak256(c)`, where `c` is encoded as
+/// its big-endian bytes (as the EVM encodes a `uint256`), see `challenge_to_bytes`. So a verifier
+/// that only knows one of the forms can check the other one with a single keccak256.
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, PrimeField};
+use core::marker::PhantomData;
+use sha3::{Digest, Keccak256};
+
+use super::Transcript;
+use crate::Error;
+
+#[derive(Debug, Clone)]
+pub struct DualTranscript<C: CurveGroup, T: Transcript<C>> {
+    _c: PhantomData<C>,
+    inner: T,
+}
+
+/// returns the byte form of the given field challenge, `keccak256(c)` with `c` encoded as its
+/// big-endian bytes
+pub fn challenge_to_bytes<F: PrimeField>(c: F) -> [u8; 32] {
+    Keccak256::digest(c.into_bigint().to_bytes_be()).into()
+}
+
+impl<C: CurveGroup, T: Transcript<C>> DualTranscript<C, T> {
+    /// returns the next challenge as a field element
+    pub fn get_challenge_field(&mut self) -> C::ScalarField {
+        self.inner.get_challenge()
+    }
+
+    /// returns the next challenge in its byte form, this is, the bytes obtained through
+    /// `challenge_to_bytes` from the field challenge that `get_challenge_field` would return
+    pub fn get_challenge_bytes(&mut self) -> [u8; 32] {
+        challenge_to_bytes(self.get_challenge_field())
+    }
+
+    /// returns the next challenge in both forms
+    pub fn get_challenge_dual(&mut self) -> (C::ScalarField, [u8; 32]) {
+        let c = self.get_challenge_field();
+        (c, challenge_to_bytes(c))
+    }
+}
+
+impl<C: CurveGroup, T: Transcript<C>> Transcript<C> for DualTranscript<C, T> {
+    type TranscriptConfig = T::TranscriptConfig;
+
+    fn new(config: &Self::TranscriptConfig) -> Self {
+        Self {
+            _c: PhantomData,
+            inner: T::new(config),
+        }
+    }
+    fn absorb(&mut self, v: &C::ScalarField) {
+        self.inner.absorb(v)
+    }
+    fn absorb_vec(&mut self, v: &[C::ScalarField]) {
+        self.inner.absorb_vec(v)
+    }
+    fn absorb_point(&mut self, v: &C) -> Result<(), Error> {
+        self.inner.absorb_point(v)
+    }
+    fn get_challenge(&mut self) -> C::ScalarField {
+        self.get_challenge_field()
+    }
+    fn get_challenge_nbits(&mut self, nbits: usize) -> Vec<bool> {
+        self.inner.get_challenge_nbits(nbits)
+    }
+    fn get_challenges(&mut self, n: usize) -> Vec<C::ScalarField> {
+        self.inner.get_challenges(n)
+    }
+    fn get_challenges_batched(&mut self, n: usize) -> Vec<C::ScalarField> {
+        self.inner.get_challenges_batched(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_pallas::{Fr, Projective};
+
+    use crate::transcript::poseidon::{poseidon_test_config, PoseidonTranscript};
+
+    type DT = DualTranscript<Projective, PoseidonTranscript<Projective>>;
+
+    #[test]
+    fn test_dual_transcript() {
+        let config = poseidon_test_config::<Fr>();
+        let mut field_transcript = DT::new(&config);
+        let mut bytes_transcript = DT::new(&config);
+        let mut poseidon_transcript = PoseidonTranscript::<Projective>::new(&config);
+        for t in [&mut field_transcript, &mut bytes_transcript] {
+            t.absorb_vec(&[Fr::from(1_u32), Fr::from(2_u32)]);
+        }
+        poseidon_transcript.absorb_vec(&[Fr::from(1_u32), Fr::from(2_u32)]);
+
+        for _ in 0..3 {
+            let c = field_transcript.get_challenge_field();
+            // the field challenges are the ones of the inner transcript
+            assert_eq!(c, poseidon_transcript.get_challenge());
+            // and the byte challenges are derived from them
+            let c_bytes = bytes_transcript.get_challenge_bytes();
+            assert_eq!(c_bytes, challenge_to_bytes(c));
+            assert_eq!(
+                c_bytes,
+                <[u8; 32]>::from(Keccak256::digest(c.into_bigint().to_bytes_be()))
+            );
+        }
+
+        let (c, c_bytes) = field_transcript.get_challenge_dual();
+        assert_eq!(c_bytes, challenge_to_bytes(c));
+        assert_eq!(c_bytes, bytes_transcript.get_challenge_bytes());
+    }
+}
@@ -0,0 +1,156 @@
+/// KeccakTranscript implements the Transcript trait using keccak256, following the encoding used
+/// by the EVM, so that a Solidity verifier can reproduce the challenges with `keccak256`:
+/// - the field elements are absorbed as their 32-byte big-endian encoding (as an `uint256`)
+/// - the points are absorbed as their affine coordinates `(x, y)`, with the point at infinity
+///   encoded as `(0, 0)`
+/// - each challenge is `uint256(h) mod r`, for `h = keccak256(abi.encodePacked(state, data))`,
+///   where `data` is the data absorbed since the last challenge. After each challenge the state
+///   is set to `h`, and it starts empty.
+///
+/// It does not have an in-circuit counterpart, since keccak is expensive to prove in an R1CS.
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+use core::marker::PhantomData;
+use sha3::{Digest, Keccak256};
+
+use super::Transcript;
+use crate::Error;
+
+#[derive(Debug, Clone)]
+pub struct KeccakTranscript<C: CurveGroup> {
+    _c: PhantomData<C>,
+    // the last squeezed hash followed by the data absorbed since then
+    buffer: Vec<u8>,
+}
+
+impl<C: CurveGroup> KeccakTranscript<C> {
+    // hashes the buffer, and sets the resulting hash as the new state
+    fn squeeze_hash(&mut self) -> [u8; 32] {
+        let h: [u8; 32] = Keccak256::digest(&self.buffer).into();
+        self.buffer = h.to_vec();
+        h
+    }
+}
+
+fn absorb_bigint<B: BigInteger>(buffer: &mut Vec<u8>, b: B) {
+    buffer.extend_from_slice(&b.to_bytes_be());
+}
+
+impl<C: CurveGroup> Transcript<C> for KeccakTranscript<C> {
+    type TranscriptConfig = ();
+
+    fn new(_config: &Self::TranscriptConfig) -> Self {
+        Self {
+            _c: PhantomData,
+            buffer: Vec::new(),
+        }
+    }
+    fn absorb(&mut self, v: &C::ScalarField) {
+        absorb_bigint(&mut self.buffer, v.into_bigint());
+    }
+    fn absorb_vec(&mut self, v: &[C::ScalarField]) {
+        for v_i in v {
+            self.absorb(v_i);
+        }
+    }
+    fn absorb_point(&mut self, p: &C) -> Result<(), Error> {
+        let affine = p.into_affine();
+        let (x, y) = affine
+            .xy()
+            .map(|(x, y)| (*x, *y))
+            .unwrap_or((C::BaseField::ZERO, C::BaseField::ZERO));
+        for coord in [x, y] {
+            let coord = coord
+                .to_base_prime_field_elements()
+                .next()
+                .ok_or(Error::InvalidPoint {
+                    name: "extension field coordinates".to_string(),
+                })?;
+            absorb_bigint(&mut self.buffer, coord.into_bigint());
+        }
+        Ok(())
+    }
+    fn get_challenge(&mut self) -> C::ScalarField {
+        C::ScalarField::from_be_bytes_mod_order(&self.squeeze_hash())
+    }
+    /// returns the `nbits` least significant bits of `uint256(h)`, in little-endian, squeezing
+    /// further hashes when more than 256 bits are requested
+    fn get_challenge_nbits(&mut self, nbits: usize) -> Vec<bool> {
+        let mut bits = Vec::with_capacity(nbits);
+        while bits.len() < nbits {
+            let h = self.squeeze_hash();
+            bits.extend(
+                h.iter()
+                    .rev()
+                    .flat_map(|b| (0..8).map(move |i| (b >> i) & 1 == 1)),
+            );
+        }
+        bits.truncate(nbits);
+        bits
+    }
+    fn get_challenges(&mut self, n: usize) -> Vec<C::ScalarField> {
+        (0..n).map(|_| self.get_challenge()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::{Fr, G1Projective as Projective};
+    use ark_ec::Group;
+    use ark_std::Zero;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_keccak_transcript_vector() {
+        // the expected values are the ones computed by the following Solidity, where r is the
+        // BN254 scalar field modulus:
+        //   bytes32 h1 = keccak256(abi.encodePacked(
+        //       uint256(1), uint256(3), uint256(4), uint256(1), uint256(2)
+        //   ));
+        //   uint256 c1 = uint256(h1) % r;
+        //   bytes32 h2 = keccak256(abi.encodePacked(h1, uint256(5)));
+        //   uint256 c2 = uint256(h2) % r;
+        //   uint16 bits = uint16(uint256(keccak256(abi.encodePacked(h2))));
+        let mut transcript = KeccakTranscript::<Projective>::new(&());
+        transcript.absorb(&Fr::from(1_u32));
+        transcript.absorb_vec(&[Fr::from(3_u32), Fr::from(4_u32)]);
+        // the BN254 generator is (1, 2)
+        transcript.absorb_point(&Projective::generator()).unwrap();
+        let c1 = transcript.get_challenge();
+        assert_eq!(
+            c1,
+            Fr::from_str(
+                "15085466867332185617623827716471313178970608655454083585389592514923991514421"
+            )
+            .unwrap()
+        );
+        transcript.absorb(&Fr::from(5_u32));
+        let c2 = transcript.get_challenge();
+        assert_eq!(
+            c2,
+            Fr::from_str(
+                "4238710394074678193040126786725181946171352578178626661661783507802913193081"
+            )
+            .unwrap()
+        );
+        let bits = transcript.get_challenge_nbits(16);
+        let expected = 46705_u16;
+        assert_eq!(
+            bits,
+            (0..16)
+                .map(|i| (expected >> i) & 1 == 1)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_keccak_transcript_point_at_infinity() {
+        // the point at infinity is absorbed as (0, 0)
+        let mut transcript_a = KeccakTranscript::<Projective>::new(&());
+        transcript_a.absorb_point(&Projective::zero()).unwrap();
+        let mut transcript_b = KeccakTranscript::<Projective>::new(&());
+        transcript_b.absorb_vec(&[Fr::from(0_u32), Fr::from(0_u32)]);
+        assert_eq!(transcript_a.get_challenge(), transcript_b.get_challenge());
+    }
+}
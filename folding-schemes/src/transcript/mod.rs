@@ -5,8 +5,21 @@ use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar};
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
 use ark_std::fmt::Debug;
 
+pub mod dual;
+pub mod keccak;
 pub mod poseidon;
 
+/// Endianness of the bit representation returned by the `get_challenge_nbits_with_endianness`
+/// methods of the `Transcript` and `TranscriptVar` traits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Endianness {
+    /// least significant bit first, matches the `scalar_mul_le` & `from_bits_le` methods
+    #[default]
+    LittleEndian,
+    /// most significant bit first, matches the `mul_bits_be` & `from_bits_be` methods
+    BigEndian,
+}
+
 pub trait Transcript<C: CurveGroup> {
     type TranscriptConfig: Debug;
 
@@ -15,9 +28,29 @@ pub trait Transcript<C: CurveGroup> {
     fn absorb_vec(&mut self, v: &[C::ScalarField]);
     fn absorb_point(&mut self, v: &C) -> Result<(), Error>;
     fn get_challenge(&mut self) -> C::ScalarField;
-    /// get_challenge_nbits returns a field element of size nbits
+    /// get_challenge_nbits returns a field element of size nbits, as its little-endian bit
+    /// representation. Its output matches the bits returned by `TranscriptVar::get_challenge_nbits`.
     fn get_challenge_nbits(&mut self, nbits: usize) -> Vec<bool>;
+    /// same as `get_challenge_nbits`, but returns the bits in the given endianness
+    fn get_challenge_nbits_with_endianness(
+        &mut self,
+        nbits: usize,
+        endianness: Endianness,
+    ) -> Vec<bool> {
+        let mut bits = self.get_challenge_nbits(nbits);
+        if endianness == Endianness::BigEndian {
+            bits.reverse();
+        }
+        bits
+    }
+    /// returns `n` challenges, which are the same as the ones returned by `n` sequential calls to
+    /// `get_challenge`
     fn get_challenges(&mut self, n: usize) -> Vec<C::ScalarField>;
+    /// returns `n` challenges squeezed at once, which is cheaper than `get_challenges` for large
+    /// `n` but gives different values
+    fn get_challenges_batched(&mut self, n: usize) -> Vec<C::ScalarField> {
+        self.get_challenges(n)
+    }
 }
 
 pub trait TranscriptVar<F: PrimeField> {
@@ -30,5 +63,19 @@ pub trait TranscriptVar<F: PrimeField> {
     /// returns the bit representation of the challenge, we use its output in-circuit for the
     /// `GC.scalar_mul_le` method.
     fn get_challenge_nbits(&mut self, nbits: usize) -> Result<Vec<Boolean<F>>, SynthesisError>;
+    /// same as `get_challenge_nbits`, but returns the bits in the given endianness
+    fn get_challenge_nbits_with_endianness(
+        &mut self,
+        nbits: usize,
+        endianness: Endianness,
+    ) -> Result<Vec<Boolean<F>>, SynthesisError> {
+        let mut bits = self.get_challenge_nbits(nbits)?;
+        if endianness == Endianness::BigEndian {
+            bits.reverse();
+        }
+        Ok(bits)
+    }
+    /// returns `n` challenges, which are the same as the ones returned by `n` sequential calls to
+    /// `get_challenge`
     fn get_challenges(&mut self, n: usize) -> Result<Vec<FpVar<F>>, SynthesisError>;
 }
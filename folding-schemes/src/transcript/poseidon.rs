@@ -7,13 +7,20 @@ use ark_ec::{AffineRepr, CurveGroup, Group};
 use ark_ff::{BigInteger, Field, PrimeField};
 use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar};
 use ark_relations::r1cs::{ConstraintSystemRef, SynthesisError};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Validate, Write,
+};
 use ark_std::Zero;
+use sha3::{Digest, Keccak256};
 
 use crate::transcript::Transcript;
 use crate::Error;
 
 use super::TranscriptVar;
 
+mod circomlib;
+pub mod presets;
+
 /// PoseidonTranscript implements the Transcript trait using the Poseidon hash
 pub struct PoseidonTranscript<C: CurveGroup>
 where
@@ -51,6 +58,9 @@ where
         self.sponge.squeeze_bits(nbits)
     }
     fn get_challenges(&mut self, n: usize) -> Vec<C::ScalarField> {
+        (0..n).map(|_| self.get_challenge()).collect()
+    }
+    fn get_challenges_batched(&mut self, n: usize) -> Vec<C::ScalarField> {
         let c = self.sponge.squeeze_field_elements(n);
         self.sponge.absorb(&c);
         c
@@ -109,10 +119,122 @@ impl<F: PrimeField> TranscriptVar<F> for PoseidonTranscriptVar<F> {
         self.sponge.squeeze_bits(nbits)
     }
     fn get_challenges(&mut self, n: usize) -> Result<Vec<FpVar<F>>, SynthesisError> {
-        let c = self.sponge.squeeze_field_elements(n)?;
-        self.sponge.absorb(&c)?;
-        Ok(c)
+        (0..n).map(|_| self.get_challenge()).collect()
+    }
+}
+
+/// returns the Poseidon configs for the scalar fields of both curves of the cycle, using the
+/// presets defined for each one of the fields (see `presets::preset_for_field`). Both configs
+/// have the same width and S-box, but the number of partial rounds and the constants depend on
+/// the field, and only the BN254 ones are the circomlib constants (see `presets`).
+#[allow(clippy::type_complexity)]
+pub fn cycle_configs<C1, C2>() -> Result<
+    (
+        PoseidonConfig<C1::ScalarField>,
+        PoseidonConfig<C2::ScalarField>,
+    ),
+    Error,
+>
+where
+    C1: CurveGroup<BaseField = C2::ScalarField, ScalarField = C2::BaseField>,
+    C2: CurveGroup,
+{
+    let config1 = presets::preset_for_field::<C1::ScalarField>()?.config()?;
+    let config2 = presets::preset_for_field::<C2::ScalarField>()?.config()?;
+    Ok((config1, config2))
+}
+
+/// returns the keccak256 digest of the given Poseidon config, which identifies the config used
+/// to compute a proof, so that a mismatch with the config of the verifier can be reported
+pub fn poseidon_config_digest<F: PrimeField>(config: &PoseidonConfig<F>) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for n in [
+        config.full_rounds,
+        config.partial_rounds,
+        config.rate,
+        config.capacity,
+    ] {
+        hasher.update((n as u64).to_le_bytes());
     }
+    hasher.update(config.alpha.to_le_bytes());
+    for matrix in [&config.ark, &config.mds] {
+        hasher.update((matrix.len() as u64).to_le_bytes());
+        for row in matrix.iter() {
+            hasher.update((row.len() as u64).to_le_bytes());
+            for c in row.iter() {
+                hasher.update(c.into_bigint().to_bytes_le());
+            }
+        }
+    }
+    hasher.finalize().into()
+}
+
+/// writes the given Poseidon config, since `PoseidonConfig` does not implement
+/// `CanonicalSerialize`. It is read back by `deserialize_poseidon_config`.
+pub fn serialize_poseidon_config<F: PrimeField, W: Write>(
+    config: &PoseidonConfig<F>,
+    mut writer: W,
+    compress: Compress,
+) -> Result<(), SerializationError> {
+    config
+        .full_rounds
+        .serialize_with_mode(&mut writer, compress)?;
+    config
+        .partial_rounds
+        .serialize_with_mode(&mut writer, compress)?;
+    config.alpha.serialize_with_mode(&mut writer, compress)?;
+    config.ark.serialize_with_mode(&mut writer, compress)?;
+    config.mds.serialize_with_mode(&mut writer, compress)?;
+    config.rate.serialize_with_mode(&mut writer, compress)?;
+    config.capacity.serialize_with_mode(&mut writer, compress)
+}
+
+/// returns the number of bytes written by `serialize_poseidon_config`
+pub fn poseidon_config_serialized_size<F: PrimeField>(
+    config: &PoseidonConfig<F>,
+    compress: Compress,
+) -> usize {
+    config.full_rounds.serialized_size(compress)
+        + config.partial_rounds.serialized_size(compress)
+        + config.alpha.serialized_size(compress)
+        + config.ark.serialized_size(compress)
+        + config.mds.serialized_size(compress)
+        + config.rate.serialized_size(compress)
+        + config.capacity.serialized_size(compress)
+}
+
+/// reads a Poseidon config written by `serialize_poseidon_config`
+pub fn deserialize_poseidon_config<F: PrimeField, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<PoseidonConfig<F>, SerializationError> {
+    let full_rounds = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+    let partial_rounds = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+    let alpha = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+    let ark = Vec::<Vec<F>>::deserialize_with_mode(&mut reader, compress, validate)?;
+    let mds = Vec::<Vec<F>>::deserialize_with_mode(&mut reader, compress, validate)?;
+    let rate = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+    let capacity = usize::deserialize_with_mode(&mut reader, compress, validate)?;
+    Ok(PoseidonConfig::new(
+        full_rounds,
+        partial_rounds,
+        alpha,
+        mds,
+        ark,
+        rate,
+        capacity,
+    ))
+}
+
+/// returns the standard Poseidon config for the field F, with width t=3 (rate 2, capacity 1),
+/// S-box x^5 and the number of full and partial rounds recommended for 128-bit security in the
+/// [Poseidon paper](https://eprint.iacr.org/2019/458.pdf), see `presets::preset_for_field`. Over
+/// BN254 it uses the circomlib constants.
+/// Unlike `poseidon_test_config`, it is meant to be used in production. Returns an error if
+/// there is no preset for F.
+pub fn poseidon_canonical_config<F: PrimeField>() -> Result<PoseidonConfig<F>, Error> {
+    presets::preset_for_field::<F>()?.config()
 }
 
 /// WARNING the method poseidon_test_config is for tests only
@@ -144,6 +266,7 @@ pub fn poseidon_test_config<F: PrimeField>() -> PoseidonConfig<F> {
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::transcript::Endianness;
     use ark_pallas::{constraints::GVar, Fq, Fr, Projective};
     use ark_r1cs_std::{alloc::AllocVar, groups::CurveVar, R1CSVar};
     use ark_relations::r1cs::ConstraintSystem;
@@ -216,4 +339,133 @@ pub mod tests {
             cPVar.value().unwrap().into_affine()
         );
     }
+
+    #[test]
+    fn test_transcript_and_transcriptvar_nbits_endianness() {
+        let nbits = crate::constants::N_BITS_RO;
+        let config = poseidon_test_config::<Fq>();
+
+        for endianness in [Endianness::LittleEndian, Endianness::BigEndian] {
+            let mut tr = PoseidonTranscript::<E2Projective>::new(&config);
+            tr.absorb(&Fq::from(42_u32));
+            let c_bits = tr.get_challenge_nbits_with_endianness(nbits, endianness);
+
+            let cs = ConstraintSystem::<Fq>::new_ref();
+            let mut tr_var = PoseidonTranscriptVar::<Fq>::new(cs.clone(), &config);
+            let v = FpVar::<Fq>::new_witness(cs.clone(), || Ok(Fq::from(42_u32))).unwrap();
+            tr_var.absorb(v).unwrap();
+            let c_var = tr_var
+                .get_challenge_nbits_with_endianness(nbits, endianness)
+                .unwrap();
+
+            // native and in-circuit bits must be the same, in the same order
+            assert_eq!(c_bits, c_var.value().unwrap());
+
+            // recompose the challenge natively and in-circuit and check that both match
+            let c_native = match endianness {
+                Endianness::LittleEndian => Fq::from_bigint(BigInteger::from_bits_le(&c_bits)),
+                Endianness::BigEndian => Fq::from_bigint(BigInteger::from_bits_be(&c_bits)),
+            }
+            .unwrap();
+            let c_var_le: Vec<Boolean<Fq>> = match endianness {
+                Endianness::LittleEndian => c_var,
+                Endianness::BigEndian => c_var.into_iter().rev().collect(),
+            };
+            let c_in_circuit = Boolean::le_bits_to_fp_var(&c_var_le).unwrap();
+            assert_eq!(c_native, c_in_circuit.value().unwrap());
+            assert!(cs.is_satisfied().unwrap());
+        }
+
+        // big-endian bits are the reverse of the little-endian ones
+        let mut tr = PoseidonTranscript::<E2Projective>::new(&config);
+        tr.absorb(&Fq::from(42_u32));
+        let mut c_le = tr.get_challenge_nbits(nbits);
+        let mut tr = PoseidonTranscript::<E2Projective>::new(&config);
+        tr.absorb(&Fq::from(42_u32));
+        let c_be = tr.get_challenge_nbits_with_endianness(nbits, Endianness::BigEndian);
+        c_le.reverse();
+        assert_eq!(c_le, c_be);
+    }
+
+    #[test]
+    fn test_poseidon_canonical_config() {
+        use ark_crypto_primitives::crh::{
+            poseidon::{
+                constraints::{CRHGadget, CRHParametersVar},
+                CRH,
+            },
+            CRHScheme, CRHSchemeGadget,
+        };
+
+        let config = poseidon_canonical_config::<ark_bn254::Fr>().unwrap();
+        assert_eq!(
+            (config.full_rounds, config.partial_rounds, config.alpha),
+            (8, 57, 5)
+        );
+        assert_eq!((config.rate, config.capacity), (2, 1));
+        assert_eq!(
+            poseidon_config_digest(&config),
+            poseidon_config_digest(&presets::BN254_T3.config().unwrap())
+        );
+
+        // the in-circuit hash matches the native one
+        let input = vec![ark_bn254::Fr::from(1_u32), ark_bn254::Fr::from(2_u32)];
+        let h = CRH::<ark_bn254::Fr>::evaluate(&config, input.clone()).unwrap();
+        let cs = ConstraintSystem::<ark_bn254::Fr>::new_ref();
+        let inputVar = Vec::<FpVar<ark_bn254::Fr>>::new_witness(cs.clone(), || Ok(input)).unwrap();
+        let crh_params = CRHParametersVar::new_constant(cs.clone(), config).unwrap();
+        let hVar = CRHGadget::<ark_bn254::Fr>::evaluate(&crh_params, &inputVar).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(hVar.value().unwrap(), h);
+    }
+
+    #[test]
+    fn test_cycle_configs() {
+        let (config1, config2) = cycle_configs::<Projective, E2Projective>().unwrap();
+        let expected1 = presets::Pallas_T3.config::<Fr>().unwrap();
+        let expected2 = presets::Vesta_T3.config::<Fq>().unwrap();
+        assert_eq!(config1.ark, expected1.ark);
+        assert_eq!(config1.mds, expected1.mds);
+        assert_eq!(config1.partial_rounds, expected1.partial_rounds);
+        assert_eq!(config2.ark, expected2.ark);
+        assert_eq!(config2.mds, expected2.mds);
+        assert_eq!(config2.partial_rounds, expected2.partial_rounds);
+    }
+
+    #[test]
+    fn test_get_challenges() {
+        let config = poseidon_test_config::<Fr>();
+        let mut tr = PoseidonTranscript::<Projective>::new(&config);
+        tr.absorb(&Fr::from(42_u32));
+        let challenges = tr.get_challenges(3);
+
+        // same values as three sequential get_challenge calls
+        let mut tr_seq = PoseidonTranscript::<Projective>::new(&config);
+        tr_seq.absorb(&Fr::from(42_u32));
+        let sequential: Vec<Fr> = (0..3).map(|_| tr_seq.get_challenge()).collect();
+        assert_eq!(challenges, sequential);
+        assert_ne!(challenges[0], challenges[1]);
+        assert_ne!(challenges[0], challenges[2]);
+        assert_ne!(challenges[1], challenges[2]);
+        // and both transcripts are left in the same state
+        assert_eq!(tr.get_challenge(), tr_seq.get_challenge());
+
+        // the batched squeeze also returns distinct values, but not the sequential ones
+        let mut tr_batched = PoseidonTranscript::<Projective>::new(&config);
+        tr_batched.absorb(&Fr::from(42_u32));
+        let batched = tr_batched.get_challenges_batched(3);
+        assert_eq!(batched.len(), 3);
+        assert_ne!(batched[0], batched[1]);
+        assert_ne!(batched[1], batched[2]);
+        assert_eq!(batched[0], sequential[0]);
+        assert_ne!(batched, sequential);
+
+        // same values in-circuit
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let mut tr_var = PoseidonTranscriptVar::<Fr>::new(cs.clone(), &config);
+        let v = FpVar::<Fr>::new_witness(cs.clone(), || Ok(Fr::from(42_u32))).unwrap();
+        tr_var.absorb(v).unwrap();
+        let challenges_var = tr_var.get_challenges(3).unwrap();
+        assert_eq!(challenges_var.value().unwrap(), challenges);
+    }
 }
@@ -0,0 +1,315 @@
+/// Named Poseidon parameter sets, to be used both by the Rust side and by the on-chain Poseidon
+/// implementation.
+///
+/// Each preset fixes the width `t = rate + capacity` (capacity is always 1), the number of full
+/// and partial rounds and the S-box exponent, following the parameters recommended for 128-bit
+/// security in the [Poseidon paper](https://eprint.iacr.org/2019/458.pdf). The BN254 presets ship
+/// the round constants and MDS matrices of circomlib, so that `circomlib_hash` matches the
+/// circomlib Poseidon. For the other presets, the constants are derived from the parameters with
+/// arkworks' `find_poseidon_ark_and_mds`, which uses the Grain LFSR described in the paper, and
+/// they have not been checked against other implementations.
+///
+/// Notice that the arkworks sponge (and so `CRH`) outputs the first rate element of the state,
+/// while circomlib outputs the capacity element, so both hashes differ even with the same
+/// constants.
+use ark_crypto_primitives::sponge::poseidon::{find_poseidon_ark_and_mds, PoseidonConfig};
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+use super::circomlib;
+use crate::Error;
+
+/// round constants and MDS matrix of a preset, as big-endian hex strings, together with the
+/// modulus of the field they are defined over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PresetConstants {
+    pub modulus: &'static str,
+    /// round constants, round by round
+    pub ark: &'static [&'static str],
+    /// MDS matrix, row by row
+    pub mds: &'static [&'static str],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoseidonPreset {
+    pub name: &'static str,
+    /// bit size of the field modulus that the preset is meant for
+    pub field_bits: u32,
+    pub rate: usize,
+    pub full_rounds: usize,
+    pub partial_rounds: usize,
+    pub alpha: u64,
+    /// constants of the preset, derived with `find_poseidon_ark_and_mds` when not set
+    pub constants: Option<PresetConstants>,
+}
+
+pub const BN254_T3: PoseidonPreset = PoseidonPreset {
+    name: "BN254_T3",
+    field_bits: 254,
+    rate: 2,
+    full_rounds: 8,
+    partial_rounds: 57,
+    alpha: 5,
+    constants: Some(PresetConstants {
+        modulus: BN254_FR_MODULUS,
+        ark: &circomlib::BN254_T3_ARK,
+        mds: &circomlib::BN254_T3_MDS,
+    }),
+};
+
+pub const BN254_T5: PoseidonPreset = PoseidonPreset {
+    name: "BN254_T5",
+    field_bits: 254,
+    rate: 4,
+    full_rounds: 8,
+    partial_rounds: 60,
+    alpha: 5,
+    constants: Some(PresetConstants {
+        modulus: BN254_FR_MODULUS,
+        ark: &circomlib::BN254_T5_ARK,
+        mds: &circomlib::BN254_T5_MDS,
+    }),
+};
+
+pub const Grumpkin_T3: PoseidonPreset = PoseidonPreset {
+    name: "Grumpkin_T3",
+    field_bits: 254,
+    rate: 2,
+    full_rounds: 8,
+    partial_rounds: 57,
+    alpha: 5,
+    constants: None,
+};
+
+pub const Pallas_T3: PoseidonPreset = PoseidonPreset {
+    name: "Pallas_T3",
+    field_bits: 255,
+    rate: 2,
+    full_rounds: 8,
+    partial_rounds: 56,
+    alpha: 5,
+    constants: None,
+};
+
+pub const Vesta_T3: PoseidonPreset = PoseidonPreset {
+    name: "Vesta_T3",
+    field_bits: 255,
+    rate: 2,
+    full_rounds: 8,
+    partial_rounds: 56,
+    alpha: 5,
+    constants: None,
+};
+
+/// moduli of the fields for which a preset is defined, as big-endian hex strings
+const BN254_FR_MODULUS: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+const GRUMPKIN_FR_MODULUS: &str =
+    "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+const PALLAS_FR_MODULUS: &str = "40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001";
+const VESTA_FR_MODULUS: &str = "40000000000000000000000000000000224698fc094cf91b992d30ed00000001";
+
+/// returns the preset meant for the field F, identified by its modulus. Returns an error if there
+/// is no preset for F.
+pub fn preset_for_field<F: PrimeField>() -> Result<PoseidonPreset, Error> {
+    let modulus = BigUint::from_bytes_be(&F::MODULUS.to_bytes_be());
+    [
+        (BN254_FR_MODULUS, BN254_T3),
+        (GRUMPKIN_FR_MODULUS, Grumpkin_T3),
+        (PALLAS_FR_MODULUS, Pallas_T3),
+        (VESTA_FR_MODULUS, Vesta_T3),
+    ]
+    .into_iter()
+    .find(|(m, _)| BigUint::parse_bytes(m.as_bytes(), 16) == Some(modulus.clone()))
+    .map(|(_, preset)| preset)
+    .ok_or(Error::NoPoseidonPreset { modulus })
+}
+
+impl PresetConstants {
+    /// parses the constants over the field F, as `(ark, mds)`, checking that F is the field of
+    /// the constants
+    fn to_field<F: PrimeField>(
+        &self,
+        preset: &str,
+        width: usize,
+    ) -> Result<(Vec<Vec<F>>, Vec<Vec<F>>), Error> {
+        let modulus = BigUint::from_bytes_be(&F::MODULUS.to_bytes_be());
+        if BigUint::parse_bytes(self.modulus.as_bytes(), 16) != Some(modulus.clone()) {
+            return Err(Error::PoseidonPresetModulusMismatch {
+                preset: preset.to_string(),
+                modulus,
+            });
+        }
+        let parse = |values: &[&str]| -> Result<Vec<Vec<F>>, Error> {
+            values
+                .chunks(width)
+                .map(|row| {
+                    row.iter()
+                        .map(|v| {
+                            BigUint::parse_bytes(v.as_bytes(), 16)
+                                .map(|v| F::from_be_bytes_mod_order(&v.to_bytes_be()))
+                                .ok_or(Error::OutOfBounds)
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        Ok((parse(self.ark)?, parse(self.mds)?))
+    }
+}
+
+impl PoseidonPreset {
+    /// returns the width `t` of the Poseidon permutation
+    pub fn width(&self) -> usize {
+        self.rate + 1
+    }
+
+    /// returns the PoseidonConfig of the preset over the field F. Returns an error if the preset
+    /// is not meant for a field of F's size, to avoid using eg. the BN254 constants over Pallas,
+    /// or if the preset ships constants of a different field of the same size.
+    pub fn config<F: PrimeField>(&self) -> Result<PoseidonConfig<F>, Error> {
+        if F::MODULUS_BIT_SIZE != self.field_bits {
+            return Err(Error::PoseidonPresetFieldMismatch {
+                preset: self.name.to_string(),
+                preset_bits: self.field_bits,
+                field_bits: F::MODULUS_BIT_SIZE,
+            });
+        }
+        let (ark, mds) = match self.constants {
+            Some(constants) => constants.to_field::<F>(self.name, self.width())?,
+            None => find_poseidon_ark_and_mds::<F>(
+                F::MODULUS_BIT_SIZE as u64,
+                self.rate,
+                self.full_rounds as u64,
+                self.partial_rounds as u64,
+                0,
+            ),
+        };
+        Ok(PoseidonConfig::new(
+            self.full_rounds,
+            self.partial_rounds,
+            self.alpha,
+            mds,
+            ark,
+            self.rate,
+            1,
+        ))
+    }
+}
+
+/// returns the circomlib Poseidon hash of the inputs, `Poseidon(inputs)`, whose length must be
+/// the rate of the config: the permutation is applied to the state `[0, inputs]` and its first
+/// element is returned. With the BN254 presets it matches the circomlib implementation (and its
+/// Solidity counterpart), unlike `CRH`, which outputs a different element of the state.
+pub fn circomlib_hash<F: PrimeField>(config: &PoseidonConfig<F>, inputs: &[F]) -> Result<F, Error> {
+    if inputs.len() != config.rate {
+        return Err(Error::NotExpectedLength {
+            got: inputs.len(),
+            expected: config.rate,
+        });
+    }
+    let mut state = [&[F::zero()], inputs].concat();
+    let rounds = config.full_rounds + config.partial_rounds;
+    for round in 0..rounds {
+        for (s, c) in state.iter_mut().zip(config.ark[round].iter()) {
+            *s += c;
+        }
+        let full = round < config.full_rounds / 2 || round >= rounds - config.full_rounds / 2;
+        let sboxes = if full { state.len() } else { 1 };
+        for s in state.iter_mut().take(sboxes) {
+            *s = s.pow([config.alpha]);
+        }
+        state = config
+            .mds
+            .iter()
+            .map(|row| row.iter().zip(state.iter()).map(|(m, s)| *m * s).sum())
+            .collect();
+    }
+    Ok(state[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_crypto_primitives::crh::{
+        poseidon::{
+            constraints::{CRHGadget, CRHParametersVar},
+            CRH,
+        },
+        CRHScheme, CRHSchemeGadget,
+    };
+    use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
+    use ark_relations::r1cs::ConstraintSystem;
+
+    fn check_preset<F: PrimeField>(preset: PoseidonPreset) {
+        let config = preset.config::<F>().unwrap();
+        assert_eq!(config.ark.len(), preset.full_rounds + preset.partial_rounds);
+        assert_eq!(config.mds.len(), preset.width());
+        // the derivation of the constants is deterministic
+        assert_eq!(config.ark, preset.config::<F>().unwrap().ark);
+
+        let input: Vec<F> = (0..preset.rate as u64).map(F::from).collect();
+        let h = CRH::<F>::evaluate(&config, input.clone()).unwrap();
+
+        // the in-circuit hash matches the native one
+        let cs = ConstraintSystem::<F>::new_ref();
+        let inputVar = Vec::<FpVar<F>>::new_witness(cs.clone(), || Ok(input)).unwrap();
+        let crh_params = CRHParametersVar::<F>::new_constant(cs.clone(), config).unwrap();
+        let hVar = CRHGadget::<F>::evaluate(&crh_params, &inputVar).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(hVar.value().unwrap(), h);
+    }
+
+    #[test]
+    fn test_poseidon_presets() {
+        check_preset::<ark_bn254::Fr>(BN254_T3);
+        check_preset::<ark_bn254::Fr>(BN254_T5);
+        check_preset::<ark_grumpkin::Fr>(Grumpkin_T3);
+        check_preset::<ark_pallas::Fr>(Pallas_T3);
+        check_preset::<ark_vesta::Fr>(Vesta_T3);
+    }
+
+    #[test]
+    fn test_poseidon_preset_field_mismatch() {
+        assert!(BN254_T3.config::<ark_pallas::Fr>().is_err());
+        assert!(Pallas_T3.config::<ark_bn254::Fr>().is_err());
+        // Grumpkin's scalar field has the same size as BN254's, but not the same modulus
+        assert!(matches!(
+            BN254_T3.config::<ark_grumpkin::Fr>(),
+            Err(Error::PoseidonPresetModulusMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_circomlib_hash() {
+        use ark_ff::MontFp;
+        type Fr = ark_bn254::Fr;
+
+        // test vectors of circomlibjs, poseidon([1, 2]) =
+        // 0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a
+        let config = BN254_T3.config::<Fr>().unwrap();
+        let h = circomlib_hash(&config, &[Fr::from(1_u32), Fr::from(2_u32)]).unwrap();
+        let expected: Fr =
+            MontFp!("7853200120776062878684798364095072458815029376092732009249414926327459813530");
+        assert_eq!(h, expected);
+
+        // poseidon([1, 2, 3, 4]) =
+        // 0x299c867db6c1fdd79dcefa40e4510b9837e60ebb1ce0663dbaa525df65250465
+        let config = BN254_T5.config::<Fr>().unwrap();
+        let inputs: Vec<Fr> = (1..=4_u32).map(Fr::from).collect();
+        let h = circomlib_hash(&config, &inputs).unwrap();
+        let expected: Fr = MontFp!(
+            "18821383157269793795438455681495246036402687001665670618754263018637548127333"
+        );
+        assert_eq!(h, expected);
+
+        assert!(circomlib_hash(&config, &inputs[..2]).is_err());
+    }
+
+    #[test]
+    fn test_preset_for_field() {
+        assert_eq!(preset_for_field::<ark_bn254::Fr>().unwrap(), BN254_T3);
+        assert_eq!(preset_for_field::<ark_grumpkin::Fr>().unwrap(), Grumpkin_T3);
+        assert_eq!(preset_for_field::<ark_pallas::Fr>().unwrap(), Pallas_T3);
+        assert_eq!(preset_for_field::<ark_vesta::Fr>().unwrap(), Vesta_T3);
+    }
+}
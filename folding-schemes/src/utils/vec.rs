@@ -3,12 +3,13 @@ use ark_poly::{
     univariate::DensePolynomial, EvaluationDomain, Evaluations, GeneralEvaluationDomain,
 };
 pub use ark_relations::r1cs::Matrix as R1CSMatrix;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_std::cfg_iter;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use crate::Error;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
 pub struct SparseMatrix<F: PrimeField> {
     pub n_rows: usize,
     pub n_cols: usize,
@@ -49,24 +50,24 @@ pub fn dense_matrix_to_sparse<F: PrimeField>(m: Vec<Vec<F>>) -> SparseMatrix<F>
 
 pub fn vec_add<F: PrimeField>(a: &[F], b: &[F]) -> Result<Vec<F>, Error> {
     if a.len() != b.len() {
-        return Err(Error::NotSameLength(
-            "a.len()".to_string(),
-            a.len(),
-            "b.len()".to_string(),
-            b.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "a.len()",
+            a_len: a.len(),
+            b: "b.len()",
+            b_len: b.len(),
+        });
     }
     Ok(a.iter().zip(b.iter()).map(|(x, y)| *x + y).collect())
 }
 
 pub fn vec_sub<F: PrimeField>(a: &[F], b: &[F]) -> Result<Vec<F>, Error> {
     if a.len() != b.len() {
-        return Err(Error::NotSameLength(
-            "a.len()".to_string(),
-            a.len(),
-            "b.len()".to_string(),
-            b.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "a.len()",
+            a_len: a.len(),
+            b: "b.len()",
+            b_len: b.len(),
+        });
     }
     Ok(a.iter().zip(b.iter()).map(|(x, y)| *x - y).collect())
 }
@@ -84,12 +85,12 @@ pub fn mat_vec_mul<F: PrimeField>(M: &[Vec<F>], z: &[F]) -> Result<Vec<F>, Error
         return Err(Error::Empty);
     }
     if M[0].len() != z.len() {
-        return Err(Error::NotSameLength(
-            "M[0].len()".to_string(),
-            M[0].len(),
-            "z.len()".to_string(),
-            z.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "M[0].len()",
+            a_len: M[0].len(),
+            b: "z.len()",
+            b_len: z.len(),
+        });
     }
 
     let mut r: Vec<F> = vec![F::zero(); M.len()];
@@ -103,12 +104,12 @@ pub fn mat_vec_mul<F: PrimeField>(M: &[Vec<F>], z: &[F]) -> Result<Vec<F>, Error
 
 pub fn mat_vec_mul_sparse<F: PrimeField>(M: &SparseMatrix<F>, z: &[F]) -> Result<Vec<F>, Error> {
     if M.n_cols != z.len() {
-        return Err(Error::NotSameLength(
-            "M.n_cols".to_string(),
-            M.n_cols,
-            "z.len()".to_string(),
-            z.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "M.n_cols",
+            a_len: M.n_cols,
+            b: "z.len()",
+            b_len: z.len(),
+        });
     }
     let mut res = vec![F::zero(); M.n_rows];
     for (row_i, row) in M.coeffs.iter().enumerate() {
@@ -121,19 +122,27 @@ pub fn mat_vec_mul_sparse<F: PrimeField>(M: &SparseMatrix<F>, z: &[F]) -> Result
 
 pub fn hadamard<F: PrimeField>(a: &[F], b: &[F]) -> Result<Vec<F>, Error> {
     if a.len() != b.len() {
-        return Err(Error::NotSameLength(
-            "a.len()".to_string(),
-            a.len(),
-            "b.len()".to_string(),
-            b.len(),
-        ));
+        return Err(Error::NotSameLength {
+            a: "a.len()",
+            a_len: a.len(),
+            b: "b.len()",
+            b_len: b.len(),
+        });
     }
     Ok(cfg_iter!(a).zip(b).map(|(a, b)| *a * b).collect())
 }
 
+/// returns the given vector padded with zeros up to the next power of two length
+pub fn pad_to_next_power_of_two<F: PrimeField>(mut v: Vec<F>) -> Vec<F> {
+    v.resize(v.len().next_power_of_two(), F::zero());
+    v
+}
+
 /// returns the interpolated polynomial of degree=v.len().next_power_of_two(), which passes through all
-/// the given elements of v.
+/// the given elements of v. The vector is padded with zeros up to the next power of two length, so
+/// the polynomial also passes through zero at the remaining points of the domain.
 pub fn poly_from_vec<F: PrimeField>(v: Vec<F>) -> Result<DensePolynomial<F>, Error> {
+    let v = pad_to_next_power_of_two(v);
     let D = GeneralEvaluationDomain::<F>::new(v.len()).ok_or(Error::NewDomainFail)?;
     Ok(Evaluations::from_vec_and_domain(v, D).interpolate())
 }
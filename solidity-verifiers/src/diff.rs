@@ -0,0 +1,320 @@
+//! Comparison of Solidity contracts modulo formatting, used to check that the contracts rendered
+//! after an upgrade of the templates are equivalent to the previously rendered ones.
+
+/// ItemDiff is a top-level item (a pragma, a constant, a function, ...) of the contracts that
+/// differs between them, with its normalized source in each one of the contracts, or `None` if
+/// the contract does not contain it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemDiff {
+    /// name of the item, prefixed by the name of its contract, eg.
+    /// `Groth16Verifier::constant alphax`
+    pub item: String,
+    pub a: Option<String>,
+    pub b: Option<String>,
+}
+
+/// ContractDiff contains the items that differ between two contracts, see `diff_contracts`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractDiff {
+    pub differences: Vec<ItemDiff>,
+}
+
+impl ContractDiff {
+    /// returns true if the contracts are equivalent modulo formatting
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// Compares the contracts `a` and `b` ignoring the whitespace and the comments (including the
+/// SPDX license identifier), and returns the items in which they differ: the items of `a` in
+/// order of appearance, followed by the items that are only in `b`. Constants, state variables,
+/// functions, modifiers, events... are identified by their name (and the name of their
+/// contract), so a change in the value of a constant or in the body of a function is reported
+/// as a single difference of that item.
+pub fn diff_contracts(a: &str, b: &str) -> ContractDiff {
+    let (items_a, items_b) = (items(&tokenize(a)), items(&tokenize(b)));
+    let get = |items: &[(String, String)], key: &str| {
+        items.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    };
+
+    let mut differences = vec![];
+    for (key, value) in items_a.iter() {
+        let b_value = get(&items_b, key);
+        if b_value.as_ref() != Some(value) {
+            differences.push(ItemDiff {
+                item: key.clone(),
+                a: Some(value.clone()),
+                b: b_value,
+            });
+        }
+    }
+    for (key, value) in items_b.iter() {
+        if get(&items_a, key).is_none() {
+            differences.push(ItemDiff {
+                item: key.clone(),
+                a: None,
+                b: Some(value.clone()),
+            });
+        }
+    }
+    ContractDiff { differences }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '$'
+}
+
+/// splits the source into words, string literals and punctuation characters, dropping the
+/// whitespace and the comments
+fn tokenize(src: &str) -> Vec<String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                // skip the escaped character
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i += 1;
+            tokens.push(chars[start..i.min(chars.len())].iter().collect());
+        } else if is_word_char(c) {
+            let start = i;
+            while i < chars.len() && is_word_char(chars[i]) {
+                i += 1;
+            }
+            tokens.push(chars[start..i].iter().collect());
+        } else {
+            tokens.push(c.to_string());
+            i += 1;
+        }
+    }
+    tokens
+}
+
+fn is_word(token: &str) -> bool {
+    token.starts_with(|c: char| is_word_char(c) || c == '"' || c == '\'')
+}
+
+/// joins the tokens back into source, with a single space between consecutive words
+fn join(tokens: &[String]) -> String {
+    let mut s = String::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i > 0 && is_word(&tokens[i - 1]) && is_word(token) {
+            s.push(' ');
+        }
+        s.push_str(token);
+    }
+    s
+}
+
+/// returns the key of the item whose source starts with the given tokens
+fn item_key(header: &[String]) -> String {
+    let first = header.first().map(String::as_str).unwrap_or("");
+    let second = header.get(1).cloned().unwrap_or_default();
+    match first {
+        "pragma" | "function" | "modifier" | "event" | "error" | "struct" | "enum" | "using" => {
+            format!("{} {}", first, second)
+        }
+        "constructor" | "fallback" | "receive" => first.to_string(),
+        "import" => join(header),
+        _ => {
+            // declarations of constants and state variables, named by the identifier before
+            // the assignment or the end of the declaration
+            let end = header
+                .iter()
+                .position(|t| t == "=" || t == ";")
+                .unwrap_or(header.len());
+            let name = header[..end].last().cloned().unwrap_or_default();
+            if header[..end].iter().any(|t| t == "constant") {
+                format!("constant {}", name)
+            } else {
+                format!("variable {}", name)
+            }
+        }
+    }
+}
+
+/// returns the items of the tokenized source, as pairs of their key and their normalized source
+fn items(tokens: &[String]) -> Vec<(String, String)> {
+    let mut items: Vec<(String, String)> = vec![];
+    let mut scopes: Vec<String> = vec![];
+    let mut push = |scopes: &[String], key: String, value: String| {
+        let key = match scopes.last() {
+            Some(scope) => format!("{}::{}", scope, key),
+            None => key,
+        };
+        // overloaded functions and repeated items are distinguished by their position
+        let n = items
+            .iter()
+            .filter(|(k, _)| k == &key || k.starts_with(&format!("{}#", key)))
+            .count();
+        let key = if n == 0 {
+            key
+        } else {
+            format!("{}#{}", key, n + 1)
+        };
+        items.push((key, value));
+    };
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] == "}" {
+            scopes.pop();
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < tokens.len() && tokens[i] != ";" && tokens[i] != "{" && tokens[i] != "}" {
+            i += 1;
+        }
+        if i == tokens.len() || tokens[i] == "}" {
+            // trailing tokens of an unterminated item
+            if start < i {
+                push(&scopes, join(&tokens[start..i]), join(&tokens[start..i]));
+            }
+            continue;
+        }
+        let header = &tokens[start..i];
+        let kind = header
+            .iter()
+            .find(|t| *t != "abstract")
+            .cloned()
+            .unwrap_or_default();
+        if tokens[i] == ";" {
+            i += 1;
+            push(&scopes, item_key(header), join(&tokens[start..i]));
+        } else if ["contract", "library", "interface"].contains(&kind.as_str()) {
+            let name = header
+                .iter()
+                .skip_while(|t| **t != kind)
+                .nth(1)
+                .cloned()
+                .unwrap_or_default();
+            i += 1;
+            push(
+                &scopes,
+                format!("{} {}", kind, name),
+                join(&tokens[start..i]),
+            );
+            scopes.push(name);
+        } else {
+            // block item, eg. a function, whose source goes up to the matching closing brace
+            let mut depth = 0;
+            while i < tokens.len() {
+                match tokens[i].as_str() {
+                    "{" => depth += 1,
+                    "}" => depth -= 1,
+                    _ => {}
+                }
+                i += 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            push(&scopes, item_key(header), join(&tokens[start..i]));
+        }
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verifiers::tests::{setup, DEFAULT_SETUP_LEN};
+    use crate::{Groth16VerifierKey, ProtocolVerifierKey, RenderOptions};
+
+    #[test]
+    fn test_diff_rendered_contracts() {
+        let (_, _, _, vk, _) = setup(DEFAULT_SETUP_LEN);
+        let render = |pragma: Option<String>| {
+            let options = RenderOptions { pragma, spdx: None };
+            String::from_utf8(Groth16VerifierKey::from(vk.clone()).render_as_template(options))
+                .unwrap()
+        };
+
+        // two renders of the same data are equivalent
+        assert!(diff_contracts(&render(None), &render(None)).is_empty());
+
+        // a changed pragma differs only in the pragma
+        let diff = diff_contracts(
+            &render(None),
+            &render(Some("pragma solidity ^0.8.20;".to_string())),
+        );
+        assert_eq!(diff.differences.len(), 1);
+        assert_eq!(diff.differences[0].item, "pragma solidity");
+        assert_eq!(
+            diff.differences[0].b,
+            Some("pragma solidity^0.8.20;".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diff_contracts_normalization() {
+        let a = r#"
+            // SPDX-License-Identifier: MIT
+            pragma solidity ^0.8.0;
+            contract A {
+                uint256 constant x = 1;
+                uint256 constant y = 2;
+                function f(uint256 v) public pure returns (uint256) {
+                    return v + x; /* the sum */
+                }
+            }
+        "#;
+        let b = r#"pragma solidity ^0.8.0;
+contract A {
+    uint256   constant x=1;
+    // comment
+    uint256 constant y = 2;
+    function f(uint256 v) public pure returns (uint256) { return v+x; }
+}"#;
+        assert!(diff_contracts(a, b).is_empty());
+
+        let changed = b.replace("y = 2", "y = 3").replace("v+x", "v*x");
+        let diff = diff_contracts(a, &changed);
+        let items: Vec<&str> = diff.differences.iter().map(|d| d.item.as_str()).collect();
+        assert_eq!(items, vec!["A::constant y", "A::function f"]);
+
+        // removed and added items
+        let renamed = b.replace("function f", "function g");
+        let diff = diff_contracts(a, &renamed);
+        assert_eq!(
+            diff.differences,
+            vec![
+                ItemDiff {
+                    item: "A::function f".to_string(),
+                    a: Some(
+                        "function f(uint256 v)public pure returns(uint256){return v+x;}"
+                            .to_string()
+                    ),
+                    b: None,
+                },
+                ItemDiff {
+                    item: "A::function g".to_string(),
+                    a: None,
+                    b: Some(
+                        "function g(uint256 v)public pure returns(uint256){return v+x;}"
+                            .to_string()
+                    ),
+                },
+            ]
+        );
+    }
+}
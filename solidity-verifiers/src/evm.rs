@@ -77,6 +77,20 @@ fn find_binary(stdout: &str, contract_name: &str) -> Option<Vec<u8>> {
     Some(hex::decode(&stdout_contract[start..stdout_contract.len() - 1]).unwrap())
 }
 
+/// Compiles the given Solidity code, deploys the contract `contract_name` in a fresh `Evm`, and
+/// calls it with `calldata`, returning whether the call returned `true`. Allows to check in Rust
+/// that the rendered verifier accepts a proof, without deploying it to a network.
+///
+/// # Panics
+/// Panics if `solc` can not be found, or the compilation or the deployment fail.
+pub fn simulate_verify(solidity: &str, contract_name: &str, calldata: Vec<u8>) -> bool {
+    let bytecode = compile_solidity(solidity, contract_name);
+    let mut evm = Evm::default();
+    let address = evm.create(bytecode);
+    let (_, output) = evm.call(address, calldata);
+    output.last() == Some(&1)
+}
+
 /// Evm runner.
 pub struct Evm {
     evm: EVM<InMemoryDB>,
@@ -0,0 +1,114 @@
+//! End-to-end pipeline of Nova + CycleFold over BN254 and Grumpkin, from the folding of the
+//! circuit to the verification of the decider proof in the EVM. It serves as a template for the
+//! users of the library.
+
+use ark_bn254::{constraints::GVar, Bn254, Fr, G1Projective as G1};
+use ark_grumpkin::{constraints::GVar as GVar2, Projective as G2};
+
+use folding_schemes::{
+    commitment::{kzg::KZG, pedersen::Pedersen, CommitmentScheme},
+    folding::nova::{
+        decider::DeciderGroth16, decider_eth::prepare_calldata, get_cs_params_len, Nova,
+        ProverParams,
+    },
+    frontend::FCircuit,
+    transcript::poseidon::poseidon_test_config,
+    Error, FoldingScheme,
+};
+
+use crate::evm::simulate_verify;
+use crate::utils::get_function_selector_for_nova_cyclefold_verifier;
+use crate::verifiers::nova_cyclefold::get_decider_template_for_cyclefold_decider;
+use crate::NovaCycleFoldVerifierKey;
+
+/// Runs the whole pipeline for the given circuit, which takes no external inputs: folds `steps`
+/// steps of it starting at `z_0`, compresses the IVC proof with the Groth16 decider and verifies
+/// it natively, renders the Solidity verifier of the decider, and verifies the proof with it in
+/// the EVM (see `simulate_verify`). Returns the rendered Solidity verifier.
+///
+/// WARNING: the params are generated from a deterministic rng and with the Poseidon config of
+/// the tests, so this is only meant for testing and as a template.
+pub fn full_pipeline<FC: FCircuit<Fr, ExternalInputs = ()>>(
+    circuit: FC,
+    z_0: Vec<Fr>,
+    steps: usize,
+) -> Result<String, Error> {
+    type NovaBN254<FC> = Nova<G1, GVar, G2, GVar2, FC, KZG<'static, Bn254>, Pedersen<G2>>;
+    type Decider<FC> = DeciderGroth16<GVar, G2, GVar2, FC, Pedersen<G2>>;
+
+    // the decider needs at least two folded steps
+    if steps < 2 {
+        return Err(Error::NotEnoughSteps);
+    }
+    let mut rng = ark_std::test_rng();
+    let poseidon_config = poseidon_test_config::<Fr>();
+    let z_len = circuit.state_len();
+
+    // fold
+    let (cs_len, cf_cs_len) =
+        get_cs_params_len::<G1, GVar, G2, GVar2, FC>(&poseidon_config, circuit.clone())?;
+    let (kzg_pk, kzg_vk) = KZG::<'static, Bn254>::setup(&mut rng, cs_len)?;
+    let (cf_pedersen_params, _) = Pedersen::<G2>::setup(&mut rng, cf_cs_len)?;
+    let prover_params = ProverParams::<G1, G2, KZG<'static, Bn254>, Pedersen<G2>> {
+        poseidon_config,
+        cs_params: kzg_pk,
+        cf_cs_params: cf_pedersen_params,
+    };
+    let mut nova = NovaBN254::<FC>::init(&prover_params, circuit, z_0)?;
+    for _ in 0..steps {
+        nova.prove_step(())?;
+    }
+
+    // decide
+    let (decider_pp, decider_vp) = Decider::<FC>::setup(nova.clone(), kzg_vk, &mut rng)?;
+    let proof = Decider::<FC>::prove(decider_pp, &mut rng, nova.clone())?;
+    let bindings = nova.bindings()?;
+    if !Decider::<FC>::verify(
+        decider_vp.clone(),
+        &proof,
+        nova.z_0.clone(),
+        nova.z_i.clone(),
+        steps as u64,
+        &bindings,
+    )? {
+        return Err(Error::SNARKVerificationFail);
+    }
+
+    // render
+    let (g16_vk, kzg_vk) = decider_vp;
+    let decider_solidity_code = get_decider_template_for_cyclefold_decider(
+        NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, z_len)),
+    );
+
+    // verify in the EVM
+    let calldata = prepare_calldata(
+        get_function_selector_for_nova_cyclefold_verifier(z_len * 2 + 1 + bindings.to_vec().len()),
+        nova.i,
+        nova.z_0,
+        nova.z_i,
+        &bindings,
+        &proof.U_i,
+        &proof.u_i,
+        proof.proof,
+    )?;
+    if !simulate_verify(&decider_solidity_code, "NovaDecider", calldata) {
+        return Err(Error::Other(
+            "the Solidity verifier rejected the decider proof".to_string(),
+        ));
+    }
+
+    Ok(decider_solidity_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use folding_schemes::frontend::examples::PoseidonFCircuit;
+
+    #[test]
+    fn test_full_pipeline_poseidon() {
+        let circuit = PoseidonFCircuit::<Fr>::new(poseidon_test_config()).unwrap();
+        let decider_solidity_code = full_pipeline(circuit, vec![Fr::from(3_u32)], 3).unwrap();
+        assert!(decider_solidity_code.contains("contract NovaDecider"));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod diff;
 pub mod evm;
+pub mod examples;
 pub mod utils;
 pub mod verifiers;
 
+pub use diff::{diff_contracts, ContractDiff, ItemDiff};
 pub use verifiers::*;
 pub use verifiers::{
-    get_decider_template_for_cyclefold_decider, Groth16VerifierKey, KZG10VerifierKey,
-    NovaCycleFoldVerifierKey, ProtocolVerifierKey,
+    get_decider_template_for_cyclefold_decider,
+    get_proxy_compatible_decider_template_for_cyclefold_decider, Groth16VerifierKey,
+    KZG10VerifierKey, NovaCycleFoldVerifierKey, ProtocolVerifierKey,
 };
@@ -16,17 +16,23 @@ pub fn get_formatted_calldata(calldata: Vec<u8>) -> Vec<String> {
     formatted_calldata
 }
 
+/// Computes the function selector from the given function signature, that is the first 4 bytes
+/// of `keccak256(fn_sig)`
+pub fn get_function_selector(fn_sig: &str) -> [u8; 4] {
+    let mut hasher = Sha3::keccak256();
+    hasher.input_str(fn_sig);
+    let hash = &mut [0u8; 32];
+    hasher.result(hash);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
 /// Computes the function selector for the nova cyclefold verifier
 /// It is computed on the fly since it depends on the length of the first parameter array
 pub fn get_function_selector_for_nova_cyclefold_verifier(
     first_param_array_length: usize,
 ) -> [u8; 4] {
-    let mut hasher = Sha3::keccak256();
     let fn_sig = format!("verifyNovaProof(uint256[{}],uint256[4],uint256[3],uint256[4],uint256[4],uint256[2],uint256[2][2],uint256[2],uint256[4],uint256[2][2])", first_param_array_length);
-    hasher.input_str(&fn_sig);
-    let hash = &mut [0u8; 32];
-    hasher.result(hash);
-    [hash[0], hash[1], hash[2], hash[3]]
+    get_function_selector(&fn_sig)
 }
 
 #[derive(Template)]
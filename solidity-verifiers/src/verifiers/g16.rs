@@ -1,7 +1,7 @@
 use crate::utils::encoding::{g1_to_fq_repr, g2_to_fq_repr};
 use crate::utils::encoding::{G1Repr, G2Repr};
 use crate::utils::HeaderInclusion;
-use crate::{ProtocolVerifierKey, GPL3_SDPX_IDENTIFIER};
+use crate::{ProtocolVerifierKey, RenderOptions, GPL3_SDPX_IDENTIFIER};
 use ark_bn254::Bn254;
 use ark_groth16::VerifyingKey;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -59,10 +59,21 @@ impl From<VerifyingKey<Bn254>> for Groth16VerifierKey {
 impl ProtocolVerifierKey for Groth16VerifierKey {
     const PROTOCOL_NAME: &'static str = "Groth16";
 
-    fn render_as_template(self, pragma: Option<String>) -> Vec<u8> {
+    fn function_signature(&self) -> String {
+        format!(
+            "verifyProof(uint256[2],uint256[2][2],uint256[2],uint256[{}])",
+            self.public_inputs_len()
+        )
+    }
+
+    fn public_inputs_len(&self) -> usize {
+        self.0.gamma_abc_g1.len() - 1
+    }
+
+    fn render_as_template(self, options: RenderOptions) -> Vec<u8> {
         HeaderInclusion::<Groth16Verifier>::builder()
-            .sdpx(GPL3_SDPX_IDENTIFIER.to_string())
-            .pragma_version(pragma.unwrap_or(PRAGMA_GROTH16_VERIFIER.to_string()))
+            .sdpx(options.spdx_or(GPL3_SDPX_IDENTIFIER))
+            .pragma_version(options.pragma_or(PRAGMA_GROTH16_VERIFIER))
             .template(self)
             .build()
             .render()
@@ -72,7 +83,7 @@ impl ProtocolVerifierKey for Groth16VerifierKey {
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::Groth16VerifierKey;
     use crate::{
         evm::{compile_solidity, save_solidity, Evm},
@@ -1,7 +1,7 @@
 use crate::utils::encoding::{g1_to_fq_repr, g2_to_fq_repr};
 use crate::utils::encoding::{G1Repr, G2Repr};
 use crate::utils::HeaderInclusion;
-use crate::{ProtocolVerifierKey, MIT_SDPX_IDENTIFIER};
+use crate::{ProtocolVerifierKey, RenderOptions, MIT_SDPX_IDENTIFIER};
 use ark_bn254::{Bn254, G1Affine};
 use ark_poly_commit::kzg10::VerifierKey;
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
@@ -22,6 +22,9 @@ pub struct KZG10Verifier {
     pub(crate) g1_crs_len: usize,
     /// The trusted setup vector.
     pub(crate) g1_crs: Vec<G1Repr>,
+    /// Whether the keys are inlined as constants instead of being stored, so that the contract
+    /// can be used behind a proxy.
+    pub(crate) proxy_compatible: bool,
 }
 
 impl From<KZG10VerifierKey> for KZG10Verifier {
@@ -36,6 +39,7 @@ impl From<KZG10VerifierKey> for KZG10Verifier {
                 .iter()
                 .map(|g1| g1_to_fq_repr(*g1))
                 .collect(),
+            proxy_compatible: false,
         }
     }
 }
@@ -58,10 +62,19 @@ impl From<(VerifierKey<Bn254>, Vec<G1Affine>)> for KZG10VerifierKey {
 impl ProtocolVerifierKey for KZG10VerifierKey {
     const PROTOCOL_NAME: &'static str = "KZG";
 
-    fn render_as_template(self, pragma: Option<String>) -> Vec<u8> {
+    fn function_signature(&self) -> String {
+        "check(uint256[2],uint256[2],uint256,uint256)".to_string()
+    }
+
+    // the evaluation point and value
+    fn public_inputs_len(&self) -> usize {
+        2
+    }
+
+    fn render_as_template(self, options: RenderOptions) -> Vec<u8> {
         HeaderInclusion::<KZG10Verifier>::builder()
-            .sdpx(MIT_SDPX_IDENTIFIER.to_string())
-            .pragma_version(pragma.unwrap_or(PRAGMA_KZG10_VERIFIER.to_string()))
+            .sdpx(options.spdx_or(MIT_SDPX_IDENTIFIER))
+            .pragma_version(options.pragma_or(PRAGMA_KZG10_VERIFIER))
             .template(self)
             .build()
             .render()
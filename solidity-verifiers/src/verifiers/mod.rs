@@ -8,15 +8,56 @@ pub const PRAGMA_KZG10_VERIFIER: &str = "pragma solidity >=0.8.1 <=0.8.4;";
 /// Default SDPX License identifier
 pub const GPL3_SDPX_IDENTIFIER: &str = "// SPDX-License-Identifier: GPL-3.0";
 pub const MIT_SDPX_IDENTIFIER: &str = "// SPDX-License-Identifier: MIT";
+
+/// RenderOptions are the options for the header of the rendered contracts, see
+/// `ProtocolVerifierKey::render_as_template`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// either a full `pragma` statement (`pragma solidity ^0.8.20;`) or only the version
+    /// (`^0.8.20`). Defaults to the pragma of the protocol.
+    pub pragma: Option<String>,
+    /// SPDX license identifier (eg. `MIT`), written as the first line of the contract. Defaults to
+    /// the license of the protocol template, GPL-3.0 for the contracts that include the Groth16
+    /// verifier.
+    pub spdx: Option<String>,
+}
+
+impl RenderOptions {
+    /// returns the `pragma` statement of the options, or the given default one
+    pub fn pragma_or(&self, default: &str) -> String {
+        match &self.pragma {
+            Some(pragma) if pragma.trim_start().starts_with("pragma") => pragma.clone(),
+            Some(version) => format!("pragma solidity {};", version.trim()),
+            None => default.to_string(),
+        }
+    }
+
+    /// returns the SPDX license line of the options, or the given default one
+    pub fn spdx_or(&self, default: &str) -> String {
+        match &self.spdx {
+            Some(spdx) => format!("// SPDX-License-Identifier: {}", spdx.trim()),
+            None => default.to_string(),
+        }
+    }
+}
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Read, SerializationError, Write};
 
+use folding_schemes::Error;
+
+use crate::utils::get_function_selector;
+
 pub mod g16;
 pub mod kzg;
 pub mod nova_cyclefold;
 
 pub use g16::Groth16VerifierKey;
 pub use kzg::KZG10VerifierKey;
-pub use nova_cyclefold::{get_decider_template_for_cyclefold_decider, NovaCycleFoldVerifierKey};
+pub use nova_cyclefold::{
+    get_decider_template_for_cyclefold_decider,
+    get_proxy_compatible_decider_template_for_cyclefold_decider, NovaCycleFoldVerifierKey,
+};
 
 pub trait ProtocolVerifierKey: CanonicalDeserialize + CanonicalSerialize {
     const PROTOCOL_NAME: &'static str;
@@ -37,7 +78,7 @@ pub trait ProtocolVerifierKey: CanonicalDeserialize + CanonicalSerialize {
     fn deserialize_protocol_verifier_key<R: Read + Copy>(
         mut reader: R,
     ) -> Result<Self, SerializationError> {
-        let name: String = String::deserialize_uncompressed(&mut reader)?;
+        let name = deserialize_protocol_name(&mut reader)?;
         let data = Self::deserialize_compressed(&mut reader)?;
 
         if name != Self::PROTOCOL_NAME {
@@ -47,28 +88,136 @@ pub trait ProtocolVerifierKey: CanonicalDeserialize + CanonicalSerialize {
         Ok(data)
     }
 
-    fn render_as_template(self, pragma: Option<String>) -> Vec<u8>;
+    /// renders the verifier contract, with the header given by the options
+    fn render_as_template(self, options: RenderOptions) -> Vec<u8>;
+
+    /// returns the signature of the verification function of the rendered contract, from which
+    /// its selector is derived
+    fn function_signature(&self) -> String;
+
+    /// returns the number of public inputs of the verification function
+    fn public_inputs_len(&self) -> usize;
+
+    /// returns the ABI encoded arguments of the verification function, from the encoded proof
+    /// and public inputs. By default the proof goes before the public inputs.
+    fn encode_arguments(&self, proof: &[u8], public_inputs: &[u8]) -> Vec<u8> {
+        [proof, public_inputs].concat()
+    }
+}
+
+/// Returns the protocol name (one of the `ProtocolVerifierKey::PROTOCOL_NAME`s) written at the
+/// beginning of a serialized protocol verifier key by `serialize_protocol_verifier_key`, which
+/// allows to know the protocol of the key before deserializing it.
+pub fn deserialize_protocol_name<R: Read>(reader: R) -> Result<String, SerializationError> {
+    String::deserialize_uncompressed(reader)
+}
+
+/// Returns the calldata to call the verification function of the contract rendered for the given
+/// `protocol` (one of the `ProtocolVerifierKey::PROTOCOL_NAME`s) and its compressed verifier key
+/// `data`, that is the selector of the function followed by its encoded arguments. `proof` is
+/// expected to be already encoded as 32-byte words, in the order of the function parameters.
+pub fn encode_calldata(
+    protocol: &str,
+    data: &[u8],
+    proof: &[u8],
+    public_inputs: &[Fr],
+) -> Result<Vec<u8>, SerializationError> {
+    if protocol == Groth16VerifierKey::PROTOCOL_NAME {
+        encode_calldata_for::<Groth16VerifierKey>(data, proof, public_inputs)
+    } else if protocol == KZG10VerifierKey::PROTOCOL_NAME {
+        encode_calldata_for::<KZG10VerifierKey>(data, proof, public_inputs)
+    } else if protocol == NovaCycleFoldVerifierKey::PROTOCOL_NAME {
+        encode_calldata_for::<NovaCycleFoldVerifierKey>(data, proof, public_inputs)
+    } else {
+        Err(SerializationError::InvalidData)
+    }
+}
+
+fn encode_calldata_for<VK: ProtocolVerifierKey>(
+    data: &[u8],
+    proof: &[u8],
+    public_inputs: &[Fr],
+) -> Result<Vec<u8>, SerializationError> {
+    let vk = VK::deserialize_compressed(data)?;
+    Ok([
+        get_function_selector(&vk.function_signature()).to_vec(),
+        vk.encode_arguments(proof, &encode_public_inputs(public_inputs)),
+    ]
+    .concat())
+}
+
+/// Returns the ABI encoding of the public inputs, as 32-byte big-endian words.
+pub fn encode_public_inputs(public_inputs: &[Fr]) -> Vec<u8> {
+    public_inputs
+        .iter()
+        .flat_map(|x| x.into_bigint().to_bytes_be())
+        .collect()
+}
+
+/// Checks that the ABI encoded public inputs `abi_bytes` match the public inputs of the proof, for
+/// the verification function of the given verifier key `data`. Each 32-byte word is compared
+/// against the canonical encoding of the corresponding public input, so non-canonical encodings
+/// are also rejected. Returns `Error::PublicInputAbiMismatch` with the index of the first
+/// mismatching input.
+pub fn check_public_inputs_abi<VK: ProtocolVerifierKey>(
+    data: &VK,
+    abi_bytes: &[u8],
+    proof_public_inputs: &[Fr],
+) -> Result<(), Error> {
+    let n = data.public_inputs_len();
+    if proof_public_inputs.len() != n {
+        return Err(Error::PublicInputCountMismatch {
+            expected: n,
+            got: proof_public_inputs.len(),
+        });
+    }
+    if abi_bytes.len() != n * 32 {
+        return Err(Error::NotExpectedLength {
+            got: abi_bytes.len(),
+            expected: n * 32,
+        });
+    }
+    for (i, (word, x)) in abi_bytes
+        .chunks(32)
+        .zip(encode_public_inputs(proof_public_inputs).chunks(32))
+        .enumerate()
+    {
+        if word != x {
+            return Err(Error::PublicInputAbiMismatch { index: i });
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 pub mod tests {
     use ark_bn254::{Bn254, Fr, G1Projective as G1};
     use ark_crypto_primitives::snark::CircuitSpecificSetupSNARK;
-    use ark_ff::PrimeField;
+    use ark_ff::{BigInteger, PrimeField};
     use ark_groth16::Groth16;
     use ark_poly_commit::kzg10::VerifierKey as KZGVerifierKey;
     use ark_r1cs_std::alloc::AllocVar;
     use ark_r1cs_std::eq::EqGadget;
     use ark_r1cs_std::fields::fp::FpVar;
     use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+    use ark_serialize::CanonicalSerialize;
     use ark_std::rand::{RngCore, SeedableRng};
     use ark_std::test_rng;
     use std::marker::PhantomData;
 
+    use super::{
+        check_public_inputs_abi, encode_calldata, encode_public_inputs, Groth16VerifierKey,
+        KZG10VerifierKey, NovaCycleFoldVerifierKey, ProtocolVerifierKey, RenderOptions,
+        GPL3_SDPX_IDENTIFIER, MIT_SDPX_IDENTIFIER, PRAGMA_GROTH16_VERIFIER, PRAGMA_KZG10_VERIFIER,
+    };
+    use crate::verifiers::g16::tests::FUNCTION_SELECTOR_GROTH16_VERIFY_PROOF;
+    use crypto::{digest::Digest, sha3::Sha3};
     use folding_schemes::commitment::{
         kzg::{ProverKey as KZGProverKey, KZG},
         CommitmentScheme,
     };
+    use folding_schemes::Error;
+    use num_bigint::BigUint;
 
     /// Default setup length for testing.
     pub const DEFAULT_SETUP_LEN: usize = 5;
@@ -117,4 +266,187 @@ pub mod tests {
             KZG::<Bn254>::setup(&mut rng, n).unwrap();
         (kzg_pk, kzg_vk, g16_pk, g16_vk, circuit)
     }
+
+    /// returns the canonical signature of the given function of the rendered contract, that is
+    /// its name followed by the types of its parameters
+    fn rendered_function_signature(contract: &str, name: &str) -> String {
+        let start = contract.find(&format!("function {}(", name)).unwrap() + 9;
+        // remove the comments, which may contain commas
+        let params: String = contract[start + name.len() + 1..]
+            .lines()
+            .map(|line| line.split("//").next().unwrap())
+            .collect();
+        let params = &params[..params.find(')').unwrap()];
+        let types: Vec<String> = params
+            .split(',')
+            .map(|param| {
+                let t = param.split_whitespace().next().unwrap();
+                match t.strip_prefix("uint") {
+                    Some(rest) if !rest.starts_with("256") => format!("uint256{}", rest),
+                    _ => t.to_string(),
+                }
+            })
+            .collect();
+        format!("{}({})", name, types.join(","))
+    }
+
+    fn keccak_selector(fn_sig: &str) -> [u8; 4] {
+        let mut hasher = Sha3::keccak256();
+        hasher.input_str(fn_sig);
+        let hash = &mut [0u8; 32];
+        hasher.result(hash);
+        [hash[0], hash[1], hash[2], hash[3]]
+    }
+
+    fn check_calldata<VK: ProtocolVerifierKey + Clone>(
+        vk: VK,
+        fn_name: &str,
+        proof: &[u8],
+        public_inputs: &[Fr],
+    ) -> Vec<u8> {
+        let mut data = vec![];
+        vk.serialize_compressed(&mut data).unwrap();
+        let calldata = encode_calldata(VK::PROTOCOL_NAME, &data, proof, public_inputs).unwrap();
+
+        let contract =
+            String::from_utf8(vk.clone().render_as_template(RenderOptions::default())).unwrap();
+        let fn_sig = rendered_function_signature(&contract, fn_name);
+        assert_eq!(fn_sig, vk.function_signature());
+        assert_eq!(calldata[..4], keccak_selector(&fn_sig));
+        assert_eq!(calldata.len(), 4 + proof.len() + public_inputs.len() * 32);
+        calldata
+    }
+
+    // returns the first two lines of the contract rendered with the given options
+    fn render_header<VK: ProtocolVerifierKey>(vk: VK, options: RenderOptions) -> Vec<String> {
+        String::from_utf8(vk.render_as_template(options))
+            .unwrap()
+            .lines()
+            .take(2)
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_render_options() {
+        let (kzg_pk, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+        let g16_vk = Groth16VerifierKey::from(g16_vk);
+        let kzg_vk = KZG10VerifierKey::from((kzg_vk, kzg_pk.powers_of_g[0..3].to_vec()));
+        let nova_vk = NovaCycleFoldVerifierKey::from((g16_vk.clone(), kzg_vk.clone(), 2));
+
+        // the defaults of each protocol, where the contracts with the Groth16 verifier are GPL-3.0
+        assert_eq!(
+            render_header(g16_vk.clone(), RenderOptions::default()),
+            [GPL3_SDPX_IDENTIFIER, PRAGMA_GROTH16_VERIFIER]
+        );
+        assert_eq!(
+            render_header(kzg_vk.clone(), RenderOptions::default()),
+            [MIT_SDPX_IDENTIFIER, PRAGMA_KZG10_VERIFIER]
+        );
+        assert_eq!(
+            render_header(nova_vk.clone(), RenderOptions::default()),
+            [GPL3_SDPX_IDENTIFIER, PRAGMA_GROTH16_VERIFIER]
+        );
+
+        // a pragma range and a license
+        let options = RenderOptions {
+            pragma: Some("^0.8.20".to_string()),
+            spdx: Some("Apache-2.0".to_string()),
+        };
+        let expected = [
+            "// SPDX-License-Identifier: Apache-2.0",
+            "pragma solidity ^0.8.20;",
+        ];
+        assert_eq!(render_header(g16_vk.clone(), options.clone()), expected);
+        assert_eq!(render_header(kzg_vk, options.clone()), expected);
+        assert_eq!(render_header(nova_vk, options), expected);
+
+        // a full pragma statement is kept as is
+        let options = RenderOptions {
+            pragma: Some("pragma solidity >=0.8.0 <0.9.0;".to_string()),
+            spdx: None,
+        };
+        assert_eq!(
+            render_header(g16_vk, options),
+            [GPL3_SDPX_IDENTIFIER, "pragma solidity >=0.8.0 <0.9.0;"]
+        );
+    }
+
+    #[test]
+    fn test_encode_calldata_selector() {
+        let (kzg_pk, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+        let g16_vk = Groth16VerifierKey::from(g16_vk);
+        let kzg_vk = KZG10VerifierKey::from((kzg_vk, kzg_pk.powers_of_g[0..3].to_vec()));
+        let z_len = 2;
+        let nova_vk = NovaCycleFoldVerifierKey::from((g16_vk.clone(), kzg_vk.clone(), z_len));
+
+        // groth16: proof words followed by the public inputs
+        let proof = vec![1_u8; 8 * 32];
+        let calldata = check_calldata(g16_vk, "verifyProof", &proof, &[Fr::from(42_u32)]);
+        assert_eq!(calldata[..4], FUNCTION_SELECTOR_GROTH16_VERIFY_PROOF);
+        assert_eq!(calldata[4..4 + proof.len()], proof);
+
+        // kzg: commitment and proof followed by the evaluation point and value
+        let proof = vec![2_u8; 4 * 32];
+        check_calldata(kzg_vk, "check", &proof, &[Fr::from(3_u32), Fr::from(5_u32)]);
+
+        // nova: [i, z_0, z_i] followed by the rest of the proof
+        let proof = vec![3_u8; 31 * 32];
+        let public_inputs: Vec<Fr> = (0..1 + 2 * z_len as u32).map(Fr::from).collect();
+        let calldata = check_calldata(nova_vk, "verifyNovaProof", &proof, &public_inputs);
+        assert_eq!(calldata[4..36], Fr::from(0_u32).into_bigint().to_bytes_be());
+        assert_eq!(calldata[4 + public_inputs.len() * 32..], proof);
+
+        // unknown protocol
+        assert!(encode_calldata("Plonk", &[], &[], &[]).is_err());
+    }
+
+    #[test]
+    fn test_check_public_inputs_abi() {
+        let (kzg_pk, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+        let g16_vk = Groth16VerifierKey::from(g16_vk);
+        let kzg_vk = KZG10VerifierKey::from((kzg_vk, kzg_pk.powers_of_g[0..3].to_vec()));
+        let nova_vk = NovaCycleFoldVerifierKey::from((g16_vk.clone(), kzg_vk, 2));
+
+        let g16_public_inputs = vec![Fr::from(42_u32)];
+        check_public_inputs_abi(
+            &g16_vk,
+            &encode_public_inputs(&g16_public_inputs),
+            &g16_public_inputs,
+        )
+        .unwrap();
+
+        // [i, z_0, z_i]
+        let public_inputs: Vec<Fr> = (0..5_u32).map(|x| Fr::from(x + 10)).collect();
+        let abi_bytes = encode_public_inputs(&public_inputs);
+        check_public_inputs_abi(&nova_vk, &abi_bytes, &public_inputs).unwrap();
+
+        // abi encoding of different public inputs
+        let mut wrong_abi_bytes = abi_bytes.clone();
+        wrong_abi_bytes[3 * 32 + 31] ^= 1;
+        assert!(matches!(
+            check_public_inputs_abi(&nova_vk, &wrong_abi_bytes, &public_inputs),
+            Err(Error::PublicInputAbiMismatch { index: 3 })
+        ));
+
+        // non-canonical encoding of the last public input, adding the modulus
+        let mut wrong_abi_bytes = abi_bytes.clone();
+        let last = BigUint::from_bytes_be(&wrong_abi_bytes[4 * 32..])
+            + BigUint::from_bytes_be(&Fr::MODULUS.to_bytes_be());
+        wrong_abi_bytes[4 * 32..].copy_from_slice(&last.to_bytes_be());
+        assert!(matches!(
+            check_public_inputs_abi(&nova_vk, &wrong_abi_bytes, &public_inputs),
+            Err(Error::PublicInputAbiMismatch { index: 4 })
+        ));
+
+        // wrong number of encoded public inputs
+        assert!(check_public_inputs_abi(&nova_vk, &abi_bytes[..4 * 32], &public_inputs).is_err());
+        assert!(matches!(
+            check_public_inputs_abi(&g16_vk, &abi_bytes, &public_inputs),
+            Err(Error::PublicInputCountMismatch {
+                expected: 1,
+                got: 5
+            })
+        ));
+    }
 }
@@ -12,7 +12,10 @@ use folding_schemes::folding::circuits::nonnative::uint::NonNativeUintVar;
 use super::g16::Groth16Verifier;
 use super::kzg::KZG10Verifier;
 use crate::utils::HeaderInclusion;
-use crate::{Groth16VerifierKey, KZG10VerifierKey, ProtocolVerifierKey, PRAGMA_GROTH16_VERIFIER};
+use crate::{
+    Groth16VerifierKey, KZG10VerifierKey, ProtocolVerifierKey, RenderOptions, GPL3_SDPX_IDENTIFIER,
+    PRAGMA_GROTH16_VERIFIER,
+};
 
 pub fn get_decider_template_for_cyclefold_decider(
     nova_cyclefold_vk: NovaCycleFoldVerifierKey,
@@ -24,6 +27,23 @@ pub fn get_decider_template_for_cyclefold_decider(
         .unwrap()
 }
 
+/// same as `get_decider_template_for_cyclefold_decider`, but renders a verifier that can be used
+/// as the implementation behind an upgradeable proxy: its keys are constants in the code instead
+/// of storage set by the constructor, and it exposes an `initialize` method to be called once
+/// through the proxy, as OpenZeppelin proxies expect.
+pub fn get_proxy_compatible_decider_template_for_cyclefold_decider(
+    nova_cyclefold_vk: NovaCycleFoldVerifierKey,
+) -> String {
+    let mut decider = NovaCycleFoldDecider::from(nova_cyclefold_vk);
+    decider.proxy_compatible = true;
+    decider.kzg10_verifier.proxy_compatible = true;
+    HeaderInclusion::<NovaCycleFoldDecider>::builder()
+        .template(decider)
+        .build()
+        .render()
+        .unwrap()
+}
+
 #[derive(Template, Default)]
 #[template(path = "nova_cyclefold_decider.askama.sol", ext = "sol")]
 pub struct NovaCycleFoldDecider {
@@ -31,27 +51,37 @@ pub struct NovaCycleFoldDecider {
     kzg10_verifier: KZG10Verifier,
     // z_len denotes the FCircuit state (z_i) length
     z_len: usize,
+    // bindings_len denotes the number of IVC bindings (see `NovaCycleFoldVerifierKey::bindings_len`)
+    bindings_len: usize,
     public_inputs_len: usize,
     num_limbs: usize,
     bits_per_limb: usize,
+    proxy_compatible: bool,
 }
 
 impl From<NovaCycleFoldVerifierKey> for NovaCycleFoldDecider {
     fn from(value: NovaCycleFoldVerifierKey) -> Self {
+        let bindings_len = value.bindings_len();
         let groth16_verifier = Groth16Verifier::from(value.g16_vk);
         let public_inputs_len = groth16_verifier.gamma_abc_len;
-        let bits_per_limb = NonNativeUintVar::<Fq>::bits_per_limb();
         Self {
             groth16_verifier,
             kzg10_verifier: KZG10Verifier::from(value.kzg_vk),
             z_len: value.z_len,
+            bindings_len,
             public_inputs_len,
-            num_limbs: (250_f32 / (bits_per_limb as f32)).ceil() as usize,
-            bits_per_limb,
+            num_limbs: num_limbs(),
+            bits_per_limb: NonNativeUintVar::<Fq>::bits_per_limb(),
+            proxy_compatible: false,
         }
     }
 }
 
+/// number of limbs of the non-native coordinates of the points in the decider public inputs
+fn num_limbs() -> usize {
+    (250_f32 / (NonNativeUintVar::<Fq>::bits_per_limb() as f32)).ceil() as usize
+}
+
 #[derive(CanonicalDeserialize, CanonicalSerialize, PartialEq, Debug, Clone)]
 pub struct NovaCycleFoldVerifierKey {
     g16_vk: Groth16VerifierKey,
@@ -62,9 +92,24 @@ pub struct NovaCycleFoldVerifierKey {
 impl ProtocolVerifierKey for NovaCycleFoldVerifierKey {
     const PROTOCOL_NAME: &'static str = "NovaCycleFold";
 
-    fn render_as_template(self, pragma: Option<String>) -> Vec<u8> {
+    fn function_signature(&self) -> String {
+        format!("verifyNovaProof(uint256[{}],uint256[4],uint256[3],uint256[4],uint256[4],uint256[2],uint256[2][2],uint256[2],uint256[4],uint256[2][2])", self.public_inputs_len())
+    }
+
+    fn public_inputs_len(&self) -> usize {
+        1 + self.z_len * 2 + self.bindings_len()
+    }
+
+    // the public inputs `[i, z_0, z_i, bindings]` are the first argument of `verifyNovaProof`
+    fn encode_arguments(&self, proof: &[u8], public_inputs: &[u8]) -> Vec<u8> {
+        [public_inputs, proof].concat()
+    }
+
+    // defaults to the GPL-3.0 license, since the contract includes the Groth16 verifier
+    fn render_as_template(self, options: RenderOptions) -> Vec<u8> {
         HeaderInclusion::<NovaCycleFoldDecider>::builder()
-            .pragma_version(pragma.unwrap_or(PRAGMA_GROTH16_VERIFIER.to_string()))
+            .sdpx(options.spdx_or(GPL3_SDPX_IDENTIFIER))
+            .pragma_version(options.pragma_or(PRAGMA_GROTH16_VERIFIER))
             .template(self)
             .build()
             .render()
@@ -99,6 +144,15 @@ impl From<(VerifyingKey<Bn254>, VerifierKey<Bn254>, usize)> for NovaCycleFoldVer
 }
 
 impl NovaCycleFoldVerifierKey {
+    /// returns the number of IVC bindings (the metadata digest, the transcript domain, and the IO
+    /// accumulator for FCircuits that expose IO), which follow `z_i` in the public inputs of the decider. It is
+    /// obtained from the number of public inputs of the Groth16 key, which are
+    /// `[i, z_0, z_i, bindings, U.u, U.x, U.cmE, U.cmW, kzg_challenges, kzg_evals, cmT, r]`.
+    pub fn bindings_len(&self) -> usize {
+        let fixed_len = 1 + self.z_len * 2 + 1 + 2 + num_limbs() * 4 + 4 + num_limbs() * 2 + 1;
+        (self.g16_vk.0.gamma_abc_g1.len() - 1).saturating_sub(fixed_len)
+    }
+
     pub fn new(
         vkey_g16: VerifyingKey<Bn254>,
         vkey_kzg: VerifierKey<Bn254>,
@@ -137,6 +191,7 @@ mod tests {
             CommitmentScheme,
         },
         folding::nova::{
+            decider::serialize_verifier_param,
             decider_eth::{prepare_calldata, Decider as DeciderEth},
             decider_eth_circuit::DeciderEthCircuit,
             get_cs_params_len, Nova, ProverParams,
@@ -151,7 +206,10 @@ mod tests {
     use crate::{
         evm::{compile_solidity, save_solidity, Evm},
         utils::{get_function_selector_for_nova_cyclefold_verifier, HeaderInclusion},
-        verifiers::nova_cyclefold::get_decider_template_for_cyclefold_decider,
+        verifiers::nova_cyclefold::{
+            get_decider_template_for_cyclefold_decider,
+            get_proxy_compatible_decider_template_for_cyclefold_decider,
+        },
         NovaCycleFoldVerifierKey, ProtocolVerifierKey,
     };
 
@@ -162,6 +220,7 @@ mod tests {
     }
     impl<F: PrimeField> FCircuit<F> for CubicFCircuit<F> {
         type Params = ();
+        type ExternalInputs = ();
         fn new(_params: Self::Params) -> Result<Self, Error> {
             Ok(Self { _f: PhantomData })
         }
@@ -175,7 +234,7 @@ mod tests {
             &self,
             _i: usize,
             z_i: Vec<F>,
-            _external_inputs: Vec<F>,
+            _external_inputs: (),
         ) -> Result<Vec<F>, Error> {
             Ok(vec![z_i[0] * z_i[0] * z_i[0] + z_i[0] + F::from(5_u32)])
         }
@@ -184,7 +243,7 @@ mod tests {
             cs: ConstraintSystemRef<F>,
             _i: usize,
             z_i: Vec<FpVar<F>>,
-            _external_inputs: Vec<FpVar<F>>,
+            _external_inputs: (),
         ) -> Result<Vec<FpVar<F>>, SynthesisError> {
             let five = FpVar::<F>::new_constant(cs.clone(), F::from(5u32))?;
             let z_i = z_i[0].clone();
@@ -204,6 +263,7 @@ mod tests {
     }
     impl<F: PrimeField> FCircuit<F> for MultiInputsFCircuit<F> {
         type Params = ();
+        type ExternalInputs = ();
 
         fn new(_params: Self::Params) -> Result<Self, Error> {
             Ok(Self { _f: PhantomData })
@@ -221,7 +281,7 @@ mod tests {
             &self,
             _i: usize,
             z_i: Vec<F>,
-            _external_inputs: Vec<F>,
+            _external_inputs: (),
         ) -> Result<Vec<F>, Error> {
             let a = z_i[0] + F::from(4_u32);
             let b = z_i[1] + F::from(40_u32);
@@ -238,7 +298,7 @@ mod tests {
             cs: ConstraintSystemRef<F>,
             _i: usize,
             z_i: Vec<FpVar<F>>,
-            _external_inputs: Vec<FpVar<F>>,
+            _external_inputs: (),
         ) -> Result<Vec<FpVar<F>>, SynthesisError> {
             let four = FpVar::<F>::new_constant(cs.clone(), F::from(4u32))?;
             let forty = FpVar::<F>::new_constant(cs.clone(), F::from(40u32))?;
@@ -269,6 +329,22 @@ mod tests {
         assert_eq!(nova_cyclefold_vk, obtained_nova_cyclefold_vk)
     }
 
+    #[test]
+    fn nova_cyclefold_vk_from_decider_groth16() {
+        let (_, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+
+        // the verifier params of the DeciderGroth16 are serialized in the NovaCycleFold format
+        let mut bytes = vec![];
+        serialize_verifier_param(&(g16_vk.clone(), kzg_vk.clone()), 1, &mut bytes).unwrap();
+        let obtained_nova_cyclefold_vk =
+            NovaCycleFoldVerifierKey::deserialize_protocol_verifier_key(bytes.as_slice()).unwrap();
+
+        assert_eq!(
+            NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, 1)),
+            obtained_nova_cyclefold_vk
+        )
+    }
+
     #[test]
     fn nova_cyclefold_decider_template_renders() {
         let (_, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
@@ -281,6 +357,22 @@ mod tests {
         save_solidity("NovaDecider.sol", &decider_solidity_code.render().unwrap());
     }
 
+    #[test]
+    fn nova_cyclefold_proxy_compatible_decider_template_renders() {
+        let (_, kzg_vk, _, g16_vk, _) = setup(DEFAULT_SETUP_LEN);
+        let nova_cyclefold_vk = NovaCycleFoldVerifierKey::from((g16_vk, kzg_vk, 1));
+
+        let decider_solidity_code =
+            get_proxy_compatible_decider_template_for_cyclefold_decider(nova_cyclefold_vk);
+        assert!(!decider_solidity_code.contains("constructor("));
+        assert!(decider_solidity_code.contains("function initialize() external"));
+        // the keys are not stored, so there are no state variables initialized at deployment
+        for key in ["G_1", "G_2", "VK", "G1_CRS"] {
+            assert!(!decider_solidity_code.contains(&format!(" {} = [", key)));
+        }
+        save_solidity("NovaDeciderProxy.sol", &decider_solidity_code);
+    }
+
     #[allow(clippy::type_complexity)]
     fn init_test_prover_params<FC: FCircuit<Fr, Params = ()>>() -> (
         ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
@@ -344,7 +436,7 @@ mod tests {
     /// - modifies the calldata and checks that it does not pass the EVM check
     /// - modifies the z_0 and checks that it does not pass the EVM check
     #[allow(clippy::type_complexity)]
-    fn nova_cyclefold_solidity_verifier_opt<FC: FCircuit<Fr, Params = ()>>(
+    fn nova_cyclefold_solidity_verifier_opt<FC: FCircuit<Fr, Params = (), ExternalInputs = ()>>(
         params: (
             ProverParams<G1, G2, KZG<'static, Bn254>, Pedersen<G2>>,
             KZGVerifierKey<Bn254>,
@@ -376,7 +468,7 @@ mod tests {
 
         let mut nova = NOVA_FCircuit::init(&fs_prover_params, f_circuit, z_0).unwrap();
         for _ in 0..n_steps {
-            nova.prove_step(vec![]).unwrap();
+            nova.prove_step(()).unwrap();
         }
 
         let rng = rand::rngs::OsRng;
@@ -389,11 +481,13 @@ mod tests {
         .unwrap();
         println!("generated Decider proof: {:?}", start.elapsed());
 
+        let bindings = nova.bindings().unwrap();
         let verified = DECIDERETH_FCircuit::<FC>::verify(
             (g16_vk, kzg_vk),
             nova.i,
             nova.z_0.clone(),
             nova.z_i.clone(),
+            &bindings,
             &nova.U_i,
             &nova.u_i,
             &proof,
@@ -401,21 +495,26 @@ mod tests {
         .unwrap();
         assert!(verified);
 
-        let function_selector =
-            get_function_selector_for_nova_cyclefold_verifier(nova.z_0.len() * 2 + 1);
+        assert_eq!(nova_cyclefold_vk.bindings_len(), bindings.to_vec().len());
+        let function_selector = get_function_selector_for_nova_cyclefold_verifier(
+            nova_cyclefold_vk.public_inputs_len(),
+        );
 
+        let z_len = nova.z_0.len();
         let calldata: Vec<u8> = prepare_calldata(
             function_selector,
             nova.i,
             nova.z_0,
             nova.z_i,
+            &bindings,
             &nova.U_i,
             &nova.u_i,
             proof,
         )
         .unwrap();
 
-        let decider_solidity_code = get_decider_template_for_cyclefold_decider(nova_cyclefold_vk);
+        let decider_solidity_code =
+            get_decider_template_for_cyclefold_decider(nova_cyclefold_vk.clone());
 
         let nova_cyclefold_verifier_bytecode =
             compile_solidity(decider_solidity_code, "NovaDecider");
@@ -443,6 +542,22 @@ mod tests {
         invalid_calldata[99] += 1;
         let (_, output) = evm.call(verifier_address, invalid_calldata.clone());
         assert_eq!(*output.last().unwrap(), 0);
+
+        // change the metadata digest, which is the first binding placed right after z_i
+        let mut invalid_calldata = calldata.clone();
+        invalid_calldata[4 + 32 * (1 + 2 * z_len) + 31] += 1;
+        let (_, output) = evm.call(verifier_address, invalid_calldata.clone());
+        assert_eq!(*output.last().unwrap(), 0);
+
+        // the proxy-compatible verifier accepts the same proof
+        let proxy_decider_solidity_code =
+            get_proxy_compatible_decider_template_for_cyclefold_decider(nova_cyclefold_vk);
+        let proxy_verifier_address =
+            evm.create(compile_solidity(proxy_decider_solidity_code, "NovaDecider"));
+        let (_, output) = evm.call(proxy_verifier_address, calldata.clone());
+        assert_eq!(*output.last().unwrap(), 1);
+        let (_, output) = evm.call(proxy_verifier_address, invalid_calldata);
+        assert_eq!(*output.last().unwrap(), 0);
     }
 
     #[test]
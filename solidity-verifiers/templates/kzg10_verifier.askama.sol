@@ -111,6 +111,50 @@ contract KZG10Verifier {
         return out[0] == 1;
     }
 
+    {% if proxy_compatible %}
+    // the keys are inlined in the code as constants, so that the contract has no state to be
+    // initialized by a constructor, and it can be used as the implementation behind a proxy
+    uint256 constant G_1_X = {{ g1.0[0] }};
+    uint256 constant G_1_Y = {{ g1.0[1] }};
+    uint256 constant G_2_X_0 = {{ g2.0[0][0] }};
+    uint256 constant G_2_X_1 = {{ g2.0[0][1] }};
+    uint256 constant G_2_Y_0 = {{ g2.0[1][0] }};
+    uint256 constant G_2_Y_1 = {{ g2.0[1][1] }};
+    uint256 constant VK_X_0 = {{ vk.0[0][0] }};
+    uint256 constant VK_X_1 = {{ vk.0[0][1] }};
+    uint256 constant VK_Y_0 = {{ vk.0[1][0] }};
+    uint256 constant VK_Y_1 = {{ vk.0[1][1] }};
+
+    function g1() internal pure returns (uint256[2] memory) {
+        return [G_1_X, G_1_Y];
+    }
+
+    function g2() internal pure returns (uint256[2][2] memory) {
+        return [[G_2_X_0, G_2_X_1], [G_2_Y_0, G_2_Y_1]];
+    }
+
+    function vk() internal pure returns (uint256[2][2] memory) {
+        return [[VK_X_0, VK_X_1], [VK_Y_0, VK_Y_1]];
+    }
+
+    {% if g1_crs_len>0 %} // only enabled if g1_crs_len>0, for batch_check
+    function g1Crs(uint256 i) internal pure returns (uint256[2] memory) {
+        uint256[2][{{ g1_crs_len }}] memory crs = [
+        {%- for (i, point) in g1_crs.iter().enumerate() %}
+            [ 
+                uint256({{ point.0[0] }}),
+                uint256({{ point.0[1] }})
+            {% if loop.last -%}
+            ]
+            {%- else -%}
+            ],
+            {%- endif -%}
+        {% endfor -%}    
+        ];
+        return crs[i];
+    }
+    {%~ endif %}
+    {% else %}
     uint256[2] G_1 = [
             {{ g1.0[0] }},
             {{ g1.0[1] }}
@@ -151,6 +195,25 @@ contract KZG10Verifier {
     ];
     {%~ endif %}
 
+    function g1() internal view returns (uint256[2] memory) {
+        return G_1;
+    }
+
+    function g2() internal view returns (uint256[2][2] memory) {
+        return G_2;
+    }
+
+    function vk() internal view returns (uint256[2][2] memory) {
+        return VK;
+    }
+
+    {% if g1_crs_len>0 %}
+    function g1Crs(uint256 i) internal view returns (uint256[2] memory) {
+        return G1_CRS[i];
+    }
+    {%~ endif %}
+    {% endif %}
+
     /**
      * @notice  Verifies a single point evaluation proof. Function name follows `ark-poly`.
      * @dev     To avoid ops in G_2, we slightly tweak how the verification is done.
@@ -181,8 +244,8 @@ contract KZG10Verifier {
         //                        |_   rhs_pairing  _|
         //
         uint256[2] memory rhs_pairing =
-            add(mulScalar(negate(pi), x), add(negate(c), mulScalar(G_1, y)));
-        return pairing(pi, VK, rhs_pairing, G_2);
+            add(mulScalar(negate(pi), x), add(negate(c), mulScalar(g1(), y)));
+        return pairing(pi, vk(), rhs_pairing, g2());
     }
 
     function evalPolyAt(uint256[] memory _coefficients, uint256 _index) public pure returns (uint256) {
@@ -221,8 +284,8 @@ contract KZG10Verifier {
         uint256[2] memory z_commit;
         uint256[2] memory l_commit;
         for (uint256 i = 0; i < x_vals.length; i++) {
-            z_commit = add(z_commit, mulScalar(G1_CRS[i], z_coeffs[i])); // update commitment to z(x)
-            l_commit = add(l_commit, mulScalar(G1_CRS[i], l_coeffs[i])); // update commitment to l(x)
+            z_commit = add(z_commit, mulScalar(g1Crs(i), z_coeffs[i])); // update commitment to z(x)
+            l_commit = add(l_commit, mulScalar(g1Crs(i), l_coeffs[i])); // update commitment to l(x)
 
             uint256 eval_z = evalPolyAt(z_coeffs, x_vals[i]);
             uint256 eval_l = evalPolyAt(l_coeffs, x_vals[i]);
@@ -231,7 +294,7 @@ contract KZG10Verifier {
             require(eval_l == y_vals[i], "checkAndCommitAuxPolys: wrong lagrange poly");
         }
         // z(x) has len(x_vals) + 1 coeffs, we add to the commitment the last coeff of z(x)
-        z_commit = add(z_commit, mulScalar(G1_CRS[z_coeffs.length - 1], z_coeffs[z_coeffs.length - 1]));
+        z_commit = add(z_commit, mulScalar(g1Crs(z_coeffs.length - 1), z_coeffs[z_coeffs.length - 1]));
 
         return (z_commit, l_commit);
     }
@@ -269,7 +332,7 @@ contract KZG10Verifier {
         (uint256[2] memory z_commit, uint256[2] memory l_commit) =
             checkAndCommitAuxPolys(z_coeffs, l_coeffs, x_vals, y_vals);
         uint256[2] memory neg_commit = negate(c);
-        return pairing(z_commit, pi, add(l_commit, neg_commit), G_2);
+        return pairing(z_commit, pi, add(l_commit, neg_commit), g2());
     }
     {%~ endif %}
 }
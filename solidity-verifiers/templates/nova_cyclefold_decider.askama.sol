@@ -45,6 +45,21 @@ library LimbsDecomposition {
  * @dev     This is an askama template which, when templated, features a Groth16 and KZG10 verifiers from which this contract inherits.
  */
 contract NovaDecider is Groth16Verifier, KZG10Verifier {
+    {% if proxy_compatible %}
+    // the only state of the contract, which lives in the storage of the proxy
+    bool private initialized;
+
+    /**
+     * @notice  Initializes the contract through the proxy, replacing the constructor. The verifier
+     *          does not have any state to set, but upgradeable proxies (eg. OpenZeppelin's) are
+     *          deployed calling an initializer once.
+     */
+    function initialize() external {
+        require(!initialized, "NovaDecider: already initialized");
+        initialized = true;
+    }
+
+    {% endif %}
     /**
      * @notice  Computes the linear combination of a and b with r as the coefficient.
      * @dev     All ops are done mod the BN254 scalar field prime
@@ -61,7 +76,7 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
      */
     function verifyNovaProof(
         // inputs are grouped to prevent errors due stack too deep
-        uint256[{{ 1 + z_len * 2 }}] calldata i_z0_zi, // [i, z0, zi] where |z0| == |zi|
+        uint256[{{ 1 + z_len * 2 + bindings_len }}] calldata i_z0_zi, // [i, z0, zi, bindings] where |z0| == |zi|
         uint256[4] calldata U_i_cmW_U_i_cmE, // [U_i_cmW[2], U_i_cmE[2]]
         uint256[3] calldata U_i_u_u_i_u_r, // [U_i_u, u_i_u, r]
         uint256[4] calldata U_i_x_u_i_cmW, // [U_i_x[2], u_i_cmW[2]]
@@ -80,7 +95,8 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
 
         public_inputs[0] = i_z0_zi[0];
 
-        for (uint i = 0; i < {{ z_len * 2 }}; i++) {
+        // z0, zi and the IVC bindings (metadata digest, transcript domain, and IO accumulator if the circuit has IO)
+        for (uint i = 0; i < {{ z_len * 2 + bindings_len }}; i++) {
             public_inputs[1 + i] = i_z0_zi[1 + i];
         }
 
@@ -91,9 +107,9 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
             uint256 x0 = rlc(U_i_x_u_i_cmW[0], U_i_u_u_i_u_r[2], u_i_x_cmT[0]);
             uint256 x1 = rlc(U_i_x_u_i_cmW[1], U_i_u_u_i_u_r[2], u_i_x_cmT[1]);
 
-            public_inputs[{{ z_len * 2 + 1 }}] = u;
-            public_inputs[{{ z_len * 2 + 2 }}] = x0;
-            public_inputs[{{ z_len * 2 + 3 }}] = x1;
+            public_inputs[{{ z_len * 2 + bindings_len + 1 }}] = u;
+            public_inputs[{{ z_len * 2 + bindings_len + 2 }}] = x0;
+            public_inputs[{{ z_len * 2 + bindings_len + 3 }}] = x1;
         }
 
         {
@@ -106,8 +122,8 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
                 uint256[{{num_limbs}}] memory cmE_y_limbs = LimbsDecomposition.decompose(cmE[1]);
             
                 for (uint8 k = 0; k < {{num_limbs}}; k++) {
-                    public_inputs[{{ z_len * 2 + 4 }} + k] = cmE_x_limbs[k];
-                    public_inputs[{{ z_len * 2 + 4 + num_limbs }} + k] = cmE_y_limbs[k];
+                    public_inputs[{{ z_len * 2 + bindings_len + 4 }} + k] = cmE_x_limbs[k];
+                    public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs }} + k] = cmE_y_limbs[k];
                 }
             }
 
@@ -124,8 +140,8 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
                 uint256[{{num_limbs}}] memory cmW_y_limbs = LimbsDecomposition.decompose(cmW[1]);
         
                 for (uint8 k = 0; k < {{num_limbs}}; k++) {
-                    public_inputs[{{ z_len * 2 + 4 + num_limbs * 2 }} + k] = cmW_x_limbs[k];
-                    public_inputs[{{ z_len * 2 + 4 + num_limbs * 3 }} + k] = cmW_y_limbs[k];
+                    public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 2 }} + k] = cmW_x_limbs[k];
+                    public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 3 }} + k] = cmW_y_limbs[k];
                 }
             }
         
@@ -134,10 +150,10 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
 
         {
             // add challenges
-            public_inputs[{{ z_len * 2 + 4 + num_limbs * 4 }}] = challenge_W_challenge_E_kzg_evals[0];
-            public_inputs[{{ z_len * 2 + 4 + num_limbs * 4 + 1 }}] = challenge_W_challenge_E_kzg_evals[1];
-            public_inputs[{{ z_len * 2 + 4 + num_limbs * 4 + 2 }}] = challenge_W_challenge_E_kzg_evals[2];
-            public_inputs[{{ z_len * 2 + 4 + num_limbs * 4 + 3 }}] = challenge_W_challenge_E_kzg_evals[3];
+            public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 4 }}] = challenge_W_challenge_E_kzg_evals[0];
+            public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 4 + 1 }}] = challenge_W_challenge_E_kzg_evals[1];
+            public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 4 + 2 }}] = challenge_W_challenge_E_kzg_evals[2];
+            public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 4 + 3 }}] = challenge_W_challenge_E_kzg_evals[3];
         
             uint256[{{num_limbs}}] memory cmT_x_limbs;
             uint256[{{num_limbs}}] memory cmT_y_limbs;
@@ -146,8 +162,8 @@ contract NovaDecider is Groth16Verifier, KZG10Verifier {
             cmT_y_limbs = LimbsDecomposition.decompose(u_i_x_cmT[3]);
         
             for (uint8 k = 0; k < {{num_limbs}}; k++) {
-                public_inputs[{{ z_len * 2 + 4 + num_limbs * 4 }} + 4 + k] = cmT_x_limbs[k]; 
-                public_inputs[{{ z_len * 2 + 4 + num_limbs * 5}} + 4 + k] = cmT_y_limbs[k];
+                public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 4 }} + 4 + k] = cmT_x_limbs[k]; 
+                public_inputs[{{ z_len * 2 + bindings_len + 4 + num_limbs * 5}} + 4 + k] = cmT_y_limbs[k];
             }
         
             // last element of the groth16 proof's public inputs is `r`